@@ -247,6 +247,12 @@ crate::enum_try_from! {
 
         /// Set `$rA` to `tx.policies[count_ones(0b1111 & tx.policyTypes) - 1].maxFee`
         PolicyMaxFee = 0x504,
+
+        /// Set `$rA` to `tx.policies[count_ones(0b11111 & tx.policyTypes) - 1].expiration`
+        PolicyExpiration = 0x505,
+
+        /// Set `$rA` to `tx.policies[count_ones(0b111111 & tx.policyTypes) - 1].feePayer`
+        PolicyFeePayer = 0x506,
     },
     Immediate12
 }
@@ -343,6 +349,8 @@ fn encode_gtf_args() {
         GTFArgs::PolicyWitnessLimit,
         GTFArgs::PolicyMaturity,
         GTFArgs::PolicyMaxFee,
+        GTFArgs::PolicyExpiration,
+        GTFArgs::PolicyFeePayer,
     ];
 
     args.into_iter().for_each(|a| {
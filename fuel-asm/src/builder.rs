@@ -0,0 +1,573 @@
+//! A small macro-assembler for building programs without manual offset arithmetic.
+//!
+//! [`AsmBuilder`] collects instructions together with symbolic jump targets ([`Label`])
+//! and embedded data blobs ([`DataRef`]). All references are resolved when the program
+//! is [built](AsmBuilder::build): jumps are encoded as the forward or backward relative
+//! variant depending on where the label was bound, and data blobs are appended after
+//! the code section.
+//!
+//! ```
+//! use fuel_asm::{op, AsmBuilder, RegId};
+//!
+//! let mut asm = AsmBuilder::new();
+//! let top = asm.new_label();
+//! let done = asm.new_label();
+//!
+//! // Count down from a constant that doesn't fit a `MOVI` immediate.
+//! asm.load_word(0x10, 1 << 20);
+//! asm.bind(top);
+//! asm.push(op::subi(0x10, 0x10, 1));
+//! asm.jump_if_not_equal(0x10, RegId::ZERO, top);
+//! asm.jump(done);
+//! asm.push(op::rvrt(RegId::ONE));
+//! asm.bind(done);
+//! asm.push(op::ret(RegId::ONE));
+//!
+//! let bytes = asm.build().expect("all labels are bound");
+//! assert_eq!(bytes.len(), 7 * fuel_asm::Instruction::SIZE);
+//! ```
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{
+    op,
+    CheckRegId,
+    Imm06,
+    Imm12,
+    Imm18,
+    Instruction,
+    RegId,
+    Word,
+};
+
+/// Width of the immediate shifted in by each step of [`AsmBuilder::load_word`].
+const LOAD_CHUNK_BITS: u16 = 12;
+
+/// Data blobs are aligned to the word size so they can be read with `LW`.
+const DATA_ALIGNMENT: usize = core::mem::size_of::<Word>();
+
+/// A symbolic jump target inside an [`AsmBuilder`] program.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Label(usize);
+
+/// A reference to a data blob embedded with [`AsmBuilder::data`].
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct DataRef {
+    /// Offset of the blob from the start of the data section.
+    offset: usize,
+    /// Length of the blob in bytes, without padding.
+    len: usize,
+}
+
+impl DataRef {
+    /// Length of the embedded blob in bytes.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the embedded blob is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+/// Errors that can occur when resolving an [`AsmBuilder`] program.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AsmError {
+    /// The label is referenced by a jump but was never bound.
+    UnboundLabel(Label),
+    /// The label was bound more than once.
+    LabelAlreadyBound(Label),
+    /// The distance to the label doesn't fit the immediate of the selected jump
+    /// instruction. The distance is expressed in instructions.
+    JumpOutOfRange {
+        /// The target of the jump.
+        label: Label,
+        /// Number of instructions between the jump and its target.
+        distance: usize,
+    },
+    /// A relative jump can't target the jump instruction itself.
+    JumpToSelf(Label),
+    /// The data blob is placed too far from the start of the program to be
+    /// addressed with an 18-bit immediate.
+    DataOutOfRange(DataRef),
+    /// The program embeds data, which can't be represented as instructions.
+    EmbeddedData,
+}
+
+impl fmt::Display for AsmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AsmError {}
+
+/// The condition under which a symbolic jump is taken.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum JumpCondition {
+    Always,
+    NotZero(RegId),
+    NotEqual(RegId, RegId),
+}
+
+/// A single instruction slot of the program, possibly not resolved yet.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Slot {
+    Instruction(Instruction),
+    Jump(JumpCondition, Label),
+    DataOffset(RegId, DataRef),
+}
+
+/// Builder for FuelVM programs with labels and embedded data.
+///
+/// Every method that appends code emits a fixed number of instructions, so the layout
+/// of the program is known before the labels are resolved.
+#[derive(Clone, Debug, Default)]
+pub struct AsmBuilder {
+    slots: Vec<Slot>,
+    /// Instruction index each label is bound to.
+    labels: Vec<Option<usize>>,
+    data: Vec<u8>,
+    error: Option<AsmError>,
+}
+
+impl AsmBuilder {
+    /// Creates an empty program.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of instructions emitted so far.
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns `true` if no instructions were emitted.
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    /// Appends a single instruction.
+    pub fn push(&mut self, instruction: Instruction) -> &mut Self {
+        self.slots.push(Slot::Instruction(instruction));
+        self
+    }
+
+    /// Appends a sequence of instructions.
+    pub fn extend<I>(&mut self, instructions: I) -> &mut Self
+    where
+        I: IntoIterator<Item = Instruction>,
+    {
+        self.slots
+            .extend(instructions.into_iter().map(Slot::Instruction));
+        self
+    }
+
+    /// Creates a new label. It must be [bound](Self::bind) before the program is built
+    /// if any jump refers to it.
+    pub fn new_label(&mut self) -> Label {
+        let label = Label(self.labels.len());
+        self.labels.push(None);
+        label
+    }
+
+    /// Binds the label to the position of the next emitted instruction.
+    pub fn bind(&mut self, label: Label) -> &mut Self {
+        let position = self.slots.len();
+        match self.labels.get_mut(label.0) {
+            Some(slot @ None) => *slot = Some(position),
+            _ => self.record_error(AsmError::LabelAlreadyBound(label)),
+        }
+        self
+    }
+
+    /// Unconditional relative jump to the label, encoded as `JMPF` or `JMPB`.
+    pub fn jump(&mut self, label: Label) -> &mut Self {
+        self.slots.push(Slot::Jump(JumpCondition::Always, label));
+        self
+    }
+
+    /// Relative jump to the label if the register is not zero, encoded as `JNZF` or
+    /// `JNZB`.
+    pub fn jump_if_not_zero<A: CheckRegId>(
+        &mut self,
+        cond: A,
+        label: Label,
+    ) -> &mut Self {
+        self.slots
+            .push(Slot::Jump(JumpCondition::NotZero(cond.check()), label));
+        self
+    }
+
+    /// Relative jump to the label if the registers are not equal, encoded as `JNEF` or
+    /// `JNEB`.
+    pub fn jump_if_not_equal<A: CheckRegId, B: CheckRegId>(
+        &mut self,
+        lhs: A,
+        rhs: B,
+        label: Label,
+    ) -> &mut Self {
+        self.slots.push(Slot::Jump(
+            JumpCondition::NotEqual(lhs.check(), rhs.check()),
+            label,
+        ));
+        self
+    }
+
+    /// Loads a 64-bit constant into the register.
+    ///
+    /// Values that fit the 18-bit immediate are loaded with a single `MOVI`. Larger
+    /// values are built from the most significant bits downwards with `SLLI` and `ORI`,
+    /// emitting at most 9 instructions.
+    pub fn load_word<A: CheckRegId>(&mut self, dst: A, value: Word) -> &mut Self {
+        let dst = dst.check();
+        let significant_bits = Word::BITS.saturating_sub(value.leading_zeros());
+        let imm18_bits = Imm18::MAX.to_u32().count_ones();
+        let chunks = significant_bits
+            .saturating_sub(imm18_bits)
+            .div_ceil(u32::from(LOAD_CHUNK_BITS));
+
+        let top = value
+            .checked_shr(chunks.saturating_mul(u32::from(LOAD_CHUNK_BITS)))
+            .unwrap_or_default();
+        self.push(op::movi(dst, Self::truncate_imm18(top)));
+
+        for chunk in (0..chunks).rev() {
+            let bits = value
+                .checked_shr(chunk.saturating_mul(u32::from(LOAD_CHUNK_BITS)))
+                .unwrap_or_default();
+            self.push(op::slli(dst, dst, LOAD_CHUNK_BITS));
+            let imm = Self::truncate_imm12(bits);
+            if imm != 0 {
+                self.push(op::ori(dst, dst, imm));
+            }
+        }
+
+        self
+    }
+
+    /// Embeds a data blob after the code section and returns a reference to it.
+    ///
+    /// Each blob starts at a word-aligned offset.
+    pub fn data<B: AsRef<[u8]>>(&mut self, bytes: B) -> DataRef {
+        let bytes = bytes.as_ref();
+        let data = DataRef {
+            offset: self.data.len(),
+            len: bytes.len(),
+        };
+        self.data.extend_from_slice(bytes);
+        let padding = self.data.len().wrapping_neg() % DATA_ALIGNMENT;
+        self.data.resize(self.data.len().saturating_add(padding), 0);
+        data
+    }
+
+    /// Embeds the word as big-endian bytes, see [`Self::data`].
+    pub fn data_word(&mut self, value: Word) -> DataRef {
+        self.data(value.to_be_bytes())
+    }
+
+    /// Loads the absolute memory address of the data blob into the register.
+    ///
+    /// The address is computed relative to `$is`, so the program remains position
+    /// independent. Always emits two instructions.
+    pub fn load_data_addr<A: CheckRegId>(&mut self, dst: A, data: DataRef) -> &mut Self {
+        let dst = dst.check();
+        self.slots.push(Slot::DataOffset(dst, data));
+        self.push(op::add(dst, dst, RegId::IS))
+    }
+
+    /// Loads the word stored in the data blob into the register.
+    ///
+    /// The blob is expected to be created with [`Self::data_word`].
+    pub fn load_data_word<A: CheckRegId>(&mut self, dst: A, data: DataRef) -> &mut Self {
+        let dst = dst.check();
+        self.load_data_addr(dst, data);
+        self.push(op::lw(dst, dst, 0))
+    }
+
+    /// Resolves all labels and data references into a list of instructions.
+    ///
+    /// Fails if the program embeds data, as it can't be represented as instructions;
+    /// use [`Self::build`] instead.
+    pub fn build_instructions(&self) -> Result<Vec<Instruction>, AsmError> {
+        if !self.data.is_empty() {
+            return Err(AsmError::EmbeddedData)
+        }
+        self.resolve_instructions()
+    }
+
+    /// Resolves the program into its bytecode, followed by the data section.
+    pub fn build(&self) -> Result<Vec<u8>, AsmError> {
+        let instructions = self.resolve_instructions()?;
+        let mut bytes: Vec<u8> = instructions.into_iter().collect();
+        if !self.data.is_empty() {
+            bytes.resize(self.data_start(), 0);
+            bytes.extend_from_slice(&self.data);
+        }
+        Ok(bytes)
+    }
+
+    /// Resolves all labels and data references into the code section.
+    fn resolve_instructions(&self) -> Result<Vec<Instruction>, AsmError> {
+        if let Some(error) = self.error {
+            return Err(error)
+        }
+
+        let data_start = self.data_start();
+        self.slots
+            .iter()
+            .enumerate()
+            .map(|(position, slot)| match *slot {
+                Slot::Instruction(instruction) => Ok(instruction),
+                Slot::Jump(condition, label) => {
+                    self.resolve_jump(position, condition, label)
+                }
+                Slot::DataOffset(dst, data) => {
+                    let offset = data_start
+                        .checked_add(data.offset)
+                        .and_then(|offset| u32::try_from(offset).ok())
+                        .and_then(Imm18::new_checked)
+                        .ok_or(AsmError::DataOutOfRange(data))?;
+                    Ok(op::movi(dst, offset.to_u32()))
+                }
+            })
+            .collect()
+    }
+
+    fn record_error(&mut self, error: AsmError) {
+        self.error.get_or_insert(error);
+    }
+
+    /// Byte offset of the data section from the start of the program.
+    fn data_start(&self) -> usize {
+        let code_len = self.slots.len().saturating_mul(Instruction::SIZE);
+        let padding = code_len.wrapping_neg() % DATA_ALIGNMENT;
+        code_len.saturating_add(padding)
+    }
+
+    fn resolve_jump(
+        &self,
+        position: usize,
+        condition: JumpCondition,
+        label: Label,
+    ) -> Result<Instruction, AsmError> {
+        let target = self
+            .labels
+            .get(label.0)
+            .copied()
+            .flatten()
+            .ok_or(AsmError::UnboundLabel(label))?;
+
+        // Relative jumps skip at least one instruction, i.e. an immediate of `0`
+        // targets the instruction right after (or before) the jump.
+        let forwards = target > position;
+        let distance = target.abs_diff(position);
+        let imm = distance.checked_sub(1).ok_or(AsmError::JumpToSelf(label))?;
+        let out_of_range = AsmError::JumpOutOfRange { label, distance };
+        let imm = u32::try_from(imm).map_err(|_| out_of_range)?;
+
+        let zero = RegId::ZERO;
+        let instruction = match condition {
+            JumpCondition::Always => {
+                let imm = Imm18::new_checked(imm).ok_or(out_of_range)?.to_u32();
+                if forwards {
+                    op::jmpf(zero, imm)
+                } else {
+                    op::jmpb(zero, imm)
+                }
+            }
+            JumpCondition::NotZero(cond) => {
+                let imm = u16::try_from(imm)
+                    .ok()
+                    .and_then(Imm12::new_checked)
+                    .ok_or(out_of_range)?
+                    .to_u16();
+                if forwards {
+                    op::jnzf(cond, zero, imm)
+                } else {
+                    op::jnzb(cond, zero, imm)
+                }
+            }
+            JumpCondition::NotEqual(lhs, rhs) => {
+                let imm = u8::try_from(imm)
+                    .ok()
+                    .and_then(Imm06::new_checked)
+                    .ok_or(out_of_range)?
+                    .to_u8();
+                if forwards {
+                    op::jnef(lhs, rhs, zero, imm)
+                } else {
+                    op::jneb(lhs, rhs, zero, imm)
+                }
+            }
+        };
+
+        Ok(instruction)
+    }
+
+    fn truncate_imm18(value: Word) -> u32 {
+        let mask = Word::from(Imm18::MAX.to_u32());
+        u32::try_from(value & mask).expect("masked to 18 bits")
+    }
+
+    fn truncate_imm12(value: Word) -> u16 {
+        let mask = Word::from(Imm12::MAX.to_u16());
+        u16::try_from(value & mask).expect("masked to 12 bits")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Evaluates the instructions emitted by `load_word` to recover the loaded value.
+    fn eval_load(instructions: &[Instruction]) -> Word {
+        let mut reg: Word = 0;
+        for instruction in instructions {
+            match *instruction {
+                Instruction::MOVI(movi) => {
+                    let (_, imm) = movi.unpack();
+                    reg = imm.into();
+                }
+                Instruction::SLLI(slli) => {
+                    let (_, _, imm) = slli.unpack();
+                    reg = reg.checked_shl(imm.into()).unwrap();
+                }
+                Instruction::ORI(ori) => {
+                    let (_, _, imm) = ori.unpack();
+                    reg |= u64::from(imm);
+                }
+                other => panic!("unexpected instruction {other:?}"),
+            }
+        }
+        reg
+    }
+
+    #[rstest::rstest]
+    #[case(0, 1)]
+    #[case(1, 1)]
+    #[case(Imm18::MAX.to_u32() as Word, 1)]
+    #[case(0x4_0000, 2)]
+    #[case(0x1_0000_0000, 3)]
+    #[case(0x8000_0000_0000_0000, 5)]
+    #[case(0x0123_4567_89ab_cdef, 9)]
+    #[case(Word::MAX, 9)]
+    fn load_word_round_trips(#[case] value: Word, #[case] expected_len: usize) {
+        let mut asm = AsmBuilder::new();
+        asm.load_word(0x10, value);
+        let instructions = asm.build_instructions().unwrap();
+
+        assert_eq!(instructions.len(), expected_len);
+        assert_eq!(eval_load(&instructions), value);
+    }
+
+    #[test]
+    fn jumps_select_direction() {
+        let mut asm = AsmBuilder::new();
+        let start = asm.new_label();
+        let end = asm.new_label();
+        asm.bind(start)
+            .push(op::noop())
+            .jump(end)
+            .push(op::noop())
+            .push(op::noop())
+            .jump_if_not_zero(0x10, start)
+            .jump_if_not_equal(0x10, 0x11, start)
+            .bind(end)
+            .push(op::ret(RegId::ONE));
+
+        let instructions = asm.build_instructions().unwrap();
+        assert_eq!(
+            instructions,
+            vec![
+                op::noop(),
+                op::jmpf(RegId::ZERO, 4),
+                op::noop(),
+                op::noop(),
+                op::jnzb(0x10, RegId::ZERO, 3),
+                op::jneb(0x10, 0x11, RegId::ZERO, 4),
+                op::ret(RegId::ONE),
+            ]
+        );
+    }
+
+    #[test]
+    fn unbound_label_is_an_error() {
+        let mut asm = AsmBuilder::new();
+        let label = asm.new_label();
+        asm.jump(label);
+
+        assert_eq!(asm.build(), Err(AsmError::UnboundLabel(label)));
+    }
+
+    #[test]
+    fn rebinding_label_is_an_error() {
+        let mut asm = AsmBuilder::new();
+        let label = asm.new_label();
+        asm.bind(label).push(op::noop()).bind(label);
+
+        assert_eq!(asm.build(), Err(AsmError::LabelAlreadyBound(label)));
+    }
+
+    #[test]
+    fn jump_to_self_is_an_error() {
+        let mut asm = AsmBuilder::new();
+        let label = asm.new_label();
+        asm.bind(label).jump(label);
+
+        assert_eq!(asm.build(), Err(AsmError::JumpToSelf(label)));
+    }
+
+    #[test]
+    fn jump_out_of_range_is_an_error() {
+        let mut asm = AsmBuilder::new();
+        let label = asm.new_label();
+        asm.jump_if_not_equal(0x10, 0x11, label);
+        asm.extend(vec![op::noop(); 64]);
+        asm.bind(label);
+
+        assert_eq!(
+            asm.build(),
+            Err(AsmError::JumpOutOfRange {
+                label,
+                distance: 65
+            })
+        );
+    }
+
+    #[test]
+    fn data_is_appended_after_code() {
+        let mut asm = AsmBuilder::new();
+        let first = asm.data([1, 2, 3]);
+        let second = asm.data_word(0xdead_beef);
+        asm.load_data_addr(0x10, first)
+            .load_data_word(0x11, second)
+            .push(op::log(0x10, 0x11, RegId::ZERO, RegId::ZERO))
+            .push(op::ret(RegId::ONE));
+
+        let bytes = asm.build().unwrap();
+        let instructions: Vec<Instruction> =
+            crate::from_bytes(bytes[..28].iter().copied())
+                .collect::<Result<_, _>>()
+                .unwrap();
+        // 7 instructions take 28 bytes, then pad to 32 for word alignment.
+        assert_eq!(instructions[0], op::movi(0x10, 32));
+        assert_eq!(instructions[2], op::movi(0x11, 40));
+        assert_eq!(bytes.len(), 48);
+        assert_eq!(&bytes[32..40], &[1, 2, 3, 0, 0, 0, 0, 0]);
+        assert_eq!(&bytes[40..48], &0xdead_beef_u64.to_be_bytes());
+    }
+
+    #[test]
+    fn build_instructions_rejects_embedded_data() {
+        let mut asm = AsmBuilder::new();
+        let data = asm.data_word(1);
+        asm.load_data_word(0x10, data).push(op::ret(0x10));
+
+        assert_eq!(asm.build_instructions(), Err(AsmError::EmbeddedData));
+        assert!(asm.build().is_ok());
+    }
+}
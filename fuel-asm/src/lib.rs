@@ -18,6 +18,8 @@
 extern crate alloc;
 
 mod args;
+#[cfg(feature = "alloc")]
+mod builder;
 mod panic_instruction;
 // This is `pub` to make documentation for the private `impl_instructions!` macro more
 // accessible.
@@ -26,6 +28,7 @@ pub mod macros;
 pub mod op;
 mod pack;
 mod panic_reason;
+pub mod semantics;
 mod unpack;
 
 #[cfg(test)]
@@ -44,12 +47,20 @@ pub type RegisterId = usize;
 /// Register value type
 pub type Word = u64;
 
+#[cfg(feature = "alloc")]
+pub use builder::{
+    AsmBuilder,
+    AsmError,
+    DataRef,
+    Label,
+};
 pub use panic_instruction::PanicInstruction;
 pub use panic_reason::PanicReason;
 
 /// Represents a 6-bit register ID, guaranteed to be masked by construction.
 #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 #[cfg_attr(feature = "typescript", wasm_bindgen::prelude::wasm_bindgen)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RegId(u8);
 
 /// Represents a 6-bit immediate value, guaranteed to be masked by construction.
@@ -863,6 +874,28 @@ impl core::convert::TryFrom<RawInstruction> for Instruction {
     }
 }
 
+// Arbitrary values, masked the same way as the constructors
+
+#[cfg(feature = "arbitrary")]
+macro_rules! impl_arbitrary_masked {
+    ($($ty:ident($raw:ty)),* $(,)?) => {
+        $(
+            impl<'a> arbitrary::Arbitrary<'a> for $ty {
+                fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+                    Ok(Self::new(u.arbitrary::<$raw>()?))
+                }
+
+                fn size_hint(depth: usize) -> (usize, Option<usize>) {
+                    <$raw as arbitrary::Arbitrary>::size_hint(depth)
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "arbitrary")]
+impl_arbitrary_masked!(RegId(u8), Imm06(u8), Imm12(u16), Imm18(u32), Imm24(u32));
+
 // Index slices with `RegId`
 
 impl<T> core::ops::Index<RegId> for [T]
@@ -1177,6 +1177,14 @@ macro_rules! impl_instructions {
             }
         }
 
+        #[cfg(feature = "arbitrary")]
+        impl<'a> arbitrary::Arbitrary<'a> for Opcode {
+            fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+                const ALL: &[Opcode] = &[$(Opcode::$Op,)*];
+                u.choose(ALL).copied()
+            }
+        }
+
         impl Opcode {
             /// Construct the instruction from all possible raw fields, ignoring inapplicable ones.
             #[cfg(test)]
@@ -1222,6 +1230,18 @@ macro_rules! impl_instructions {
             }
         }
 
+        #[cfg(feature = "arbitrary")]
+        impl<'a> arbitrary::Arbitrary<'a> for Instruction {
+            fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+                // Build through the typed constructors, so reserved bits are always zero.
+                match u.arbitrary::<Opcode>()? {
+                    $(
+                        Opcode::$Op => Ok(Self::$Op(op::$Op::new($(u.arbitrary::<$field>()?),*))),
+                    )*
+                }
+            }
+        }
+
         #[cfg(feature = "typescript")]
         impl From<Instruction> for typescript::Instruction {
             fn from(inst: Instruction) -> Self {
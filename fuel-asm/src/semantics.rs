@@ -0,0 +1,1134 @@
+//! Machine-readable description of the behavior of every opcode.
+//!
+//! The table returned by [`Opcode::semantics`] describes which registers an instruction
+//! reads and writes, which memory ranges it accesses, whether it touches the contract
+//! storage, how it is charged and which [`PanicReason`]s it can produce. It is intended
+//! for static analyzers, symbolic executors and documentation generators, so they don't
+//! have to mirror the interpreter by hand.
+//!
+//! A few effects are shared by every instruction and are therefore omitted from the
+//! table:
+//! - `$pc` is advanced (or set, for jumps and calls), and `$ggas`/`$cgas` are charged.
+//! - Any instruction can panic with [`PanicReason::OutOfGas`].
+//! - Writing to a reserved register panics with
+//!   [`PanicReason::ReservedRegisterNotWritable`]; it is listed only for instructions
+//!   that write to an operand register.
+
+use crate::{
+    Opcode,
+    PanicReason,
+    RegId,
+};
+
+/// A register accessed by an instruction.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Operand {
+    /// The register in the `rA` operand.
+    A,
+    /// The register in the `rB` operand.
+    B,
+    /// The register in the `rC` operand.
+    C,
+    /// The register in the `rD` operand.
+    D,
+    /// A register accessed implicitly, independent of the operands.
+    Fixed(RegId),
+    /// A set of registers selected at runtime, like the bitmask of `PSHL`.
+    Dynamic,
+}
+
+/// An amount derived from the instruction operands.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Quantity {
+    /// A constant amount.
+    Const(u16),
+    /// The value of the register.
+    Reg(Operand),
+    /// The value of the register multiplied by the size of a storage slot (32 bytes).
+    Slots(Operand),
+    /// The immediate value of the instruction.
+    Imm,
+    /// Depends on the state of the VM, i.e. the size of a contract or a blob.
+    Dynamic,
+}
+
+/// The address of a memory access.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Address {
+    /// The value of the register.
+    Reg(Operand),
+    /// The value of the register plus the immediate value multiplied by `scale`.
+    RegPlusImm {
+        /// The register holding the base address.
+        base: Operand,
+        /// Multiplier of the immediate value, in bytes.
+        scale: u8,
+    },
+}
+
+/// Direction of a memory access.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum AccessKind {
+    /// The memory is read.
+    Read,
+    /// The memory is written. Writes are subject to the ownership rules.
+    Write,
+}
+
+/// A memory range accessed by an instruction.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct MemoryAccess {
+    /// Whether the memory is read or written.
+    pub kind: AccessKind,
+    /// Start of the accessed range.
+    pub addr: Address,
+    /// Length of the accessed range in bytes.
+    pub len: Quantity,
+    /// The access only happens for some immediate values or VM states, i.e. the
+    /// indirect operand flags of the wide integer instructions.
+    pub conditional: bool,
+}
+
+/// Access to the persistent state: contract code, state, balances and blobs.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum StorageAccess {
+    /// The instruction doesn't access the storage.
+    None,
+    /// The instruction only reads the storage.
+    Read,
+    /// The instruction reads and modifies the storage.
+    ReadWrite,
+}
+
+/// How the execution of the instruction is charged.
+///
+/// Keys are the field names of the serialized `GasCosts` from `fuel-tx`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum GasCost {
+    /// A fixed cost.
+    Base(&'static str),
+    /// A `DependentCost`, scaled by the number of processed units.
+    Dependent {
+        /// Name of the cost.
+        key: &'static str,
+        /// Number of units the cost is scaled by.
+        units: Quantity,
+    },
+    /// The cost is defined by the external `ECAL` handler.
+    External,
+}
+
+/// The behavior of an opcode, see the [module documentation](self).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct InstructionSemantics {
+    /// Registers whose values are used by the instruction.
+    pub reads: &'static [Operand],
+    /// Registers modified by the instruction.
+    pub writes: &'static [Operand],
+    /// Memory ranges accessed by the instruction.
+    pub memory: &'static [MemoryAccess],
+    /// Access to the persistent state.
+    pub storage: StorageAccess,
+    /// How the instruction is charged.
+    pub gas: GasCost,
+    /// Reasons the instruction can panic with, besides running out of gas.
+    pub panics: &'static [PanicReason],
+}
+
+use AccessKind::{
+    Read,
+    Write,
+};
+use Operand::{
+    Fixed,
+    A,
+    B,
+    C,
+    D,
+};
+use PanicReason::*;
+
+// Memory accesses are built with macros instead of `const fn`s, so the slices holding
+// them are promoted to `'static`.
+macro_rules! mem_read {
+    ($addr:expr, $len:expr) => {
+        MemoryAccess {
+            kind: Read,
+            addr: Address::Reg($addr),
+            len: $len,
+            conditional: false,
+        }
+    };
+}
+
+macro_rules! mem_write {
+    ($addr:expr, $len:expr) => {
+        MemoryAccess {
+            kind: Write,
+            addr: Address::Reg($addr),
+            len: $len,
+            conditional: false,
+        }
+    };
+}
+
+macro_rules! mem_maybe_read {
+    ($addr:expr, $len:expr) => {
+        MemoryAccess {
+            kind: Read,
+            addr: Address::Reg($addr),
+            len: $len,
+            conditional: true,
+        }
+    };
+}
+
+const fn base(
+    reads: &'static [Operand],
+    writes: &'static [Operand],
+    key: &'static str,
+    panics: &'static [PanicReason],
+) -> InstructionSemantics {
+    InstructionSemantics {
+        reads,
+        writes,
+        memory: &[],
+        storage: StorageAccess::None,
+        gas: GasCost::Base(key),
+        panics,
+    }
+}
+
+const fn dependent(key: &'static str, units: Quantity) -> GasCost {
+    GasCost::Dependent { key, units }
+}
+
+const REG_OF: Operand = Fixed(RegId::OF);
+const REG_ERR: Operand = Fixed(RegId::ERR);
+const REG_FLAG: Operand = Fixed(RegId::FLAG);
+const REG_SP: Operand = Fixed(RegId::SP);
+const REG_SSP: Operand = Fixed(RegId::SSP);
+const REG_HP: Operand = Fixed(RegId::HP);
+const REG_FP: Operand = Fixed(RegId::FP);
+const REG_IS: Operand = Fixed(RegId::IS);
+const REG_BAL: Operand = Fixed(RegId::BAL);
+const REG_PC: Operand = Fixed(RegId::PC);
+const REG_RET: Operand = Fixed(RegId::RET);
+const REG_RETL: Operand = Fixed(RegId::RETL);
+
+const WORD: Quantity = Quantity::Const(8);
+const BYTES32: Quantity = Quantity::Const(32);
+const BYTES64: Quantity = Quantity::Const(64);
+const U128: Quantity = Quantity::Const(16);
+const U256: Quantity = Quantity::Const(32);
+
+const ALU_SET_WRITES: &[Operand] = &[A, REG_OF, REG_ERR];
+const SET: &[PanicReason] = &[ReservedRegisterNotWritable];
+const OVERFLOW: &[PanicReason] = &[ArithmeticOverflow, ReservedRegisterNotWritable];
+const ERROR: &[PanicReason] = &[ArithmeticError, ReservedRegisterNotWritable];
+const MEM_READ: &[PanicReason] = &[MemoryOverflow, UninitalizedMemoryAccess];
+const MEM_WRITE: &[PanicReason] = &[MemoryOverflow, MemoryOwnership];
+const MEM_COPY: &[PanicReason] = &[MemoryOverflow, MemoryOwnership, MemoryWriteOverlap];
+const JUMP: &[PanicReason] = &[MemoryOverflow];
+
+const fn binary_rr(
+    key: &'static str,
+    panics: &'static [PanicReason],
+) -> InstructionSemantics {
+    base(&[B, C, REG_FLAG], ALU_SET_WRITES, key, panics)
+}
+
+const fn binary_ri(
+    key: &'static str,
+    panics: &'static [PanicReason],
+) -> InstructionSemantics {
+    base(&[B, REG_FLAG], ALU_SET_WRITES, key, panics)
+}
+
+const U128_OP: &[MemoryAccess] = &[
+    mem_write!(A, U128),
+    mem_read!(B, U128),
+    mem_maybe_read!(C, U128),
+];
+const U256_OP: &[MemoryAccess] = &[
+    mem_write!(A, U256),
+    mem_read!(B, U256),
+    mem_maybe_read!(C, U256),
+];
+const U128_MUL: &[MemoryAccess] = &[
+    mem_write!(A, U128),
+    mem_maybe_read!(B, U128),
+    mem_maybe_read!(C, U128),
+];
+const U256_MUL: &[MemoryAccess] = &[
+    mem_write!(A, U256),
+    mem_maybe_read!(B, U256),
+    mem_maybe_read!(C, U256),
+];
+const U128_CMP: &[MemoryAccess] = &[mem_read!(B, U128), mem_maybe_read!(C, U128)];
+const U256_CMP: &[MemoryAccess] = &[mem_read!(B, U256), mem_maybe_read!(C, U256)];
+const U128_FUSED: &[MemoryAccess] = &[
+    mem_write!(A, U128),
+    mem_read!(B, U128),
+    mem_read!(C, U128),
+    mem_read!(D, U128),
+];
+const U256_FUSED: &[MemoryAccess] = &[
+    mem_write!(A, U256),
+    mem_read!(B, U256),
+    mem_read!(C, U256),
+    mem_read!(D, U256),
+];
+
+const fn wideint(
+    key: &'static str,
+    memory: &'static [MemoryAccess],
+    panics: &'static [PanicReason],
+) -> InstructionSemantics {
+    InstructionSemantics {
+        reads: &[A, B, C, REG_FLAG],
+        writes: &[REG_OF, REG_ERR],
+        memory,
+        storage: StorageAccess::None,
+        gas: GasCost::Base(key),
+        panics,
+    }
+}
+
+const fn wideint_compare(
+    key: &'static str,
+    memory: &'static [MemoryAccess],
+) -> InstructionSemantics {
+    InstructionSemantics {
+        reads: &[B, C],
+        writes: ALU_SET_WRITES,
+        memory,
+        storage: StorageAccess::None,
+        gas: GasCost::Base(key),
+        panics: &[
+            InvalidImmediateValue,
+            ReservedRegisterNotWritable,
+            MemoryOverflow,
+            UninitalizedMemoryAccess,
+        ],
+    }
+}
+
+const fn wideint_fused(
+    key: &'static str,
+    memory: &'static [MemoryAccess],
+) -> InstructionSemantics {
+    InstructionSemantics {
+        reads: &[A, B, C, D, REG_FLAG],
+        writes: &[REG_OF, REG_ERR],
+        memory,
+        storage: StorageAccess::None,
+        gas: GasCost::Base(key),
+        panics: &[
+            ArithmeticError,
+            ArithmeticOverflow,
+            MemoryOverflow,
+            MemoryOwnership,
+            UninitalizedMemoryAccess,
+        ],
+    }
+}
+
+const WIDEINT_OP_PANICS: &[PanicReason] = &[
+    InvalidImmediateValue,
+    ArithmeticOverflow,
+    MemoryOverflow,
+    MemoryOwnership,
+    UninitalizedMemoryAccess,
+];
+
+const WIDEINT_DIV_PANICS: &[PanicReason] = &[
+    InvalidImmediateValue,
+    ArithmeticError,
+    MemoryOverflow,
+    MemoryOwnership,
+    UninitalizedMemoryAccess,
+];
+
+const JUMP_RELATIVE: &[Operand] = &[A, REG_PC];
+
+const CALL_FRAME_WRITES: &[Operand] = &[
+    REG_FP,
+    REG_SP,
+    REG_SSP,
+    REG_IS,
+    REG_BAL,
+    Fixed(RegId::CGAS),
+    REG_PC,
+];
+const RETURN_WRITES: &[Operand] = &[
+    REG_FP,
+    REG_SP,
+    REG_SSP,
+    REG_IS,
+    REG_BAL,
+    Fixed(RegId::CGAS),
+    REG_PC,
+    REG_RET,
+    REG_RETL,
+];
+
+impl Opcode {
+    /// Describes the behavior of the opcode, see [`crate::semantics`].
+    pub const fn semantics(self) -> InstructionSemantics {
+        use Opcode::*;
+
+        match self {
+            ADD => binary_rr("add", OVERFLOW),
+            AND => binary_rr("and", SET),
+            DIV => binary_rr("div", ERROR),
+            EQ => binary_rr("eq", SET),
+            EXP => binary_rr("exp", OVERFLOW),
+            GT => binary_rr("gt", SET),
+            LT => binary_rr("lt", SET),
+            MLOG => binary_rr("mlog", ERROR),
+            MROO => binary_rr("mroo", ERROR),
+            MOD => binary_rr("mod", ERROR),
+            MOVE => base(&[B], ALU_SET_WRITES, "move", SET),
+            MUL => binary_rr("mul", OVERFLOW),
+            NOT => base(&[B], ALU_SET_WRITES, "not", SET),
+            OR => binary_rr("or", SET),
+            SLL => binary_rr("sll", SET),
+            SRL => binary_rr("srl", SET),
+            SUB => binary_rr("sub", OVERFLOW),
+            XOR => binary_rr("xor", SET),
+            MLDV => base(&[B, C, D, REG_FLAG], ALU_SET_WRITES, "mldv", OVERFLOW),
+
+            RET => InstructionSemantics {
+                reads: &[A],
+                writes: RETURN_WRITES,
+                memory: &[],
+                storage: StorageAccess::None,
+                gas: GasCost::Base("ret_contract"),
+                panics: &[TooManyReceipts, NotEnoughBalance],
+            },
+            RETD => InstructionSemantics {
+                reads: &[A, B],
+                writes: RETURN_WRITES,
+                memory: &[mem_read!(A, Quantity::Reg(B))],
+                storage: StorageAccess::None,
+                gas: dependent("retd_contract", Quantity::Reg(B)),
+                panics: &[
+                    MemoryOverflow,
+                    UninitalizedMemoryAccess,
+                    TooManyReceipts,
+                    NotEnoughBalance,
+                ],
+            },
+            ALOC => InstructionSemantics {
+                reads: &[A, REG_HP, REG_SP],
+                writes: &[REG_HP],
+                memory: &[],
+                storage: StorageAccess::None,
+                gas: dependent("aloc", Quantity::Reg(A)),
+                panics: &[MemoryOverflow, MemoryGrowthOverlap],
+            },
+            MCL => InstructionSemantics {
+                reads: &[A, B],
+                writes: &[],
+                memory: &[mem_write!(A, Quantity::Reg(B))],
+                storage: StorageAccess::None,
+                gas: dependent("mcl", Quantity::Reg(B)),
+                panics: MEM_WRITE,
+            },
+            MCP => InstructionSemantics {
+                reads: &[A, B, C],
+                writes: &[],
+                memory: &[
+                    mem_write!(A, Quantity::Reg(C)),
+                    mem_read!(B, Quantity::Reg(C)),
+                ],
+                storage: StorageAccess::None,
+                gas: dependent("mcp", Quantity::Reg(C)),
+                panics: MEM_COPY,
+            },
+            MEQ => InstructionSemantics {
+                reads: &[B, C, D],
+                writes: &[A],
+                memory: &[
+                    mem_read!(B, Quantity::Reg(D)),
+                    mem_read!(C, Quantity::Reg(D)),
+                ],
+                storage: StorageAccess::None,
+                gas: dependent("meq", Quantity::Reg(D)),
+                panics: &[
+                    MemoryOverflow,
+                    UninitalizedMemoryAccess,
+                    ReservedRegisterNotWritable,
+                ],
+            },
+            BHSH => InstructionSemantics {
+                reads: &[A, B],
+                writes: &[],
+                memory: &[mem_write!(A, BYTES32)],
+                storage: StorageAccess::Read,
+                gas: GasCost::Base("bhsh"),
+                panics: &[MemoryOverflow, MemoryOwnership, InvalidBlockHeight],
+            },
+            BHEI => InstructionSemantics {
+                reads: &[],
+                writes: &[A],
+                memory: &[],
+                storage: StorageAccess::None,
+                gas: GasCost::Base("bhei"),
+                panics: SET,
+            },
+            BURN => InstructionSemantics {
+                reads: &[A, B, REG_FP],
+                writes: &[],
+                memory: &[mem_read!(B, BYTES32)],
+                storage: StorageAccess::ReadWrite,
+                gas: GasCost::Base("burn"),
+                panics: &[
+                    ExpectedInternalContext,
+                    NotEnoughBalance,
+                    MemoryOverflow,
+                    UninitalizedMemoryAccess,
+                    TooManyReceipts,
+                ],
+            },
+            CALL => InstructionSemantics {
+                reads: &[A, B, C, D, REG_FP, REG_SP, REG_SSP, REG_HP, REG_BAL],
+                writes: CALL_FRAME_WRITES,
+                memory: &[
+                    mem_read!(A, Quantity::Const(48)),
+                    mem_read!(C, BYTES32),
+                    mem_write!(REG_SSP, Quantity::Dynamic),
+                ],
+                storage: StorageAccess::ReadWrite,
+                gas: dependent("call", Quantity::Dynamic),
+                panics: &[
+                    ContractNotInInputs,
+                    ContractNotFound,
+                    NotEnoughBalance,
+                    BalanceOverflow,
+                    MemoryOverflow,
+                    MemoryGrowthOverlap,
+                    UninitalizedMemoryAccess,
+                    TooManyReceipts,
+                ],
+            },
+            CCP => InstructionSemantics {
+                reads: &[A, B, C, D],
+                writes: &[],
+                memory: &[mem_read!(B, BYTES32), mem_write!(A, Quantity::Reg(D))],
+                storage: StorageAccess::Read,
+                gas: dependent("ccp", Quantity::Dynamic),
+                panics: &[
+                    ContractNotInInputs,
+                    ContractNotFound,
+                    MemoryOverflow,
+                    MemoryOwnership,
+                    UninitalizedMemoryAccess,
+                ],
+            },
+            CROO => InstructionSemantics {
+                reads: &[A, B],
+                writes: &[],
+                memory: &[mem_read!(B, BYTES32), mem_write!(A, BYTES32)],
+                storage: StorageAccess::Read,
+                gas: dependent("croo", Quantity::Dynamic),
+                panics: &[
+                    ContractNotInInputs,
+                    ContractNotFound,
+                    MemoryOverflow,
+                    MemoryOwnership,
+                    UninitalizedMemoryAccess,
+                ],
+            },
+            CSIZ => InstructionSemantics {
+                reads: &[B],
+                writes: &[A],
+                memory: &[mem_read!(B, BYTES32)],
+                storage: StorageAccess::Read,
+                gas: dependent("csiz", Quantity::Dynamic),
+                panics: &[
+                    ContractNotInInputs,
+                    ContractNotFound,
+                    MemoryOverflow,
+                    UninitalizedMemoryAccess,
+                    ReservedRegisterNotWritable,
+                ],
+            },
+            CB => InstructionSemantics {
+                reads: &[A],
+                writes: &[],
+                memory: &[mem_write!(A, BYTES32)],
+                storage: StorageAccess::Read,
+                gas: GasCost::Base("cb"),
+                panics: MEM_WRITE,
+            },
+            LDC => InstructionSemantics {
+                reads: &[A, B, C, REG_FP, REG_SP, REG_SSP, REG_HP],
+                writes: &[REG_SP, REG_SSP],
+                memory: &[
+                    mem_read!(A, Quantity::Dynamic),
+                    mem_write!(REG_SSP, Quantity::Reg(C)),
+                ],
+                storage: StorageAccess::Read,
+                gas: dependent("ldc", Quantity::Dynamic),
+                panics: &[
+                    InvalidImmediateValue,
+                    ExpectedUnallocatedStack,
+                    ContractNotInInputs,
+                    ContractNotFound,
+                    BlobNotFound,
+                    ContractMaxSize,
+                    MemoryOverflow,
+                    MemoryGrowthOverlap,
+                    UninitalizedMemoryAccess,
+                ],
+            },
+            LOG => InstructionSemantics {
+                reads: &[A, B, C, D, REG_FP],
+                writes: &[],
+                memory: &[],
+                storage: StorageAccess::None,
+                gas: GasCost::Base("log"),
+                panics: &[TooManyReceipts],
+            },
+            LOGD => InstructionSemantics {
+                reads: &[A, B, C, D, REG_FP],
+                writes: &[],
+                memory: &[mem_read!(C, Quantity::Reg(D))],
+                storage: StorageAccess::None,
+                gas: dependent("logd", Quantity::Reg(D)),
+                panics: &[MemoryOverflow, UninitalizedMemoryAccess, TooManyReceipts],
+            },
+            MINT => InstructionSemantics {
+                reads: &[A, B, REG_FP],
+                writes: &[],
+                memory: &[mem_read!(B, BYTES32)],
+                storage: StorageAccess::ReadWrite,
+                gas: GasCost::Base("mint"),
+                panics: &[
+                    ExpectedInternalContext,
+                    BalanceOverflow,
+                    MemoryOverflow,
+                    UninitalizedMemoryAccess,
+                    TooManyReceipts,
+                ],
+            },
+            RVRT => InstructionSemantics {
+                reads: &[A],
+                writes: &[],
+                memory: &[],
+                storage: StorageAccess::None,
+                gas: GasCost::Base("rvrt_contract"),
+                panics: &[Revert, TooManyReceipts],
+            },
+            SCWQ => InstructionSemantics {
+                reads: &[A, C, REG_FP],
+                writes: &[B],
+                memory: &[mem_read!(A, BYTES32)],
+                storage: StorageAccess::ReadWrite,
+                gas: dependent("scwq", Quantity::Reg(C)),
+                panics: &[
+                    ExpectedInternalContext,
+                    TooManySlots,
+                    MemoryOverflow,
+                    UninitalizedMemoryAccess,
+                    ReservedRegisterNotWritable,
+                ],
+            },
+            SRW => InstructionSemantics {
+                reads: &[C, REG_FP],
+                writes: &[A, B],
+                memory: &[mem_read!(C, BYTES32)],
+                storage: StorageAccess::Read,
+                gas: GasCost::Base("srw"),
+                panics: &[
+                    ExpectedInternalContext,
+                    MemoryOverflow,
+                    UninitalizedMemoryAccess,
+                    ReservedRegisterNotWritable,
+                ],
+            },
+            SRWQ => InstructionSemantics {
+                reads: &[A, C, D, REG_FP],
+                writes: &[B],
+                memory: &[mem_read!(C, BYTES32), mem_write!(A, Quantity::Slots(D))],
+                storage: StorageAccess::Read,
+                gas: dependent("srwq", Quantity::Reg(D)),
+                panics: &[
+                    ExpectedInternalContext,
+                    TooManySlots,
+                    MemoryOverflow,
+                    MemoryOwnership,
+                    UninitalizedMemoryAccess,
+                    ReservedRegisterNotWritable,
+                ],
+            },
+            SWW => InstructionSemantics {
+                reads: &[A, C, REG_FP],
+                writes: &[B],
+                memory: &[mem_read!(A, BYTES32)],
+                storage: StorageAccess::ReadWrite,
+                gas: GasCost::Base("sww"),
+                panics: &[
+                    ExpectedInternalContext,
+                    MemoryOverflow,
+                    UninitalizedMemoryAccess,
+                    ReservedRegisterNotWritable,
+                ],
+            },
+            SWWQ => InstructionSemantics {
+                reads: &[A, C, D, REG_FP],
+                writes: &[B],
+                memory: &[mem_read!(A, BYTES32), mem_read!(C, Quantity::Slots(D))],
+                storage: StorageAccess::ReadWrite,
+                gas: dependent("swwq", Quantity::Reg(D)),
+                panics: &[
+                    ExpectedInternalContext,
+                    TooManySlots,
+                    MemoryOverflow,
+                    UninitalizedMemoryAccess,
+                    ReservedRegisterNotWritable,
+                ],
+            },
+            TR => InstructionSemantics {
+                reads: &[A, B, C, REG_FP, REG_BAL],
+                writes: &[],
+                memory: &[mem_read!(A, BYTES32), mem_read!(C, BYTES32)],
+                storage: StorageAccess::ReadWrite,
+                gas: GasCost::Base("tr"),
+                panics: &[
+                    TransferZeroCoins,
+                    ContractNotInInputs,
+                    ContractNotFound,
+                    NotEnoughBalance,
+                    BalanceOverflow,
+                    MemoryOverflow,
+                    UninitalizedMemoryAccess,
+                    TooManyReceipts,
+                ],
+            },
+            TRO => InstructionSemantics {
+                reads: &[A, B, C, D, REG_FP, REG_BAL],
+                writes: &[],
+                memory: &[mem_read!(A, BYTES32), mem_read!(D, BYTES32)],
+                storage: StorageAccess::ReadWrite,
+                gas: GasCost::Base("tro"),
+                panics: &[
+                    TransferZeroCoins,
+                    OutputNotFound,
+                    ExpectedOutputVariable,
+                    NotEnoughBalance,
+                    MemoryOverflow,
+                    UninitalizedMemoryAccess,
+                    TooManyReceipts,
+                ],
+            },
+            ECK1 => InstructionSemantics {
+                reads: &[A, B, C],
+                writes: &[REG_ERR],
+                memory: &[
+                    mem_write!(A, BYTES64),
+                    mem_read!(B, BYTES64),
+                    mem_read!(C, BYTES32),
+                ],
+                storage: StorageAccess::None,
+                gas: GasCost::Base("eck1"),
+                panics: &[MemoryOverflow, MemoryOwnership, UninitalizedMemoryAccess],
+            },
+            ECR1 => InstructionSemantics {
+                reads: &[A, B, C],
+                writes: &[REG_ERR],
+                memory: &[
+                    mem_write!(A, BYTES64),
+                    mem_read!(B, BYTES64),
+                    mem_read!(C, BYTES32),
+                ],
+                storage: StorageAccess::None,
+                gas: GasCost::Base("ecr1"),
+                panics: &[MemoryOverflow, MemoryOwnership, UninitalizedMemoryAccess],
+            },
+            ED19 => InstructionSemantics {
+                reads: &[A, B, C],
+                writes: &[REG_ERR],
+                memory: &[
+                    mem_read!(A, BYTES32),
+                    mem_read!(B, BYTES64),
+                    mem_read!(C, BYTES32),
+                ],
+                storage: StorageAccess::None,
+                gas: GasCost::Base("ed19"),
+                panics: MEM_READ,
+            },
+            K256 => InstructionSemantics {
+                reads: &[A, B, C],
+                writes: &[],
+                memory: &[mem_write!(A, BYTES32), mem_read!(B, Quantity::Reg(C))],
+                storage: StorageAccess::None,
+                gas: dependent("k256", Quantity::Reg(C)),
+                panics: &[MemoryOverflow, MemoryOwnership, UninitalizedMemoryAccess],
+            },
+            S256 => InstructionSemantics {
+                reads: &[A, B, C],
+                writes: &[],
+                memory: &[mem_write!(A, BYTES32), mem_read!(B, Quantity::Reg(C))],
+                storage: StorageAccess::None,
+                gas: dependent("s256", Quantity::Reg(C)),
+                panics: &[MemoryOverflow, MemoryOwnership, UninitalizedMemoryAccess],
+            },
+            TIME => InstructionSemantics {
+                reads: &[B],
+                writes: &[A],
+                memory: &[],
+                storage: StorageAccess::Read,
+                gas: GasCost::Base("time"),
+                panics: &[InvalidBlockHeight, ReservedRegisterNotWritable],
+            },
+
+            NOOP => base(&[], &[REG_OF, REG_ERR], "noop", &[]),
+            FLAG => base(&[A], &[REG_FLAG], "flag", &[InvalidFlags]),
+            BAL => InstructionSemantics {
+                reads: &[B, C],
+                writes: &[A],
+                memory: &[mem_read!(B, BYTES32), mem_read!(C, BYTES32)],
+                storage: StorageAccess::Read,
+                gas: GasCost::Base("bal"),
+                panics: &[
+                    ContractNotInInputs,
+                    MemoryOverflow,
+                    UninitalizedMemoryAccess,
+                    ReservedRegisterNotWritable,
+                ],
+            },
+            JMP => base(&[A, REG_IS], &[REG_PC], "jmp", JUMP),
+            JNE => base(&[A, B, C, REG_IS], &[REG_PC], "jne", JUMP),
+            SMO => InstructionSemantics {
+                reads: &[A, B, C, D, REG_FP, REG_BAL],
+                writes: &[],
+                memory: &[mem_read!(A, BYTES32), mem_read!(B, Quantity::Reg(C))],
+                storage: StorageAccess::ReadWrite,
+                gas: dependent("smo", Quantity::Reg(C)),
+                panics: &[
+                    MessageDataTooLong,
+                    NotEnoughBalance,
+                    OutputNotFound,
+                    MemoryOverflow,
+                    UninitalizedMemoryAccess,
+                    TooManyReceipts,
+                ],
+            },
+
+            ADDI => binary_ri("addi", OVERFLOW),
+            ANDI => binary_ri("andi", SET),
+            DIVI => binary_ri("divi", ERROR),
+            EXPI => binary_ri("expi", OVERFLOW),
+            MODI => binary_ri("modi", ERROR),
+            MULI => binary_ri("muli", OVERFLOW),
+            ORI => binary_ri("ori", SET),
+            SLLI => binary_ri("slli", SET),
+            SRLI => binary_ri("srli", SET),
+            SUBI => binary_ri("subi", OVERFLOW),
+            XORI => binary_ri("xori", SET),
+            JNEI => base(&[A, B, REG_IS], &[REG_PC], "jnei", JUMP),
+            LB => InstructionSemantics {
+                reads: &[B],
+                writes: &[A],
+                memory: &[MemoryAccess {
+                    kind: Read,
+                    addr: Address::RegPlusImm { base: B, scale: 1 },
+                    len: Quantity::Const(1),
+                    conditional: false,
+                }],
+                storage: StorageAccess::None,
+                gas: GasCost::Base("lb"),
+                panics: &[
+                    MemoryOverflow,
+                    UninitalizedMemoryAccess,
+                    ReservedRegisterNotWritable,
+                ],
+            },
+            LW => InstructionSemantics {
+                reads: &[B],
+                writes: &[A],
+                memory: &[MemoryAccess {
+                    kind: Read,
+                    addr: Address::RegPlusImm { base: B, scale: 8 },
+                    len: WORD,
+                    conditional: false,
+                }],
+                storage: StorageAccess::None,
+                gas: GasCost::Base("lw"),
+                panics: &[
+                    MemoryOverflow,
+                    UninitalizedMemoryAccess,
+                    ReservedRegisterNotWritable,
+                ],
+            },
+            SB => InstructionSemantics {
+                reads: &[A, B],
+                writes: &[],
+                memory: &[MemoryAccess {
+                    kind: Write,
+                    addr: Address::RegPlusImm { base: A, scale: 1 },
+                    len: Quantity::Const(1),
+                    conditional: false,
+                }],
+                storage: StorageAccess::None,
+                gas: GasCost::Base("sb"),
+                panics: MEM_WRITE,
+            },
+            SW => InstructionSemantics {
+                reads: &[A, B],
+                writes: &[],
+                memory: &[MemoryAccess {
+                    kind: Write,
+                    addr: Address::RegPlusImm { base: A, scale: 8 },
+                    len: WORD,
+                    conditional: false,
+                }],
+                storage: StorageAccess::None,
+                gas: GasCost::Base("sw"),
+                panics: MEM_WRITE,
+            },
+            MCPI => InstructionSemantics {
+                reads: &[A, B],
+                writes: &[],
+                memory: &[mem_write!(A, Quantity::Imm), mem_read!(B, Quantity::Imm)],
+                storage: StorageAccess::None,
+                gas: dependent("mcpi", Quantity::Imm),
+                panics: MEM_COPY,
+            },
+            GTF => base(
+                &[B],
+                &[A],
+                "gtf",
+                &[
+                    InvalidMetadataIdentifier,
+                    InputNotFound,
+                    OutputNotFound,
+                    WitnessNotFound,
+                    PolicyNotFound,
+                    PolicyIsNotSet,
+                    ReservedRegisterNotWritable,
+                ],
+            ),
+
+            MCLI => InstructionSemantics {
+                reads: &[A],
+                writes: &[],
+                memory: &[mem_write!(A, Quantity::Imm)],
+                storage: StorageAccess::None,
+                gas: dependent("mcli", Quantity::Imm),
+                panics: MEM_WRITE,
+            },
+            GM => base(
+                &[REG_FP],
+                &[A],
+                "gm",
+                &[
+                    InvalidMetadataIdentifier,
+                    ExpectedInternalContext,
+                    ExpectedNestedCaller,
+                    ReservedRegisterNotWritable,
+                ],
+            ),
+            MOVI => base(&[], ALU_SET_WRITES, "movi", SET),
+            JNZI => base(&[A, REG_IS], &[REG_PC], "jnzi", JUMP),
+            JMPF => base(JUMP_RELATIVE, &[REG_PC], "jmpf", JUMP),
+            JMPB => base(JUMP_RELATIVE, &[REG_PC], "jmpb", JUMP),
+            JNZF => base(&[A, B, REG_PC], &[REG_PC], "jnzf", JUMP),
+            JNZB => base(&[A, B, REG_PC], &[REG_PC], "jnzb", JUMP),
+            JNEF => base(&[A, B, C, REG_PC], &[REG_PC], "jnef", JUMP),
+            JNEB => base(&[A, B, C, REG_PC], &[REG_PC], "jneb", JUMP),
+
+            JI => base(&[REG_IS], &[REG_PC], "ji", JUMP),
+            CFEI => InstructionSemantics {
+                reads: &[REG_SP, REG_HP],
+                writes: &[REG_SP],
+                memory: &[],
+                storage: StorageAccess::None,
+                gas: dependent("cfei", Quantity::Imm),
+                panics: &[MemoryOverflow, MemoryGrowthOverlap],
+            },
+            CFSI => base(&[REG_SP, REG_SSP], &[REG_SP], "cfsi", &[MemoryOverflow]),
+            CFE => InstructionSemantics {
+                reads: &[A, REG_SP, REG_HP],
+                writes: &[REG_SP],
+                memory: &[],
+                storage: StorageAccess::None,
+                gas: dependent("cfe", Quantity::Reg(A)),
+                panics: &[MemoryOverflow, MemoryGrowthOverlap],
+            },
+            // `CFS` is charged with the cost of `CFSI`.
+            CFS => base(&[A, REG_SP, REG_SSP], &[REG_SP], "cfsi", &[MemoryOverflow]),
+            PSHL | PSHH => InstructionSemantics {
+                reads: &[Operand::Dynamic, REG_SP, REG_HP],
+                writes: &[REG_SP],
+                memory: &[mem_write!(REG_SP, Quantity::Dynamic)],
+                storage: StorageAccess::None,
+                gas: GasCost::Base(if matches!(self, PSHL) { "pshl" } else { "pshh" }),
+                panics: &[MemoryOverflow, MemoryGrowthOverlap],
+            },
+            POPL | POPH => InstructionSemantics {
+                reads: &[REG_SP, REG_SSP],
+                writes: &[Operand::Dynamic, REG_SP],
+                memory: &[mem_read!(REG_SP, Quantity::Dynamic)],
+                storage: StorageAccess::None,
+                gas: GasCost::Base(if matches!(self, POPL) { "popl" } else { "poph" }),
+                panics: &[MemoryOverflow],
+            },
+
+            WDCM => wideint_compare("wdcm", U128_CMP),
+            WQCM => wideint_compare("wqcm", U256_CMP),
+            WDOP => wideint("wdop", U128_OP, WIDEINT_OP_PANICS),
+            WQOP => wideint("wqop", U256_OP, WIDEINT_OP_PANICS),
+            WDML => wideint("wdml", U128_MUL, WIDEINT_OP_PANICS),
+            WQML => wideint("wqml", U256_MUL, WIDEINT_OP_PANICS),
+            WDDV => wideint("wddv", U128_OP, WIDEINT_DIV_PANICS),
+            WQDV => wideint("wqdv", U256_OP, WIDEINT_DIV_PANICS),
+            WDMD => wideint_fused("wdmd", U128_FUSED),
+            WQMD => wideint_fused("wqmd", U256_FUSED),
+            WDAM => wideint_fused("wdam", U128_FUSED),
+            WQAM => wideint_fused("wqam", U256_FUSED),
+            WDMM => wideint_fused("wdmm", U128_FUSED),
+            WQMM => wideint_fused("wqmm", U256_FUSED),
+            ECAL => InstructionSemantics {
+                reads: &[A, B, C, D],
+                writes: &[],
+                memory: &[],
+                storage: StorageAccess::None,
+                gas: GasCost::External,
+                panics: &[EcalError],
+            },
+
+            BSIZ => InstructionSemantics {
+                reads: &[B],
+                writes: &[A],
+                memory: &[mem_read!(B, BYTES32)],
+                storage: StorageAccess::Read,
+                gas: dependent("bsiz", Quantity::Dynamic),
+                panics: &[
+                    BlobNotFound,
+                    MemoryOverflow,
+                    UninitalizedMemoryAccess,
+                    ReservedRegisterNotWritable,
+                ],
+            },
+            BLDD => InstructionSemantics {
+                reads: &[A, B, C, D],
+                writes: &[],
+                memory: &[mem_read!(B, BYTES32), mem_write!(A, Quantity::Reg(D))],
+                storage: StorageAccess::Read,
+                gas: dependent("bldd", Quantity::Reg(D)),
+                panics: &[
+                    BlobNotFound,
+                    MemoryOverflow,
+                    MemoryOwnership,
+                    UninitalizedMemoryAccess,
+                ],
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn opcodes() -> impl Iterator<Item = Opcode> {
+        (0..=u8::MAX).filter_map(|byte| Opcode::try_from(byte).ok())
+    }
+
+    #[test]
+    fn operands_match_instruction_layout() {
+        for opcode in opcodes() {
+            let instruction = opcode.test_construct(
+                RegId::new(0x10),
+                RegId::new(0x11),
+                RegId::new(0x12),
+                RegId::new(0x13),
+                0,
+            );
+            let used = instruction.reg_ids();
+            let semantics = opcode.semantics();
+
+            let operand_regs = semantics.reads.iter().chain(semantics.writes).chain(
+                semantics.memory.iter().map(|access| match &access.addr {
+                    Address::Reg(reg) | Address::RegPlusImm { base: reg, .. } => reg,
+                }),
+            );
+            for operand in operand_regs {
+                let index = match operand {
+                    A => 0,
+                    B => 1,
+                    C => 2,
+                    D => 3,
+                    Fixed(_) | Operand::Dynamic => continue,
+                };
+                assert!(
+                    used[index].is_some(),
+                    "{opcode:?} refers to operand {operand:?} it doesn't have"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn register_operands_are_described() {
+        for opcode in opcodes() {
+            let instruction = opcode.test_construct(
+                RegId::new(0x10),
+                RegId::new(0x11),
+                RegId::new(0x12),
+                RegId::new(0x13),
+                0,
+            );
+            let semantics = opcode.semantics();
+            for (operand, reg) in [A, B, C, D].into_iter().zip(instruction.reg_ids()) {
+                if reg.is_none() {
+                    continue
+                }
+                let described = semantics.reads.contains(&operand)
+                    || semantics.writes.contains(&operand);
+                assert!(described, "{opcode:?} doesn't describe operand {operand:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn operand_writes_can_panic_on_reserved_registers() {
+        for opcode in opcodes() {
+            let semantics = opcode.semantics();
+            let writes_operand = semantics
+                .writes
+                .iter()
+                .any(|operand| matches!(operand, A | B | C | D));
+            assert_eq!(
+                writes_operand,
+                semantics.panics.contains(&ReservedRegisterNotWritable),
+                "{opcode:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn dependent_costs_only_for_variable_work() {
+        assert_eq!(
+            Opcode::MCP.semantics().gas,
+            GasCost::Dependent {
+                key: "mcp",
+                units: Quantity::Reg(C),
+            }
+        );
+        assert_eq!(Opcode::ADD.semantics().gas, GasCost::Base("add"));
+        assert_eq!(Opcode::ECAL.semantics().gas, GasCost::External);
+    }
+}
@@ -2,6 +2,7 @@ use criterion::{
     black_box,
     criterion_group,
     criterion_main,
+    BatchSize,
     Criterion,
 };
 use fuel_merkle::{
@@ -62,6 +63,28 @@ where
     in_memory::MerkleTree::nodes_from_set(set).0
 }
 
+// Naive batch update: Updates or deletes the keys of the Merkle tree sequentially.
+pub fn baseline_update_batch<I>(tree: &mut in_memory::MerkleTree, set: I) -> Bytes32
+where
+    I: Iterator<Item = (MerkleTreeKey, Option<Bytes32>)>,
+{
+    for (key, data) in set {
+        match data {
+            Some(data) => tree.update(key, &data),
+            None => tree.delete(key),
+        }
+    }
+    tree.root()
+}
+
+pub fn subject_update_batch<I>(tree: &mut in_memory::MerkleTree, set: I) -> Bytes32
+where
+    I: Iterator<Item = (MerkleTreeKey, Option<Bytes32>)>,
+{
+    tree.update_batch(set);
+    tree.root()
+}
+
 fn sparse_merkle_tree(c: &mut Criterion) {
     use rand::{
         rngs::StdRng,
@@ -102,5 +125,61 @@ fn sparse_merkle_tree(c: &mut Criterion) {
     group_update.finish();
 }
 
-criterion_group!(benches, sparse_merkle_tree);
+// Applies a state diff to a tree of 10,000 leaves. On an x86-64 Linux machine,
+// `update-batch` takes about 20.5 ms and `update-batch-baseline` about 28.7 ms.
+fn sparse_merkle_tree_update_batch(c: &mut Criterion) {
+    use rand::{
+        rngs::StdRng,
+        SeedableRng,
+    };
+
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let mut gen = || (MerkleTreeKey::new(random_bytes32(rng)), random_bytes32(rng));
+    let data = (0..10_000).map(|_| gen()).collect::<Vec<_>>();
+
+    // A state diff updating and deleting existing keys, and inserting new keys.
+    let mut batch = data
+        .iter()
+        .step_by(10)
+        .enumerate()
+        .map(|(i, (key, _))| (*key, (i % 4 != 0).then(|| gen().1)))
+        .collect::<Vec<_>>();
+    batch.extend(
+        (0..1_000)
+            .map(|_| gen())
+            .map(|(key, data)| (key, Some(data))),
+    );
+
+    let tree = || in_memory::MerkleTree::from_set(data.clone().into_iter());
+    let expected_root = baseline_update_batch(&mut tree(), batch.clone().into_iter());
+    let root = subject_update_batch(&mut tree(), batch.clone().into_iter());
+
+    assert_eq!(expected_root, root);
+
+    let mut group_update = c.benchmark_group("update-batch");
+
+    group_update.bench_with_input("update-batch", &batch, |b, batch| {
+        b.iter_batched(
+            tree,
+            |mut tree| {
+                subject_update_batch(&mut tree, black_box(batch.clone().into_iter()))
+            },
+            BatchSize::LargeInput,
+        );
+    });
+
+    group_update.bench_with_input("update-batch-baseline", &batch, |b, batch| {
+        b.iter_batched(
+            tree,
+            |mut tree| {
+                baseline_update_batch(&mut tree, black_box(batch.clone().into_iter()))
+            },
+            BatchSize::LargeInput,
+        );
+    });
+
+    group_update.finish();
+}
+
+criterion_group!(benches, sparse_merkle_tree, sparse_merkle_tree_update_batch);
 criterion_main!(benches);
@@ -19,4 +19,13 @@ pub use primitive::Primitive;
 pub mod in_memory;
 pub mod root_calculator;
 
-pub use verify::verify;
+pub use verify::{
+    verify,
+    verify_consistency,
+    verify_consistency_with_hasher,
+    verify_many,
+    verify_many_with_hasher,
+    verify_range,
+    verify_range_with_hasher,
+    verify_with_hasher,
+};
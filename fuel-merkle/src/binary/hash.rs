@@ -1,39 +1,25 @@
 use crate::common::{
-    empty_sum_sha256,
     Bytes32,
+    Hasher,
     Prefix,
 };
 
-use digest::Digest;
-use sha2::Sha256;
-
-type Hash = Sha256;
-
 // Merkle Tree hash of an empty list
 // MTH({}) = Hash()
-pub const fn empty_sum() -> &'static Bytes32 {
-    empty_sum_sha256()
+pub fn empty_sum<H: Hasher>() -> Bytes32 {
+    H::hash([])
 }
 
 // Merkle tree hash of an n-element list D[n]
 // MTH(D[n]) = Hash(0x01 || MTH(D[0:k]) || MTH(D[k:n])
-pub fn node_sum(lhs_data: &Bytes32, rhs_data: &Bytes32) -> Bytes32 {
-    let mut hash = Hash::new();
-
-    hash.update(Prefix::Node);
-    hash.update(lhs_data);
-    hash.update(rhs_data);
-
-    hash.finalize().into()
+pub fn node_sum<H: Hasher>(lhs_data: &Bytes32, rhs_data: &Bytes32) -> Bytes32 {
+    let input: [&[u8]; 3] = [Prefix::Node.as_ref(), lhs_data, rhs_data];
+    H::hash_iter(input)
 }
 
 // Merkle tree hash of a list with one entry
 // MTH({d(0)}) = Hash(0x00 || d(0))
-pub fn leaf_sum(data: &[u8]) -> Bytes32 {
-    let mut hash = Hash::new();
-
-    hash.update(Prefix::Leaf);
-    hash.update(data);
-
-    hash.finalize().into()
+pub fn leaf_sum<H: Hasher>(data: &[u8]) -> Bytes32 {
+    let input: [&[u8]; 2] = [Prefix::Leaf.as_ref(), data];
+    H::hash_iter(input)
 }
@@ -11,6 +11,8 @@ use crate::{
     storage::Mappable,
 };
 
+use core::ops::Range;
+
 /// The table of the Binary Merkle Tree's nodes. [`MerkleTree`] works with it as
 /// a binary array, where the storage key of the node is the `u64` index and
 /// value is the [`Node`](crate::binary::Node).
@@ -51,6 +53,18 @@ impl MerkleTree {
         self.tree.prove(proof_index).ok()
     }
 
+    pub fn prove_many(&self, proof_indices: &[u64]) -> Option<(Bytes32, ProofSet)> {
+        self.tree.prove_many(proof_indices).ok()
+    }
+
+    pub fn prove_range(&self, range: Range<u64>) -> Option<(Bytes32, ProofSet)> {
+        self.tree.prove_range(range).ok()
+    }
+
+    pub fn prove_consistency(&self, old_leaves_count: u64) -> Option<ProofSet> {
+        self.tree.prove_consistency(old_leaves_count).ok()
+    }
+
     pub fn reset(&mut self) {
         self.tree.reset();
     }
@@ -71,13 +85,14 @@ mod test {
         node_sum,
     };
     use fuel_merkle_test_helpers::TEST_DATA;
+    use sha2::Sha256;
 
     #[test]
     fn root_returns_the_empty_root_for_0_leaves() {
         let tree = MerkleTree::new();
 
         let root = tree.root();
-        assert_eq!(root, empty_sum().clone());
+        assert_eq!(root, empty_sum::<Sha256>());
     }
 
     #[test]
@@ -89,7 +104,7 @@ mod test {
             tree.push(datum);
         }
 
-        let leaf_0 = leaf_sum(data[0]);
+        let leaf_0 = leaf_sum::<Sha256>(data[0]);
 
         let root = tree.root();
         assert_eq!(root, leaf_0);
@@ -119,20 +134,20 @@ mod test {
         // 00  02  04  06  08  10     12
         // 00  01  02  03  04  05     06
 
-        let leaf_0 = leaf_sum(data[0]);
-        let leaf_1 = leaf_sum(data[1]);
-        let leaf_2 = leaf_sum(data[2]);
-        let leaf_3 = leaf_sum(data[3]);
-        let leaf_4 = leaf_sum(data[4]);
-        let leaf_5 = leaf_sum(data[5]);
-        let leaf_6 = leaf_sum(data[6]);
-
-        let node_1 = node_sum(&leaf_0, &leaf_1);
-        let node_5 = node_sum(&leaf_2, &leaf_3);
-        let node_3 = node_sum(&node_1, &node_5);
-        let node_9 = node_sum(&leaf_4, &leaf_5);
-        let node_11 = node_sum(&node_9, &leaf_6);
-        let node_7 = node_sum(&node_3, &node_11);
+        let leaf_0 = leaf_sum::<Sha256>(data[0]);
+        let leaf_1 = leaf_sum::<Sha256>(data[1]);
+        let leaf_2 = leaf_sum::<Sha256>(data[2]);
+        let leaf_3 = leaf_sum::<Sha256>(data[3]);
+        let leaf_4 = leaf_sum::<Sha256>(data[4]);
+        let leaf_5 = leaf_sum::<Sha256>(data[5]);
+        let leaf_6 = leaf_sum::<Sha256>(data[6]);
+
+        let node_1 = node_sum::<Sha256>(&leaf_0, &leaf_1);
+        let node_5 = node_sum::<Sha256>(&leaf_2, &leaf_3);
+        let node_3 = node_sum::<Sha256>(&node_1, &node_5);
+        let node_9 = node_sum::<Sha256>(&leaf_4, &leaf_5);
+        let node_11 = node_sum::<Sha256>(&node_9, &leaf_6);
+        let node_7 = node_sum::<Sha256>(&node_3, &node_11);
 
         let root = tree.root();
         assert_eq!(root, node_7);
@@ -168,7 +183,7 @@ mod test {
             tree.push(datum);
         }
 
-        let leaf_0 = leaf_sum(data[0]);
+        let leaf_0 = leaf_sum::<Sha256>(data[0]);
 
         {
             let (root, proof_set) = tree.prove(0).unwrap();
@@ -201,20 +216,20 @@ mod test {
         // 00  02  04  06  08  10     12
         // 00  01  02  03  04  05     06
 
-        let leaf_0 = leaf_sum(data[0]);
-        let leaf_1 = leaf_sum(data[1]);
-        let leaf_2 = leaf_sum(data[2]);
-        let leaf_3 = leaf_sum(data[3]);
-        let leaf_4 = leaf_sum(data[4]);
-        let leaf_5 = leaf_sum(data[5]);
-        let leaf_6 = leaf_sum(data[6]);
-
-        let node_1 = node_sum(&leaf_0, &leaf_1);
-        let node_5 = node_sum(&leaf_2, &leaf_3);
-        let node_3 = node_sum(&node_1, &node_5);
-        let node_9 = node_sum(&leaf_4, &leaf_5);
-        let node_11 = node_sum(&node_9, &leaf_6);
-        let node_7 = node_sum(&node_3, &node_11);
+        let leaf_0 = leaf_sum::<Sha256>(data[0]);
+        let leaf_1 = leaf_sum::<Sha256>(data[1]);
+        let leaf_2 = leaf_sum::<Sha256>(data[2]);
+        let leaf_3 = leaf_sum::<Sha256>(data[3]);
+        let leaf_4 = leaf_sum::<Sha256>(data[4]);
+        let leaf_5 = leaf_sum::<Sha256>(data[5]);
+        let leaf_6 = leaf_sum::<Sha256>(data[6]);
+
+        let node_1 = node_sum::<Sha256>(&leaf_0, &leaf_1);
+        let node_5 = node_sum::<Sha256>(&leaf_2, &leaf_3);
+        let node_3 = node_sum::<Sha256>(&node_1, &node_5);
+        let node_9 = node_sum::<Sha256>(&leaf_4, &leaf_5);
+        let node_11 = node_sum::<Sha256>(&node_9, &leaf_6);
+        let node_7 = node_sum::<Sha256>(&node_3, &node_11);
 
         {
             let (root, proof_set) = tree.prove(0).unwrap();
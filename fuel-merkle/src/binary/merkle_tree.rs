@@ -2,14 +2,18 @@ use crate::{
     binary::{
         empty_sum,
         in_memory::NodesTable,
+        node_sum,
         Node,
         Primitive,
     },
     common::{
+        empty_sum_sha256,
         Bytes32,
+        Hasher,
         Position,
         ProofSet,
         StorageMap,
+        WriteBatch,
     },
     storage::{
         Mappable,
@@ -24,7 +28,9 @@ use alloc::vec::Vec;
 use core::{
     convert::Infallible,
     marker::PhantomData,
+    ops::Range,
 };
+use sha2::Sha256;
 
 use super::root_calculator::{
     MerkleRootCalculator,
@@ -39,6 +45,9 @@ pub enum MerkleTreeError<StorageError> {
     #[display(fmt = "cannot load node with key {_0}; the key is not found in storage")]
     LoadError(u64),
 
+    #[display(fmt = "leaves count {_0} is greater than the leaves count of the tree")]
+    InvalidLeavesCount(u64),
+
     #[display(fmt = "{}", _0)]
     StorageError(StorageError),
 
@@ -52,26 +61,33 @@ impl<StorageError> From<StorageError> for MerkleTreeError<StorageError> {
     }
 }
 
+/// The binary Merkle tree of the nodes in the `TableType` of the `StorageType`,
+/// hashing its nodes with `H`, SHA-256 by default.
+///
+/// [`push`](Self::push) writes the nodes of the new leaf with a single
+/// [`StorageMutate::apply_batch`], so its atomicity is the one of the storage.
 #[derive(Debug, Clone)]
-pub struct MerkleTree<TableType, StorageType> {
+pub struct MerkleTree<TableType, StorageType, H = Sha256> {
     storage: StorageType,
-    nodes: MerkleRootCalculator,
+    nodes: MerkleRootCalculator<H>,
     leaves_count: u64,
     phantom_table: PhantomData<TableType>,
 }
 
 impl<TableType, StorageType> MerkleTree<TableType, StorageType> {
     pub const fn empty_root() -> &'static Bytes32 {
-        empty_sum()
+        empty_sum_sha256()
     }
+}
 
+impl<TableType, StorageType, H: Hasher> MerkleTree<TableType, StorageType, H> {
     pub fn root(&self) -> Bytes32 {
         let mut scratch_storage = StorageMap::<NodesTable>::new();
         let root_node = self
             .root_node::<Infallible>(&mut scratch_storage)
             .expect("The type doesn't allow constructing invalid trees.");
         match root_node {
-            None => *Self::empty_root(),
+            None => empty_sum::<H>(),
             Some(ref node) => *node.hash(),
         }
     }
@@ -80,6 +96,14 @@ impl<TableType, StorageType> MerkleTree<TableType, StorageType> {
         self.leaves_count
     }
 
+    pub fn into_storage(self) -> StorageType {
+        self.storage
+    }
+
+    pub fn storage(&self) -> &StorageType {
+        &self.storage
+    }
+
     /// The root node is generated by joining all MMR peaks, where a peak is
     /// defined as the head of a balanced subtree. A tree can be composed of a
     /// single balanced subtree, in which case the tree is itself balanced, or
@@ -112,7 +136,7 @@ impl<TableType, StorageType> MerkleTree<TableType, StorageType> {
                 .position()
                 .parent()
                 .map_err(|_| MerkleTreeError::TooLarge)?;
-            head = Node::create_node(parent, node, &head);
+            head = Node::create_node::<H>(parent, node, &head);
             StorageMutateInfallible::insert(
                 scratch_storage,
                 &head.key(),
@@ -130,9 +154,30 @@ where
     StorageType: StorageInspect<TableType, Error = StorageError>,
 {
     pub fn new(storage: StorageType) -> Self {
+        Self::new_with_hasher(storage)
+    }
+
+    /// Loads the tree with `leaves_count` leaves. See
+    /// [`load_with_hasher`](Self::load_with_hasher).
+    pub fn load(
+        storage: StorageType,
+        leaves_count: u64,
+    ) -> Result<Self, MerkleTreeError<StorageError>> {
+        Self::load_with_hasher(storage, leaves_count)
+    }
+}
+
+impl<TableType, StorageType, StorageError, H> MerkleTree<TableType, StorageType, H>
+where
+    TableType: Mappable<Key = u64, Value = Primitive, OwnedValue = Primitive>,
+    StorageType: StorageInspect<TableType, Error = StorageError>,
+    H: Hasher,
+{
+    /// Creates an empty tree hashing its nodes with `H`.
+    pub fn new_with_hasher(storage: StorageType) -> Self {
         Self {
             storage,
-            nodes: MerkleRootCalculator::new(),
+            nodes: MerkleRootCalculator::new_with_hasher(),
             leaves_count: 0,
             phantom_table: Default::default(),
         }
@@ -217,25 +262,32 @@ where
     ///
     /// By excluding the root position `07`, we have established the set of
     /// side positions `03`, `09`, and `12`, matching our set of MMR peaks.
-    pub fn load(
+    ///
+    /// The nodes of the tree are hashed with `H`.
+    pub fn load_with_hasher(
         storage: StorageType,
         leaves_count: u64,
     ) -> Result<Self, MerkleTreeError<StorageError>> {
-        let mut nodes = Vec::new();
         let peaks = peak_positions(leaves_count).ok_or(MerkleTreeError::TooLarge)?;
-        for peak in peaks.iter() {
-            let key = peak.in_order_index();
-            let node = storage
-                .get(&key)?
-                .ok_or(MerkleTreeError::LoadError(key))?
-                .into_owned()
-                .into();
-            nodes.push(node);
-        }
+        let keys = peaks
+            .iter()
+            .map(|peak| peak.in_order_index())
+            .collect::<Vec<_>>();
+        // The peaks are read from the storage at once.
+        let key_refs = keys.iter().collect::<Vec<_>>();
+        let nodes = storage
+            .get_many(&key_refs)?
+            .into_iter()
+            .zip(keys.iter())
+            .map(|(primitive, key)| {
+                let primitive = primitive.ok_or(MerkleTreeError::LoadError(*key))?;
+                Ok(primitive.into_owned().into())
+            })
+            .collect::<Result<Vec<_>, MerkleTreeError<StorageError>>>()?;
 
         Ok(Self {
             storage,
-            nodes: MerkleRootCalculator::new_with_stack(nodes),
+            nodes: MerkleRootCalculator::from_stack(nodes),
             leaves_count,
             phantom_table: Default::default(),
         })
@@ -267,17 +319,28 @@ where
             .root_node(&mut scratch_storage)?
             .expect("Root node must be present, as leaves_count is nonzero");
 
-        // Get side nodes. First, we check the scratch storage. If the side node
-        // is not found in scratch storage, we then check main storage. Finally,
-        // if the side node is not found in main storage, we exit with a load
-        // error.
+        // Get side nodes. First, we check the scratch storage. The side nodes
+        // not found in scratch storage are then read from main storage at once.
+        // Finally, if a side node is not found in main storage, we exit with a
+        // load error.
+        let keys = side_positions
+            .iter()
+            .map(|side_position| side_position.in_order_index())
+            .collect::<Vec<_>>();
+        let stored_keys = keys
+            .iter()
+            .filter(|key| !StorageInspectInfallible::contains_key(&scratch_storage, key))
+            .collect::<Vec<_>>();
+        let mut stored_primitives =
+            StorageInspect::get_many(&self.storage, &stored_keys)?.into_iter();
         let mut proof_set = ProofSet::new();
-        for side_position in side_positions {
-            let key = side_position.in_order_index();
-            let primitive = StorageInspectInfallible::get(&scratch_storage, &key)
-                .or(StorageInspect::get(&self.storage, &key)?)
-                .ok_or(MerkleTreeError::LoadError(key))?
-                .into_owned();
+        for key in keys {
+            let primitive = match StorageInspectInfallible::get(&scratch_storage, &key) {
+                Some(primitive) => Some(primitive),
+                None => stored_primitives.next().flatten(),
+            }
+            .ok_or(MerkleTreeError::LoadError(key))?
+            .into_owned();
             let node = Node::from(primitive);
             proof_set.push(*node.hash());
         }
@@ -286,41 +349,217 @@ where
         Ok((root, proof_set))
     }
 
+    /// Generates a proof of the leaves at the `proof_indices`, which must be strictly
+    /// increasing, verified by [`verify_many`](crate::binary::verify_many).
+    ///
+    /// The proof set holds the roots of the largest subtrees without proven leaves,
+    /// in the depth-first order of the subtrees from left to right.
+    pub fn prove_many(
+        &self,
+        proof_indices: &[u64],
+    ) -> Result<(Bytes32, ProofSet), MerkleTreeError<StorageError>> {
+        let mut previous_index = None;
+        for &proof_index in proof_indices {
+            if proof_index >= self.leaves_count
+                || previous_index.is_some_and(|previous| proof_index <= previous)
+            {
+                return Err(MerkleTreeError::InvalidProofIndex(proof_index))
+            }
+            previous_index = Some(proof_index);
+        }
+
+        let mut proof_set = ProofSet::new();
+        if self.leaves_count > 0 {
+            self.multiproof_subtree(0, self.leaves_count, proof_indices, &mut proof_set)?;
+        }
+
+        Ok((self.root(), proof_set))
+    }
+
+    /// Generates a proof of the contiguous leaves at the indices in `range`, verified by
+    /// [`verify_range`](crate::binary::verify_range).
+    ///
+    /// The proof set is the one of [`prove_many`](Self::prove_many) for the indices in
+    /// `range`.
+    pub fn prove_range(
+        &self,
+        range: Range<u64>,
+    ) -> Result<(Bytes32, ProofSet), MerkleTreeError<StorageError>> {
+        if range.end > self.leaves_count {
+            return Err(MerkleTreeError::InvalidProofIndex(range.end))
+        }
+        let proof_indices = range.collect::<Vec<_>>();
+        self.prove_many(&proof_indices)
+    }
+
+    /// Generates a proof that the tree of the first `old_leaves_count` leaves is a
+    /// prefix of this tree, i.e. that this tree is an append-only extension of it.
+    ///
+    /// The proof set is the consistency proof of
+    /// [RFC 6962](https://www.rfc-editor.org/rfc/rfc6962#section-2.1.2), ordered from
+    /// the leaves to the root, and is verified by
+    /// [`verify_consistency`](crate::binary::verify_consistency).
+    pub fn prove_consistency(
+        &self,
+        old_leaves_count: u64,
+    ) -> Result<ProofSet, MerkleTreeError<StorageError>> {
+        if old_leaves_count > self.leaves_count {
+            return Err(MerkleTreeError::InvalidLeavesCount(old_leaves_count))
+        }
+
+        let mut proof_set = ProofSet::new();
+        if old_leaves_count > 0 {
+            self.consistency_subproof(
+                old_leaves_count,
+                0,
+                self.leaves_count,
+                true,
+                &mut proof_set,
+            )?;
+        }
+
+        Ok(proof_set)
+    }
+
     pub fn reset(&mut self) {
         self.nodes.clear();
     }
+
+    /// Appends the RFC 6962 `SUBPROOF(m, D[start:end], complete)` to the proof set,
+    /// where `m` is the number of leaves of the old tree in `D[start:end]`.
+    fn consistency_subproof(
+        &self,
+        m: u64,
+        start: u64,
+        end: u64,
+        complete: bool,
+        proof_set: &mut ProofSet,
+    ) -> Result<(), MerkleTreeError<StorageError>> {
+        #[allow(clippy::arithmetic_side_effects)] // m <= end - start
+        let leaves_count = end - start;
+        if m == leaves_count {
+            if !complete {
+                proof_set.push(self.subtree_root(start, end)?);
+            }
+            return Ok(())
+        }
+
+        // The largest power of two smaller than the leaves count, which is at least 2
+        #[allow(clippy::arithmetic_side_effects)] // leaves_count > m > 0
+        let split = 1u64 << (leaves_count - 1).ilog2();
+        #[allow(clippy::arithmetic_side_effects)] // split < leaves_count
+        let middle = start + split;
+        if m <= split {
+            self.consistency_subproof(m, start, middle, complete, proof_set)?;
+            proof_set.push(self.subtree_root(middle, end)?);
+        } else {
+            #[allow(clippy::arithmetic_side_effects)] // m > split
+            let m = m - split;
+            self.consistency_subproof(m, middle, end, false, proof_set)?;
+            proof_set.push(self.subtree_root(start, middle)?);
+        }
+
+        Ok(())
+    }
+
+    /// Appends the proof set of the leaves at the `proof_indices` in `D[start:end]`:
+    /// the subtrees without proven leaves are proven by their roots, and the others
+    /// are split as in the Merkle tree hash.
+    fn multiproof_subtree(
+        &self,
+        start: u64,
+        end: u64,
+        proof_indices: &[u64],
+        proof_set: &mut ProofSet,
+    ) -> Result<(), MerkleTreeError<StorageError>> {
+        if proof_indices.is_empty() {
+            proof_set.push(self.subtree_root(start, end)?);
+            return Ok(())
+        }
+
+        #[allow(clippy::arithmetic_side_effects)] // start < end
+        let leaves_count = end - start;
+        if leaves_count == 1 {
+            return Ok(())
+        }
+
+        #[allow(clippy::arithmetic_side_effects)] // leaves_count > 1
+        let middle = start + (1u64 << (leaves_count - 1).ilog2());
+        let split = proof_indices.partition_point(|index| *index < middle);
+        self.multiproof_subtree(start, middle, &proof_indices[..split], proof_set)?;
+        self.multiproof_subtree(middle, end, &proof_indices[split..], proof_set)?;
+
+        Ok(())
+    }
+
+    /// The Merkle tree hash of the leaves `D[start:end]`, where `start` is aligned to
+    /// the largest power of two not greater than `end - start`. Balanced subtrees are
+    /// loaded from the storage, and the other subtrees are built from them.
+    fn subtree_root(
+        &self,
+        start: u64,
+        end: u64,
+    ) -> Result<Bytes32, MerkleTreeError<StorageError>> {
+        #[allow(clippy::arithmetic_side_effects)] // start < end
+        let leaves_count = end - start;
+        if leaves_count.is_power_of_two() {
+            // The head of the balanced subtree of the leaves `start..end`
+            #[allow(clippy::arithmetic_side_effects)] // leaves_count > 0
+            let offset = leaves_count - 1;
+            let key = start
+                .checked_mul(2)
+                .and_then(|index| index.checked_add(offset))
+                .ok_or(MerkleTreeError::TooLarge)?;
+            let primitive = StorageInspect::get(&self.storage, &key)?
+                .ok_or(MerkleTreeError::LoadError(key))?
+                .into_owned();
+            return Ok(*Node::from(primitive).hash())
+        }
+
+        #[allow(clippy::arithmetic_side_effects)] // leaves_count > 2
+        let middle = start + (1u64 << (leaves_count - 1).ilog2());
+        let lhs = self.subtree_root(start, middle)?;
+        let rhs = self.subtree_root(middle, end)?;
+        Ok(node_sum::<H>(&lhs, &rhs))
+    }
 }
 
-impl<TableType, StorageType, StorageError> MerkleTree<TableType, StorageType>
+impl<TableType, StorageType, StorageError, H> MerkleTree<TableType, StorageType, H>
 where
     TableType: Mappable<Key = u64, Value = Primitive, OwnedValue = Primitive>,
     StorageType: StorageMutate<TableType, Error = StorageError>,
+    H: Hasher,
 {
     /// Adds a new leaf node to the tree.
-    /// # WARNING
-    /// This code might modify the storage, and then return an error.
-    /// TODO: fix this issue
+    ///
+    /// The new nodes are written to the storage at once with
+    /// [`StorageMutate::apply_batch`]. If it fails, the tree is unchanged.
     pub fn push(&mut self, data: &[u8]) -> Result<(), MerkleTreeError<StorageError>> {
-        let new_node = Node::create_leaf(self.leaves_count, data)
+        let new_node = Node::create_leaf::<H>(self.leaves_count, data)
             .ok_or(MerkleTreeError::TooLarge)?;
 
-        // u64 cannot overflow, as memory is finite
-        #[allow(clippy::arithmetic_side_effects)]
-        {
-            self.leaves_count += 1;
-        }
-
-        self.nodes
+        let mut nodes = self.nodes.clone();
+        let mut batch = WriteBatch::<TableType, _>::new(&mut self.storage);
+        nodes
             .push_with_callback(new_node, |node| {
-                self.storage
+                batch
                     .insert(&node.key(), &node.into())
                     .map_err(MerkleTreeError::StorageError)
-                    .map(|_| ())
             })
             .map_err(|err| match err {
                 NodeStackPushError::Callback(err) => err,
                 NodeStackPushError::TooLarge => MerkleTreeError::TooLarge,
-            })
+            })?;
+        batch.commit()?;
+
+        self.nodes = nodes;
+        // u64 cannot overflow, as memory is finite
+        #[allow(clippy::arithmetic_side_effects)]
+        {
+            self.leaves_count += 1;
+        }
+
+        Ok(())
     }
 }
 
@@ -365,19 +604,38 @@ mod test {
             empty_sum,
             leaf_sum,
             node_sum,
+            root_calculator::MerkleRootCalculator,
+            verify,
+            verify_consistency,
+            verify_consistency_with_hasher,
+            verify_with_hasher,
             Node,
             Primitive,
         },
-        common::StorageMap,
+        common::{
+            Bytes32,
+            StorageMap,
+            WriteBatch,
+        },
     };
+    use digest::Digest;
     use fuel_merkle_test_helpers::TEST_DATA;
     use fuel_storage::{
         Mappable,
         StorageInspect,
         StorageMutate,
     };
+    use sha2::Sha256;
+    use sha3::Keccak256;
 
-    use alloc::vec::Vec;
+    use alloc::{
+        borrow::Cow,
+        vec::Vec,
+    };
+    use core::{
+        cell::RefCell,
+        convert::Infallible,
+    };
 
     #[derive(Debug)]
     struct TestTable;
@@ -389,6 +647,46 @@ mod test {
         type Value = Self::OwnedValue;
     }
 
+    /// A table whose owned key is a different type than its key.
+    struct WrappedKeyTable;
+
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    struct WrappedKey(u64);
+
+    impl From<u64> for WrappedKey {
+        fn from(key: u64) -> Self {
+            Self(key)
+        }
+    }
+
+    impl core::borrow::Borrow<u64> for WrappedKey {
+        fn borrow(&self) -> &u64 {
+            &self.0
+        }
+    }
+
+    impl Mappable for WrappedKeyTable {
+        type Key = u64;
+        type OwnedKey = WrappedKey;
+        type OwnedValue = Primitive;
+        type Value = Self::OwnedValue;
+    }
+
+    #[test]
+    fn test_push_with_owned_key_other_than_key() {
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage_map);
+        let mut wrapped_storage_map = StorageMap::<WrappedKeyTable>::new();
+        let mut wrapped_tree = MerkleTree::new(&mut wrapped_storage_map);
+
+        for datum in TEST_DATA[0..5].iter() {
+            tree.push(datum).unwrap();
+            wrapped_tree.push(datum).unwrap();
+        }
+
+        assert_eq!(wrapped_tree.root(), tree.root());
+    }
+
     #[test]
     fn test_push_builds_internal_tree_structure() {
         let mut storage_map = StorageMap::<TestTable>::new();
@@ -414,17 +712,17 @@ mod test {
         // 00  02  04  06  08  10     12
         // 00  01  02  03  04  05     06
 
-        let leaf_0 = leaf_sum(data[0]);
-        let leaf_1 = leaf_sum(data[1]);
-        let leaf_2 = leaf_sum(data[2]);
-        let leaf_3 = leaf_sum(data[3]);
-        let leaf_4 = leaf_sum(data[4]);
-        let leaf_5 = leaf_sum(data[5]);
-        let leaf_6 = leaf_sum(data[6]);
-        let node_1 = node_sum(&leaf_0, &leaf_1);
-        let node_5 = node_sum(&leaf_2, &leaf_3);
-        let node_3 = node_sum(&node_1, &node_5);
-        let node_9 = node_sum(&leaf_4, &leaf_5);
+        let leaf_0 = leaf_sum::<Sha256>(data[0]);
+        let leaf_1 = leaf_sum::<Sha256>(data[1]);
+        let leaf_2 = leaf_sum::<Sha256>(data[2]);
+        let leaf_3 = leaf_sum::<Sha256>(data[3]);
+        let leaf_4 = leaf_sum::<Sha256>(data[4]);
+        let leaf_5 = leaf_sum::<Sha256>(data[5]);
+        let leaf_6 = leaf_sum::<Sha256>(data[6]);
+        let node_1 = node_sum::<Sha256>(&leaf_0, &leaf_1);
+        let node_5 = node_sum::<Sha256>(&leaf_2, &leaf_3);
+        let node_3 = node_sum::<Sha256>(&node_1, &node_5);
+        let node_9 = node_sum::<Sha256>(&leaf_4, &leaf_5);
 
         let s_leaf_0 = storage_map.get(&0).unwrap().unwrap();
         let s_leaf_1 = storage_map.get(&2).unwrap().unwrap();
@@ -516,7 +814,7 @@ mod test {
         let tree = MerkleTree::new(&mut storage_map);
 
         let root = tree.root();
-        assert_eq!(root, empty_sum().clone());
+        assert_eq!(root, empty_sum::<Sha256>());
     }
 
     #[test]
@@ -529,7 +827,7 @@ mod test {
             let _ = tree.push(datum);
         }
 
-        let leaf_0 = leaf_sum(data[0]);
+        let leaf_0 = leaf_sum::<Sha256>(data[0]);
 
         let root = tree.root();
         assert_eq!(root, leaf_0);
@@ -560,20 +858,20 @@ mod test {
         // 00  02  04  06  08  10     12
         // 00  01  02  03  04  05     06
 
-        let leaf_0 = leaf_sum(data[0]);
-        let leaf_1 = leaf_sum(data[1]);
-        let leaf_2 = leaf_sum(data[2]);
-        let leaf_3 = leaf_sum(data[3]);
-        let leaf_4 = leaf_sum(data[4]);
-        let leaf_5 = leaf_sum(data[5]);
-        let leaf_6 = leaf_sum(data[6]);
-
-        let node_1 = node_sum(&leaf_0, &leaf_1);
-        let node_5 = node_sum(&leaf_2, &leaf_3);
-        let node_3 = node_sum(&node_1, &node_5);
-        let node_9 = node_sum(&leaf_4, &leaf_5);
-        let node_11 = node_sum(&node_9, &leaf_6);
-        let node_7 = node_sum(&node_3, &node_11);
+        let leaf_0 = leaf_sum::<Sha256>(data[0]);
+        let leaf_1 = leaf_sum::<Sha256>(data[1]);
+        let leaf_2 = leaf_sum::<Sha256>(data[2]);
+        let leaf_3 = leaf_sum::<Sha256>(data[3]);
+        let leaf_4 = leaf_sum::<Sha256>(data[4]);
+        let leaf_5 = leaf_sum::<Sha256>(data[5]);
+        let leaf_6 = leaf_sum::<Sha256>(data[6]);
+
+        let node_1 = node_sum::<Sha256>(&leaf_0, &leaf_1);
+        let node_5 = node_sum::<Sha256>(&leaf_2, &leaf_3);
+        let node_3 = node_sum::<Sha256>(&node_1, &node_5);
+        let node_9 = node_sum::<Sha256>(&leaf_4, &leaf_5);
+        let node_11 = node_sum::<Sha256>(&node_9, &leaf_6);
+        let node_7 = node_sum::<Sha256>(&node_3, &node_11);
 
         let root = tree.root();
         assert_eq!(root, node_7);
@@ -617,7 +915,7 @@ mod test {
             let _ = tree.push(datum);
         }
 
-        let leaf_0 = leaf_sum(data[0]);
+        let leaf_0 = leaf_sum::<Sha256>(data[0]);
 
         {
             let (root, proof_set) = tree.prove(0).unwrap();
@@ -644,14 +942,14 @@ mod test {
         // 00  02  04  06
         // 00  01  02  03
 
-        let leaf_0 = leaf_sum(data[0]);
-        let leaf_1 = leaf_sum(data[1]);
-        let leaf_2 = leaf_sum(data[2]);
-        let leaf_3 = leaf_sum(data[3]);
+        let leaf_0 = leaf_sum::<Sha256>(data[0]);
+        let leaf_1 = leaf_sum::<Sha256>(data[1]);
+        let leaf_2 = leaf_sum::<Sha256>(data[2]);
+        let leaf_3 = leaf_sum::<Sha256>(data[3]);
 
-        let node_1 = node_sum(&leaf_0, &leaf_1);
-        let node_5 = node_sum(&leaf_2, &leaf_3);
-        let node_3 = node_sum(&node_1, &node_5);
+        let node_1 = node_sum::<Sha256>(&leaf_0, &leaf_1);
+        let node_5 = node_sum::<Sha256>(&leaf_2, &leaf_3);
+        let node_3 = node_sum::<Sha256>(&node_1, &node_5);
 
         {
             let (root, proof_set) = tree.prove(0).unwrap();
@@ -700,16 +998,16 @@ mod test {
         // 00  02  04  06  08
         // 00  01  02  03  04
 
-        let leaf_0 = leaf_sum(data[0]);
-        let leaf_1 = leaf_sum(data[1]);
-        let leaf_2 = leaf_sum(data[2]);
-        let leaf_3 = leaf_sum(data[3]);
-        let leaf_4 = leaf_sum(data[4]);
+        let leaf_0 = leaf_sum::<Sha256>(data[0]);
+        let leaf_1 = leaf_sum::<Sha256>(data[1]);
+        let leaf_2 = leaf_sum::<Sha256>(data[2]);
+        let leaf_3 = leaf_sum::<Sha256>(data[3]);
+        let leaf_4 = leaf_sum::<Sha256>(data[4]);
 
-        let node_1 = node_sum(&leaf_0, &leaf_1);
-        let node_5 = node_sum(&leaf_2, &leaf_3);
-        let node_3 = node_sum(&node_1, &node_5);
-        let node_7 = node_sum(&node_3, &leaf_4);
+        let node_1 = node_sum::<Sha256>(&leaf_0, &leaf_1);
+        let node_5 = node_sum::<Sha256>(&leaf_2, &leaf_3);
+        let node_3 = node_sum::<Sha256>(&node_1, &node_5);
+        let node_7 = node_sum::<Sha256>(&node_3, &leaf_4);
 
         {
             let (root, proof_set) = tree.prove(0).unwrap();
@@ -771,20 +1069,20 @@ mod test {
         // 00  02  04  06  08  10     12
         // 00  01  02  03  04  05     06
 
-        let leaf_0 = leaf_sum(data[0]);
-        let leaf_1 = leaf_sum(data[1]);
-        let leaf_2 = leaf_sum(data[2]);
-        let leaf_3 = leaf_sum(data[3]);
-        let leaf_4 = leaf_sum(data[4]);
-        let leaf_5 = leaf_sum(data[5]);
-        let leaf_6 = leaf_sum(data[6]);
-
-        let node_1 = node_sum(&leaf_0, &leaf_1);
-        let node_5 = node_sum(&leaf_2, &leaf_3);
-        let node_3 = node_sum(&node_1, &node_5);
-        let node_9 = node_sum(&leaf_4, &leaf_5);
-        let node_11 = node_sum(&node_9, &leaf_6);
-        let node_7 = node_sum(&node_3, &node_11);
+        let leaf_0 = leaf_sum::<Sha256>(data[0]);
+        let leaf_1 = leaf_sum::<Sha256>(data[1]);
+        let leaf_2 = leaf_sum::<Sha256>(data[2]);
+        let leaf_3 = leaf_sum::<Sha256>(data[3]);
+        let leaf_4 = leaf_sum::<Sha256>(data[4]);
+        let leaf_5 = leaf_sum::<Sha256>(data[5]);
+        let leaf_6 = leaf_sum::<Sha256>(data[6]);
+
+        let node_1 = node_sum::<Sha256>(&leaf_0, &leaf_1);
+        let node_5 = node_sum::<Sha256>(&leaf_2, &leaf_3);
+        let node_3 = node_sum::<Sha256>(&node_1, &node_5);
+        let node_9 = node_sum::<Sha256>(&leaf_4, &leaf_5);
+        let node_11 = node_sum::<Sha256>(&node_9, &leaf_6);
+        let node_7 = node_sum::<Sha256>(&node_3, &node_11);
 
         {
             let (root, proof_set) = tree.prove(0).unwrap();
@@ -836,6 +1134,76 @@ mod test {
         }
     }
 
+    #[test]
+    fn prove_consistency_returns_the_rfc_6962_proof_set_for_3_of_7_leaves() {
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage_map);
+
+        let data = &TEST_DATA[0..7]; // 7 leaves
+        for datum in data.iter() {
+            let _ = tree.push(datum);
+        }
+
+        let leaf_0 = leaf_sum::<Sha256>(data[0]);
+        let leaf_1 = leaf_sum::<Sha256>(data[1]);
+        let leaf_2 = leaf_sum::<Sha256>(data[2]);
+        let leaf_3 = leaf_sum::<Sha256>(data[3]);
+        let leaf_4 = leaf_sum::<Sha256>(data[4]);
+        let leaf_5 = leaf_sum::<Sha256>(data[5]);
+        let leaf_6 = leaf_sum::<Sha256>(data[6]);
+        let node_1 = node_sum::<Sha256>(&leaf_0, &leaf_1);
+        let node_9 = node_sum::<Sha256>(&leaf_4, &leaf_5);
+        let node_11 = node_sum::<Sha256>(&node_9, &leaf_6);
+
+        let proof_set = tree.prove_consistency(3).unwrap();
+        assert_eq!(proof_set, vec![leaf_2, leaf_3, node_1, node_11]);
+    }
+
+    #[test]
+    fn prove_consistency_returns_proof_sets_verified_by_verify_consistency() {
+        const LEAVES_COUNT: usize = 33;
+
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage_map);
+
+        let mut roots = vec![tree.root()];
+        for datum in TEST_DATA.iter().cycle().take(LEAVES_COUNT) {
+            tree.push(datum).unwrap();
+            roots.push(tree.root());
+        }
+        let tree =
+            MerkleTree::<TestTable, _>::load(&storage_map, LEAVES_COUNT as u64).unwrap();
+
+        for (old_leaves_count, old_root) in roots.iter().enumerate() {
+            let old_leaves_count = old_leaves_count as u64;
+            let proof_set = tree.prove_consistency(old_leaves_count).unwrap();
+            assert!(verify_consistency(
+                old_root,
+                old_leaves_count,
+                &tree.root(),
+                LEAVES_COUNT as u64,
+                &proof_set
+            ));
+        }
+    }
+
+    #[test]
+    fn prove_consistency_returns_invalid_leaves_count_error_when_old_leaves_count_is_greater_than_number_of_leaves(
+    ) {
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage_map);
+
+        let data = &TEST_DATA[0..5]; // 5 leaves
+        for datum in data.iter() {
+            let _ = tree.push(datum);
+        }
+
+        let err = tree
+            .prove_consistency(6)
+            .expect_err("Expected prove_consistency() to return Error; got Ok");
+        assert!(matches!(err, MerkleTreeError::InvalidLeavesCount(6)));
+    }
+
     #[test]
     fn reset_reverts_tree_to_empty_state() {
         let mut storage_map = StorageMap::<TestTable>::new();
@@ -857,14 +1225,14 @@ mod test {
             let _ = tree.push(datum);
         }
 
-        let leaf_0 = leaf_sum(data[0]);
-        let leaf_1 = leaf_sum(data[1]);
-        let leaf_2 = leaf_sum(data[2]);
-        let leaf_3 = leaf_sum(data[3]);
+        let leaf_0 = leaf_sum::<Sha256>(data[0]);
+        let leaf_1 = leaf_sum::<Sha256>(data[1]);
+        let leaf_2 = leaf_sum::<Sha256>(data[2]);
+        let leaf_3 = leaf_sum::<Sha256>(data[3]);
 
-        let node_1 = node_sum(&leaf_0, &leaf_1);
-        let node_5 = node_sum(&leaf_2, &leaf_3);
-        let node_3 = node_sum(&node_1, &node_5);
+        let node_1 = node_sum::<Sha256>(&leaf_0, &leaf_1);
+        let node_5 = node_sum::<Sha256>(&leaf_2, &leaf_3);
+        let node_3 = node_sum::<Sha256>(&node_1, &node_5);
 
         let root = tree.root();
         let expected_root = node_3;
@@ -908,4 +1276,260 @@ mod test {
         // Then
         assert_eq!(result, Err(MerkleTreeError::TooLarge));
     }
+
+    #[test]
+    fn root_returns_the_merkle_root_hashed_with_the_hasher() {
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree =
+            MerkleTree::<TestTable, _, Keccak256>::new_with_hasher(&mut storage_map);
+        assert_eq!(tree.root(), Bytes32::from(Keccak256::digest([])));
+
+        let data = &TEST_DATA[0..3];
+        for datum in data.iter() {
+            let _ = tree.push(datum);
+        }
+
+        let keccak =
+            |data: &[&[u8]]| -> Bytes32 { Keccak256::digest(data.concat()).into() };
+        let leaf_0 = keccak(&[&[0x00], data[0]]);
+        let leaf_1 = keccak(&[&[0x00], data[1]]);
+        let leaf_2 = keccak(&[&[0x00], data[2]]);
+        let node_1 = keccak(&[&[0x01], &leaf_0, &leaf_1]);
+        let node_3 = keccak(&[&[0x01], &node_1, &leaf_2]);
+        assert_eq!(tree.root(), node_3);
+    }
+
+    #[test]
+    fn load_with_hasher_returns_the_tree_hashed_with_the_hasher() {
+        const LEAVES_COUNT: usize = 7;
+
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree =
+            MerkleTree::<TestTable, _, Keccak256>::new_with_hasher(&mut storage_map);
+        let data = &TEST_DATA[0..LEAVES_COUNT];
+        for datum in data.iter() {
+            let _ = tree.push(datum);
+        }
+        let root = tree.root();
+        let old_root = {
+            let mut calculator = MerkleRootCalculator::<Keccak256>::new_with_hasher();
+            for datum in &data[..3] {
+                calculator.push(datum);
+            }
+            calculator.root()
+        };
+
+        let tree = MerkleTree::<TestTable, _, Keccak256>::load_with_hasher(
+            &storage_map,
+            LEAVES_COUNT as u64,
+        )
+        .unwrap();
+        let (proof_root, proof_set) = tree.prove(2).unwrap();
+        let consistency_proof_set = tree.prove_consistency(3).unwrap();
+
+        assert_eq!(tree.root(), root);
+        assert_eq!(proof_root, root);
+        assert!(verify_with_hasher::<Keccak256, _>(
+            &root,
+            &data[2],
+            &proof_set,
+            2,
+            LEAVES_COUNT as u64
+        ));
+        assert!(!verify(&root, &data[2], &proof_set, 2, LEAVES_COUNT as u64));
+        assert!(verify_consistency_with_hasher::<Keccak256>(
+            &old_root,
+            3,
+            &root,
+            LEAVES_COUNT as u64,
+            &consistency_proof_set
+        ));
+    }
+
+    #[test]
+    fn prove_many_returns_the_merkle_root_and_proof_set_for_7_leaves() {
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage_map);
+
+        let data = &TEST_DATA[0..7]; // 7 leaves
+        for datum in data.iter() {
+            let _ = tree.push(datum);
+        }
+
+        //               07
+        //              /  \
+        //             /    \
+        //            /      \
+        //           /        \
+        //          /          \
+        //         /            \
+        //       03              11
+        //      /  \            /  \
+        //     /    \          /    \
+        //   01      05      09      \
+        //  /  \    /  \    /  \      \
+        // 00  02  04  06  08  10     12
+        // 00  01  02  03  04  05     06
+
+        let leaf_0 = leaf_sum::<Sha256>(data[0]);
+        let leaf_2 = leaf_sum::<Sha256>(data[2]);
+        let leaf_3 = leaf_sum::<Sha256>(data[3]);
+        let leaf_5 = leaf_sum::<Sha256>(data[5]);
+        let leaf_6 = leaf_sum::<Sha256>(data[6]);
+        let node_5 = node_sum::<Sha256>(&leaf_2, &leaf_3);
+
+        let (root, proof_set) = tree.prove_many(&[1, 4]).unwrap();
+        assert_eq!(root, tree.root());
+        assert_eq!(proof_set, [leaf_0, node_5, leaf_5, leaf_6]);
+
+        let (root, proof_set) = tree.prove_many(&[]).unwrap();
+        assert_eq!(proof_set, [root]);
+    }
+
+    #[test]
+    fn prove_many_returns_invalid_proof_index_error_for_invalid_indices() {
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage_map);
+
+        let data = &TEST_DATA[0..7]; // 7 leaves
+        for datum in data.iter() {
+            let _ = tree.push(datum);
+        }
+
+        assert!(matches!(
+            tree.prove_many(&[4, 1]),
+            Err(MerkleTreeError::InvalidProofIndex(1))
+        ));
+        assert!(matches!(
+            tree.prove_many(&[1, 1]),
+            Err(MerkleTreeError::InvalidProofIndex(1))
+        ));
+        assert!(matches!(
+            tree.prove_many(&[1, 7]),
+            Err(MerkleTreeError::InvalidProofIndex(7))
+        ));
+        assert!(matches!(
+            tree.prove_range(5..8),
+            Err(MerkleTreeError::InvalidProofIndex(8))
+        ));
+    }
+
+    #[test]
+    fn prove_range_returns_the_proof_set_of_the_leaves_in_the_range() {
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage_map);
+
+        let data = &TEST_DATA[0..10]; // 10 leaves
+        for datum in data.iter() {
+            let _ = tree.push(datum);
+        }
+
+        let range_proof = tree.prove_range(2..7).unwrap();
+        let proof = tree.prove_many(&[2, 3, 4, 5, 6]).unwrap();
+
+        assert_eq!(range_proof, proof);
+    }
+
+    /// A storage recording the number of keys of each of its reads.
+    #[derive(Default)]
+    struct ReadRecordingStorage {
+        storage: StorageMap<TestTable>,
+        reads: RefCell<Vec<usize>>,
+    }
+
+    impl StorageInspect<TestTable> for ReadRecordingStorage {
+        type Error = Infallible;
+
+        fn get(&self, key: &u64) -> Result<Option<Cow<'_, Primitive>>, Self::Error> {
+            self.reads.borrow_mut().push(1);
+            self.storage.get(key)
+        }
+
+        fn contains_key(&self, key: &u64) -> Result<bool, Self::Error> {
+            self.storage.contains_key(key)
+        }
+
+        fn get_many(
+            &self,
+            keys: &[&u64],
+        ) -> Result<Vec<Option<Cow<'_, Primitive>>>, Self::Error> {
+            self.reads.borrow_mut().push(keys.len());
+            self.storage.get_many(keys)
+        }
+    }
+
+    impl StorageMutate<TestTable> for ReadRecordingStorage {
+        fn replace(
+            &mut self,
+            key: &u64,
+            value: &Primitive,
+        ) -> Result<Option<Primitive>, Self::Error> {
+            self.storage.replace(key, value)
+        }
+
+        fn take(&mut self, key: &u64) -> Result<Option<Primitive>, Self::Error> {
+            self.storage.take(key)
+        }
+
+        fn apply_batch(
+            &mut self,
+            changes: &[(&u64, Option<&Primitive>)],
+        ) -> Result<(), Self::Error> {
+            self.storage.apply_batch(changes)
+        }
+    }
+
+    #[test]
+    fn load_reads_the_peaks_from_the_storage_at_once() {
+        const LEAVES_COUNT: usize = 7;
+
+        let mut storage = ReadRecordingStorage::default();
+        let mut tree = MerkleTree::new(&mut storage);
+        for datum in &TEST_DATA[0..LEAVES_COUNT] {
+            let _ = tree.push(datum);
+        }
+        let root = tree.root();
+
+        let tree = MerkleTree::load(&storage, LEAVES_COUNT as u64).unwrap();
+
+        assert_eq!(tree.root(), root);
+        assert_eq!(*storage.reads.borrow(), [3]);
+    }
+
+    #[test]
+    fn prove_reads_the_side_nodes_from_the_storage_at_once() {
+        const LEAVES_COUNT: usize = 7;
+
+        let mut storage = ReadRecordingStorage::default();
+        let mut tree = MerkleTree::new(&mut storage);
+        let data = &TEST_DATA[0..LEAVES_COUNT];
+        for datum in data {
+            let _ = tree.push(datum);
+        }
+
+        let (root, proof_set) = tree.prove(0).unwrap();
+
+        // The side nodes are 02, 05, and 11, of which 11 is built in scratch storage.
+        assert!(verify(&root, &data[0], &proof_set, 0, LEAVES_COUNT as u64));
+        assert_eq!(*storage.reads.borrow(), [2]);
+    }
+
+    #[test]
+    fn push_writes_the_nodes_to_the_storage_on_commit_of_the_write_batch() {
+        const LEAVES_COUNT: usize = 7;
+
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(WriteBatch::new(&mut storage_map));
+        for datum in &TEST_DATA[0..LEAVES_COUNT] {
+            let _ = tree.push(datum);
+        }
+        let root = tree.root();
+        let batch = tree.into_storage();
+        assert!(batch.storage().is_empty());
+
+        let storage_map = batch.commit().unwrap();
+
+        let tree = MerkleTree::load(&*storage_map, LEAVES_COUNT as u64).unwrap();
+        assert_eq!(tree.root(), root);
+    }
 }
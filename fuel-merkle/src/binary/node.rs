@@ -5,6 +5,7 @@ use crate::{
     },
     common::{
         Bytes32,
+        Hasher,
         Position,
     },
 };
@@ -23,19 +24,19 @@ impl Node {
     }
 
     /// Returns `None` if the leaf cannot be created due to incorrect position.
-    pub fn create_leaf(index: u64, data: &[u8]) -> Option<Self> {
+    pub fn create_leaf<H: Hasher>(index: u64, data: &[u8]) -> Option<Self> {
         let position = Position::from_leaf_index(index)?;
-        let hash = leaf_sum(data);
+        let hash = leaf_sum::<H>(data);
         Some(Self { position, hash })
     }
 
     /// Creates a new node with the given children.
-    pub fn create_node(
+    pub fn create_node<H: Hasher>(
         position: Position,
         left_child: &Self,
         right_child: &Self,
     ) -> Self {
-        let hash = node_sum(left_child.hash(), right_child.hash());
+        let hash = node_sum::<H>(left_child.hash(), right_child.hash());
         Self { position, hash }
     }
 
@@ -1,15 +1,27 @@
-use core::convert::Infallible;
+use core::{
+    convert::Infallible,
+    fmt,
+    marker::PhantomData,
+};
 
 use crate::{
     binary::{
         empty_sum,
         Node,
     },
-    common::Bytes32,
+    common::{
+        Bytes32,
+        Hasher,
+        ProofSet,
+    },
 };
 
 use crate::alloc::borrow::ToOwned;
-use alloc::vec::Vec;
+use alloc::{
+    vec,
+    vec::Vec,
+};
+use sha2::Sha256;
 
 #[derive(Debug)]
 pub(crate) enum NodeStackPushError<E> {
@@ -17,36 +29,88 @@ pub(crate) enum NodeStackPushError<E> {
     TooLarge,
 }
 
-#[derive(Default, Debug, Clone, PartialEq)]
+/// Calculates the root of a binary Merkle tree hashing its nodes with `H`, SHA-256
+/// by default, from its leaves, without storing the nodes.
+///
+/// The leaves pushed with [`push_proven`](Self::push_proven) are proven along the way,
+/// with the proof returned by [`root_with_proof`](Self::root_with_proof).
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-pub struct MerkleRootCalculator {
+pub struct MerkleRootCalculator<H = Sha256> {
     stack: Vec<Node>,
+    /// The partial proof sets of the nodes of the stack, `None` for the nodes
+    /// without proven leaves. Empty while no proven leaf is pushed.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    proofs: Vec<Option<ProofSet>>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    phantom_hasher: PhantomData<H>,
 }
 
 impl MerkleRootCalculator {
     pub fn new() -> Self {
-        Self { stack: Vec::new() }
+        Self::new_with_hasher()
     }
 
     pub fn new_with_stack(stack: Vec<Node>) -> Self {
-        Self { stack }
+        Self::from_stack(stack)
+    }
+}
+
+impl<H> MerkleRootCalculator<H> {
+    /// Creates a calculator hashing the nodes with `H`.
+    pub fn new_with_hasher() -> Self {
+        Self::from_stack(Vec::new())
+    }
+
+    pub(crate) fn from_stack(stack: Vec<Node>) -> Self {
+        Self {
+            stack,
+            proofs: Vec::new(),
+            phantom_hasher: PhantomData,
+        }
     }
 
     pub fn clear(&mut self) {
         self.stack.clear();
+        self.proofs.clear();
+    }
+
+    pub fn stack(&self) -> &Vec<Node> {
+        &self.stack
     }
+}
 
+impl<H: Hasher> MerkleRootCalculator<H> {
     /// Push a leaf to stack of nodes, propagating changes through the tree.
     /// Calls `node_created` for each new node created, stopping on first error.
     pub(crate) fn push_with_callback<F, E>(
         &mut self,
         node: Node,
+        node_created: F,
+    ) -> Result<(), NodeStackPushError<E>>
+    where
+        F: FnMut(&Node) -> Result<(), E>,
+    {
+        self.push_node_with_callback(node, false, node_created)
+    }
+
+    fn push_node_with_callback<F, E>(
+        &mut self,
+        node: Node,
+        proven: bool,
         mut node_created: F,
     ) -> Result<(), NodeStackPushError<E>>
     where
         F: FnMut(&Node) -> Result<(), E>,
     {
         node_created(&node).map_err(NodeStackPushError::Callback)?;
+        if proven || !self.proofs.is_empty() {
+            // The nodes pushed before the first proven leaf have no proven leaves.
+            self.proofs.resize(self.stack.len(), None);
+            self.proofs.push(proven.then(ProofSet::new));
+        }
         self.stack.push(node);
 
         // Propagate changes through the tree.
@@ -62,8 +126,9 @@ impl MerkleRootCalculator {
                 .position()
                 .parent()
                 .map_err(|_| NodeStackPushError::TooLarge)?;
-            let new = Node::create_node(parent_pos, lhs, rhs);
+            let new = Node::create_node::<H>(parent_pos, lhs, rhs);
             node_created(&new).map_err(NodeStackPushError::Callback)?;
+            self.merge_proofs();
             let _ = self.stack.pop();
             let _ = self.stack.pop();
             self.stack.push(new);
@@ -72,37 +137,96 @@ impl MerkleRootCalculator {
         Ok(())
     }
 
+    /// Merges the partial proof sets of the two nodes on top of the stack into the
+    /// one of their parent.
+    fn merge_proofs(&mut self) {
+        if self.proofs.is_empty() {
+            return
+        }
+
+        let rhs_proof = self.proofs.pop().expect("The proofs match the stack");
+        let lhs_proof = self.proofs.pop().expect("The proofs match the stack");
+        #[allow(clippy::arithmetic_side_effects)] // The stack has at least two nodes
+        let (lhs, rhs) = (
+            &self.stack[self.stack.len() - 2],
+            &self.stack[self.stack.len() - 1],
+        );
+        let proof = match (lhs_proof, rhs_proof) {
+            (None, None) => None,
+            (Some(mut lhs_proof), None) => {
+                lhs_proof.push(*rhs.hash());
+                Some(lhs_proof)
+            }
+            (None, Some(mut rhs_proof)) => {
+                rhs_proof.insert(0, *lhs.hash());
+                Some(rhs_proof)
+            }
+            (Some(mut lhs_proof), Some(rhs_proof)) => {
+                lhs_proof.extend(rhs_proof);
+                Some(lhs_proof)
+            }
+        };
+        self.proofs.push(proof);
+    }
+
     /// Push a new leaf node.
     /// Panics if the tree would be too large to compute the root for.
     /// In practice this never occurs, as you'd run out of memory first.
     pub fn push(&mut self, data: &[u8]) {
-        let node = Node::create_leaf(0, data).expect("Zero is a valid index for a leaf");
+        let node =
+            Node::create_leaf::<H>(0, data).expect("Zero is a valid index for a leaf");
         self.push_with_callback::<_, Infallible>(node, |_| Ok(()))
             .expect("Tree too large");
     }
 
-    pub fn root(mut self) -> Bytes32 {
+    /// Push a new leaf node to be proven. The proven leaves are given to
+    /// [`verify_many`](crate::binary::verify_many) with the proof set of
+    /// [`root_with_proof`](Self::root_with_proof).
+    /// Panics if the tree would be too large to compute the root for.
+    pub fn push_proven(&mut self, data: &[u8]) {
+        let node =
+            Node::create_leaf::<H>(0, data).expect("Zero is a valid index for a leaf");
+        self.push_node_with_callback::<_, Infallible>(node, true, |_| Ok(()))
+            .expect("Tree too large");
+    }
+
+    pub fn root(self) -> Bytes32 {
+        self.root_with_proof().0
+    }
+
+    /// Returns the root and the proof set of the leaves pushed with
+    /// [`push_proven`](Self::push_proven), the one generated by
+    /// [`MerkleTree::prove_many`](crate::binary::MerkleTree::prove_many).
+    pub fn root_with_proof(mut self) -> (Bytes32, ProofSet) {
         if self.stack.is_empty() {
-            return empty_sum().to_owned()
+            return (empty_sum::<H>(), ProofSet::new())
         }
         while self.stack.len() > 1 {
+            self.merge_proofs();
             let right_child = self.stack.pop().expect("Checked in loop bound");
             let left_child = self.stack.pop().expect("Checked in loop bound");
             let merged_pos = left_child
                 .position()
                 .parent()
                 .expect("Left child has no parent");
-            let merged_node = Node::create_node(merged_pos, &left_child, &right_child);
+            let merged_node =
+                Node::create_node::<H>(merged_pos, &left_child, &right_child);
             self.stack.push(merged_node);
         }
-        self.stack.pop().unwrap().hash().to_owned()
+        let root = self.stack.pop().unwrap().hash().to_owned();
+        // Without proven leaves, the root proves that there are none.
+        let proof_set = match self.proofs.pop() {
+            Some(Some(proof_set)) => proof_set,
+            _ => vec![root],
+        };
+        (root, proof_set)
     }
 
     pub fn root_from_iterator<I: Iterator<Item = T>, T: AsRef<[u8]>>(
         self,
         iterator: I,
     ) -> Bytes32 {
-        let mut calculator = MerkleRootCalculator::new();
+        let mut calculator = Self::new_with_hasher();
 
         for data in iterator {
             calculator.push(data.as_ref());
@@ -110,9 +234,36 @@ impl MerkleRootCalculator {
 
         calculator.root()
     }
+}
 
-    pub fn stack(&self) -> &Vec<Node> {
-        &self.stack
+impl<H> Default for MerkleRootCalculator<H> {
+    fn default() -> Self {
+        Self::new_with_hasher()
+    }
+}
+
+impl<H> Clone for MerkleRootCalculator<H> {
+    fn clone(&self) -> Self {
+        Self {
+            stack: self.stack.clone(),
+            proofs: self.proofs.clone(),
+            phantom_hasher: PhantomData,
+        }
+    }
+}
+
+impl<H> PartialEq for MerkleRootCalculator<H> {
+    fn eq(&self, other: &Self) -> bool {
+        self.stack == other.stack && self.proofs == other.proofs
+    }
+}
+
+impl<H> fmt::Debug for MerkleRootCalculator<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MerkleRootCalculator")
+            .field("stack", &self.stack)
+            .field("proofs", &self.proofs)
+            .finish()
     }
 }
 
@@ -120,9 +271,11 @@ impl MerkleRootCalculator {
 mod test {
     use super::*;
     use crate::binary::in_memory::MerkleTree;
+    use digest::Digest;
     use fuel_merkle_test_helpers::TEST_DATA;
     #[cfg(test)]
     use serde_json as _;
+    use sha3::Keccak256;
 
     #[test]
     fn root_returns_the_empty_root_for_0_leaves() {
@@ -132,6 +285,14 @@ mod test {
         assert_eq!(tree.root(), calculate_root.root());
     }
 
+    #[test]
+    fn root_returns_the_empty_root_for_0_leaves_with_the_hasher() {
+        let calculate_root = MerkleRootCalculator::<Keccak256>::new_with_hasher();
+
+        let expected_root = Bytes32::from(Keccak256::digest([]));
+        assert_eq!(calculate_root.root(), expected_root);
+    }
+
     #[test]
     fn root_returns_the_merkle_root_for_1_leaf() {
         let mut tree = MerkleTree::new();
@@ -188,6 +349,81 @@ mod test {
         assert_eq!(tree.root(), root);
     }
 
+    #[test]
+    fn root_with_proof_returns_the_proof_set_of_the_proven_leaves() {
+        for leaves_count in 0..=8usize {
+            let mut tree = MerkleTree::new();
+            for datum in TEST_DATA[0..leaves_count].iter() {
+                tree.push(datum);
+            }
+
+            for subset in 0..1u32 << leaves_count {
+                let proven = |index: usize| subset & (1 << index) != 0;
+                let mut calculator = MerkleRootCalculator::new();
+                for (index, datum) in TEST_DATA[0..leaves_count].iter().enumerate() {
+                    if proven(index) {
+                        calculator.push_proven(datum);
+                    } else {
+                        calculator.push(datum);
+                    }
+                }
+                let proof_indices = (0..leaves_count)
+                    .filter(|index| proven(*index))
+                    .map(|index| index as u64)
+                    .collect::<Vec<_>>();
+
+                assert_eq!(
+                    calculator.root_with_proof(),
+                    tree.prove_many(&proof_indices).unwrap(),
+                    "{leaves_count} leaves, {proof_indices:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn root_returns_the_merkle_root_with_proven_leaves() {
+        let mut tree = MerkleTree::new();
+        let mut calculate_root = MerkleRootCalculator::new();
+
+        let data = &TEST_DATA[0..7];
+        for (index, datum) in data.iter().enumerate() {
+            tree.push(datum);
+            if index % 3 == 0 {
+                calculate_root.push_proven(datum);
+            } else {
+                calculate_root.push(datum);
+            }
+        }
+
+        assert_eq!(tree.root(), calculate_root.root());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serialize_deserialize_with_proven_leaves() {
+        let mut calculator = MerkleRootCalculator::new();
+
+        let data = &TEST_DATA[0..7];
+        for datum in data[..3].iter() {
+            calculator.push(datum);
+        }
+        calculator.push_proven(data[3]);
+        let json = serde_json::to_string(&calculator).unwrap();
+
+        let mut deserialized_calculator: MerkleRootCalculator =
+            serde_json::from_str(&json).expect("Unable to read from str");
+        for datum in data[4..].iter() {
+            calculator.push(datum);
+            deserialized_calculator.push(datum);
+        }
+
+        assert_eq!(
+            calculator.root_with_proof(),
+            deserialized_calculator.root_with_proof()
+        );
+    }
+
     #[test]
     #[cfg(feature = "serde")]
     fn test_serialize_deserialize() {
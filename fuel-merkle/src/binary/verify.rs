@@ -1,14 +1,20 @@
 use crate::{
     binary::{
+        empty_sum,
         leaf_sum,
         node_sum,
     },
     common::{
         Bytes32,
+        Hasher,
         ProofSet,
     },
 };
 
+use alloc::vec::Vec;
+use core::iter::Peekable;
+use sha2::Sha256;
+
 /// Returns None if:
 /// - `num_leaves` is 0
 /// - the result doens't fit in an usize
@@ -49,6 +55,18 @@ pub fn verify<T: AsRef<[u8]>>(
     proof_set: &ProofSet,
     proof_index: u64,
     num_leaves: u64,
+) -> bool {
+    verify_with_hasher::<Sha256, T>(root, data, proof_set, proof_index, num_leaves)
+}
+
+/// Verifies the inclusion proof of the leaf at `proof_index` against the `root` of a
+/// tree hashing its nodes with `H`, as in [`verify`].
+pub fn verify_with_hasher<H: Hasher, T: AsRef<[u8]>>(
+    root: &Bytes32,
+    data: &T,
+    proof_set: &ProofSet,
+    proof_index: u64,
+    num_leaves: u64,
 ) -> bool {
     if num_leaves <= 1 {
         if !proof_set.is_empty() {
@@ -62,7 +80,7 @@ pub fn verify<T: AsRef<[u8]>>(
         return false;
     }
 
-    let mut sum = leaf_sum(data.as_ref());
+    let mut sum = leaf_sum::<H>(data.as_ref());
     if proof_set.is_empty() {
         return if num_leaves == 1 { *root == sum } else { false }
     }
@@ -95,9 +113,9 @@ pub fn verify<T: AsRef<[u8]>>(
         let proof_data = proof_set[parent];
         #[allow(clippy::arithmetic_side_effects)] // proof_index > subtree_start_index
         if proof_index - subtree_start_index < (1 << parent) {
-            sum = node_sum(&sum, &proof_data);
+            sum = node_sum::<H>(&sum, &proof_data);
         } else {
-            sum = node_sum(&proof_data, &sum);
+            sum = node_sum::<H>(&proof_data, &sum);
         }
 
         #[allow(clippy::arithmetic_side_effects)] // path_length_from_key checks
@@ -111,7 +129,7 @@ pub fn verify<T: AsRef<[u8]>>(
             return false
         }
         let proof_data = proof_set[parent];
-        sum = node_sum(&sum, &proof_data);
+        sum = node_sum::<H>(&sum, &proof_data);
         #[allow(clippy::arithmetic_side_effects)] // path_length_from_key checks
         {
             parent += 1;
@@ -120,7 +138,7 @@ pub fn verify<T: AsRef<[u8]>>(
 
     while parent < proof_set.len() {
         let proof_data = proof_set[parent];
-        sum = node_sum(&proof_data, &sum);
+        sum = node_sum::<H>(&proof_data, &sum);
         #[allow(clippy::arithmetic_side_effects)] // path_length_from_key checks
         {
             parent += 1;
@@ -130,9 +148,205 @@ pub fn verify<T: AsRef<[u8]>>(
     sum == *root
 }
 
+/// Verifies the proof of the `leaves`, given as `(index, data)` pairs with strictly
+/// increasing indices, against the `root` of a tree of `num_leaves` leaves, as
+/// generated by [`MerkleTree::prove_many`](crate::binary::MerkleTree::prove_many).
+pub fn verify_many<T: AsRef<[u8]>>(
+    root: &Bytes32,
+    leaves: &[(u64, T)],
+    proof_set: &ProofSet,
+    num_leaves: u64,
+) -> bool {
+    verify_many_with_hasher::<Sha256, T>(root, leaves, proof_set, num_leaves)
+}
+
+/// Verifies the proof of the `leaves` of a tree hashing its nodes with `H`, as in
+/// [`verify_many`].
+pub fn verify_many_with_hasher<H: Hasher, T: AsRef<[u8]>>(
+    root: &Bytes32,
+    leaves: &[(u64, T)],
+    proof_set: &ProofSet,
+    num_leaves: u64,
+) -> bool {
+    let indices_are_increasing = leaves
+        .windows(2)
+        .all(|pair| matches!(pair, [(lhs, _), (rhs, _)] if lhs < rhs));
+    if !indices_are_increasing {
+        return false
+    }
+    if let Some((last_index, _)) = leaves.last() {
+        if *last_index >= num_leaves {
+            return false
+        }
+    }
+    if num_leaves == 0 {
+        return proof_set.is_empty() && *root == empty_sum::<H>()
+    }
+
+    let mut leaves = leaves.iter().peekable();
+    let mut proof = proof_set.iter();
+    let sum = multiproof_subtree_sum::<H, T>(0, num_leaves, &mut leaves, &mut proof);
+
+    sum.as_ref() == Some(root) && proof.next().is_none()
+}
+
+/// Verifies the proof of the contiguous leaves with the `data`, starting at the
+/// index `start`, against the `root` of a tree of `num_leaves` leaves, as generated
+/// by [`MerkleTree::prove_range`](crate::binary::MerkleTree::prove_range).
+pub fn verify_range<T: AsRef<[u8]>>(
+    root: &Bytes32,
+    data: &[T],
+    start: u64,
+    proof_set: &ProofSet,
+    num_leaves: u64,
+) -> bool {
+    verify_range_with_hasher::<Sha256, T>(root, data, start, proof_set, num_leaves)
+}
+
+/// Verifies the proof of the contiguous leaves of a tree hashing its nodes with `H`,
+/// as in [`verify_range`].
+pub fn verify_range_with_hasher<H: Hasher, T: AsRef<[u8]>>(
+    root: &Bytes32,
+    data: &[T],
+    start: u64,
+    proof_set: &ProofSet,
+    num_leaves: u64,
+) -> bool {
+    // The indices past `u64::MAX` are not zipped, which makes the proof invalid.
+    let leaves = (start..=u64::MAX)
+        .zip(data)
+        .map(|(index, data)| (index, data.as_ref()))
+        .collect::<Vec<_>>();
+    if leaves.len() != data.len() {
+        return false
+    }
+
+    verify_many_with_hasher::<H, _>(root, &leaves, proof_set, num_leaves)
+}
+
+/// Calculates the root of the subtree of the leaves `start..end` from the proven
+/// `leaves` and the `proof` of the subtrees without proven leaves, consuming both in
+/// the depth-first order of the subtrees from left to right.
+///
+/// Returns `None` if the proof is too short.
+fn multiproof_subtree_sum<'a, H: Hasher, T: AsRef<[u8]> + 'a>(
+    start: u64,
+    end: u64,
+    leaves: &mut Peekable<impl Iterator<Item = &'a (u64, T)>>,
+    proof: &mut impl Iterator<Item = &'a Bytes32>,
+) -> Option<Bytes32> {
+    let contains_proven_leaf = matches!(leaves.peek(), Some((index, _)) if *index < end);
+    if !contains_proven_leaf {
+        return proof.next().copied()
+    }
+
+    #[allow(clippy::arithmetic_side_effects)] // The index of the leaf is in start..end
+    let leaves_count = end - start;
+    if leaves_count == 1 {
+        let (_, data) = leaves.next()?;
+        return Some(leaf_sum::<H>(data.as_ref()))
+    }
+
+    #[allow(clippy::arithmetic_side_effects)] // leaves_count > 1
+    let middle = start + (1u64 << (leaves_count - 1).ilog2());
+    let lhs = multiproof_subtree_sum::<H, T>(start, middle, leaves, proof)?;
+    let rhs = multiproof_subtree_sum::<H, T>(middle, end, leaves, proof)?;
+    Some(node_sum::<H>(&lhs, &rhs))
+}
+
+/// Verifies that the tree of `leaves_count` leaves with the given `root` is an
+/// append-only extension of the tree of `old_leaves_count` leaves with the given
+/// `old_root`, following the algorithm of
+/// [RFC 9162](https://www.rfc-editor.org/rfc/rfc9162#section-2.1.4.2) for RFC 6962
+/// consistency proofs.
+pub fn verify_consistency(
+    old_root: &Bytes32,
+    old_leaves_count: u64,
+    root: &Bytes32,
+    leaves_count: u64,
+    proof_set: &ProofSet,
+) -> bool {
+    verify_consistency_with_hasher::<Sha256>(
+        old_root,
+        old_leaves_count,
+        root,
+        leaves_count,
+        proof_set,
+    )
+}
+
+/// Verifies the consistency proof of trees hashing their nodes with `H`, as in
+/// [`verify_consistency`].
+pub fn verify_consistency_with_hasher<H: Hasher>(
+    old_root: &Bytes32,
+    old_leaves_count: u64,
+    root: &Bytes32,
+    leaves_count: u64,
+    proof_set: &ProofSet,
+) -> bool {
+    if old_leaves_count > leaves_count {
+        return false
+    }
+    if old_leaves_count == 0 {
+        return proof_set.is_empty() && *old_root == empty_sum::<H>()
+    }
+    if old_leaves_count == leaves_count {
+        return proof_set.is_empty() && old_root == root
+    }
+
+    // When the old tree is balanced, its root is the first node of the proof.
+    let mut proof = proof_set.iter();
+    let first = if old_leaves_count.is_power_of_two() {
+        old_root
+    } else {
+        match proof.next() {
+            Some(first) => first,
+            None => return false,
+        }
+    };
+
+    // The indices of the last leaves of the old and new trees, shifted to the
+    // height of the current node.
+    #[allow(clippy::arithmetic_side_effects)] // 0 < old_leaves_count < leaves_count
+    let (mut old_index, mut index) = (old_leaves_count - 1, leaves_count - 1);
+    while old_index & 1 == 1 {
+        old_index >>= 1;
+        index >>= 1;
+    }
+
+    let mut old_sum = *first;
+    let mut sum = *first;
+    for proof_data in proof {
+        if index == 0 {
+            return false
+        }
+
+        if old_index & 1 == 1 || old_index == index {
+            old_sum = node_sum::<H>(proof_data, &old_sum);
+            sum = node_sum::<H>(proof_data, &sum);
+            while old_index & 1 == 0 && old_index != 0 {
+                old_index >>= 1;
+                index >>= 1;
+            }
+        } else {
+            sum = node_sum::<H>(&sum, proof_data);
+        }
+
+        old_index >>= 1;
+        index >>= 1;
+    }
+
+    old_sum == *old_root && sum == *root && index == 0
+}
+
 #[cfg(test)]
 mod test {
-    use super::verify;
+    use super::{
+        verify,
+        verify_consistency,
+        verify_many,
+        verify_range,
+    };
     use crate::{
         binary::{
             MerkleTree,
@@ -140,6 +354,7 @@ mod test {
         },
         common::StorageMap,
     };
+    use alloc::vec::Vec;
     use fuel_merkle_test_helpers::TEST_DATA;
     use fuel_storage::Mappable;
 
@@ -260,4 +475,263 @@ mod test {
         );
         assert!(!verification);
     }
+
+    #[test]
+    fn verify_consistency_returns_true_when_the_proof_set_matches_the_given_merkle_roots()
+    {
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage_map);
+
+        const OLD_LEAVES_COUNT: usize = 5;
+        const LEAVES_COUNT: usize = 10;
+
+        for datum in TEST_DATA[0..OLD_LEAVES_COUNT].iter() {
+            tree.push(datum).unwrap();
+        }
+        let old_root = tree.root();
+        for datum in TEST_DATA[OLD_LEAVES_COUNT..LEAVES_COUNT].iter() {
+            tree.push(datum).unwrap();
+        }
+        let root = tree.root();
+        let proof_set = tree.prove_consistency(OLD_LEAVES_COUNT as u64).unwrap();
+
+        let verification = verify_consistency(
+            &old_root,
+            OLD_LEAVES_COUNT as u64,
+            &root,
+            LEAVES_COUNT as u64,
+            &proof_set,
+        );
+        assert!(verification);
+    }
+
+    #[test]
+    fn verify_consistency_returns_false_when_the_old_tree_is_not_a_prefix_of_the_tree() {
+        // The old tree's leaves differ from the first leaves of the new tree, so
+        // the new tree is not an append-only extension of the old tree.
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage_map);
+
+        const OLD_LEAVES_COUNT: usize = 3;
+        const LEAVES_COUNT: usize = 7;
+
+        for datum in TEST_DATA[1..OLD_LEAVES_COUNT + 1].iter() {
+            tree.push(datum).unwrap();
+        }
+        let old_root = tree.root();
+
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage_map);
+        for datum in TEST_DATA[0..LEAVES_COUNT].iter() {
+            tree.push(datum).unwrap();
+        }
+        let root = tree.root();
+        let proof_set = tree.prove_consistency(OLD_LEAVES_COUNT as u64).unwrap();
+
+        let verification = verify_consistency(
+            &old_root,
+            OLD_LEAVES_COUNT as u64,
+            &root,
+            LEAVES_COUNT as u64,
+            &proof_set,
+        );
+        assert!(!verification);
+    }
+
+    #[test]
+    fn verify_consistency_returns_false_when_the_leaves_counts_are_invalid() {
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage_map);
+
+        const OLD_LEAVES_COUNT: usize = 4;
+        const LEAVES_COUNT: usize = 6;
+
+        for datum in TEST_DATA[0..OLD_LEAVES_COUNT].iter() {
+            tree.push(datum).unwrap();
+        }
+        let old_root = tree.root();
+        for datum in TEST_DATA[OLD_LEAVES_COUNT..LEAVES_COUNT].iter() {
+            tree.push(datum).unwrap();
+        }
+        let root = tree.root();
+        let proof_set = tree.prove_consistency(OLD_LEAVES_COUNT as u64).unwrap();
+
+        assert!(!verify_consistency(
+            &old_root,
+            OLD_LEAVES_COUNT as u64,
+            &root,
+            LEAVES_COUNT as u64 * 2,
+            &proof_set,
+        ));
+        assert!(!verify_consistency(
+            &old_root,
+            OLD_LEAVES_COUNT as u64 - 1,
+            &root,
+            LEAVES_COUNT as u64,
+            &proof_set,
+        ));
+        assert!(!verify_consistency(
+            &root,
+            LEAVES_COUNT as u64,
+            &old_root,
+            OLD_LEAVES_COUNT as u64,
+            &proof_set,
+        ));
+    }
+
+    #[test]
+    fn verify_many_returns_true_for_every_set_of_leaves_of_trees_up_to_10_leaves() {
+        for leaves_count in 0..=10usize {
+            let mut storage_map = StorageMap::<TestTable>::new();
+            let mut tree = MerkleTree::new(&mut storage_map);
+            for datum in TEST_DATA[0..leaves_count].iter() {
+                tree.push(datum).unwrap();
+            }
+
+            for subset in 0..1u32 << leaves_count {
+                let leaves = (0..leaves_count)
+                    .filter(|index| subset & (1 << index) != 0)
+                    .map(|index| (index as u64, TEST_DATA[index]))
+                    .collect::<Vec<_>>();
+                let proof_indices =
+                    leaves.iter().map(|(index, _)| *index).collect::<Vec<_>>();
+                let (root, proof_set) = tree.prove_many(&proof_indices).unwrap();
+
+                let verification =
+                    verify_many(&root, &leaves, &proof_set, leaves_count as u64);
+                assert!(verification, "{leaves_count} leaves, {proof_indices:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn verify_many_returns_false_when_the_data_of_a_leaf_is_not_in_the_tree() {
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage_map);
+
+        const LEAVES_COUNT: usize = 7;
+
+        for datum in TEST_DATA[0..LEAVES_COUNT].iter() {
+            tree.push(datum).unwrap();
+        }
+        let (root, proof_set) = tree.prove_many(&[1, 4]).unwrap();
+
+        let leaves = [(1, TEST_DATA[1]), (4, TEST_DATA[5])];
+        let verification = verify_many(&root, &leaves, &proof_set, LEAVES_COUNT as u64);
+        assert!(!verification);
+    }
+
+    #[test]
+    fn verify_many_returns_false_when_the_proof_indices_are_invalid() {
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage_map);
+
+        const LEAVES_COUNT: usize = 7;
+
+        for datum in TEST_DATA[0..LEAVES_COUNT].iter() {
+            tree.push(datum).unwrap();
+        }
+        let (root, proof_set) = tree.prove_many(&[1, 4]).unwrap();
+
+        let unordered_leaves = [(4, TEST_DATA[4]), (1, TEST_DATA[1])];
+        let duplicated_leaves = [(1, TEST_DATA[1]), (1, TEST_DATA[1]), (4, TEST_DATA[4])];
+        let shifted_leaves = [(0, TEST_DATA[1]), (4, TEST_DATA[4])];
+        assert!(!verify_many(
+            &root,
+            &unordered_leaves,
+            &proof_set,
+            LEAVES_COUNT as u64
+        ));
+        assert!(!verify_many(
+            &root,
+            &duplicated_leaves,
+            &proof_set,
+            LEAVES_COUNT as u64
+        ));
+        assert!(!verify_many(
+            &root,
+            &shifted_leaves,
+            &proof_set,
+            LEAVES_COUNT as u64
+        ));
+    }
+
+    #[test]
+    fn verify_many_returns_false_when_the_proof_set_has_extra_nodes() {
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage_map);
+
+        const LEAVES_COUNT: usize = 7;
+
+        for datum in TEST_DATA[0..LEAVES_COUNT].iter() {
+            tree.push(datum).unwrap();
+        }
+        let (root, mut proof_set) = tree.prove_many(&[1, 4]).unwrap();
+        proof_set.push(root);
+
+        let leaves = [(1, TEST_DATA[1]), (4, TEST_DATA[4])];
+        let verification = verify_many(&root, &leaves, &proof_set, LEAVES_COUNT as u64);
+        assert!(!verification);
+    }
+
+    #[test]
+    fn verify_range_returns_true_when_the_proof_set_matches_the_given_merkle_root() {
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage_map);
+
+        const LEAVES_COUNT: usize = 10;
+        const START: usize = 3;
+        const END: usize = 8;
+
+        for datum in TEST_DATA[0..LEAVES_COUNT].iter() {
+            tree.push(datum).unwrap();
+        }
+        let (root, proof_set) = tree.prove_range(START as u64..END as u64).unwrap();
+
+        let verification = verify_range(
+            &root,
+            &TEST_DATA[START..END],
+            START as u64,
+            &proof_set,
+            LEAVES_COUNT as u64,
+        );
+        assert!(verification);
+    }
+
+    #[test]
+    fn verify_range_returns_false_when_the_range_does_not_match_the_proof_set() {
+        let mut storage_map = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage_map);
+
+        const LEAVES_COUNT: usize = 10;
+        const START: usize = 3;
+        const END: usize = 8;
+
+        for datum in TEST_DATA[0..LEAVES_COUNT].iter() {
+            tree.push(datum).unwrap();
+        }
+        let (root, proof_set) = tree.prove_range(START as u64..END as u64).unwrap();
+
+        assert!(!verify_range(
+            &root,
+            &TEST_DATA[START..END - 1],
+            START as u64,
+            &proof_set,
+            LEAVES_COUNT as u64,
+        ));
+        assert!(!verify_range(
+            &root,
+            &TEST_DATA[START + 1..END + 1],
+            START as u64 + 1,
+            &proof_set,
+            LEAVES_COUNT as u64,
+        ));
+        assert!(!verify_range(
+            &root,
+            &TEST_DATA[START..END],
+            u64::MAX - 1,
+            &proof_set,
+            u64::MAX,
+        ));
+    }
 }
@@ -5,6 +5,7 @@ mod position;
 mod position_path;
 mod prefix;
 mod storage_map;
+mod write_batch;
 
 pub(crate) mod error;
 pub(crate) mod node;
@@ -13,6 +14,7 @@ pub(crate) mod path;
 pub use path_iterator::AsPathIterator;
 pub use position::Position;
 pub use storage_map::StorageMap;
+pub use write_batch::WriteBatch;
 
 pub(crate) use msb::Msb;
 pub(crate) use position_path::PositionPath;
@@ -34,6 +36,7 @@ pub type ProofSet = Vec<Bytes32>;
 pub use hash::{
     sum,
     sum_iter,
+    Hasher,
 };
 
 // Merkle Tree hash of an empty list
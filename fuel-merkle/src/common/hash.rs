@@ -1,17 +1,154 @@
 use super::Bytes32;
 
+use core::iter;
+use digest::{
+    consts::U32,
+    Digest,
+    OutputSizeUser,
+};
+
+/// The hash function of the nodes of the Merkle trees, producing 32-byte hashes.
+///
+/// It is implemented for the [`Digest`] implementations with 32-byte outputs, like
+/// SHA-256, the default hash function of the trees, and Keccak-256. Other hash
+/// functions, like SNARK-friendly ones, can implement it on their own type.
+pub trait Hasher {
+    /// Hashes the concatenation of the `data`.
+    fn hash_iter<I, T>(data: I) -> Bytes32
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<[u8]>;
+
+    fn hash<T: AsRef<[u8]>>(data: T) -> Bytes32 {
+        Self::hash_iter(iter::once(data))
+    }
+}
+
+impl<D> Hasher for D
+where
+    D: Digest + OutputSizeUser<OutputSize = U32>,
+{
+    fn hash_iter<I, T>(data: I) -> Bytes32
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<[u8]>,
+    {
+        let mut hash = D::new();
+        for data in data {
+            hash.update(data.as_ref());
+        }
+        hash.finalize().into()
+    }
+}
+
 pub fn sum<T: AsRef<[u8]>>(data: T) -> Bytes32 {
-    use digest::Digest;
-    let mut hash = sha2::Sha256::new();
-    hash.update(data.as_ref());
-    hash.finalize().into()
+    sha2::Sha256::hash(data)
 }
 
 pub fn sum_iter<I: IntoIterator<Item = T>, T: AsRef<[u8]>>(iterator: I) -> Bytes32 {
-    use digest::Digest;
-    let mut hash = sha2::Sha256::new();
-    for data in iterator {
-        hash.update(data.as_ref());
+    sha2::Sha256::hash_iter(iterator)
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod test {
+    use super::*;
+    use crate::{
+        common::StorageMap,
+        sparse::{
+            proof::Proof,
+            MerkleTree,
+            MerkleTreeKey,
+            Primitive,
+        },
+        storage::Mappable,
+    };
+
+    #[derive(Debug)]
+    struct TestTable;
+
+    impl Mappable for TestTable {
+        type Key = Self::OwnedKey;
+        type OwnedKey = Bytes32;
+        type OwnedValue = Primitive;
+        type Value = Self::OwnedValue;
+    }
+
+    /// A hash function that isn't a [`Digest`]: four FNV-1a lanes with different
+    /// offset bases.
+    struct ToyHasher;
+
+    impl Hasher for ToyHasher {
+        fn hash_iter<I, T>(data: I) -> Bytes32
+        where
+            I: IntoIterator<Item = T>,
+            T: AsRef<[u8]>,
+        {
+            let mut lanes = [0u64, 1, 2, 3].map(|lane| 0xcbf29ce484222325 ^ lane);
+            for data in data {
+                for byte in data.as_ref() {
+                    for lane in &mut lanes {
+                        *lane = (*lane ^ u64::from(*byte)).wrapping_mul(0x100000001b3);
+                    }
+                }
+            }
+
+            let mut hash = [0; 32];
+            for (chunk, lane) in hash.chunks_exact_mut(8).zip(lanes) {
+                chunk.copy_from_slice(&lane.to_be_bytes());
+            }
+            hash
+        }
+    }
+
+    type ToyTree = MerkleTree<TestTable, StorageMap<TestTable>, ToyHasher>;
+
+    fn key(n: u32) -> MerkleTreeKey {
+        MerkleTreeKey::new(n.to_be_bytes())
+    }
+
+    fn toy_tree(set: &[(u32, &[u8])]) -> ToyTree {
+        let mut tree = ToyTree::new_with_hasher(StorageMap::new());
+        for (n, data) in set {
+            tree.update(key(*n), data).unwrap();
+        }
+        tree
+    }
+
+    #[test]
+    fn verify_with_hasher__verifies_proof_of_tree_with_custom_hasher() {
+        // Given
+        let tree = toy_tree(&[(0, b"DATA_0"), (1, b"DATA_1"), (2, b"DATA_2")]);
+        let root = tree.root();
+
+        // When
+        let proof = tree.generate_proof(&key(1)).unwrap();
+
+        // Then
+        let Proof::Inclusion(proof) = proof else {
+            panic!("Expected an inclusion proof");
+        };
+        assert!(proof.verify_with_hasher::<ToyHasher>(&root, &key(1), b"DATA_1"));
+        assert!(!proof.verify_with_hasher::<ToyHasher>(&root, &key(1), b"DATA_0"));
+        assert!(!proof.verify(&root, &key(1), b"DATA_1"));
+    }
+
+    #[test]
+    fn update_root_with_hasher__computes_root_of_tree_with_custom_hasher() {
+        // Given
+        let tree = toy_tree(&[(0, b"DATA_0"), (1, b"DATA_1"), (2, b"DATA_2")]);
+        let root = tree.root();
+        let witness = tree.generate_update_witness(&[key(0), key(1)]).unwrap();
+
+        // When
+        let updated_root = witness.update_root_with_hasher::<ToyHasher, _, _>(
+            &root,
+            &[(key(0), Some(b"DATA_0")), (key(1), Some(b"DATA_1"))],
+            &[(key(0), Some(&b"DATA_3"[..])), (key(1), None)],
+        );
+
+        // Then
+        let expected_tree = toy_tree(&[(0, b"DATA_3"), (2, b"DATA_2")]);
+        assert_eq!(updated_root, Ok(expected_tree.root()));
     }
-    hash.finalize().into()
 }
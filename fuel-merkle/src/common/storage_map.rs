@@ -87,6 +87,24 @@ where
         let value = self.map.remove(key);
         Ok(value)
     }
+
+    fn apply_batch(
+        &mut self,
+        changes: &[(&Type::Key, Option<&Type::Value>)],
+    ) -> Result<(), Self::Error> {
+        for (key, value) in changes {
+            match value {
+                Some(value) => {
+                    self.map
+                        .insert((*key).to_owned().into(), (*value).to_owned().into());
+                }
+                None => {
+                    self.map.remove(*key);
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -172,6 +190,40 @@ mod test {
         assert_eq!(store.contains_key(&key).unwrap(), true);
     }
 
+    #[test]
+    fn test_get_many_returns_values_for_given_keys_in_order() {
+        let mut store = StorageMap::<TestTable>::new();
+        let _ = store.insert(&TestKey(0), &TestValue(0));
+        let _ = store.insert(&TestKey(2), &TestValue(2));
+
+        assert_eq!(
+            store
+                .get_many(&[&TestKey(2), &TestKey(1), &TestKey(0)])
+                .unwrap(),
+            vec![
+                Some(Cow::Borrowed(&TestValue(2))),
+                None,
+                Some(Cow::Borrowed(&TestValue(0)))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_apply_batch_inserts_and_removes_values_for_given_keys() {
+        let mut store = StorageMap::<TestTable>::new();
+        let _ = store.insert(&TestKey(0), &TestValue(0));
+
+        store
+            .apply_batch(&[(&TestKey(0), None), (&TestKey(1), Some(&TestValue(1)))])
+            .unwrap();
+
+        assert_eq!(store.get(&TestKey(0)).unwrap(), None);
+        assert_eq!(
+            store.get(&TestKey(1)).unwrap(),
+            Some(Cow::Borrowed(&TestValue(1)))
+        );
+    }
+
     #[test]
     fn test_contains_key_returns_false_for_invalid_key() {
         let invalid_key = TestKey(0);
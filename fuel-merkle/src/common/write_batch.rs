@@ -0,0 +1,359 @@
+use crate::{
+    alloc::borrow::ToOwned,
+    storage::{
+        Mappable,
+        StorageInspect,
+        StorageMutate,
+    },
+};
+
+use alloc::{
+    borrow::Cow,
+    vec::Vec,
+};
+use core::{
+    borrow::Borrow,
+    fmt,
+    hash::Hash,
+};
+use hashbrown::HashMap;
+
+/// A storage buffering the changes to the `storage` in memory until they are
+/// committed. Reads see the buffered changes before falling through to the `storage`.
+///
+/// The Merkle trees buffer the node writes of every update in a `WriteBatch` and
+/// apply them to their storage at once with [`StorageMutate::apply_batch`]. A
+/// `WriteBatch` is itself a [`StorageMutate`], so using it as the storage of a
+/// tree collects the node writes of several updates, which [`commit`](Self::commit)
+/// then applies to the `storage` atomically:
+///
+/// ```ignore
+/// let mut tree = MerkleTree::new(WriteBatch::new(&mut storage));
+/// tree.push(data_0)?;
+/// tree.push(data_1)?;
+/// tree.into_storage().commit()?;
+/// ```
+pub struct WriteBatch<Type, StorageType>
+where
+    Type: Mappable,
+{
+    storage: StorageType,
+    /// The buffered changes, with `None` for the removed keys. They are keyed by the
+    /// owned form of `Type::Key`, which can always be borrowed as a `Type::Key`.
+    changes: HashMap<<Type::Key as ToOwned>::Owned, Option<Type::OwnedValue>>,
+}
+
+impl<Type, StorageType> fmt::Debug for WriteBatch<Type, StorageType>
+where
+    Type: Mappable,
+    <Type::Key as ToOwned>::Owned: fmt::Debug,
+    Type::OwnedValue: fmt::Debug,
+    StorageType: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WriteBatch")
+            .field("storage", &self.storage)
+            .field("changes", &self.changes)
+            .finish()
+    }
+}
+
+impl<Type, StorageType> Clone for WriteBatch<Type, StorageType>
+where
+    Type: Mappable,
+    <Type::Key as ToOwned>::Owned: Clone,
+    StorageType: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            storage: self.storage.clone(),
+            changes: self.changes.clone(),
+        }
+    }
+}
+
+impl<Type, StorageType> WriteBatch<Type, StorageType>
+where
+    Type: Mappable,
+{
+    pub fn new(storage: StorageType) -> Self {
+        Self {
+            storage,
+            changes: Default::default(),
+        }
+    }
+
+    pub fn storage(&self) -> &StorageType {
+        &self.storage
+    }
+
+    /// Returns `true` if there are no buffered changes.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// The number of keys with buffered changes.
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// Discards the buffered changes and returns the storage.
+    pub fn into_storage(self) -> StorageType {
+        self.storage
+    }
+}
+
+impl<Type, StorageType> WriteBatch<Type, StorageType>
+where
+    Type: Mappable,
+    Type::OwnedValue: Borrow<Type::Value>,
+    StorageType: StorageMutate<Type>,
+{
+    /// Applies the buffered changes to the storage atomically and returns the storage.
+    pub fn commit(self) -> Result<StorageType, StorageType::Error> {
+        let Self {
+            mut storage,
+            changes,
+        } = self;
+        let changes = changes
+            .iter()
+            .map(|(key, value)| (key.borrow(), value.as_ref().map(Borrow::borrow)))
+            .collect::<Vec<_>>();
+        storage.apply_batch(&changes)?;
+        Ok(storage)
+    }
+}
+
+impl<Type, StorageType> StorageInspect<Type> for WriteBatch<Type, StorageType>
+where
+    Type: Mappable,
+    Type::Key: Eq + Hash,
+    <Type::Key as ToOwned>::Owned: Eq + Hash,
+    StorageType: StorageInspect<Type>,
+{
+    type Error = StorageType::Error;
+
+    fn get(
+        &self,
+        key: &Type::Key,
+    ) -> Result<Option<Cow<'_, Type::OwnedValue>>, Self::Error> {
+        match self.changes.get(key) {
+            Some(value) => Ok(value.as_ref().map(Cow::Borrowed)),
+            None => self.storage.get(key),
+        }
+    }
+
+    fn contains_key(&self, key: &Type::Key) -> Result<bool, Self::Error> {
+        match self.changes.get(key) {
+            Some(value) => Ok(value.is_some()),
+            None => self.storage.contains_key(key),
+        }
+    }
+
+    fn get_many(
+        &self,
+        keys: &[&Type::Key],
+    ) -> Result<Vec<Option<Cow<'_, Type::OwnedValue>>>, Self::Error> {
+        // The keys without buffered changes are read from the storage at once.
+        let stored_keys = keys
+            .iter()
+            .filter(|key| !self.changes.contains_key(**key))
+            .copied()
+            .collect::<Vec<_>>();
+        let mut stored_values = self.storage.get_many(&stored_keys)?.into_iter();
+        let values = keys
+            .iter()
+            .map(|key| match self.changes.get(*key) {
+                Some(value) => value.as_ref().map(Cow::Borrowed),
+                None => stored_values.next().flatten(),
+            })
+            .collect();
+        Ok(values)
+    }
+}
+
+impl<Type, StorageType> StorageMutate<Type> for WriteBatch<Type, StorageType>
+where
+    Type: Mappable,
+    Type::Key: Eq + Hash,
+    <Type::Key as ToOwned>::Owned: Eq + Hash,
+    StorageType: StorageInspect<Type>,
+{
+    fn insert(
+        &mut self,
+        key: &Type::Key,
+        value: &Type::Value,
+    ) -> Result<(), Self::Error> {
+        self.changes
+            .insert(key.to_owned(), Some(value.to_owned().into()));
+        Ok(())
+    }
+
+    fn replace(
+        &mut self,
+        key: &Type::Key,
+        value: &Type::Value,
+    ) -> Result<Option<Type::OwnedValue>, Self::Error> {
+        let previous = self.get(key)?.map(Cow::into_owned);
+        self.insert(key, value)?;
+        Ok(previous)
+    }
+
+    fn remove(&mut self, key: &Type::Key) -> Result<(), Self::Error> {
+        self.changes.insert(key.to_owned(), None);
+        Ok(())
+    }
+
+    fn take(&mut self, key: &Type::Key) -> Result<Option<Type::OwnedValue>, Self::Error> {
+        let previous = self.get(key)?.map(Cow::into_owned);
+        self.remove(key)?;
+        Ok(previous)
+    }
+
+    fn apply_batch(
+        &mut self,
+        changes: &[(&Type::Key, Option<&Type::Value>)],
+    ) -> Result<(), Self::Error> {
+        for (key, value) in changes {
+            let value = value.map(|value| value.to_owned().into());
+            self.changes.insert((*key).to_owned(), value);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::common::StorageMap;
+
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    struct TestKey(u32);
+
+    #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+    struct TestValue(u32);
+
+    struct TestTable;
+
+    impl Mappable for TestTable {
+        type Key = Self::OwnedKey;
+        type OwnedKey = TestKey;
+        type OwnedValue = TestValue;
+        type Value = Self::OwnedValue;
+    }
+
+    #[test]
+    fn test_get_returns_value_from_storage_for_key_without_changes() {
+        let mut storage = StorageMap::<TestTable>::new();
+        let _ = storage.insert(&TestKey(0), &TestValue(0));
+        let batch = WriteBatch::new(&storage);
+
+        assert_eq!(
+            batch.get(&TestKey(0)).unwrap(),
+            Some(Cow::Borrowed(&TestValue(0)))
+        );
+    }
+
+    #[test]
+    fn test_get_returns_buffered_value_for_key_with_changes() {
+        let mut storage = StorageMap::<TestTable>::new();
+        let _ = storage.insert(&TestKey(0), &TestValue(0));
+        let _ = storage.insert(&TestKey(1), &TestValue(1));
+        let mut batch = WriteBatch::new(&mut storage);
+        let _ = batch.insert(&TestKey(0), &TestValue(2));
+        let _ = batch.remove(&TestKey(1));
+
+        assert_eq!(
+            batch.get(&TestKey(0)).unwrap(),
+            Some(Cow::Borrowed(&TestValue(2)))
+        );
+        assert_eq!(batch.get(&TestKey(1)).unwrap(), None);
+        assert_eq!(batch.contains_key(&TestKey(1)).unwrap(), false);
+    }
+
+    #[test]
+    fn test_get_many_returns_buffered_and_stored_values_in_order() {
+        let mut storage = StorageMap::<TestTable>::new();
+        let _ = storage.insert(&TestKey(0), &TestValue(0));
+        let _ = storage.insert(&TestKey(1), &TestValue(1));
+        let mut batch = WriteBatch::new(&mut storage);
+        let _ = batch.remove(&TestKey(0));
+        let _ = batch.insert(&TestKey(2), &TestValue(2));
+
+        let values = batch
+            .get_many(&[&TestKey(0), &TestKey(1), &TestKey(2), &TestKey(3)])
+            .unwrap();
+
+        assert_eq!(
+            values,
+            [
+                None,
+                Some(Cow::Borrowed(&TestValue(1))),
+                Some(Cow::Borrowed(&TestValue(2))),
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn test_take_returns_the_previous_value_from_storage() {
+        let mut storage = StorageMap::<TestTable>::new();
+        let _ = storage.insert(&TestKey(0), &TestValue(0));
+        let mut batch = WriteBatch::new(&mut storage);
+
+        assert_eq!(batch.take(&TestKey(0)).unwrap(), Some(TestValue(0)));
+        assert_eq!(batch.take(&TestKey(0)).unwrap(), None);
+    }
+
+    #[test]
+    fn test_changes_are_not_written_to_storage_before_commit() {
+        let mut storage = StorageMap::<TestTable>::new();
+        let _ = storage.insert(&TestKey(0), &TestValue(0));
+        let mut batch = WriteBatch::new(&mut storage);
+        let _ = batch.remove(&TestKey(0));
+        let _ = batch.insert(&TestKey(1), &TestValue(1));
+
+        let storage = batch.into_storage();
+
+        assert_eq!(
+            storage.get(&TestKey(0)).unwrap(),
+            Some(Cow::Borrowed(&TestValue(0)))
+        );
+        assert_eq!(storage.get(&TestKey(1)).unwrap(), None);
+    }
+
+    #[test]
+    fn test_apply_batch_buffers_the_changes() {
+        let mut storage = StorageMap::<TestTable>::new();
+        let _ = storage.insert(&TestKey(0), &TestValue(0));
+        let mut batch = WriteBatch::new(&mut storage);
+
+        batch
+            .apply_batch(&[(&TestKey(0), None), (&TestKey(1), Some(&TestValue(1)))])
+            .unwrap();
+
+        assert_eq!(batch.get(&TestKey(0)).unwrap(), None);
+        assert_eq!(
+            batch.get(&TestKey(1)).unwrap(),
+            Some(Cow::Borrowed(&TestValue(1)))
+        );
+        assert!(batch.storage().contains_key(&TestKey(0)).unwrap());
+    }
+
+    #[test]
+    fn test_commit_writes_the_changes_to_storage() {
+        let mut storage = StorageMap::<TestTable>::new();
+        let _ = storage.insert(&TestKey(0), &TestValue(0));
+        let mut batch = WriteBatch::new(&mut storage);
+        let _ = batch.remove(&TestKey(0));
+        let _ = batch.insert(&TestKey(1), &TestValue(1));
+
+        let storage = batch.commit().unwrap();
+
+        assert_eq!(storage.get(&TestKey(0)).unwrap(), None);
+        assert_eq!(
+            storage.get(&TestKey(1)).unwrap(),
+            Some(Cow::Borrowed(&TestValue(1)))
+        );
+    }
+}
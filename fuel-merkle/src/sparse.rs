@@ -5,6 +5,7 @@ mod primitive;
 pub(crate) use hash::zero_sum;
 
 pub use merkle_tree::{
+    Leaves,
     MerkleTree,
     MerkleTreeError,
     MerkleTreeKey,
@@ -12,6 +13,8 @@ pub use merkle_tree::{
 pub use primitive::Primitive;
 pub mod in_memory;
 pub mod proof;
+pub mod versioned;
+pub mod witness;
 
 use crate::common::Bytes32;
 
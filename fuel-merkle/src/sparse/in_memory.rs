@@ -6,7 +6,12 @@ use crate::{
     sparse::{
         self,
         merkle_tree::MerkleTreeKey,
-        proof::Proof,
+        proof::{
+            MultiProof,
+            Proof,
+            RangeProof,
+        },
+        witness::UpdateWitness,
         Primitive,
     },
     storage::{
@@ -196,13 +201,48 @@ impl MerkleTree {
         let _ = self.tree.delete(key);
     }
 
+    pub fn update_batch<I, D>(&mut self, set: I)
+    where
+        I: IntoIterator<Item = (MerkleTreeKey, Option<D>)>,
+        D: AsRef<[u8]>,
+    {
+        let _ = self.tree.update_batch(set);
+    }
+
     pub fn root(&self) -> Bytes32 {
         self.tree.root()
     }
 
+    /// Returns an iterator over the leaves of the tree in the order of their keys,
+    /// yielding the key of each leaf and the hash of its value.
+    pub fn leaves(&self) -> impl Iterator<Item = (Bytes32, Bytes32)> + '_ {
+        self.tree
+            .leaves()
+            .map(|leaf| leaf.expect("`Storage` can't return error"))
+    }
+
     pub fn generate_proof(&self, key: &MerkleTreeKey) -> Option<Proof> {
         self.tree.generate_proof(key).ok()
     }
+
+    pub fn generate_multiproof(&self, keys: &[MerkleTreeKey]) -> Option<MultiProof> {
+        self.tree.generate_multiproof(keys).ok()
+    }
+
+    pub fn generate_range_proof(
+        &self,
+        start: &MerkleTreeKey,
+        end: &MerkleTreeKey,
+    ) -> Option<RangeProof> {
+        self.tree.generate_range_proof(start, end).ok()
+    }
+
+    pub fn generate_update_witness(
+        &self,
+        keys: &[MerkleTreeKey],
+    ) -> Option<UpdateWitness> {
+        self.tree.generate_update_witness(keys).ok()
+    }
 }
 
 impl Default for MerkleTree {
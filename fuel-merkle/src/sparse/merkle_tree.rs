@@ -1,10 +1,12 @@
 mod branch;
+mod leaves;
 mod node;
 
 use branch::{
     merge_branches,
     Branch,
 };
+pub use leaves::Leaves;
 use node::{
     Node,
     StorageNode,
@@ -15,17 +17,35 @@ use crate::{
     common::{
         error::DeserializeError,
         node::ChildError,
+        path::{
+            Path,
+            Side,
+        },
         AsPathIterator,
         Bytes32,
+        Hasher,
+        WriteBatch,
     },
     sparse::{
         empty_sum,
+        hash::calculate_leaf_hash,
         proof::{
+            multiproof_root,
+            side_position,
             ExclusionLeaf,
             ExclusionLeafData,
             ExclusionProof,
             InclusionProof,
+            MultiProof,
+            MultiProofLeaf,
             Proof,
+            RangeProof,
+        },
+        witness::{
+            NodePreimage,
+            PartialTree,
+            SideNodes,
+            UpdateWitness,
         },
         Primitive,
     },
@@ -36,7 +56,9 @@ use crate::{
     },
 };
 use alloc::{
+    collections::BTreeMap,
     format,
+    vec,
     vec::Vec,
 };
 use core::{
@@ -48,6 +70,7 @@ use core::{
     marker::PhantomData,
     ops::Deref,
 };
+use sha2::Sha256;
 
 #[derive(Debug, Clone, derive_more::Display)]
 pub enum MerkleTreeError<StorageError> {
@@ -156,14 +179,21 @@ impl From<Bytes32> for MerkleTreeKey {
     }
 }
 
+/// The sparse Merkle tree of the nodes in the `TableType` of the `StorageType`,
+/// hashing its nodes with `H`, SHA-256 by default.
+///
+/// Each [`update`](Self::update), [`delete`](Self::delete) and
+/// [`update_batch`](Self::update_batch) writes its nodes with a single
+/// [`StorageMutate::apply_batch`], so its atomicity is the one of the storage.
 #[derive(Debug)]
-pub struct MerkleTree<TableType, StorageType> {
+pub struct MerkleTree<TableType, StorageType, H = Sha256> {
     root_node: Node,
     storage: StorageType,
     phantom_table: PhantomData<TableType>,
+    phantom_hasher: PhantomData<H>,
 }
 
-impl<TableType, StorageType> MerkleTree<TableType, StorageType> {
+impl<TableType, StorageType, H> MerkleTree<TableType, StorageType, H> {
     pub const fn empty_root() -> &'static Bytes32 {
         empty_sum()
     }
@@ -180,6 +210,10 @@ impl<TableType, StorageType> MerkleTree<TableType, StorageType> {
         &self.storage
     }
 
+    pub(crate) fn storage_mut(&mut self) -> &mut StorageType {
+        &mut self.storage
+    }
+
     fn root_node(&self) -> &Node {
         &self.root_node
     }
@@ -196,19 +230,57 @@ where
     StorageType: StorageInspect<TableType, Error = StorageError>,
 {
     pub fn new(storage: StorageType) -> Self {
+        Self::new_with_hasher(storage)
+    }
+
+    pub fn load(
+        storage: StorageType,
+        root: &Bytes32,
+    ) -> Result<Self, MerkleTreeError<StorageError>> {
+        Self::load_with_hasher(storage, root)
+    }
+
+    /// Build a sparse Merkle tree from a set of key-value pairs. This is
+    /// equivalent to creating an empty sparse Merkle tree and sequentially
+    /// calling [update](Self::update) for each key-value pair. This constructor
+    /// is more performant than calling individual sequential updates and is the
+    /// preferred approach when the key-values are known upfront. Leaves can be
+    /// appended to the returned tree using `update` to further accumulate leaf
+    /// data.
+    pub fn from_set<B, I, D>(storage: StorageType, set: I) -> Result<Self, StorageError>
+    where
+        StorageType: StorageMutate<TableType>,
+        I: Iterator<Item = (B, D)>,
+        B: Into<Bytes32>,
+        D: AsRef<[u8]>,
+    {
+        Self::from_set_with_hasher(storage, set)
+    }
+}
+
+impl<TableType, StorageType, StorageError, H> MerkleTree<TableType, StorageType, H>
+where
+    TableType: Mappable<Key = Bytes32, Value = Primitive, OwnedValue = Primitive>,
+    StorageType: StorageInspect<TableType, Error = StorageError>,
+    H: Hasher,
+{
+    /// Creates an empty tree hashing its nodes with `H`.
+    pub fn new_with_hasher(storage: StorageType) -> Self {
         Self {
             root_node: Node::create_placeholder(),
             storage,
             phantom_table: Default::default(),
+            phantom_hasher: Default::default(),
         }
     }
 
-    pub fn load(
+    /// Loads the tree with the `root` hashing its nodes with `H`.
+    pub fn load_with_hasher(
         storage: StorageType,
         root: &Bytes32,
     ) -> Result<Self, MerkleTreeError<StorageError>> {
         if root == Self::empty_root() {
-            let tree = Self::new(storage);
+            let tree = Self::new_with_hasher(storage);
             Ok(tree)
         } else {
             let primitive = storage
@@ -216,22 +288,80 @@ where
                 .ok_or_else(|| MerkleTreeError::LoadError(*root))?
                 .into_owned();
             let tree = Self {
-                root_node: primitive
-                    .try_into()
+                root_node: Node::from_primitive::<H>(primitive)
                     .map_err(MerkleTreeError::DeserializeError)?,
                 storage,
                 phantom_table: Default::default(),
+                phantom_hasher: Default::default(),
             };
             Ok(tree)
         }
     }
 
+    /// Returns an iterator over the leaves of the tree in the order of their keys,
+    /// yielding the key of each leaf and the hash of its value.
+    pub fn leaves(&self) -> Leaves<'_, TableType, StorageType, H> {
+        Leaves::new(self, [0; 32]..=[u8::MAX; 32])
+    }
+
+    /// Returns an iterator over the leaves of the tree with keys in `start..=end`, in
+    /// the order of their keys.
+    pub fn leaves_in_range(
+        &self,
+        start: &MerkleTreeKey,
+        end: &MerkleTreeKey,
+    ) -> Leaves<'_, TableType, StorageType, H> {
+        Leaves::new(self, **start..=**end)
+    }
+
+    fn load_node(&self, key: &Bytes32) -> Result<Node, MerkleTreeError<StorageError>> {
+        if key == empty_sum() {
+            return Ok(Node::create_placeholder())
+        }
+        let primitive = self
+            .storage
+            .get(key)?
+            .ok_or(MerkleTreeError::LoadError(*key))?
+            .into_owned();
+        Node::from_primitive::<H>(primitive).map_err(MerkleTreeError::DeserializeError)
+    }
+
+    /// Loads the nodes with the `keys` using a single batched read of the storage.
+    fn load_nodes(
+        &self,
+        keys: &[&Bytes32],
+    ) -> Result<Vec<Node>, MerkleTreeError<StorageError>> {
+        let stored_keys = keys
+            .iter()
+            .filter(|key| **key != empty_sum())
+            .copied()
+            .collect::<Vec<_>>();
+        let mut primitives = self.storage.get_many(&stored_keys)?.into_iter();
+        keys.iter()
+            .map(|key| {
+                if *key == empty_sum() {
+                    return Ok(Node::create_placeholder())
+                }
+                let primitive = primitives
+                    .next()
+                    .flatten()
+                    .ok_or(MerkleTreeError::LoadError(**key))?
+                    .into_owned();
+                Node::from_primitive::<H>(primitive)
+                    .map_err(MerkleTreeError::DeserializeError)
+            })
+            .collect()
+    }
+
+    /// Reads the nodes on the path to the `leaf_key` one at a time. Unlike the path of
+    /// the binary tree, it can't be prefetched with `get_many`, since the key of a node
+    /// is only known once its parent is read.
     fn path_set(
         &self,
         leaf_key: &Bytes32,
     ) -> Result<(Vec<Node>, Vec<Bytes32>), MerkleTreeError<StorageError>> {
         let root_node = self.root_node().clone();
-        let root_storage_node = StorageNode::new(&self.storage, root_node);
+        let root_storage_node = StorageNode::<_, _, H>::new(&self.storage, root_node);
         let (mut path_nodes, mut side_nodes): (Vec<Node>, Vec<Bytes32>) =
             root_storage_node
                 .as_path_iter(leaf_key)
@@ -253,19 +383,15 @@ where
     }
 }
 
-impl<TableType, StorageType, StorageError> MerkleTree<TableType, StorageType>
+impl<TableType, StorageType, StorageError, H> MerkleTree<TableType, StorageType, H>
 where
     TableType: Mappable<Key = Bytes32, Value = Primitive, OwnedValue = Primitive>,
     StorageType: StorageMutate<TableType, Error = StorageError>,
+    H: Hasher,
 {
-    /// Build a sparse Merkle tree from a set of key-value pairs. This is
-    /// equivalent to creating an empty sparse Merkle tree and sequentially
-    /// calling [update](Self::update) for each key-value pair. This constructor
-    /// is more performant than calling individual sequential updates and is the
-    /// preferred approach when the key-values are known upfront. Leaves can be
-    /// appended to the returned tree using `update` to further accumulate leaf
-    /// data.
-    pub fn from_set<B, I, D>(
+    /// Builds a tree hashing its nodes with `H` from a set of key-value pairs, as
+    /// in [`MerkleTree::from_set`].
+    pub fn from_set_with_hasher<B, I, D>(
         mut storage: StorageType,
         set: I,
     ) -> Result<Self, StorageError>
@@ -278,10 +404,10 @@ where
             .into_iter()
             .map(|(k, v)| (k.into(), v))
             .collect::<alloc::collections::BTreeMap<Bytes32, D>>();
-        let mut branches = sorted
+        let branches = sorted
             .iter()
             .filter(|(_, value)| !value.as_ref().is_empty())
-            .map(|(key, data)| Node::create_leaf(key, data))
+            .map(|(key, data)| Node::create_leaf::<H>(key, data))
             .map(Into::<Branch>::into)
             .collect::<Vec<_>>();
 
@@ -290,16 +416,30 @@ where
             storage.insert(leaf.hash(), &leaf.as_ref().into())?;
         }
 
+        let root_node = Self::root_node_from_branches(&mut storage, branches)?;
+        let tree = Self {
+            root_node,
+            storage,
+            phantom_table: Default::default(),
+            phantom_hasher: Default::default(),
+        };
+        Ok(tree)
+    }
+
+    /// Builds the root node from `branches` sorted left to right. A branch is either
+    /// a leaf or the root of a subtree, and the leaves of the subtrees are not in the
+    /// other branches. Only the new nodes are inserted into the `storage`.
+    fn root_node_from_branches(
+        storage: &mut StorageType,
+        mut branches: Vec<Branch>,
+    ) -> Result<Node, StorageError> {
         if branches.is_empty() {
-            let tree = Self::new(storage);
-            return Ok(tree)
+            return Ok(Node::create_placeholder())
         }
 
-        if branches.len() == 1 {
+        if branches.len() == 1 && branches[0].node.is_leaf() {
             let leaf = branches.pop().expect("Expected at least 1 leaf").node;
-            let mut tree = Self::new(storage);
-            tree.set_root_node(leaf);
-            return Ok(tree)
+            return Ok(leaf)
         }
 
         let mut nodes = Vec::<Branch>::with_capacity(branches.len());
@@ -327,8 +467,10 @@ where
         // possible.
         while let Some(left) = branches.pop() {
             if let Some(current) = nodes.last() {
+                // The bits of a subtree are the path to its root, so the proximity
+                // of a subtree to its neighbors is lower than its depth.
                 #[allow(clippy::cast_possible_truncation)] // Key is 32 bytes
-                let left_proximity = current.node.common_path_length(&left.node) as u32;
+                let left_proximity = current.bits.common_path_length(&left.bits) as u32;
                 while {
                     // The current node's proximity to its right neighbor was
                     // stored previously. We now compare the distances between
@@ -354,7 +496,7 @@ where
                     let current =
                         nodes.pop().expect("Expected current node to be present");
                     let right = nodes.pop().expect("Expected right node to be present");
-                    let merged = merge_branches(&mut storage, current, right)?;
+                    let merged = merge_branches::<H, _, _>(storage, current, right)?;
                     nodes.push(merged);
 
                     // Now that the current node and its right neighbour are
@@ -377,7 +519,7 @@ where
                 .pop()
                 .expect("Nodes stack must have at least 1 element");
             while let Some(next) = nodes.pop() {
-                node = merge_branches(&mut storage, node, next)?;
+                node = merge_branches::<H, _, _>(storage, node, next)?;
             }
             node
         };
@@ -393,19 +535,14 @@ where
         let depth = Node::max_height() - height;
         let placeholders = iter::repeat(Node::create_placeholder()).take(depth as usize);
         for placeholder in placeholders {
-            node = Node::create_node_on_path(&path, &node, &placeholder);
+            node = Node::create_node_on_path::<H>(&path, &node, &placeholder);
             storage.insert(node.hash(), &node.as_ref().into())?;
         }
 
-        let tree = Self {
-            root_node: node,
-            storage,
-            phantom_table: Default::default(),
-        };
-        Ok(tree)
+        Ok(node)
     }
 
-    pub fn update(
+    fn update_nodes(
         &mut self,
         key: MerkleTreeKey,
         data: &[u8],
@@ -413,11 +550,11 @@ where
         if data.is_empty() {
             // If the data is empty, this signifies a delete operation for the
             // given key.
-            self.delete(key)?;
+            self.delete_nodes(key)?;
             return Ok(())
         }
 
-        let leaf_node = Node::create_leaf(key.as_ref(), data);
+        let leaf_node = Node::create_leaf::<H>(key.as_ref(), data);
         self.storage
             .insert(leaf_node.hash(), &leaf_node.as_ref().into())?;
 
@@ -435,7 +572,7 @@ where
         Ok(())
     }
 
-    pub fn delete(
+    fn delete_nodes(
         &mut self,
         key: MerkleTreeKey,
     ) -> Result<(), MerkleTreeError<StorageError>> {
@@ -458,6 +595,100 @@ where
         Ok(())
     }
 
+    fn update_batch_nodes<I, D>(
+        &mut self,
+        set: I,
+    ) -> Result<(), MerkleTreeError<StorageError>>
+    where
+        I: IntoIterator<Item = (MerkleTreeKey, Option<D>)>,
+        D: AsRef<[u8]>,
+    {
+        let sorted = set
+            .into_iter()
+            .map(|(key, data)| (*key, data))
+            .collect::<BTreeMap<Bytes32, Option<D>>>();
+        if sorted.is_empty() {
+            return Ok(())
+        }
+
+        let keys = sorted.keys().copied().collect::<Vec<_>>();
+        let mut branches = Vec::new();
+        let root_node = self.root_node().clone();
+        self.collect_branches(root_node, *empty_sum(), &keys, &mut branches)?;
+
+        for (key, data) in &sorted {
+            let Some(data) = data.as_ref().map(AsRef::as_ref) else {
+                continue
+            };
+            if data.is_empty() {
+                continue
+            }
+            let leaf_node = Node::create_leaf::<H>(key, data);
+            self.storage
+                .insert(leaf_node.hash(), &leaf_node.as_ref().into())?;
+            branches.push(leaf_node.into());
+        }
+        branches.sort_by_key(|branch| branch.bits);
+
+        let root_node = Self::root_node_from_branches(&mut self.storage, branches)?;
+        self.set_root_node(root_node);
+
+        Ok(())
+    }
+
+    /// Collects, left to right, the branches of the subtree of `node` at `position`
+    /// that don't contain the sorted `keys`, and removes the nodes on the paths of
+    /// the keys and the leaves of the keys.
+    fn collect_branches(
+        &mut self,
+        node: Node,
+        position: Bytes32,
+        keys: &[Bytes32],
+        branches: &mut Vec<Branch>,
+    ) -> Result<(), MerkleTreeError<StorageError>> {
+        if node.is_placeholder() {
+            return Ok(())
+        }
+
+        if node.is_leaf() {
+            if keys.binary_search(node.leaf_key()).is_ok() {
+                self.storage.remove(node.hash())?;
+            } else {
+                branches.push(node.into());
+            }
+            return Ok(())
+        }
+
+        if keys.is_empty() {
+            branches.push(Branch {
+                bits: position,
+                node,
+            });
+            return Ok(())
+        }
+
+        self.storage.remove(node.hash())?;
+
+        #[allow(clippy::arithmetic_side_effects)] // height <= max_height
+        let depth = Node::max_height() - node.height();
+        let split = keys.partition_point(|key| {
+            matches!(key.get_instruction(depth), Some(Side::Left))
+        });
+        #[allow(clippy::arithmetic_side_effects)] // An internal node is above the leaves
+        let child_depth = depth + 1;
+        let right_position =
+            side_position(&position, child_depth).expect("The depth is at least 1");
+
+        let [left_child, right_child]: [Node; 2] = self
+            .load_nodes(&[node.left_child_key(), node.right_child_key()])?
+            .try_into()
+            .expect("Two nodes are loaded");
+        self.collect_branches(left_child, position, &keys[..split], branches)?;
+        self.collect_branches(right_child, right_position, &keys[split..], branches)?;
+
+        Ok(())
+    }
+
     fn update_with_path_set(
         &mut self,
         requested_leaf_node: &Node,
@@ -498,7 +729,7 @@ where
             // Merge leaves
             if !actual_leaf_node.is_placeholder() {
                 current_node =
-                    Node::create_node_on_path(path, &current_node, actual_leaf_node);
+                    Node::create_node_on_path::<H>(path, &current_node, actual_leaf_node);
                 self.storage
                     .insert(current_node.hash(), &current_node.as_ref().into())?;
             }
@@ -512,7 +743,7 @@ where
                 iter::repeat(Node::create_placeholder()).take(placeholders_count);
             for placeholder in placeholders {
                 current_node =
-                    Node::create_node_on_path(path, &current_node, &placeholder);
+                    Node::create_node_on_path::<H>(path, &current_node, &placeholder);
                 self.storage
                     .insert(current_node.hash(), &current_node.as_ref().into())?;
             }
@@ -525,13 +756,13 @@ where
             side_nodes.iter().zip(path_nodes.iter().skip(1 /* leaf */))
         {
             let new_parent = if old_parent.bytes_lo() == side_node {
-                Node::create_node_from_hashes(
+                Node::create_node_from_hashes::<H>(
                     *side_node,
                     *current_node.hash(),
                     old_parent.height(),
                 )
             } else {
-                Node::create_node_from_hashes(
+                Node::create_node_from_hashes::<H>(
                     *current_node.hash(),
                     *side_node,
                     old_parent.height(),
@@ -575,12 +806,12 @@ where
         // calculation. We then create a valid ancestor node for the orphaned
         // leaf node by joining it with the earliest non-placeholder side node.
         if let Some(first_side_node) = side_nodes.first() {
-            let first_side_node: Node = self
+            let primitive = self
                 .storage
                 .get(first_side_node)?
                 .ok_or(MerkleTreeError::LoadError(*first_side_node))?
-                .into_owned()
-                .try_into()
+                .into_owned();
+            let first_side_node = Node::from_primitive::<H>(primitive)
                 .map_err(MerkleTreeError::DeserializeError)?;
 
             if first_side_node.is_leaf() {
@@ -608,13 +839,13 @@ where
                         parent.bytes_lo() == side_node || parent.bytes_hi() == side_node
                     }) {
                         let new_parent = if old_parent.bytes_lo() == side_node {
-                            Node::create_node_from_hashes(
+                            Node::create_node_from_hashes::<H>(
                                 *side_node,
                                 *current_node.hash(),
                                 old_parent.height(),
                             )
                         } else {
-                            Node::create_node_from_hashes(
+                            Node::create_node_from_hashes::<H>(
                                 *current_node.hash(),
                                 *side_node,
                                 old_parent.height(),
@@ -631,13 +862,13 @@ where
         // Merge side nodes
         for (side_node, old_parent) in side_nodes_iter.zip(path_nodes_iter) {
             let new_parent = if old_parent.bytes_lo() == side_node {
-                Node::create_node_from_hashes(
+                Node::create_node_from_hashes::<H>(
                     *side_node,
                     *current_node.hash(),
                     old_parent.height(),
                 )
             } else {
-                Node::create_node_from_hashes(
+                Node::create_node_from_hashes::<H>(
                     *current_node.hash(),
                     *side_node,
                     old_parent.height(),
@@ -655,10 +886,81 @@ where
     }
 }
 
-impl<TableType, StorageType, StorageError> MerkleTree<TableType, StorageType>
+impl<TableType, StorageType, StorageError, H> MerkleTree<TableType, StorageType, H>
+where
+    TableType: Mappable<Key = Bytes32, Value = Primitive, OwnedValue = Primitive>,
+    StorageType: StorageMutate<TableType, Error = StorageError>,
+    H: Hasher,
+{
+    /// Sets the `data` of the `key`. An empty `data` deletes the key.
+    ///
+    /// The node writes are applied to the storage at once with
+    /// [`StorageMutate::apply_batch`]. If it fails, the tree is unchanged.
+    pub fn update(
+        &mut self,
+        key: MerkleTreeKey,
+        data: &[u8],
+    ) -> Result<(), MerkleTreeError<StorageError>> {
+        self.write_batch(|tree| tree.update_nodes(key, data))
+    }
+
+    /// Deletes the `key`.
+    ///
+    /// The node writes are applied to the storage at once with
+    /// [`StorageMutate::apply_batch`]. If it fails, the tree is unchanged.
+    pub fn delete(
+        &mut self,
+        key: MerkleTreeKey,
+    ) -> Result<(), MerkleTreeError<StorageError>> {
+        self.write_batch(|tree| tree.delete_nodes(key))
+    }
+
+    /// Applies the updates of the `set`, in order. A `None` or empty value deletes
+    /// the key. This is equivalent to sequentially calling [update](Self::update)
+    /// or [delete](Self::delete) for each entry, but rebuilds the ancestors shared
+    /// by the updated keys once, like [from_set](Self::from_set), and applies the
+    /// node writes of all the updates at once.
+    pub fn update_batch<I, D>(
+        &mut self,
+        set: I,
+    ) -> Result<(), MerkleTreeError<StorageError>>
+    where
+        I: IntoIterator<Item = (MerkleTreeKey, Option<D>)>,
+        D: AsRef<[u8]>,
+    {
+        self.write_batch(|tree| tree.update_batch_nodes(set))
+    }
+
+    /// Runs `f` on a copy of the tree buffering its node writes in a [`WriteBatch`],
+    /// then applies them to the storage and takes the new root.
+    fn write_batch<F>(&mut self, f: F) -> Result<(), MerkleTreeError<StorageError>>
+    where
+        F: FnOnce(
+            &mut MerkleTree<TableType, WriteBatch<TableType, &mut StorageType>, H>,
+        ) -> Result<(), MerkleTreeError<StorageError>>,
+    {
+        let mut tree = MerkleTree {
+            root_node: self.root_node.clone(),
+            storage: WriteBatch::new(&mut self.storage),
+            phantom_table: PhantomData,
+            phantom_hasher: PhantomData,
+        };
+        f(&mut tree)?;
+        let MerkleTree {
+            root_node, storage, ..
+        } = tree;
+        storage.commit()?;
+        self.root_node = root_node;
+
+        Ok(())
+    }
+}
+
+impl<TableType, StorageType, StorageError, H> MerkleTree<TableType, StorageType, H>
 where
     TableType: Mappable<Key = Bytes32, Value = Primitive, OwnedValue = Primitive>,
     StorageType: StorageInspect<TableType, Error = StorageError>,
+    H: Hasher,
 {
     pub fn generate_proof(
         &self,
@@ -721,6 +1023,151 @@ where
         };
         Ok(proof)
     }
+
+    /// Generates a single proof of the inclusion or exclusion of each of the `keys`.
+    /// Unlike individual proofs, the [`MultiProof`] contains the side nodes shared
+    /// by the paths of the keys once, and omits the side nodes that are on the path
+    /// of another key.
+    pub fn generate_multiproof(
+        &self,
+        keys: &[MerkleTreeKey],
+    ) -> Result<MultiProof, MerkleTreeError<StorageError>> {
+        let (proof, _) = self.multiproof_with_side_nodes(keys)?;
+        Ok(proof)
+    }
+
+    /// Generates the [`RangeProof`] of the leaves with keys in `start..=end`, proving
+    /// that the tree has no other leaf in the range.
+    pub fn generate_range_proof(
+        &self,
+        start: &MerkleTreeKey,
+        end: &MerkleTreeKey,
+    ) -> Result<RangeProof, MerkleTreeError<StorageError>> {
+        let mut keys = vec![*start];
+        for leaf in self.leaves_in_range(start, end) {
+            let (key, _) = leaf?;
+            keys.push(MerkleTreeKey(key));
+        }
+        keys.push(*end);
+
+        let proof = self.generate_multiproof(&keys)?;
+        Ok(RangeProof { proof })
+    }
+
+    /// Generates the [`UpdateWitness`] of the `keys`, to compute the root of the
+    /// tree after updating or deleting any of them without the tree.
+    pub fn generate_update_witness(
+        &self,
+        keys: &[MerkleTreeKey],
+    ) -> Result<UpdateWitness, MerkleTreeError<StorageError>> {
+        let (proof, side_nodes) = self.multiproof_with_side_nodes(keys)?;
+
+        // A side node requires a preimage if its sibling subtree becomes empty.
+        // This happens for the most side nodes when every included key is deleted,
+        // since the leaves of excluded keys can't be deleted.
+        let mut tree = PartialTree::new(side_nodes);
+        for leaf in &proof.leaves {
+            if let MultiProofLeaf::Exclusion {
+                leaf: ExclusionLeaf::Leaf(data),
+                ..
+            } = leaf
+            {
+                tree.insert(
+                    data.leaf_key,
+                    calculate_leaf_hash::<H>(&data.leaf_key, &data.leaf_value),
+                );
+            }
+        }
+        for (leaf, key) in proof.leaves.iter().zip(keys) {
+            if let MultiProofLeaf::Inclusion { .. } = leaf {
+                tree.remove(key);
+            }
+        }
+
+        let mut preimages = Vec::new();
+        tree.root::<H, _, _>(|hash| {
+            let node = self.load_node(hash)?;
+            let preimage = if node.is_leaf() {
+                NodePreimage::Leaf {
+                    key: *node.leaf_key(),
+                    value: *node.leaf_data(),
+                }
+            } else {
+                NodePreimage::Node {
+                    left: *node.bytes_lo(),
+                    right: *node.bytes_hi(),
+                }
+            };
+            preimages.push(preimage);
+            Ok::<_, MerkleTreeError<StorageError>>(node.is_leaf())
+        })?
+        .expect("The side nodes are not on the paths of the keys");
+
+        Ok(UpdateWitness { proof, preimages })
+    }
+
+    /// Generates the multiproof of the `keys` together with the
+    /// `(position, depth, hash)` of the side nodes of its proof set.
+    fn multiproof_with_side_nodes(
+        &self,
+        keys: &[MerkleTreeKey],
+    ) -> Result<(MultiProof, SideNodes), MerkleTreeError<StorageError>> {
+        let mut leaves = Vec::with_capacity(keys.len());
+        let mut hashes = Vec::with_capacity(keys.len());
+        let mut side_nodes = BTreeMap::new();
+
+        for key in keys {
+            let path = key.as_ref();
+            let (path_nodes, path_side_nodes) = self.path_set(path)?;
+            #[allow(clippy::cast_possible_truncation)] // Key is 32 bytes
+            let depth = path_side_nodes.len() as u32;
+
+            // The side nodes are ordered from the leaf up, the first one being the
+            // sibling of the leaf at `depth`.
+            for (side_depth, side_node) in (1..=depth).rev().zip(path_side_nodes) {
+                let position =
+                    side_position(path, side_depth).expect("The depth is at least 1");
+                side_nodes.insert((side_depth, position), side_node);
+            }
+
+            let actual_leaf = &path_nodes[0];
+            let leaf = if !actual_leaf.is_placeholder() && actual_leaf.leaf_key() == path
+            {
+                MultiProofLeaf::Inclusion { depth }
+            } else if actual_leaf.is_placeholder() {
+                MultiProofLeaf::Exclusion {
+                    depth,
+                    leaf: ExclusionLeaf::Placeholder,
+                }
+            } else {
+                MultiProofLeaf::Exclusion {
+                    depth,
+                    leaf: ExclusionLeaf::Leaf(ExclusionLeafData {
+                        leaf_key: *actual_leaf.leaf_key(),
+                        leaf_value: *actual_leaf.leaf_data(),
+                    }),
+                }
+            };
+            leaves.push(leaf);
+            hashes.push((depth, *path, *actual_leaf.hash()));
+        }
+
+        // Replay the verification to collect, in order, the side nodes that the
+        // verifier can't compute from the leaves.
+        let mut proof_set = Vec::new();
+        let mut proof_side_nodes = Vec::new();
+        if !keys.is_empty() {
+            multiproof_root::<H, _, _>(hashes, |depth, position| {
+                let side_node = side_nodes.get(&(depth, *position)).copied();
+                proof_set.extend(side_node);
+                proof_side_nodes.extend(side_node.map(|hash| (*position, depth, hash)));
+                side_node
+            })
+            .expect("The paths of the keys contain every required side node");
+        }
+
+        Ok((MultiProof { leaves, proof_set }, proof_side_nodes))
+    }
 }
 
 #[cfg(test)]
@@ -732,17 +1179,30 @@ mod test {
             sum,
             Bytes32,
             StorageMap,
+            WriteBatch,
         },
         sparse::{
             empty_sum,
+            proof::Proof,
             MerkleTree,
             MerkleTreeError,
             MerkleTreeKey,
             Primitive,
         },
     };
-    use fuel_storage::Mappable;
+    use alloc::{
+        borrow::Cow,
+        collections::BTreeMap,
+    };
+    use digest::Digest;
+    use fuel_storage::{
+        Mappable,
+        StorageInspect,
+        StorageMutate,
+    };
     use hex;
+    use sha2::Sha256;
+    use sha3::Keccak256;
 
     fn random_bytes32<R>(rng: &mut R) -> Bytes32
     where
@@ -790,6 +1250,53 @@ mod test {
         assert_eq!(hex::encode(root), expected_root);
     }
 
+    /// A table whose owned key is a different type than its key.
+    struct WrappedKeyTable;
+
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    struct WrappedKey(Bytes32);
+
+    impl From<Bytes32> for WrappedKey {
+        fn from(key: Bytes32) -> Self {
+            Self(key)
+        }
+    }
+
+    impl core::borrow::Borrow<Bytes32> for WrappedKey {
+        fn borrow(&self) -> &Bytes32 {
+            &self.0
+        }
+    }
+
+    impl Mappable for WrappedKeyTable {
+        type Key = Bytes32;
+        type OwnedKey = WrappedKey;
+        type OwnedValue = Primitive;
+        type Value = Self::OwnedValue;
+    }
+
+    #[test]
+    fn test_update_with_owned_key_other_than_key() {
+        let mut storage = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage);
+        let mut wrapped_storage = StorageMap::<WrappedKeyTable>::new();
+        let mut wrapped_tree = MerkleTree::new(&mut wrapped_storage);
+
+        for tree_key in [key(b"\x00\x00\x00\x00"), key(b"\x00\x00\x00\x01")] {
+            tree.update(tree_key, b"DATA").unwrap();
+            wrapped_tree.update(tree_key, b"DATA").unwrap();
+        }
+        tree.delete(key(b"\x00\x00\x00\x00")).unwrap();
+        wrapped_tree.delete(key(b"\x00\x00\x00\x00")).unwrap();
+        tree.update_batch([(key(b"\x00\x00\x00\x02"), Some(b"DATA"))])
+            .unwrap();
+        wrapped_tree
+            .update_batch([(key(b"\x00\x00\x00\x02"), Some(b"DATA"))])
+            .unwrap();
+
+        assert_eq!(wrapped_tree.root(), tree.root());
+    }
+
     #[test]
     fn test_update_2() {
         let mut storage = StorageMap::<TestTable>::new();
@@ -1206,11 +1713,11 @@ mod test {
 
         let leaf_1_key = key(b"\x00\x00\x00\x00");
         let leaf_1_data = b"DATA_1";
-        let leaf_1 = Node::create_leaf(&leaf_1_key.0, leaf_1_data);
+        let leaf_1 = Node::create_leaf::<Sha256>(&leaf_1_key.0, leaf_1_data);
 
         let leaf_2_key = MerkleTreeKey::new_without_hash(*leaf_1.hash());
         let leaf_2_data = b"DATA_2";
-        let leaf_2 = Node::create_leaf(&leaf_2_key.0, leaf_2_data);
+        let leaf_2 = Node::create_leaf::<Sha256>(&leaf_2_key.0, leaf_2_data);
 
         tree.update(leaf_2_key, leaf_2_data).unwrap();
         tree.update(leaf_1_key, leaf_1_data).unwrap();
@@ -1503,6 +2010,110 @@ mod test {
         assert_eq!(root, expected_root);
     }
 
+    #[test]
+    fn test_update_batch_yields_expected_root() {
+        let rng = &mut rand::thread_rng();
+        let initial = (0_u32..50)
+            .map(|i| (key(i.to_be_bytes()), random_bytes32(rng)))
+            .collect::<Vec<_>>();
+        let batch = (40_u32..60)
+            .map(|i| {
+                let value = (i % 3 != 0).then(|| random_bytes32(rng).to_vec());
+                (key(i.to_be_bytes()), value)
+            })
+            .chain([(key(45_u32.to_be_bytes()), None)])
+            .collect::<Vec<_>>();
+
+        let expected_root = {
+            let mut storage = StorageMap::<TestTable>::new();
+            let mut tree = MerkleTree::new(&mut storage);
+            for (key, value) in &initial {
+                tree.update(*key, value).unwrap();
+            }
+            for (key, value) in &batch {
+                match value {
+                    Some(value) => tree.update(*key, value).unwrap(),
+                    None => tree.delete(*key).unwrap(),
+                }
+            }
+            tree.root()
+        };
+
+        let root = {
+            let mut storage = StorageMap::<TestTable>::new();
+            let mut tree =
+                MerkleTree::from_set(&mut storage, initial.into_iter()).unwrap();
+            tree.update_batch(batch).unwrap();
+            tree.root()
+        };
+
+        assert_eq!(root, expected_root);
+    }
+
+    #[test]
+    fn test_update_batch_deleting_every_key_yields_empty_root() {
+        let keys = (0_u32..10)
+            .map(|i| key(i.to_be_bytes()))
+            .collect::<Vec<_>>();
+        let mut storage = StorageMap::<TestTable>::new();
+        let mut tree =
+            MerkleTree::from_set(&mut storage, keys.iter().map(|key| (*key, b"DATA")))
+                .unwrap();
+
+        tree.update_batch(keys.iter().map(|key| (*key, None::<&[u8]>)))
+            .unwrap();
+
+        assert_eq!(tree.root(), *empty_sum());
+        assert_eq!(tree.storage().len(), 0);
+    }
+
+    #[test]
+    fn test_update_batch_removes_old_entries() {
+        let initial = (0_u32..100)
+            .map(|i| (key(i.to_be_bytes()), b"DATA".to_vec()))
+            .collect::<Vec<_>>();
+        let batch = (0_u32..100)
+            .step_by(7)
+            .map(|i| {
+                let value = (i % 2 == 0).then(|| b"ANOTHER_DATA".to_vec());
+                (key(i.to_be_bytes()), value)
+            })
+            .collect::<Vec<_>>();
+        let mut expected = initial
+            .iter()
+            .map(|(key, value)| (**key, value.clone()))
+            .collect::<BTreeMap<_, _>>();
+        for (key, value) in &batch {
+            match value {
+                Some(value) => expected.insert(**key, value.clone()),
+                None => expected.remove(&**key),
+            };
+        }
+
+        let mut storage = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::from_set(&mut storage, initial.into_iter()).unwrap();
+        tree.update_batch(batch).unwrap();
+
+        let mut expected_storage = StorageMap::<TestTable>::new();
+        let expected_tree =
+            MerkleTree::from_set(&mut expected_storage, expected.into_iter()).unwrap();
+        assert_eq!(tree.root(), expected_tree.root());
+        assert_eq!(tree.storage().len(), expected_tree.storage().len());
+    }
+
+    #[test]
+    fn test_update_batch_with_empty_set_does_not_change_root() {
+        let mut storage = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage);
+        tree.update(key(b"\x00\x00\x00\x00"), b"DATA").unwrap();
+        let root = tree.root();
+
+        tree.update_batch(core::iter::empty::<(MerkleTreeKey, Option<&[u8]>)>())
+            .unwrap();
+
+        assert_eq!(tree.root(), root);
+    }
+
     #[test]
     fn merkle_tree__generate_proof__returns_proof_with_proof_set_for_given_key() {
         // Given
@@ -1545,14 +2156,14 @@ mod test {
         tree.update(MerkleTreeKey::new_without_hash(k3), &v3)
             .expect("Expected successful update");
 
-        let l0 = Node::create_leaf(&k0, v0);
-        let l1 = Node::create_leaf(&k1, v1);
-        let l2 = Node::create_leaf(&k2, v2);
-        let l3 = Node::create_leaf(&k3, v3);
-        let n0 = Node::create_node(&l1, &l3, 252);
-        let n1 = Node::create_node(&n0, &Node::create_placeholder(), 253);
-        let n2 = Node::create_node(&n1, &l2, 254);
-        let n3 = Node::create_node(&l0, &n2, 255);
+        let l0 = Node::create_leaf::<Sha256>(&k0, v0);
+        let l1 = Node::create_leaf::<Sha256>(&k1, v1);
+        let l2 = Node::create_leaf::<Sha256>(&k2, v2);
+        let l3 = Node::create_leaf::<Sha256>(&k3, v3);
+        let n0 = Node::create_node::<Sha256>(&l1, &l3, 252);
+        let n1 = Node::create_node::<Sha256>(&n0, &Node::create_placeholder(), 253);
+        let n2 = Node::create_node::<Sha256>(&n1, &l2, 254);
+        let n3 = Node::create_node::<Sha256>(&l0, &n2, 255);
 
         {
             // When
@@ -1715,4 +2326,384 @@ mod test {
         // Then
         assert!(proof.is_exclusion());
     }
+    #[test]
+    fn merkle_tree__leaves__returns_leaves_ordered_by_key() {
+        // Given
+        let mut storage = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage);
+        let leaves = [
+            ([0xff; 32], b"DATA_0"),
+            ([0x00; 32], b"DATA_1"),
+            ([0x80; 32], b"DATA_2"),
+            ([0x7f; 32], b"DATA_3"),
+            ([0x01; 32], b"DATA_4"),
+        ];
+        for (key, data) in leaves {
+            tree.update(MerkleTreeKey::new_without_hash(key), data)
+                .unwrap();
+        }
+
+        // When
+        let result = tree.leaves().collect::<Result<Vec<_>, _>>();
+
+        // Then
+        let mut expected = leaves
+            .iter()
+            .map(|(key, data)| (*key, sum(data)))
+            .collect::<Vec<_>>();
+        expected.sort();
+        assert_eq!(result.unwrap(), expected);
+    }
+
+    #[test]
+    fn merkle_tree__leaves__returns_no_leaves_for_empty_tree() {
+        // Given
+        let mut storage = StorageMap::<TestTable>::new();
+        let tree = MerkleTree::new(&mut storage);
+
+        // When
+        let leaves = tree.leaves().collect::<Vec<_>>();
+
+        // Then
+        assert!(leaves.is_empty());
+    }
+
+    #[test]
+    fn merkle_tree__leaves__returns_load_error_for_missing_node() {
+        // Given
+        let mut tree = MerkleTree::new(StorageMap::<TestTable>::new());
+        tree.update(key(b"\x00\x00\x00\x00"), b"DATA_0").unwrap();
+        tree.update(key(b"\x00\x00\x00\x01"), b"DATA_1").unwrap();
+        let leaf = Node::create_leaf::<Sha256>(&key(b"\x00\x00\x00\x01"), b"DATA_1");
+        tree.storage_mut().remove(leaf.hash()).unwrap();
+
+        // When
+        let result = tree.leaves().collect::<Result<Vec<_>, _>>();
+
+        // Then
+        assert!(
+            matches!(result, Err(MerkleTreeError::LoadError(hash)) if hash == *leaf.hash())
+        );
+    }
+
+    #[test]
+    fn merkle_tree__leaves_in_range__returns_leaves_with_keys_in_range() {
+        // Given
+        let mut storage = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage);
+        for byte in [0x00, 0x40, 0x80, 0xc0, 0xff] {
+            tree.update(MerkleTreeKey::new_without_hash([byte; 32]), b"DATA")
+                .unwrap();
+        }
+        let start = MerkleTreeKey::new_without_hash([0x40; 32]);
+        let end = MerkleTreeKey::new_without_hash([0xc0; 32]);
+
+        // When
+        let result = tree
+            .leaves_in_range(&start, &end)
+            .map(|leaf| leaf.map(|(key, _)| key))
+            .collect::<Result<Vec<_>, _>>();
+
+        // Then
+        assert_eq!(result.unwrap(), vec![[0x40; 32], [0x80; 32], [0xc0; 32]]);
+    }
+
+    #[test]
+    fn merkle_tree__generate_range_proof__returns_proof_of_leaves_in_range() {
+        // Given
+        let mut storage = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage);
+        let leaves = (0_u32..100)
+            .map(|i| (key(i.to_be_bytes()), b"DATA"))
+            .collect::<Vec<_>>();
+        for (key, data) in &leaves {
+            tree.update(*key, *data).unwrap();
+        }
+        let start = MerkleTreeKey::new_without_hash([0x40; 32]);
+        let end = MerkleTreeKey::new_without_hash([0x80; 32]);
+
+        // When
+        let proof = tree.generate_range_proof(&start, &end).unwrap();
+
+        // Then
+        let mut expected = leaves
+            .into_iter()
+            .filter(|(key, _)| (*start..=*end).contains(key))
+            .collect::<Vec<_>>();
+        expected.sort_by_key(|(key, _)| **key);
+        assert!(!expected.is_empty());
+        assert!(proof.verify(&tree.root(), &start, &end, &expected));
+        expected.pop();
+        assert!(!proof.verify(&tree.root(), &start, &end, &expected));
+    }
+
+    #[test]
+    fn merkle_tree__generate_range_proof__returns_proof_of_empty_range() {
+        // Given
+        let mut storage = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage);
+        for byte in [0x00, 0x40, 0xff] {
+            tree.update(MerkleTreeKey::new_without_hash([byte; 32]), b"DATA")
+                .unwrap();
+        }
+        let start = MerkleTreeKey::new_without_hash([0x41; 32]);
+        let end = MerkleTreeKey::new_without_hash([0xfe; 32]);
+
+        // When
+        let proof = tree.generate_range_proof(&start, &end).unwrap();
+
+        // Then
+        let no_leaves: [(MerkleTreeKey, &[u8]); 0] = [];
+        assert!(proof.verify(&tree.root(), &start, &end, &no_leaves));
+        let leaves = [(MerkleTreeKey::new_without_hash([0x80; 32]), b"DATA")];
+        assert!(!proof.verify(&tree.root(), &start, &end, &leaves));
+    }
+
+    #[test]
+    fn merkle_tree__root__hashes_the_nodes_with_the_hasher() {
+        // Given
+        let mut storage = StorageMap::<TestTable>::new();
+        let mut tree =
+            MerkleTree::<TestTable, _, Keccak256>::new_with_hasher(&mut storage);
+        let k0 = [0x00; 32];
+        let k1 = [0xff; 32];
+
+        // When
+        tree.update(MerkleTreeKey::new_without_hash(k0), b"DATA_0")
+            .unwrap();
+        tree.update(MerkleTreeKey::new_without_hash(k1), b"DATA_1")
+            .unwrap();
+
+        // Then
+        let keccak =
+            |data: &[&[u8]]| -> Bytes32 { Keccak256::digest(data.concat()).into() };
+        let l0 = keccak(&[&[0x00], &k0, &keccak(&[b"DATA_0"])]);
+        let l1 = keccak(&[&[0x00], &k1, &keccak(&[b"DATA_1"])]);
+        let expected_root = keccak(&[&[0x01], &l0, &l1]);
+        assert_eq!(tree.root(), expected_root);
+    }
+
+    #[test]
+    fn merkle_tree__load_with_hasher__returns_the_tree_with_the_hasher() {
+        // Given
+        let mut storage = StorageMap::<TestTable>::new();
+        let leaves = (0_u32..10)
+            .map(|i| (key(i.to_be_bytes()), b"DATA"))
+            .collect::<Vec<_>>();
+        let mut tree =
+            MerkleTree::<TestTable, _, Keccak256>::new_with_hasher(&mut storage);
+        for (key, data) in &leaves[..5] {
+            tree.update(*key, *data).unwrap();
+        }
+        let root = tree.root();
+
+        // When
+        let mut tree =
+            MerkleTree::<TestTable, _, Keccak256>::load_with_hasher(&mut storage, &root)
+                .unwrap();
+        for (key, data) in &leaves[5..] {
+            tree.update(*key, *data).unwrap();
+        }
+
+        // Then
+        let expected_tree = MerkleTree::<TestTable, _, Keccak256>::from_set_with_hasher(
+            StorageMap::<TestTable>::new(),
+            leaves.iter().map(|(key, data)| (*key, data)),
+        )
+        .unwrap();
+        let sha256_tree = MerkleTree::<TestTable, _>::from_set(
+            StorageMap::<TestTable>::new(),
+            leaves.iter().map(|(key, data)| (*key, data)),
+        )
+        .unwrap();
+        assert_eq!(tree.root(), expected_tree.root());
+        assert_ne!(tree.root(), sha256_tree.root());
+    }
+
+    #[test]
+    fn merkle_tree__generate_proof__returns_proof_verified_with_the_hasher() {
+        // Given
+        let mut storage = StorageMap::<TestTable>::new();
+        let mut tree =
+            MerkleTree::<TestTable, _, Keccak256>::new_with_hasher(&mut storage);
+        for i in 0_u32..10 {
+            tree.update(key(i.to_be_bytes()), b"DATA").unwrap();
+        }
+        let included = key(3_u32.to_be_bytes());
+        let excluded = key(10_u32.to_be_bytes());
+
+        // When
+        let inclusion = tree.generate_proof(&included).unwrap();
+        let exclusion = tree.generate_proof(&excluded).unwrap();
+        let multiproof = tree.generate_multiproof(&[included, excluded]).unwrap();
+
+        // Then
+        let root = tree.root();
+        let Proof::Inclusion(inclusion) = inclusion else {
+            panic!("Expected an inclusion proof")
+        };
+        assert!(inclusion.verify_with_hasher::<Keccak256>(&root, &included, b"DATA"));
+        assert!(!inclusion.verify(&root, &included, b"DATA"));
+        let Proof::Exclusion(exclusion) = exclusion else {
+            panic!("Expected an exclusion proof")
+        };
+        assert!(exclusion.verify_with_hasher::<Keccak256>(&root, &excluded));
+        assert!(!exclusion.verify(&root, &excluded));
+        let keys = [(included, Some(b"DATA")), (excluded, None)];
+        assert!(multiproof.verify_with_hasher::<Keccak256, _>(&root, &keys));
+        assert!(!multiproof.verify(&root, &keys));
+    }
+
+    #[test]
+    fn merkle_tree__update_batch__writes_the_nodes_to_the_storage_on_commit_of_the_write_batch(
+    ) {
+        // Given
+        let mut storage = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage);
+        for i in 0_u32..5 {
+            tree.update(key(i.to_be_bytes()), b"DATA").unwrap();
+        }
+        let old_root = tree.root();
+        let mut tree =
+            MerkleTree::load(WriteBatch::new(&mut storage), &old_root).unwrap();
+
+        // When
+        tree.update_batch(
+            (3_u32..10).map(|i| (key(i.to_be_bytes()), Some(b"CHANGED_DATA"))),
+        )
+        .unwrap();
+        let root = tree.root();
+        let batch = tree.into_storage();
+        let uncommitted_tree = MerkleTree::load(&**batch.storage(), &root).map(|_| ());
+        let storage = batch.commit().unwrap();
+
+        // Then
+        assert!(matches!(
+            uncommitted_tree,
+            Err(MerkleTreeError::LoadError(_))
+        ));
+        let tree = MerkleTree::load(&*storage, &root).unwrap();
+        let expected_tree = MerkleTree::from_set(
+            StorageMap::<TestTable>::new(),
+            (0_u32..3)
+                .map(|i| (key(i.to_be_bytes()), b"DATA".as_slice()))
+                .chain(
+                    (3_u32..10)
+                        .map(|i| (key(i.to_be_bytes()), b"CHANGED_DATA".as_slice())),
+                ),
+        )
+        .unwrap();
+        assert_eq!(tree.root(), expected_tree.root());
+        assert_eq!(tree.leaves().count(), 10);
+    }
+
+    /// A storage accepting single writes but rejecting every batch.
+    #[derive(Default)]
+    struct BatchRejectingStorage {
+        storage: StorageMap<TestTable>,
+    }
+
+    impl StorageInspect<TestTable> for BatchRejectingStorage {
+        type Error = &'static str;
+
+        fn get(&self, key: &Bytes32) -> Result<Option<Cow<'_, Primitive>>, Self::Error> {
+            Ok(self.storage.get(key).unwrap())
+        }
+
+        fn contains_key(&self, key: &Bytes32) -> Result<bool, Self::Error> {
+            Ok(self.storage.contains_key(key).unwrap())
+        }
+    }
+
+    impl StorageMutate<TestTable> for BatchRejectingStorage {
+        fn replace(
+            &mut self,
+            key: &Bytes32,
+            value: &Primitive,
+        ) -> Result<Option<Primitive>, Self::Error> {
+            Ok(self.storage.replace(key, value).unwrap())
+        }
+
+        fn take(&mut self, key: &Bytes32) -> Result<Option<Primitive>, Self::Error> {
+            Ok(self.storage.take(key).unwrap())
+        }
+
+        fn apply_batch(
+            &mut self,
+            _changes: &[(&Bytes32, Option<&Primitive>)],
+        ) -> Result<(), Self::Error> {
+            Err("batch rejected")
+        }
+    }
+
+    #[test]
+    fn merkle_tree__update__leaves_the_tree_unchanged_if_the_batch_fails() {
+        // Given
+        let mut storage = BatchRejectingStorage::default();
+        let mut tree = MerkleTree::new(&mut storage);
+
+        // When
+        let result = tree.update(key(b"KEY"), b"DATA");
+
+        // Then
+        assert!(matches!(
+            result,
+            Err(MerkleTreeError::StorageError("batch rejected"))
+        ));
+        assert_eq!(tree.root(), *empty_sum());
+        assert!(storage.storage.is_empty());
+    }
+
+    /// A storage implementing only the single writes, so its batches use the default
+    /// `apply_batch`.
+    #[derive(Default)]
+    struct SingleWriteStorage {
+        storage: StorageMap<TestTable>,
+    }
+
+    impl StorageInspect<TestTable> for SingleWriteStorage {
+        type Error = core::convert::Infallible;
+
+        fn get(&self, key: &Bytes32) -> Result<Option<Cow<'_, Primitive>>, Self::Error> {
+            self.storage.get(key)
+        }
+
+        fn contains_key(&self, key: &Bytes32) -> Result<bool, Self::Error> {
+            self.storage.contains_key(key)
+        }
+    }
+
+    impl StorageMutate<TestTable> for SingleWriteStorage {
+        fn replace(
+            &mut self,
+            key: &Bytes32,
+            value: &Primitive,
+        ) -> Result<Option<Primitive>, Self::Error> {
+            self.storage.replace(key, value)
+        }
+
+        fn take(&mut self, key: &Bytes32) -> Result<Option<Primitive>, Self::Error> {
+            self.storage.take(key)
+        }
+    }
+
+    #[test]
+    fn merkle_tree__update__supports_storages_without_batches() {
+        // Given
+        let mut storage = SingleWriteStorage::default();
+        let mut tree = MerkleTree::new(&mut storage);
+        let mut expected_tree = MerkleTree::new(StorageMap::<TestTable>::new());
+
+        // When
+        for i in 0_u32..10 {
+            tree.update(key(i.to_be_bytes()), b"DATA").unwrap();
+            expected_tree.update(key(i.to_be_bytes()), b"DATA").unwrap();
+        }
+        tree.delete(key(3_u32.to_be_bytes())).unwrap();
+        expected_tree.delete(key(3_u32.to_be_bytes())).unwrap();
+
+        // Then
+        assert_eq!(tree.root(), expected_tree.root());
+        assert_eq!(storage.storage.len(), expected_tree.into_storage().len());
+    }
 }
@@ -0,0 +1,113 @@
+use crate::{
+    common::{
+        Bytes32,
+        Hasher,
+    },
+    sparse::{
+        merkle_tree::{
+            MerkleTree,
+            MerkleTreeError,
+            Node,
+        },
+        proof::{
+            side_position,
+            subtree_range,
+        },
+        Primitive,
+    },
+    storage::{
+        Mappable,
+        StorageInspect,
+    },
+};
+
+use alloc::{
+    vec,
+    vec::Vec,
+};
+use core::ops::RangeInclusive;
+use sha2::Sha256;
+
+/// An iterator over the leaves of a [`MerkleTree`] in the order of their keys,
+/// yielding the key of each leaf and the hash of its value.
+///
+/// This struct is created by [`MerkleTree::leaves`] and
+/// [`MerkleTree::leaves_in_range`]. Only the subtrees overlapping the range of keys
+/// are loaded from the storage.
+pub struct Leaves<'a, TableType, StorageType, H = Sha256> {
+    tree: &'a MerkleTree<TableType, StorageType, H>,
+    range: RangeInclusive<Bytes32>,
+    /// The nodes to visit, with their depth and position, the next one on top.
+    stack: Vec<(u32, Bytes32, Node)>,
+}
+
+impl<'a, TableType, StorageType, H> Leaves<'a, TableType, StorageType, H> {
+    pub(super) fn new(
+        tree: &'a MerkleTree<TableType, StorageType, H>,
+        range: RangeInclusive<Bytes32>,
+    ) -> Self {
+        let stack = if range.is_empty() {
+            vec![]
+        } else {
+            vec![(0, Bytes32::default(), tree.root_node().clone())]
+        };
+        Self { tree, range, stack }
+    }
+
+    fn overlaps(&self, position: &Bytes32, depth: u32) -> bool {
+        let subtree = subtree_range(position, depth);
+        subtree.start() <= self.range.end() && subtree.end() >= self.range.start()
+    }
+}
+
+impl<'a, TableType, StorageType, StorageError, H> Iterator
+    for Leaves<'a, TableType, StorageType, H>
+where
+    TableType: Mappable<Key = Bytes32, Value = Primitive, OwnedValue = Primitive>,
+    StorageType: StorageInspect<TableType, Error = StorageError>,
+    H: Hasher,
+{
+    type Item = Result<(Bytes32, Bytes32), MerkleTreeError<StorageError>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((depth, position, node)) = self.stack.pop() {
+            if node.is_placeholder() {
+                continue;
+            }
+
+            if node.is_leaf() {
+                if self.range.contains(node.leaf_key()) {
+                    return Some(Ok((*node.leaf_key(), *node.leaf_data())));
+                }
+                continue;
+            }
+
+            // The right child is pushed first, to visit the left child first.
+            #[allow(clippy::arithmetic_side_effects)] // Internal nodes are above leaves
+            let child_depth = depth + 1;
+            let right_position =
+                side_position(&position, child_depth).expect("The depth is at least 1");
+            let (positions, keys): (Vec<_>, Vec<_>) = [
+                (right_position, node.right_child_key()),
+                (position, node.left_child_key()),
+            ]
+            .into_iter()
+            .filter(|(child_position, _)| self.overlaps(child_position, child_depth))
+            .unzip();
+            // Both children are read from the storage at once.
+            match self.tree.load_nodes(&keys) {
+                Ok(children) => {
+                    self.stack.extend(positions.into_iter().zip(children).map(
+                        |(child_position, child)| (child_depth, child_position, child),
+                    ))
+                }
+                Err(err) => {
+                    self.stack.clear();
+                    return Some(Err(err));
+                }
+            }
+        }
+
+        None
+    }
+}
@@ -11,8 +11,8 @@ use crate::{
             Path,
             Side,
         },
-        sum,
         Bytes32,
+        Hasher,
         Prefix,
     },
     sparse::{
@@ -56,14 +56,14 @@ impl Node {
         Node::key_size_bits()
     }
 
-    pub fn new(
+    pub fn new<H: Hasher>(
         height: u32,
         prefix: Prefix,
         bytes_lo: Bytes32,
         bytes_hi: Bytes32,
     ) -> Self {
         Self::Node {
-            hash: calculate_hash(&prefix, &bytes_lo, &bytes_hi),
+            hash: calculate_hash::<H>(&prefix, &bytes_lo, &bytes_hi),
             height,
             prefix,
             bytes_lo,
@@ -71,10 +71,23 @@ impl Node {
         }
     }
 
-    pub fn create_leaf<D: AsRef<[u8]>>(key: &Bytes32, data: D) -> Self {
-        let bytes_hi = sum(data);
+    /// Converts the `primitive` read from the storage into a node, computing its
+    /// hash with `H`.
+    pub fn from_primitive<H: Hasher>(
+        primitive: Primitive,
+    ) -> Result<Self, DeserializeError> {
+        let height = primitive.height();
+        let prefix = primitive.prefix()?;
+        let bytes_lo = *primitive.bytes_lo();
+        let bytes_hi = *primitive.bytes_hi();
+        let node = Self::new::<H>(height, prefix, bytes_lo, bytes_hi);
+        Ok(node)
+    }
+
+    pub fn create_leaf<H: Hasher>(key: &Bytes32, data: impl AsRef<[u8]>) -> Self {
+        let bytes_hi = H::hash(data);
         Self::Node {
-            hash: calculate_leaf_hash(key, &bytes_hi),
+            hash: calculate_leaf_hash::<H>(key, &bytes_hi),
             height: 0u32,
             prefix: Prefix::Leaf,
             bytes_lo: *key,
@@ -82,11 +95,15 @@ impl Node {
         }
     }
 
-    pub fn create_node(left_child: &Node, right_child: &Node, height: u32) -> Self {
+    pub fn create_node<H: Hasher>(
+        left_child: &Node,
+        right_child: &Node,
+        height: u32,
+    ) -> Self {
         let bytes_lo = *left_child.hash();
         let bytes_hi = *right_child.hash();
         Self::Node {
-            hash: calculate_node_hash(&bytes_lo, &bytes_hi),
+            hash: calculate_node_hash::<H>(&bytes_lo, &bytes_hi),
             height,
             prefix: Prefix::Node,
             bytes_lo,
@@ -94,13 +111,13 @@ impl Node {
         }
     }
 
-    pub fn create_node_from_hashes(
+    pub fn create_node_from_hashes<H: Hasher>(
         bytes_lo: Bytes32,
         bytes_hi: Bytes32,
         height: u32,
     ) -> Self {
         Self::Node {
-            hash: calculate_node_hash(&bytes_lo, &bytes_hi),
+            hash: calculate_node_hash::<H>(&bytes_lo, &bytes_hi),
             height,
             prefix: Prefix::Node,
             bytes_lo,
@@ -108,7 +125,7 @@ impl Node {
         }
     }
 
-    pub fn create_node_on_path(
+    pub fn create_node_on_path<H: Hasher>(
         path: &dyn Path,
         path_node: &Node,
         side_node: &Node,
@@ -123,8 +140,10 @@ impl Node {
             #[allow(clippy::arithmetic_side_effects)] // parent_depth <= max_height
             let parent_height = Node::max_height() - parent_depth;
             match path.get_instruction(parent_depth).unwrap() {
-                Side::Left => Node::create_node(path_node, side_node, parent_height),
-                Side::Right => Node::create_node(side_node, path_node, parent_height),
+                Side::Left => Node::create_node::<H>(path_node, side_node, parent_height),
+                Side::Right => {
+                    Node::create_node::<H>(side_node, path_node, parent_height)
+                }
             }
         } else {
             // When joining two nodes, or a node and a leaf, the joined node is
@@ -136,8 +155,10 @@ impl Node {
             #[allow(clippy::arithmetic_side_effects)] // parent_height <= max_height
             let parent_depth = Node::max_height() - parent_height;
             match path.get_instruction(parent_depth).unwrap() {
-                Side::Left => Node::create_node(path_node, side_node, parent_height),
-                Side::Right => Node::create_node(side_node, path_node, parent_height),
+                Side::Left => Node::create_node::<H>(path_node, side_node, parent_height),
+                Side::Right => {
+                    Node::create_node::<H>(side_node, path_node, parent_height)
+                }
             }
         }
     }
@@ -315,19 +336,6 @@ impl From<&Node> for Primitive {
     }
 }
 
-impl TryFrom<Primitive> for Node {
-    type Error = DeserializeError;
-
-    fn try_from(primitive: Primitive) -> Result<Self, Self::Error> {
-        let height = primitive.height();
-        let prefix = primitive.prefix()?;
-        let bytes_lo = *primitive.bytes_lo();
-        let bytes_hi = *primitive.bytes_hi();
-        let node = Self::new(height, prefix, bytes_lo, bytes_hi);
-        Ok(node)
-    }
-}
-
 impl fmt::Debug for Node {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if self.is_node() {
@@ -348,33 +356,36 @@ impl fmt::Debug for Node {
     }
 }
 
-pub(super) struct StorageNode<'storage, TableType, StorageType> {
+pub(super) struct StorageNode<'storage, TableType, StorageType, H> {
     storage: &'storage StorageType,
     node: Node,
     phantom_table: PhantomData<TableType>,
+    phantom_hasher: PhantomData<H>,
 }
 
-impl<TableType, StorageType> Clone for StorageNode<'_, TableType, StorageType> {
+impl<TableType, StorageType, H> Clone for StorageNode<'_, TableType, StorageType, H> {
     fn clone(&self) -> Self {
         Self {
             storage: self.storage,
             node: self.node.clone(),
             phantom_table: Default::default(),
+            phantom_hasher: Default::default(),
         }
     }
 }
 
-impl<'s, TableType, StorageType> StorageNode<'s, TableType, StorageType> {
+impl<'s, TableType, StorageType, H> StorageNode<'s, TableType, StorageType, H> {
     pub fn new(storage: &'s StorageType, node: Node) -> Self {
         Self {
             node,
             storage,
             phantom_table: Default::default(),
+            phantom_hasher: Default::default(),
         }
     }
 }
 
-impl<TableType, StorageType> StorageNode<'_, TableType, StorageType> {
+impl<TableType, StorageType, H> StorageNode<'_, TableType, StorageType, H> {
     pub fn hash(&self) -> &Bytes32 {
         self.node.hash()
     }
@@ -384,7 +395,7 @@ impl<TableType, StorageType> StorageNode<'_, TableType, StorageType> {
     }
 }
 
-impl<TableType, StorageType> NodeTrait for StorageNode<'_, TableType, StorageType> {
+impl<TableType, StorageType, H> NodeTrait for StorageNode<'_, TableType, StorageType, H> {
     type Key = Bytes32;
 
     fn height(&self) -> u32 {
@@ -417,10 +428,12 @@ pub enum StorageNodeError<StorageError> {
     DeserializeError(DeserializeError),
 }
 
-impl<TableType, StorageType> ParentNodeTrait for StorageNode<'_, TableType, StorageType>
+impl<TableType, StorageType, H> ParentNodeTrait
+    for StorageNode<'_, TableType, StorageType, H>
 where
     StorageType: StorageInspect<TableType>,
     TableType: Mappable<Key = Bytes32, Value = Primitive, OwnedValue = Primitive>,
+    H: Hasher,
 {
     type ChildKey = Bytes32;
     type Error = StorageNodeError<StorageType::Error>;
@@ -442,9 +455,7 @@ where
             .get(key)
             .map_err(StorageNodeError::StorageError)?
             .ok_or(ChildError::ChildNotFound(*key))?;
-        Ok(primitive
-            .into_owned()
-            .try_into()
+        Ok(Node::from_primitive::<H>(primitive.into_owned())
             .map(|node| Self::new(self.storage, node))
             .map_err(StorageNodeError::DeserializeError)?)
     }
@@ -469,9 +480,7 @@ where
             .get(key)
             .map_err(StorageNodeError::StorageError)?
             .ok_or(ChildError::ChildNotFound(*key))?;
-        Ok(primitive
-            .into_owned()
-            .try_into()
+        Ok(Node::from_primitive::<H>(primitive.into_owned())
             .map(|node| Self::new(self.storage, node))
             .map_err(StorageNodeError::DeserializeError)?)
     }
@@ -484,10 +493,11 @@ where
     }
 }
 
-impl<TableType, StorageType> fmt::Debug for StorageNode<'_, TableType, StorageType>
+impl<TableType, StorageType, H> fmt::Debug for StorageNode<'_, TableType, StorageType, H>
 where
     StorageType: StorageInspect<TableType>,
     TableType: Mappable<Key = Bytes32, Value = Primitive, OwnedValue = Primitive>,
+    H: Hasher,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if self.is_node() {
@@ -524,6 +534,7 @@ mod test_node {
             Primitive,
         },
     };
+    use sha2::Sha256;
 
     fn leaf_hash(key: &Bytes32, data: &[u8]) -> Bytes32 {
         let mut buffer = [0; 65];
@@ -535,7 +546,7 @@ mod test_node {
 
     #[test]
     fn test_create_leaf_returns_a_valid_leaf() {
-        let leaf = Node::create_leaf(&sum(b"LEAF"), [1u8; 32]);
+        let leaf = Node::create_leaf::<Sha256>(&sum(b"LEAF"), [1u8; 32]);
         assert_eq!(leaf.is_leaf(), true);
         assert_eq!(leaf.is_node(), false);
         assert_eq!(leaf.height(), 0);
@@ -546,9 +557,9 @@ mod test_node {
 
     #[test]
     fn test_create_node_returns_a_valid_node() {
-        let left_child = Node::create_leaf(&sum(b"LEFT CHILD"), [1u8; 32]);
-        let right_child = Node::create_leaf(&sum(b"RIGHT CHILD"), [1u8; 32]);
-        let node = Node::create_node(&left_child, &right_child, 1);
+        let left_child = Node::create_leaf::<Sha256>(&sum(b"LEFT CHILD"), [1u8; 32]);
+        let right_child = Node::create_leaf::<Sha256>(&sum(b"RIGHT CHILD"), [1u8; 32]);
+        let node = Node::create_node::<Sha256>(&left_child, &right_child, 1);
         assert_eq!(node.is_leaf(), false);
         assert_eq!(node.is_node(), true);
         assert_eq!(node.height(), 1);
@@ -574,7 +585,7 @@ mod test_node {
     fn test_create_leaf_from_primitive_returns_a_valid_leaf() {
         let primitive = (0, Prefix::Leaf as u8, [0xff; 32], [0xff; 32]);
 
-        let node: Node = primitive.try_into().unwrap();
+        let node = Node::from_primitive::<Sha256>(primitive).unwrap();
         assert_eq!(node.is_leaf(), true);
         assert_eq!(node.is_node(), false);
         assert_eq!(node.height(), 0);
@@ -587,7 +598,7 @@ mod test_node {
     fn test_create_node_from_primitive_returns_a_valid_node() {
         let primitive = (255, Prefix::Node as u8, [0xff; 32], [0xff; 32]);
 
-        let node: Node = primitive.try_into().unwrap();
+        let node = Node::from_primitive::<Sha256>(primitive).unwrap();
         assert_eq!(node.is_leaf(), false);
         assert_eq!(node.is_node(), true);
         assert_eq!(node.height(), 255);
@@ -601,8 +612,8 @@ mod test_node {
         let primitive = (0xff, 0xff, [0xff; 32], [0xff; 32]);
 
         // Should return Error; prefix 0xff is does not represent a node or leaf
-        let err = Node::try_from(primitive)
-            .expect_err("Expected try_from() to be Error; got OK");
+        let err = Node::from_primitive::<Sha256>(primitive)
+            .expect_err("Expected from_primitive() to be Error; got OK");
         assert!(matches!(
             err,
             DeserializeError::PrefixError(PrefixError::InvalidPrefix(0xff))
@@ -616,7 +627,7 @@ mod test_node {
         let expected_primitive =
             (0_u32, Prefix::Leaf as u8, sum(b"LEAF"), sum([1u8; 32]));
 
-        let leaf = Node::create_leaf(&sum(b"LEAF"), [1u8; 32]);
+        let leaf = Node::create_leaf::<Sha256>(&sum(b"LEAF"), [1u8; 32]);
         let primitive = Primitive::from(&leaf);
 
         assert_eq!(primitive, expected_primitive);
@@ -633,9 +644,9 @@ mod test_node {
             leaf_hash(&sum(b"RIGHT CHILD"), &[1u8; 32]),
         );
 
-        let left_child = Node::create_leaf(&sum(b"LEFT CHILD"), [1u8; 32]);
-        let right_child = Node::create_leaf(&sum(b"RIGHT CHILD"), [1u8; 32]);
-        let node = Node::create_node(&left_child, &right_child, 1);
+        let left_child = Node::create_leaf::<Sha256>(&sum(b"LEFT CHILD"), [1u8; 32]);
+        let right_child = Node::create_leaf::<Sha256>(&sum(b"RIGHT CHILD"), [1u8; 32]);
+        let node = Node::create_node::<Sha256>(&left_child, &right_child, 1);
         let primitive = Primitive::from(&node);
 
         assert_eq!(primitive, expected_primitive);
@@ -651,7 +662,7 @@ mod test_node {
         expected_buffer[33..65].clone_from_slice(&sum([1u8; 32]));
         let expected_value = sum(expected_buffer);
 
-        let node = Node::create_leaf(&sum(b"LEAF"), [1u8; 32]);
+        let node = Node::create_leaf::<Sha256>(&sum(b"LEAF"), [1u8; 32]);
         let value = *node.hash();
 
         assert_eq!(value, expected_value);
@@ -669,9 +680,9 @@ mod test_node {
             .clone_from_slice(&leaf_hash(&sum(b"RIGHT CHILD"), &[1u8; 32]));
         let expected_value = sum(expected_buffer);
 
-        let left_child = Node::create_leaf(&sum(b"LEFT CHILD"), [1u8; 32]);
-        let right_child = Node::create_leaf(&sum(b"RIGHT CHILD"), [1u8; 32]);
-        let node = Node::create_node(&left_child, &right_child, 1);
+        let left_child = Node::create_leaf::<Sha256>(&sum(b"LEFT CHILD"), [1u8; 32]);
+        let right_child = Node::create_leaf::<Sha256>(&sum(b"RIGHT CHILD"), [1u8; 32]);
+        let node = Node::create_node::<Sha256>(&left_child, &right_child, 1);
         let value = *node.hash();
 
         assert_eq!(value, expected_value);
@@ -703,6 +714,7 @@ mod test_storage_node {
             StorageMutate,
         },
     };
+    use sha2::Sha256;
 
     pub struct TestTable;
 
@@ -717,16 +729,16 @@ mod test_storage_node {
     fn test_node_left_child_returns_the_left_child() {
         let mut s = StorageMap::<TestTable>::new();
 
-        let leaf_0 = Node::create_leaf(&sum(b"Hello World"), [1u8; 32]);
+        let leaf_0 = Node::create_leaf::<Sha256>(&sum(b"Hello World"), [1u8; 32]);
         let _ = s.insert(leaf_0.hash(), &leaf_0.as_ref().into());
 
-        let leaf_1 = Node::create_leaf(&sum(b"Goodbye World"), [1u8; 32]);
+        let leaf_1 = Node::create_leaf::<Sha256>(&sum(b"Goodbye World"), [1u8; 32]);
         let _ = s.insert(leaf_1.hash(), &leaf_1.as_ref().into());
 
-        let node_0 = Node::create_node(&leaf_0, &leaf_1, 1);
+        let node_0 = Node::create_node::<Sha256>(&leaf_0, &leaf_1, 1);
         let _ = s.insert(node_0.hash(), &node_0.as_ref().into());
 
-        let storage_node = StorageNode::new(&s, node_0);
+        let storage_node = StorageNode::<_, _, Sha256>::new(&s, node_0);
         let child = storage_node.left_child().unwrap();
 
         assert_eq!(child.hash(), leaf_0.hash());
@@ -736,16 +748,16 @@ mod test_storage_node {
     fn test_node_right_child_returns_the_right_child() {
         let mut s = StorageMap::<TestTable>::new();
 
-        let leaf_0 = Node::create_leaf(&sum(b"Hello World"), [1u8; 32]);
+        let leaf_0 = Node::create_leaf::<Sha256>(&sum(b"Hello World"), [1u8; 32]);
         let _ = s.insert(leaf_0.hash(), &leaf_0.as_ref().into());
 
-        let leaf_1 = Node::create_leaf(&sum(b"Goodbye World"), [1u8; 32]);
+        let leaf_1 = Node::create_leaf::<Sha256>(&sum(b"Goodbye World"), [1u8; 32]);
         let _ = s.insert(leaf_1.hash(), &leaf_1.as_ref().into());
 
-        let node_0 = Node::create_node(&leaf_0, &leaf_1, 1);
+        let node_0 = Node::create_node::<Sha256>(&leaf_0, &leaf_1, 1);
         let _ = s.insert(node_0.hash(), &node_0.as_ref().into());
 
-        let storage_node = StorageNode::new(&s, node_0);
+        let storage_node = StorageNode::<_, _, Sha256>::new(&s, node_0);
         let child = storage_node.right_child().unwrap();
 
         assert_eq!(child.hash(), leaf_1.hash());
@@ -755,13 +767,13 @@ mod test_storage_node {
     fn test_node_left_child_returns_placeholder_when_key_is_zero_sum() {
         let mut s = StorageMap::<TestTable>::new();
 
-        let leaf = Node::create_leaf(&sum(b"Goodbye World"), [1u8; 32]);
+        let leaf = Node::create_leaf::<Sha256>(&sum(b"Goodbye World"), [1u8; 32]);
         let _ = s.insert(leaf.hash(), &leaf.as_ref().into());
 
-        let node_0 = Node::create_node(&Node::create_placeholder(), &leaf, 1);
+        let node_0 = Node::create_node::<Sha256>(&Node::create_placeholder(), &leaf, 1);
         let _ = s.insert(node_0.hash(), &node_0.as_ref().into());
 
-        let storage_node = StorageNode::new(&s, node_0);
+        let storage_node = StorageNode::<_, _, Sha256>::new(&s, node_0);
         let child = storage_node.left_child().unwrap();
 
         assert!(child.node.is_placeholder());
@@ -771,13 +783,13 @@ mod test_storage_node {
     fn test_node_right_child_returns_placeholder_when_key_is_zero_sum() {
         let mut s = StorageMap::<TestTable>::new();
 
-        let leaf = Node::create_leaf(&sum(b"Goodbye World"), [1u8; 32]);
+        let leaf = Node::create_leaf::<Sha256>(&sum(b"Goodbye World"), [1u8; 32]);
         let _ = s.insert(leaf.hash(), &leaf.as_ref().into());
 
-        let node_0 = Node::create_node(&leaf, &Node::create_placeholder(), 1);
+        let node_0 = Node::create_node::<Sha256>(&leaf, &Node::create_placeholder(), 1);
         let _ = s.insert(node_0.hash(), &node_0.as_ref().into());
 
-        let storage_node = StorageNode::new(&s, node_0);
+        let storage_node = StorageNode::<_, _, Sha256>::new(&s, node_0);
         let child = storage_node.right_child().unwrap();
 
         assert!(child.node.is_placeholder());
@@ -787,8 +799,8 @@ mod test_storage_node {
     fn test_node_left_child_returns_error_when_node_is_leaf() {
         let s = StorageMap::<TestTable>::new();
 
-        let leaf_0 = Node::create_leaf(&sum(b"Hello World"), [1u8; 32]);
-        let storage_node = StorageNode::new(&s, leaf_0);
+        let leaf_0 = Node::create_leaf::<Sha256>(&sum(b"Hello World"), [1u8; 32]);
+        let storage_node = StorageNode::<_, _, Sha256>::new(&s, leaf_0);
         let err = storage_node
             .left_child()
             .expect_err("Expected left_child() to return Error; got OK");
@@ -800,8 +812,8 @@ mod test_storage_node {
     fn test_node_right_child_returns_error_when_node_is_leaf() {
         let s = StorageMap::<TestTable>::new();
 
-        let leaf_0 = Node::create_leaf(&sum(b"Hello World"), [1u8; 32]);
-        let storage_node = StorageNode::new(&s, leaf_0);
+        let leaf_0 = Node::create_leaf::<Sha256>(&sum(b"Hello World"), [1u8; 32]);
+        let storage_node = StorageNode::<_, _, Sha256>::new(&s, leaf_0);
         let err = storage_node
             .right_child()
             .expect_err("Expected right_child() to return Error; got OK");
@@ -813,11 +825,11 @@ mod test_storage_node {
     fn test_node_left_child_returns_error_when_key_is_not_found() {
         let s = StorageMap::<TestTable>::new();
 
-        let leaf_0 = Node::create_leaf(&sum(b"Hello World"), [0u8; 32]);
-        let leaf_1 = Node::create_leaf(&sum(b"Goodbye World"), [1u8; 32]);
-        let node_0 = Node::create_node(&leaf_0, &leaf_1, 1);
+        let leaf_0 = Node::create_leaf::<Sha256>(&sum(b"Hello World"), [0u8; 32]);
+        let leaf_1 = Node::create_leaf::<Sha256>(&sum(b"Goodbye World"), [1u8; 32]);
+        let node_0 = Node::create_node::<Sha256>(&leaf_0, &leaf_1, 1);
 
-        let storage_node = StorageNode::new(&s, node_0);
+        let storage_node = StorageNode::<_, _, Sha256>::new(&s, node_0);
         let err = storage_node
             .left_child()
             .expect_err("Expected left_child() to return Error; got Ok");
@@ -833,11 +845,11 @@ mod test_storage_node {
     fn test_node_right_child_returns_error_when_key_is_not_found() {
         let s = StorageMap::<TestTable>::new();
 
-        let leaf_0 = Node::create_leaf(&sum(b"Hello World"), [1u8; 32]);
-        let leaf_1 = Node::create_leaf(&sum(b"Goodbye World"), [1u8; 32]);
-        let node_0 = Node::create_node(&leaf_0, &leaf_1, 1);
+        let leaf_0 = Node::create_leaf::<Sha256>(&sum(b"Hello World"), [1u8; 32]);
+        let leaf_1 = Node::create_leaf::<Sha256>(&sum(b"Goodbye World"), [1u8; 32]);
+        let node_0 = Node::create_node::<Sha256>(&leaf_0, &leaf_1, 1);
 
-        let storage_node = StorageNode::new(&s, node_0);
+        let storage_node = StorageNode::<_, _, Sha256>::new(&s, node_0);
         let err = storage_node
             .right_child()
             .expect_err("Expected right_child() to return Error; got Ok");
@@ -853,12 +865,12 @@ mod test_storage_node {
     fn test_node_left_child_returns_deserialize_error_when_primitive_is_invalid() {
         let mut s = StorageMap::<TestTable>::new();
 
-        let leaf_0 = Node::create_leaf(&sum(b"Hello World"), [1u8; 32]);
+        let leaf_0 = Node::create_leaf::<Sha256>(&sum(b"Hello World"), [1u8; 32]);
         let _ = s.insert(leaf_0.hash(), &(0xff, 0xff, [0xff; 32], [0xff; 32]));
-        let leaf_1 = Node::create_leaf(&sum(b"Goodbye World"), [1u8; 32]);
-        let node_0 = Node::create_node(&leaf_0, &leaf_1, 1);
+        let leaf_1 = Node::create_leaf::<Sha256>(&sum(b"Goodbye World"), [1u8; 32]);
+        let node_0 = Node::create_node::<Sha256>(&leaf_0, &leaf_1, 1);
 
-        let storage_node = StorageNode::new(&s, node_0);
+        let storage_node = StorageNode::<_, _, Sha256>::new(&s, node_0);
         let err = storage_node
             .left_child()
             .expect_err("Expected left_child() to be Error; got Ok");
@@ -875,12 +887,12 @@ mod test_storage_node {
     fn test_node_right_child_returns_deserialize_error_when_primitive_is_invalid() {
         let mut s = StorageMap::<TestTable>::new();
 
-        let leaf_0 = Node::create_leaf(&sum(b"Hello World"), [1u8; 32]);
-        let leaf_1 = Node::create_leaf(&sum(b"Goodbye World"), [1u8; 32]);
+        let leaf_0 = Node::create_leaf::<Sha256>(&sum(b"Hello World"), [1u8; 32]);
+        let leaf_1 = Node::create_leaf::<Sha256>(&sum(b"Goodbye World"), [1u8; 32]);
         let _ = s.insert(leaf_1.hash(), &(0xff, 0xff, [0xff; 32], [0xff; 32]));
-        let node_0 = Node::create_node(&leaf_0, &leaf_1, 1);
+        let node_0 = Node::create_node::<Sha256>(&leaf_0, &leaf_1, 1);
 
-        let storage_node = StorageNode::new(&s, node_0);
+        let storage_node = StorageNode::<_, _, Sha256>::new(&s, node_0);
         let err = storage_node
             .right_child()
             .expect_err("Expected right_child() to be Error; got Ok");
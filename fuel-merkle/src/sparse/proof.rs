@@ -4,8 +4,8 @@ use crate::{
             Path,
             Side,
         },
-        sum,
         Bytes32,
+        Hasher,
         ProofSet,
     },
     sparse::{
@@ -18,13 +18,30 @@ use crate::{
     },
 };
 
-use alloc::vec::Vec;
+use alloc::{
+    collections::BTreeMap,
+    vec::Vec,
+};
 use core::{
+    cmp::Reverse,
     fmt,
     fmt::Debug,
+    iter,
+    ops::RangeInclusive,
 };
-
-#[derive(Debug, Clone, Eq, PartialEq)]
+use sha2::Sha256;
+
+mod encoding;
+
+#[derive(
+    Debug,
+    Clone,
+    Eq,
+    PartialEq,
+    fuel_types::canonical::Deserialize,
+    fuel_types::canonical::Serialize,
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Proof {
     Inclusion(InclusionProof),
     Exclusion(ExclusionProof),
@@ -51,26 +68,38 @@ impl Proof {
 }
 
 #[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InclusionProof {
+    #[cfg_attr(feature = "serde", serde(with = "encoding::compressed_proof_set"))]
     pub proof_set: ProofSet,
 }
 
 impl InclusionProof {
     pub fn verify(&self, root: &Bytes32, key: &MerkleTreeKey, value: &[u8]) -> bool {
+        self.verify_with_hasher::<Sha256>(root, key, value)
+    }
+
+    /// Verifies the proof against the `root` of a tree hashing its nodes with `H`.
+    pub fn verify_with_hasher<H: Hasher>(
+        &self,
+        root: &Bytes32,
+        key: &MerkleTreeKey,
+        value: &[u8],
+    ) -> bool {
         let Self { proof_set } = self;
 
         if proof_set.len() > 256usize {
             return false;
         }
 
-        let mut current = calculate_leaf_hash(key, &sum(value));
+        let mut current = calculate_leaf_hash::<H>(key, &H::hash(value));
         for (i, side_hash) in proof_set.iter().enumerate() {
             #[allow(clippy::arithmetic_side_effects)] // Cannot underflow
             let index =
                 u32::try_from(proof_set.len() - 1 - i).expect("We've checked it above");
             current = match key.get_instruction(index).expect("Infallible") {
-                Side::Left => calculate_node_hash(&current, side_hash),
-                Side::Right => calculate_node_hash(side_hash, &current),
+                Side::Left => calculate_node_hash::<H>(&current, side_hash),
+                Side::Right => calculate_node_hash::<H>(side_hash, &current),
             };
         }
         current == *root
@@ -86,13 +115,28 @@ impl Debug for InclusionProof {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(
+    Debug,
+    Clone,
+    Eq,
+    PartialEq,
+    fuel_types::canonical::Deserialize,
+    fuel_types::canonical::Serialize,
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ExclusionLeaf {
     Leaf(ExclusionLeafData),
     Placeholder,
 }
 
-#[derive(Clone, Eq, PartialEq)]
+#[derive(
+    Clone,
+    Eq,
+    PartialEq,
+    fuel_types::canonical::Deserialize,
+    fuel_types::canonical::Serialize,
+)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExclusionLeafData {
     /// The leaf key.
     pub leaf_key: Bytes32,
@@ -110,10 +154,10 @@ impl Debug for ExclusionLeafData {
 }
 
 impl ExclusionLeaf {
-    fn hash(&self) -> Bytes32 {
+    fn hash<H: Hasher>(&self) -> Bytes32 {
         match self {
             ExclusionLeaf::Leaf(data) => {
-                calculate_leaf_hash(&data.leaf_key, &data.leaf_value)
+                calculate_leaf_hash::<H>(&data.leaf_key, &data.leaf_value)
             }
             ExclusionLeaf::Placeholder => *zero_sum(),
         }
@@ -121,13 +165,24 @@ impl ExclusionLeaf {
 }
 
 #[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ExclusionProof {
+    #[cfg_attr(feature = "serde", serde(with = "encoding::compressed_proof_set"))]
     pub proof_set: ProofSet,
     pub leaf: ExclusionLeaf,
 }
 
 impl ExclusionProof {
     pub fn verify(&self, root: &Bytes32, key: &MerkleTreeKey) -> bool {
+        self.verify_with_hasher::<Sha256>(root, key)
+    }
+
+    /// Verifies the proof against the `root` of a tree hashing its nodes with `H`.
+    pub fn verify_with_hasher<H: Hasher>(
+        &self,
+        root: &Bytes32,
+        key: &MerkleTreeKey,
+    ) -> bool {
         let Self { proof_set, leaf } = self;
 
         if let ExclusionLeaf::Leaf(data) = leaf {
@@ -140,14 +195,14 @@ impl ExclusionProof {
             return false;
         }
 
-        let mut current = leaf.hash();
+        let mut current = leaf.hash::<H>();
         for (i, side_hash) in proof_set.iter().enumerate() {
             #[allow(clippy::arithmetic_side_effects)] // Cannot underflow
             let index =
                 u32::try_from(proof_set.len() - 1 - i).expect("We've checked it above");
             current = match key.get_instruction(index).expect("Infallible") {
-                Side::Left => calculate_node_hash(&current, side_hash),
-                Side::Right => calculate_node_hash(side_hash, &current),
+                Side::Left => calculate_node_hash::<H>(&current, side_hash),
+                Side::Right => calculate_node_hash::<H>(side_hash, &current),
             };
         }
         current == *root
@@ -164,6 +219,310 @@ impl Debug for ExclusionProof {
     }
 }
 
+/// The proof of one key of a [`MultiProof`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum MultiProofLeaf {
+    /// The key is included in the tree. Its leaf is at `depth`.
+    Inclusion { depth: u32 },
+    /// The key is not included in the tree. The `leaf` at `depth` occupies the
+    /// position of the key, as in an [`ExclusionProof`].
+    Exclusion { depth: u32, leaf: ExclusionLeaf },
+}
+
+impl MultiProofLeaf {
+    /// The depth of the leaf, which is the length of the proof set of the key's
+    /// individual proof.
+    pub fn depth(&self) -> u32 {
+        match self {
+            MultiProofLeaf::Inclusion { depth } => *depth,
+            MultiProofLeaf::Exclusion { depth, .. } => *depth,
+        }
+    }
+}
+
+/// The proof of the inclusion or exclusion of several keys against a single root.
+///
+/// The paths of the keys share their upper side nodes, and the side nodes of a path
+/// may be nodes of another path. The proof set contains each side node that can't be
+/// computed from the leaves only once, in the order the verifier uses them: the
+/// nodes are merged from the deepest up, and at equal depths, from the leftmost
+/// position.
+#[derive(Clone, Eq, PartialEq)]
+pub struct MultiProof {
+    /// The proofs of the keys, in the order of the keys.
+    pub leaves: Vec<MultiProofLeaf>,
+    pub proof_set: ProofSet,
+}
+
+impl MultiProof {
+    /// Verifies the proof of the `keys` against the `root`. The value of each key is
+    /// `Some` for an included key and `None` for an excluded one.
+    pub fn verify<V>(&self, root: &Bytes32, keys: &[(MerkleTreeKey, Option<V>)]) -> bool
+    where
+        V: AsRef<[u8]>,
+    {
+        self.verify_with_hasher::<Sha256, V>(root, keys)
+    }
+
+    /// Verifies the proof of the `keys` against the `root` of a tree hashing its
+    /// nodes with `H`.
+    pub fn verify_with_hasher<H, V>(
+        &self,
+        root: &Bytes32,
+        keys: &[(MerkleTreeKey, Option<V>)],
+    ) -> bool
+    where
+        H: Hasher,
+        V: AsRef<[u8]>,
+    {
+        let Some(hashes) = self.leaf_hashes::<H, V>(keys) else {
+            return false;
+        };
+
+        let mut proof_set = self.proof_set.iter();
+        let computed =
+            multiproof_root::<H, _, _>(hashes, |_, _| proof_set.next().copied());
+
+        computed == Some(*root) && proof_set.next().is_none()
+    }
+
+    /// The `(depth, key, hash)` of the leaves proving the `keys`, as expected by
+    /// [`multiproof_root`]. Returns `None` if the leaves don't match the keys.
+    pub(crate) fn leaf_hashes<H, V>(
+        &self,
+        keys: &[(MerkleTreeKey, Option<V>)],
+    ) -> Option<Vec<(u32, Bytes32, Bytes32)>>
+    where
+        H: Hasher,
+        V: AsRef<[u8]>,
+    {
+        let leaves = &self.leaves;
+
+        if leaves.is_empty() || leaves.len() != keys.len() {
+            return None;
+        }
+
+        let mut hashes = Vec::with_capacity(leaves.len());
+        for (leaf, (key, value)) in leaves.iter().zip(keys) {
+            let hash = match (leaf, value) {
+                (MultiProofLeaf::Inclusion { .. }, Some(value)) => {
+                    calculate_leaf_hash::<H>(key, &H::hash(value))
+                }
+                (MultiProofLeaf::Exclusion { leaf, .. }, None) => {
+                    if let ExclusionLeaf::Leaf(data) = leaf {
+                        if data.leaf_key == key.as_ref() {
+                            return None;
+                        }
+                    }
+                    leaf.hash::<H>()
+                }
+                _ => return None,
+            };
+            hashes.push((leaf.depth(), **key, hash));
+        }
+
+        Some(hashes)
+    }
+}
+
+impl Debug for MultiProof {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let proof_set = self.proof_set.iter().map(hex::encode).collect::<Vec<_>>();
+        f.debug_struct("MultiProof")
+            .field("Leaves", &self.leaves)
+            .field("Proof set", &proof_set)
+            .finish()
+    }
+}
+
+/// The proof that the leaves of a tree with keys in a range are exactly a given set
+/// of leaves.
+///
+/// The proof is the [`MultiProof`] of the first key of the range, the keys of the
+/// leaves in the range and the last key of the range, in this order. The side nodes
+/// of the proof overlapping the range must be placeholders, so that the tree has no
+/// other leaf in the range.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RangeProof {
+    pub proof: MultiProof,
+}
+
+impl RangeProof {
+    /// Verifies that the leaves of the tree with the `root` with keys in
+    /// `start..=end` are exactly the `leaves`, ordered by key.
+    pub fn verify<V>(
+        &self,
+        root: &Bytes32,
+        start: &MerkleTreeKey,
+        end: &MerkleTreeKey,
+        leaves: &[(MerkleTreeKey, V)],
+    ) -> bool
+    where
+        V: AsRef<[u8]>,
+    {
+        self.verify_with_hasher::<Sha256, V>(root, start, end, leaves)
+    }
+
+    /// Verifies the proof of the `leaves` against the `root` of a tree hashing its
+    /// nodes with `H`.
+    pub fn verify_with_hasher<H, V>(
+        &self,
+        root: &Bytes32,
+        start: &MerkleTreeKey,
+        end: &MerkleTreeKey,
+        leaves: &[(MerkleTreeKey, V)],
+    ) -> bool
+    where
+        H: Hasher,
+        V: AsRef<[u8]>,
+    {
+        let range = **start..=**end;
+        if range.is_empty()
+            || !leaves.iter().all(|(key, _)| range.contains(key))
+            || !leaves.windows(2).all(|pair| *pair[0].0 < *pair[1].0)
+        {
+            return false;
+        }
+
+        let value = |key: &Bytes32| {
+            leaves
+                .binary_search_by(|(leaf_key, _)| (**leaf_key).cmp(key))
+                .ok()
+                .map(|index| leaves[index].1.as_ref())
+        };
+        let keys = iter::once((*start, value(start)))
+            .chain(leaves.iter().map(|(key, data)| (*key, Some(data.as_ref()))))
+            .chain(iter::once((*end, value(end))))
+            .collect::<Vec<_>>();
+        let Some(hashes) = self.proof.leaf_hashes::<H, _>(&keys) else {
+            return false;
+        };
+
+        // A leaf excluding a bound of the range may be in the range, and then must
+        // be one of the leaves.
+        let unknown_leaf_in_range = self.proof.leaves.iter().any(|leaf| match leaf {
+            MultiProofLeaf::Exclusion {
+                leaf: ExclusionLeaf::Leaf(data),
+                ..
+            } => range.contains(&data.leaf_key) && value(&data.leaf_key).is_none(),
+            _ => false,
+        });
+        if unknown_leaf_in_range {
+            return false;
+        }
+
+        let mut proof_set = self.proof.proof_set.iter();
+        let computed = multiproof_root::<H, _, _>(hashes, |depth, position| {
+            let side_node = *proof_set.next()?;
+            let side_range = subtree_range(position, depth);
+            let overlaps =
+                side_range.start() <= range.end() && side_range.end() >= range.start();
+            (!overlaps || side_node == *zero_sum()).then_some(side_node)
+        });
+
+        computed == Some(*root) && proof_set.next().is_none()
+    }
+}
+
+/// The maximum depth of a leaf, the number of bits of the key.
+pub(crate) const MAX_DEPTH: u32 = 256;
+
+/// The position of the node at `depth` on the path of `key`: the `depth` first
+/// bits of the key, followed by zeros.
+pub(crate) fn path_position(key: &Bytes32, depth: u32) -> Bytes32 {
+    let mut position = *key;
+    for index in depth..MAX_DEPTH {
+        set_bit(&mut position, index, false);
+    }
+    position
+}
+
+/// The positions of the leaves of the subtree of the node at `depth` on the path of
+/// `key`: the `depth` first bits of the key, followed by any bits.
+pub(crate) fn subtree_range(key: &Bytes32, depth: u32) -> RangeInclusive<Bytes32> {
+    let mut last = *key;
+    for index in depth..MAX_DEPTH {
+        set_bit(&mut last, index, true);
+    }
+    path_position(key, depth)..=last
+}
+
+/// The position of the sibling of the node at `depth` on the path of `key`.
+pub(crate) fn side_position(key: &Bytes32, depth: u32) -> Option<Bytes32> {
+    let parent_depth = depth.checked_sub(1)?;
+    let mut position = path_position(key, depth);
+    let side = matches!(key.get_instruction(parent_depth)?, Side::Left);
+    set_bit(&mut position, parent_depth, side);
+    Some(position)
+}
+
+fn set_bit(bytes: &mut Bytes32, index: u32, bit: bool) {
+    let byte_index = (index / 8) as usize;
+    #[allow(clippy::arithmetic_side_effects)] // index % 8 < 8
+    let mask = 0x80u8 >> (index % 8);
+    if let Some(byte) = bytes.get_mut(byte_index) {
+        if bit {
+            *byte |= mask;
+        } else {
+            *byte &= !mask;
+        }
+    }
+}
+
+/// Computes the root from the `(depth, key, hash)` of the leaves of a multiproof.
+/// `side_node` provides the hash of the side node at a depth and position that
+/// can't be computed from the leaves. Returns `None` if the leaves are inconsistent
+/// or a side node is missing.
+pub(crate) fn multiproof_root<H, I, F>(leaves: I, mut side_node: F) -> Option<Bytes32>
+where
+    H: Hasher,
+    I: IntoIterator<Item = (u32, Bytes32, Bytes32)>,
+    F: FnMut(u32, &Bytes32) -> Option<Bytes32>,
+{
+    // The known nodes by depth, deepest first, and position.
+    let mut nodes = BTreeMap::<(Reverse<u32>, Bytes32), Bytes32>::new();
+    for (depth, key, hash) in leaves {
+        if depth > MAX_DEPTH {
+            return None;
+        }
+        let position = (Reverse(depth), path_position(&key, depth));
+        // Several keys may share a leaf, e.g. two keys excluded by the same leaf.
+        if nodes
+            .insert(position, hash)
+            .is_some_and(|known| known != hash)
+        {
+            return None;
+        }
+    }
+
+    loop {
+        let (&(Reverse(depth), position), &hash) = nodes.iter().next()?;
+        let Some(parent_depth) = depth.checked_sub(1) else {
+            return (nodes.len() == 1).then_some(hash);
+        };
+        nodes.remove(&(Reverse(depth), position));
+
+        let side = side_position(&position, depth)?;
+        let side_hash = match nodes.remove(&(Reverse(depth), side)) {
+            Some(side_hash) => side_hash,
+            None => side_node(depth, &side)?,
+        };
+        let parent = match position.get_instruction(parent_depth)? {
+            Side::Left => calculate_node_hash::<H>(&hash, &side_hash),
+            Side::Right => calculate_node_hash::<H>(&side_hash, &hash),
+        };
+
+        // A leaf can't be the ancestor of another leaf.
+        let parent_position = (
+            Reverse(parent_depth),
+            path_position(&position, parent_depth),
+        );
+        if nodes.insert(parent_position, parent).is_some() {
+            return None;
+        }
+    }
+}
+
 #[cfg(test)]
 #[allow(non_snake_case)]
 mod test {
@@ -798,3 +1157,421 @@ mod test_random {
         assert!(exclusion);
     }
 }
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod test_multiproof {
+    use crate::{
+        common::{
+            Bytes32,
+            StorageMap,
+        },
+        sparse::{
+            proof::{
+                MultiProofLeaf,
+                Proof,
+            },
+            MerkleTree,
+            MerkleTreeKey,
+            Primitive,
+        },
+    };
+    use alloc::vec::Vec;
+    use fuel_storage::Mappable;
+
+    use rand::{
+        prelude::StdRng,
+        SeedableRng,
+    };
+
+    #[derive(Debug)]
+    struct TestTable;
+
+    impl Mappable for TestTable {
+        type Key = Self::OwnedKey;
+        type OwnedKey = Bytes32;
+        type OwnedValue = Primitive;
+        type Value = Self::OwnedValue;
+    }
+
+    type Entries = Vec<(MerkleTreeKey, Option<Bytes32>)>;
+
+    fn random_bytes32<R>(rng: &mut R) -> Bytes32
+    where
+        R: rand::Rng + ?Sized,
+    {
+        let mut bytes = [0u8; 32];
+        rng.fill(bytes.as_mut());
+        bytes
+    }
+
+    /// Returns a tree with `count` random leaves, and 5 of its keys with their values
+    /// followed by 5 excluded keys.
+    fn tree_and_entries(
+        storage: &mut StorageMap<TestTable>,
+        count: usize,
+    ) -> (MerkleTree<TestTable, &mut StorageMap<TestTable>>, Entries) {
+        let mut rng = StdRng::seed_from_u64(0xDEADBEEF);
+        let mut tree = MerkleTree::new(storage);
+        let mut entries = Entries::new();
+
+        for i in 0..count {
+            let key = random_bytes32(&mut rng).into();
+            let value = random_bytes32(&mut rng);
+            tree.update(key, &value).unwrap();
+            #[allow(clippy::arithmetic_side_effects)] // The divisor is at least 1
+            let sampled = i % (count / 5).max(1) == 0;
+            if sampled && entries.len() < 5 {
+                entries.push((key, Some(value)));
+            }
+        }
+        for _ in 0..5 {
+            entries.push((random_bytes32(&mut rng).into(), None));
+        }
+
+        (tree, entries)
+    }
+
+    fn keys(entries: &Entries) -> Vec<MerkleTreeKey> {
+        entries.iter().map(|(key, _)| *key).collect()
+    }
+
+    #[test]
+    fn multiproof__verify__returns_true_for_included_and_excluded_keys() {
+        let mut storage = StorageMap::<TestTable>::new();
+        let (tree, entries) = tree_and_entries(&mut storage, 1_000);
+        let root = tree.root();
+
+        // Given
+        let proof = tree.generate_multiproof(&keys(&entries)).unwrap();
+
+        // When
+        let verification = proof.verify(&root, &entries);
+
+        // Then
+        assert!(verification);
+        let (included, excluded) = proof.leaves.split_at(5);
+        assert!(included
+            .iter()
+            .all(|leaf| matches!(leaf, MultiProofLeaf::Inclusion { .. })));
+        assert!(excluded
+            .iter()
+            .all(|leaf| matches!(leaf, MultiProofLeaf::Exclusion { .. })));
+    }
+
+    #[test]
+    fn multiproof__proof_set__is_smaller_than_the_individual_proof_sets() {
+        let mut storage = StorageMap::<TestTable>::new();
+        let (tree, entries) = tree_and_entries(&mut storage, 1_000);
+
+        // Given
+        let individual: usize = entries
+            .iter()
+            .map(|(key, _)| tree.generate_proof(key).unwrap().proof_set().len())
+            .sum();
+
+        // When
+        let proof = tree.generate_multiproof(&keys(&entries)).unwrap();
+
+        // Then
+        assert!(proof.proof_set.len() < individual);
+    }
+
+    #[test]
+    fn multiproof__proof_set__of_one_key_is_the_individual_proof_set() {
+        let mut storage = StorageMap::<TestTable>::new();
+        let (tree, entries) = tree_and_entries(&mut storage, 100);
+
+        for (key, _) in entries {
+            // Given
+            let proof = tree.generate_proof(&key).unwrap();
+
+            // When
+            let multiproof = tree.generate_multiproof(&[key]).unwrap();
+
+            // Then
+            assert_eq!(&multiproof.proof_set, proof.proof_set());
+            assert_eq!(
+                multiproof.leaves[0].depth() as usize,
+                proof.proof_set().len()
+            );
+            assert_eq!(
+                matches!(multiproof.leaves[0], MultiProofLeaf::Inclusion { .. }),
+                matches!(proof, Proof::Inclusion(_))
+            );
+        }
+    }
+
+    #[test]
+    fn multiproof__verify__returns_true_for_repeated_keys() {
+        let mut storage = StorageMap::<TestTable>::new();
+        let (tree, mut entries) = tree_and_entries(&mut storage, 100);
+        let root = tree.root();
+        entries.extend(entries.clone());
+
+        // Given
+        let proof = tree.generate_multiproof(&keys(&entries)).unwrap();
+
+        // When
+        let verification = proof.verify(&root, &entries);
+
+        // Then
+        assert!(verification);
+    }
+
+    #[test]
+    fn multiproof__verify__returns_true_for_small_trees() {
+        for count in 0..4 {
+            let mut storage = StorageMap::<TestTable>::new();
+            let (tree, entries) = tree_and_entries(&mut storage, count);
+            let root = tree.root();
+
+            // Given
+            let proof = tree.generate_multiproof(&keys(&entries)).unwrap();
+
+            // When
+            let verification = proof.verify(&root, &entries);
+
+            // Then
+            assert!(verification, "{count} leaves");
+        }
+    }
+
+    #[test]
+    fn multiproof__verify__returns_false_for_incorrect_value() {
+        let mut storage = StorageMap::<TestTable>::new();
+        let (tree, mut entries) = tree_and_entries(&mut storage, 1_000);
+        let root = tree.root();
+        let proof = tree.generate_multiproof(&keys(&entries)).unwrap();
+
+        // Given
+        entries[2].1 = Some([0xff; 32]);
+
+        // When
+        let verification = proof.verify(&root, &entries);
+
+        // Then
+        assert!(!verification);
+    }
+
+    #[test]
+    fn multiproof__verify__returns_false_for_excluded_key_with_a_value() {
+        let mut storage = StorageMap::<TestTable>::new();
+        let (tree, mut entries) = tree_and_entries(&mut storage, 1_000);
+        let root = tree.root();
+        let proof = tree.generate_multiproof(&keys(&entries)).unwrap();
+
+        // Given
+        entries[7].1 = Some([0xff; 32]);
+
+        // When
+        let verification = proof.verify(&root, &entries);
+
+        // Then
+        assert!(!verification);
+    }
+
+    #[test]
+    fn multiproof__verify__returns_false_for_incorrect_key() {
+        let mut storage = StorageMap::<TestTable>::new();
+        let (tree, mut entries) = tree_and_entries(&mut storage, 1_000);
+        let root = tree.root();
+        let proof = tree.generate_multiproof(&keys(&entries)).unwrap();
+
+        // Given
+        entries.swap(5, 6);
+        entries.swap(0, 1);
+
+        // When
+        let verification = proof.verify(&root, &entries);
+
+        // Then
+        assert!(!verification);
+    }
+
+    #[test]
+    fn multiproof__verify__returns_false_for_incorrect_root() {
+        let mut storage = StorageMap::<TestTable>::new();
+        let (tree, entries) = tree_and_entries(&mut storage, 1_000);
+        let proof = tree.generate_multiproof(&keys(&entries)).unwrap();
+
+        // Given
+        let root = [0xff; 32];
+
+        // When
+        let verification = proof.verify(&root, &entries);
+
+        // Then
+        assert!(!verification);
+    }
+
+    #[test]
+    fn multiproof__verify__returns_false_for_missing_or_extra_side_nodes() {
+        let mut storage = StorageMap::<TestTable>::new();
+        let (tree, entries) = tree_and_entries(&mut storage, 1_000);
+        let root = tree.root();
+        let proof = tree.generate_multiproof(&keys(&entries)).unwrap();
+
+        // Given
+        let mut missing = proof.clone();
+        missing.proof_set.pop();
+        let mut extra = proof.clone();
+        extra.proof_set.push([0; 32]);
+
+        // When
+        let verifications = [
+            missing.verify(&root, &entries),
+            extra.verify(&root, &entries),
+        ];
+
+        // Then
+        assert_eq!(verifications, [false, false]);
+    }
+
+    #[test]
+    fn multiproof__verify__returns_false_for_missing_keys() {
+        let mut storage = StorageMap::<TestTable>::new();
+        let (tree, entries) = tree_and_entries(&mut storage, 1_000);
+        let root = tree.root();
+        let proof = tree.generate_multiproof(&keys(&entries)).unwrap();
+
+        // When
+        let verification = proof.verify(&root, &entries[..9]);
+
+        // Then
+        assert!(!verification);
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod test_range_proof {
+    use crate::{
+        common::{
+            Bytes32,
+            StorageMap,
+        },
+        sparse::{
+            proof::RangeProof,
+            MerkleTree,
+            MerkleTreeKey,
+            Primitive,
+        },
+    };
+    use fuel_storage::Mappable;
+
+    #[derive(Debug)]
+    struct TestTable;
+
+    impl Mappable for TestTable {
+        type Key = Self::OwnedKey;
+        type OwnedKey = Bytes32;
+        type OwnedValue = Primitive;
+        type Value = Self::OwnedValue;
+    }
+
+    fn key(byte: u8) -> MerkleTreeKey {
+        MerkleTreeKey::new_without_hash([byte; 32])
+    }
+
+    // 1:       N0
+    //         /  \
+    // 2:     N1   \
+    //       /  \   \
+    //      L0  L1  L2
+    //      00  40  ff
+    fn tree(
+        storage: &mut StorageMap<TestTable>,
+    ) -> MerkleTree<TestTable, &mut StorageMap<TestTable>> {
+        let mut tree = MerkleTree::new(storage);
+        tree.update(key(0x00), b"DATA_0").unwrap();
+        tree.update(key(0x40), b"DATA_1").unwrap();
+        tree.update(key(0xff), b"DATA_2").unwrap();
+        tree
+    }
+
+    #[test]
+    fn range_proof__verify__returns_true_for_leaves_in_range() {
+        let mut storage = StorageMap::<TestTable>::new();
+        let tree = tree(&mut storage);
+        let root = tree.root();
+
+        // Given
+        let (start, end) = (key(0x01), key(0x80));
+        let proof = tree.generate_range_proof(&start, &end).unwrap();
+
+        // When
+        let verification = proof.verify(&root, &start, &end, &[(key(0x40), b"DATA_1")]);
+
+        // Then
+        assert!(verification);
+    }
+
+    #[test]
+    fn range_proof__verify__returns_false_for_side_node_in_range() {
+        let mut storage = StorageMap::<TestTable>::new();
+        let tree = tree(&mut storage);
+        let root = tree.root();
+
+        // Given
+        let (start, end) = (key(0x01), key(0x80));
+        let proof = RangeProof {
+            // Hides L1 in the side node of the path of `start`
+            proof: tree.generate_multiproof(&[start, end]).unwrap(),
+        };
+
+        // When
+        let verification = proof.verify::<&[u8]>(&root, &start, &end, &[]);
+
+        // Then
+        assert!(!verification);
+    }
+
+    #[test]
+    fn range_proof__verify__returns_false_for_leaf_in_range_excluding_bound() {
+        let mut storage = StorageMap::<TestTable>::new();
+        let tree = tree(&mut storage);
+        let root = tree.root();
+
+        // Given
+        let (start, end) = (key(0x30), key(0x50));
+        let proof = RangeProof {
+            // L1 excludes both `start` and `end`
+            proof: tree.generate_multiproof(&[start, end]).unwrap(),
+        };
+
+        // When
+        let verification = proof.verify::<&[u8]>(&root, &start, &end, &[]);
+
+        // Then
+        assert!(!verification);
+    }
+
+    #[test]
+    fn range_proof__verify__returns_false_for_leaves_out_of_order_or_range() {
+        let mut storage = StorageMap::<TestTable>::new();
+        let tree = tree(&mut storage);
+        let root = tree.root();
+        let (start, end) = (key(0x00), key(0xff));
+        let proof = tree.generate_range_proof(&start, &end).unwrap();
+
+        // Given
+        let ordered = [
+            (key(0x00), b"DATA_0"),
+            (key(0x40), b"DATA_1"),
+            (key(0xff), b"DATA_2"),
+        ];
+        let unordered = [ordered[1], ordered[0], ordered[2]];
+
+        // When
+        let verifications = [
+            proof.verify(&root, &start, &end, &ordered),
+            proof.verify(&root, &start, &end, &unordered),
+            proof.verify(&root, &start, &key(0xfe), &ordered),
+            proof.verify(&root, &end, &start, &ordered),
+        ];
+
+        // Then
+        assert_eq!(verifications, [true, false, false, false]);
+    }
+}
@@ -0,0 +1,474 @@
+//! The canonical and serde encodings of the sparse Merkle tree proofs.
+//!
+//! The proof sets are compressed: the empty side nodes are omitted and recorded in
+//! a bitmap instead. The layout is specified in
+//! `docs/specs/sparse_merkle_tree_proof_encoding.md`.
+
+use crate::{
+    common::{
+        Bytes32,
+        ProofSet,
+    },
+    sparse::{
+        proof::{
+            ExclusionProof,
+            InclusionProof,
+        },
+        zero_sum,
+    },
+};
+
+use alloc::vec::Vec;
+use fuel_types::canonical::{
+    Deserialize,
+    Error,
+    Input,
+    Output,
+    Serialize,
+};
+
+/// The maximal number of side nodes of a proof set, the height of the tree.
+const MAX_SIDE_NODES: usize = 256;
+
+/// The size of the length and the bitmap of the compressed proof set.
+const HEADER_SIZE: usize = 40;
+
+/// The size of a side node.
+const SIDE_NODE_SIZE: usize = 32;
+
+/// A proof set without its empty side nodes, which are recorded in the `bitmap`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+struct CompressedProofSet {
+    /// The number of side nodes of the proof set, including the empty ones.
+    len: u64,
+    /// The bit `i` of the bitmap, counting from the most significant bit of its
+    /// first byte, is set if the side node `i` of the proof set is empty.
+    bitmap: Bytes32,
+    /// The side nodes that are not empty, in the order of the proof set.
+    side_nodes: Vec<Bytes32>,
+}
+
+impl CompressedProofSet {
+    fn compress(proof_set: &ProofSet) -> Result<Self, Error> {
+        if proof_set.len() > MAX_SIDE_NODES {
+            return Err(Error::Unknown("the proof set has more than 256 side nodes"))
+        }
+
+        let mut bitmap = Bytes32::default();
+        let mut side_nodes = Vec::new();
+        for (i, side_node) in proof_set.iter().enumerate() {
+            if side_node == zero_sum() {
+                bitmap[i / 8] |= 0x80 >> (i % 8);
+            } else {
+                side_nodes.push(*side_node);
+            }
+        }
+
+        Ok(Self {
+            len: proof_set.len() as u64,
+            bitmap,
+            side_nodes,
+        })
+    }
+
+    fn decompress(self) -> Result<ProofSet, Error> {
+        let (len, count) = side_nodes_count(self.len, &self.bitmap)?;
+        if self.side_nodes.len() != count {
+            return Err(Error::Unknown(
+                "the number of side nodes doesn't match the bitmap",
+            ))
+        }
+        if self
+            .side_nodes
+            .iter()
+            .any(|side_node| side_node == zero_sum())
+        {
+            return Err(Error::Unknown(
+                "an empty side node is not recorded in the bitmap",
+            ))
+        }
+
+        let mut side_nodes = self.side_nodes.into_iter();
+        let proof_set = (0..len)
+            .map(|i| {
+                if is_empty_side_node(&self.bitmap, i) {
+                    *zero_sum()
+                } else {
+                    side_nodes.next().expect("The count is checked above")
+                }
+            })
+            .collect();
+        Ok(proof_set)
+    }
+}
+
+/// Returns `true` if the bit of the side node `i` is set in the `bitmap`.
+fn is_empty_side_node(bitmap: &Bytes32, i: usize) -> bool {
+    bitmap[i / 8] & (0x80 >> (i % 8)) != 0
+}
+
+/// Validates the `len` and the `bitmap` of a compressed proof set, returning the
+/// number of side nodes and the number of the side nodes that are not empty.
+fn side_nodes_count(len: u64, bitmap: &Bytes32) -> Result<(usize, usize), Error> {
+    let len = usize::try_from(len)
+        .ok()
+        .filter(|len| *len <= MAX_SIDE_NODES)
+        .ok_or(Error::Unknown("the proof set has more than 256 side nodes"))?;
+    if (len..MAX_SIDE_NODES).any(|i| is_empty_side_node(bitmap, i)) {
+        return Err(Error::Unknown(
+            "the bitmap records side nodes past the end of the proof set",
+        ))
+    }
+
+    let empty_count = (0..len).filter(|i| is_empty_side_node(bitmap, *i)).count();
+    #[allow(clippy::arithmetic_side_effects)] // empty_count <= len
+    Ok((len, len - empty_count))
+}
+
+/// The size of the compressed `proof_set`.
+fn compressed_size(proof_set: &ProofSet) -> usize {
+    let count = proof_set
+        .iter()
+        .filter(|side_node| *side_node != zero_sum())
+        .count();
+    HEADER_SIZE.saturating_add(count.saturating_mul(SIDE_NODE_SIZE))
+}
+
+impl Serialize for CompressedProofSet {
+    fn size_static(&self) -> usize {
+        HEADER_SIZE
+    }
+
+    fn size_dynamic(&self) -> usize {
+        self.side_nodes.len().saturating_mul(SIDE_NODE_SIZE)
+    }
+
+    fn encode_static<O: Output + ?Sized>(&self, buffer: &mut O) -> Result<(), Error> {
+        self.len.encode(buffer)?;
+        self.bitmap.encode(buffer)
+    }
+
+    fn encode_dynamic<O: Output + ?Sized>(&self, buffer: &mut O) -> Result<(), Error> {
+        for side_node in &self.side_nodes {
+            side_node.encode(buffer)?;
+        }
+        Ok(())
+    }
+}
+
+impl Deserialize for CompressedProofSet {
+    fn decode_static<I: Input + ?Sized>(buffer: &mut I) -> Result<Self, Error> {
+        let len = u64::decode(buffer)?;
+        let bitmap = Bytes32::decode(buffer)?;
+        Ok(Self {
+            len,
+            bitmap,
+            side_nodes: Vec::new(),
+        })
+    }
+
+    fn decode_dynamic<I: Input + ?Sized>(&mut self, buffer: &mut I) -> Result<(), Error> {
+        let (_, count) = side_nodes_count(self.len, &self.bitmap)?;
+        self.side_nodes = (0..count)
+            .map(|_| Bytes32::decode(buffer))
+            .collect::<Result<_, _>>()?;
+        Ok(())
+    }
+}
+
+// The proof sets are variable-sized, so they are encoded in the dynamic part.
+
+impl Serialize for InclusionProof {
+    fn size_static(&self) -> usize {
+        0
+    }
+
+    fn size_dynamic(&self) -> usize {
+        compressed_size(&self.proof_set)
+    }
+
+    fn encode_static<O: Output + ?Sized>(&self, _buffer: &mut O) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn encode_dynamic<O: Output + ?Sized>(&self, buffer: &mut O) -> Result<(), Error> {
+        CompressedProofSet::compress(&self.proof_set)?.encode(buffer)
+    }
+}
+
+impl Deserialize for InclusionProof {
+    fn decode_static<I: Input + ?Sized>(_buffer: &mut I) -> Result<Self, Error> {
+        Ok(Self {
+            proof_set: ProofSet::new(),
+        })
+    }
+
+    fn decode_dynamic<I: Input + ?Sized>(&mut self, buffer: &mut I) -> Result<(), Error> {
+        self.proof_set = CompressedProofSet::decode(buffer)?.decompress()?;
+        Ok(())
+    }
+}
+
+impl Serialize for ExclusionProof {
+    fn size_static(&self) -> usize {
+        self.leaf.size_static()
+    }
+
+    fn size_dynamic(&self) -> usize {
+        self.leaf
+            .size_dynamic()
+            .saturating_add(compressed_size(&self.proof_set))
+    }
+
+    fn encode_static<O: Output + ?Sized>(&self, buffer: &mut O) -> Result<(), Error> {
+        self.leaf.encode_static(buffer)
+    }
+
+    fn encode_dynamic<O: Output + ?Sized>(&self, buffer: &mut O) -> Result<(), Error> {
+        self.leaf.encode_dynamic(buffer)?;
+        CompressedProofSet::compress(&self.proof_set)?.encode(buffer)
+    }
+}
+
+impl Deserialize for ExclusionProof {
+    fn decode_static<I: Input + ?Sized>(buffer: &mut I) -> Result<Self, Error> {
+        Ok(Self {
+            proof_set: ProofSet::new(),
+            leaf: Deserialize::decode_static(buffer)?,
+        })
+    }
+
+    fn decode_dynamic<I: Input + ?Sized>(&mut self, buffer: &mut I) -> Result<(), Error> {
+        self.leaf.decode_dynamic(buffer)?;
+        self.proof_set = CompressedProofSet::decode(buffer)?.decompress()?;
+        Ok(())
+    }
+}
+
+/// Serializes a proof set with serde as a [`CompressedProofSet`].
+#[cfg(feature = "serde")]
+pub(super) mod compressed_proof_set {
+    use super::CompressedProofSet;
+    use crate::common::ProofSet;
+    use serde::{
+        de,
+        ser,
+        Deserialize,
+        Deserializer,
+        Serialize,
+        Serializer,
+    };
+
+    pub fn serialize<S: Serializer>(
+        proof_set: &ProofSet,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        CompressedProofSet::compress(proof_set)
+            .map_err(ser::Error::custom)?
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<ProofSet, D::Error> {
+        CompressedProofSet::deserialize(deserializer)?
+            .decompress()
+            .map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod test {
+    use crate::{
+        common::{
+            Bytes32,
+            StorageMap,
+        },
+        sparse::{
+            proof::{
+                ExclusionLeaf,
+                ExclusionLeafData,
+                ExclusionProof,
+                InclusionProof,
+                Proof,
+            },
+            zero_sum,
+            MerkleTree,
+            MerkleTreeKey,
+            Primitive,
+        },
+    };
+    use alloc::vec::Vec;
+    use fuel_storage::Mappable;
+    use fuel_types::canonical::{
+        Deserialize,
+        Error,
+        Serialize,
+    };
+
+    #[derive(Debug)]
+    struct TestTable;
+
+    impl Mappable for TestTable {
+        type Key = Self::OwnedKey;
+        type OwnedKey = Bytes32;
+        type OwnedValue = Primitive;
+        type Value = Self::OwnedValue;
+    }
+
+    const SIDE_NODE_0: Bytes32 = [0x11; 32];
+    const SIDE_NODE_2: Bytes32 = [0x22; 32];
+
+    /// The proof set of the test vectors of the specification.
+    fn proof_set() -> Vec<Bytes32> {
+        vec![SIDE_NODE_0, *zero_sum(), SIDE_NODE_2]
+    }
+
+    /// The compressed proof set of the test vectors of the specification.
+    fn encoded_proof_set() -> Vec<u8> {
+        let mut bitmap = [0u8; 32];
+        bitmap[0] = 0b0100_0000;
+        [&3u64.to_be_bytes()[..], &bitmap, &SIDE_NODE_0, &SIDE_NODE_2].concat()
+    }
+
+    #[test]
+    fn proof__to_bytes__returns_the_compressed_inclusion_proof() {
+        // Given
+        let proof = Proof::Inclusion(InclusionProof {
+            proof_set: proof_set(),
+        });
+
+        // When
+        let bytes = proof.to_bytes();
+
+        // Then
+        let expected_bytes = [&0u64.to_be_bytes()[..], &encoded_proof_set()].concat();
+        assert_eq!(bytes, expected_bytes);
+        assert_eq!(proof.size(), expected_bytes.len());
+    }
+
+    #[test]
+    fn proof__to_bytes__returns_the_compressed_exclusion_proof() {
+        // Given
+        let leaf_key = [0x33; 32];
+        let leaf_value = [0x44; 32];
+        let proof = Proof::Exclusion(ExclusionProof {
+            proof_set: proof_set(),
+            leaf: ExclusionLeaf::Leaf(ExclusionLeafData {
+                leaf_key,
+                leaf_value,
+            }),
+        });
+        let placeholder_proof = Proof::Exclusion(ExclusionProof {
+            proof_set: proof_set(),
+            leaf: ExclusionLeaf::Placeholder,
+        });
+
+        // When
+        let bytes = proof.to_bytes();
+        let placeholder_bytes = placeholder_proof.to_bytes();
+
+        // Then
+        let expected_bytes = [
+            &1u64.to_be_bytes()[..],
+            &0u64.to_be_bytes(),
+            &leaf_key,
+            &leaf_value,
+            &encoded_proof_set(),
+        ]
+        .concat();
+        let expected_placeholder_bytes = [
+            &1u64.to_be_bytes()[..],
+            &1u64.to_be_bytes(),
+            &encoded_proof_set(),
+        ]
+        .concat();
+        assert_eq!(bytes, expected_bytes);
+        assert_eq!(placeholder_bytes, expected_placeholder_bytes);
+        assert_eq!(proof.size(), expected_bytes.len());
+        assert_eq!(placeholder_proof.size(), expected_placeholder_bytes.len());
+    }
+
+    #[test]
+    fn proof__from_bytes__returns_the_proofs_generated_by_the_tree() {
+        // Given
+        let mut storage = StorageMap::<TestTable>::new();
+        let mut tree = MerkleTree::new(&mut storage);
+        for i in 0_u32..100 {
+            tree.update(MerkleTreeKey::new(i.to_be_bytes()), b"DATA")
+                .unwrap();
+        }
+
+        for i in 0_u32..200 {
+            let proof = tree
+                .generate_proof(&MerkleTreeKey::new(i.to_be_bytes()))
+                .unwrap();
+
+            // When
+            let decoded = Proof::from_bytes(&proof.to_bytes());
+
+            // Then
+            assert_eq!(decoded, Ok(proof));
+        }
+    }
+
+    #[test]
+    fn inclusion_proof__from_bytes__returns_error_for_invalid_bitmap() {
+        // Given
+        let mut bitmap_past_the_end = encoded_proof_set();
+        bitmap_past_the_end[8] = 0b0101_0000;
+        let mut too_many_side_nodes = encoded_proof_set();
+        too_many_side_nodes[..8].copy_from_slice(&257u64.to_be_bytes());
+        let mut unrecorded_empty_side_node = encoded_proof_set();
+        unrecorded_empty_side_node[8] = 0;
+        unrecorded_empty_side_node.extend_from_slice(&[0; 32]);
+        unrecorded_empty_side_node[40..].rotate_right(32);
+
+        // When
+        let results = [
+            bitmap_past_the_end,
+            too_many_side_nodes,
+            unrecorded_empty_side_node,
+        ]
+        .map(|bytes| InclusionProof::from_bytes(&bytes));
+
+        // Then
+        for result in results {
+            assert!(matches!(result, Err(Error::Unknown(_))), "{result:?}");
+        }
+    }
+
+    #[test]
+    fn inclusion_proof__from_bytes__returns_error_for_missing_side_nodes() {
+        // Given
+        let bytes = encoded_proof_set();
+
+        // When
+        let result = InclusionProof::from_bytes(&bytes[..bytes.len() - 32]);
+
+        // Then
+        assert_eq!(result, Err(Error::BufferIsTooShort));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn proof__serde__round_trips_the_compressed_proof() {
+        // Given
+        let proof = Proof::Exclusion(ExclusionProof {
+            proof_set: proof_set(),
+            leaf: ExclusionLeaf::Placeholder,
+        });
+
+        // When
+        let json = serde_json::to_value(&proof).unwrap();
+        let decoded: Proof = serde_json::from_value(json.clone()).unwrap();
+
+        // Then
+        let proof_set = &json["Exclusion"]["proof_set"];
+        assert_eq!(proof_set["len"], 3);
+        assert_eq!(proof_set["side_nodes"].as_array().unwrap().len(), 2);
+        assert_eq!(decoded, proof);
+    }
+}
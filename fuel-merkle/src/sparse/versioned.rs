@@ -0,0 +1,972 @@
+use crate::{
+    common::{
+        error::DeserializeError,
+        Bytes32,
+        Hasher,
+        Prefix,
+    },
+    sparse::{
+        empty_sum,
+        primitive::PrimitiveView,
+        proof::{
+            MultiProof,
+            Proof,
+        },
+        MerkleTree,
+        MerkleTreeError,
+        MerkleTreeKey,
+        Primitive,
+    },
+    storage::{
+        Mappable,
+        StorageInspect,
+        StorageMutate,
+    },
+};
+
+use alloc::{
+    borrow::Cow,
+    collections::BTreeSet,
+    vec::Vec,
+};
+use core::{
+    marker::PhantomData,
+    mem,
+};
+use sha2::Sha256;
+
+/// The version of a [`VersionedMerkleTree`], for example a block height.
+pub type Version = u64;
+
+/// The root of a version of a [`VersionedMerkleTree`], the version it was committed
+/// on top of and the version committed on top of it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VersionRecord {
+    pub root: Bytes32,
+    pub previous: Option<Version>,
+    pub next: Option<Version>,
+}
+
+#[derive(Debug, Clone, derive_more::Display)]
+pub enum VersionedMerkleTreeError<StorageError> {
+    #[display(fmt = "version {} is not found", _0)]
+    VersionNotFound(Version),
+
+    #[display(fmt = "version {} is not greater than the current version {}", _0, _1)]
+    VersionNotIncreasing(Version, Version),
+
+    #[display(fmt = "version {} already exists", _0)]
+    VersionExists(Version),
+
+    #[display(fmt = "version {} is not the latest version", _0)]
+    VersionNotLatest(Version),
+
+    #[display(fmt = "the tree has uncommitted changes")]
+    UncommittedChanges,
+
+    #[display(fmt = "{}", _0)]
+    MerkleTreeError(MerkleTreeError<StorageError>),
+}
+
+impl<StorageError> From<MerkleTreeError<StorageError>>
+    for VersionedMerkleTreeError<StorageError>
+{
+    fn from(err: MerkleTreeError<StorageError>) -> Self {
+        VersionedMerkleTreeError::MerkleTreeError(err)
+    }
+}
+
+impl<StorageError> From<StorageError> for VersionedMerkleTreeError<StorageError> {
+    fn from(err: StorageError) -> Self {
+        VersionedMerkleTreeError::MerkleTreeError(MerkleTreeError::StorageError(err))
+    }
+}
+
+/// The storage of the nodes of a [`VersionedMerkleTree`]. The nodes inserted since the
+/// last commit are recorded, and nodes are never removed by the updates of the tree,
+/// since older versions may reference them.
+#[derive(Debug)]
+pub struct VersionedNodes<StorageType> {
+    storage: StorageType,
+    pending: BTreeSet<Bytes32>,
+}
+
+impl<StorageType> VersionedNodes<StorageType> {
+    fn new(storage: StorageType) -> Self {
+        Self {
+            storage,
+            pending: BTreeSet::new(),
+        }
+    }
+}
+
+impl<TableType, StorageType> StorageInspect<TableType> for VersionedNodes<StorageType>
+where
+    TableType: Mappable,
+    StorageType: StorageInspect<TableType>,
+{
+    type Error = StorageType::Error;
+
+    fn get(
+        &self,
+        key: &TableType::Key,
+    ) -> Result<Option<Cow<'_, TableType::OwnedValue>>, Self::Error> {
+        self.storage.get(key)
+    }
+
+    fn contains_key(&self, key: &TableType::Key) -> Result<bool, Self::Error> {
+        self.storage.contains_key(key)
+    }
+
+    fn get_many(
+        &self,
+        keys: &[&TableType::Key],
+    ) -> Result<Vec<Option<Cow<'_, TableType::OwnedValue>>>, Self::Error> {
+        self.storage.get_many(keys)
+    }
+}
+
+impl<TableType, StorageType> StorageMutate<TableType> for VersionedNodes<StorageType>
+where
+    TableType: Mappable<Key = Bytes32>,
+    StorageType: StorageMutate<TableType>,
+{
+    fn replace(
+        &mut self,
+        key: &TableType::Key,
+        value: &TableType::Value,
+    ) -> Result<Option<TableType::OwnedValue>, Self::Error> {
+        let previous = self.storage.replace(key, value)?;
+        if previous.is_none() {
+            self.pending.insert(*key);
+        }
+        Ok(previous)
+    }
+
+    fn take(
+        &mut self,
+        _key: &TableType::Key,
+    ) -> Result<Option<TableType::OwnedValue>, Self::Error> {
+        // The nodes are removed when no version references them anymore.
+        Ok(None)
+    }
+
+    fn apply_batch(
+        &mut self,
+        changes: &[(&TableType::Key, Option<&TableType::Value>)],
+    ) -> Result<(), Self::Error> {
+        // The removals are dropped, like in `take`.
+        let insertions = changes
+            .iter()
+            .filter(|(_, value)| value.is_some())
+            .copied()
+            .collect::<Vec<_>>();
+        let keys = insertions.iter().map(|(key, _)| *key).collect::<Vec<_>>();
+        let inserted = self
+            .storage
+            .get_many(&keys)?
+            .into_iter()
+            .zip(&keys)
+            .filter(|(node, _)| node.is_none())
+            .map(|(_, key)| **key)
+            .collect::<Vec<_>>();
+        self.storage.apply_batch(&insertions)?;
+        self.pending.extend(inserted);
+        Ok(())
+    }
+}
+
+/// A sparse Merkle tree keeping the nodes of its committed versions, so that the root
+/// of a version can be [loaded](Self::load) to query it or generate proofs.
+///
+/// Each node has a reference count: the number of nodes of the storage and versions
+/// referencing it. [`commit`](Self::commit) references the nodes of the new root, and
+/// [`prune`](Self::prune) removes old versions and the nodes that only they
+/// referenced.
+///
+/// The nodes are hashed with `H`, SHA-256 by default, as in [`MerkleTree`].
+#[derive(Debug)]
+pub struct VersionedMerkleTree<
+    NodesTable,
+    RefCountsTable,
+    VersionsTable,
+    StorageType,
+    H = Sha256,
+> {
+    tree: MerkleTree<NodesTable, VersionedNodes<StorageType>, H>,
+    version: Option<Version>,
+    phantom_tables: PhantomData<(RefCountsTable, VersionsTable)>,
+}
+
+impl<NodesTable, RefCountsTable, VersionsTable, StorageType, H>
+    VersionedMerkleTree<NodesTable, RefCountsTable, VersionsTable, StorageType, H>
+{
+    pub fn root(&self) -> Bytes32 {
+        self.tree.root()
+    }
+
+    /// The version of the tree: the last committed or loaded version.
+    pub fn version(&self) -> Option<Version> {
+        self.version
+    }
+
+    pub fn storage(&self) -> &StorageType {
+        &self.tree.storage().storage
+    }
+}
+
+impl<NodesTable, RefCountsTable, VersionsTable, StorageType, StorageError>
+    VersionedMerkleTree<NodesTable, RefCountsTable, VersionsTable, StorageType>
+where
+    NodesTable: Mappable<Key = Bytes32, Value = Primitive, OwnedValue = Primitive>,
+    RefCountsTable: Mappable<Key = Bytes32, Value = u64, OwnedValue = u64>,
+    VersionsTable:
+        Mappable<Key = Version, Value = VersionRecord, OwnedValue = VersionRecord>,
+    StorageType: StorageInspect<NodesTable, Error = StorageError>
+        + StorageInspect<RefCountsTable, Error = StorageError>
+        + StorageInspect<VersionsTable, Error = StorageError>,
+{
+    /// Creates an empty tree without versions, over a storage without versions.
+    pub fn new(storage: StorageType) -> Self {
+        Self::new_with_hasher(storage)
+    }
+
+    /// Loads the tree at the committed `version`.
+    pub fn load(
+        storage: StorageType,
+        version: Version,
+    ) -> Result<Self, VersionedMerkleTreeError<StorageError>> {
+        Self::load_with_hasher(storage, version)
+    }
+}
+
+impl<NodesTable, RefCountsTable, VersionsTable, StorageType, StorageError, H>
+    VersionedMerkleTree<NodesTable, RefCountsTable, VersionsTable, StorageType, H>
+where
+    H: Hasher,
+    NodesTable: Mappable<Key = Bytes32, Value = Primitive, OwnedValue = Primitive>,
+    RefCountsTable: Mappable<Key = Bytes32, Value = u64, OwnedValue = u64>,
+    VersionsTable:
+        Mappable<Key = Version, Value = VersionRecord, OwnedValue = VersionRecord>,
+    StorageType: StorageInspect<NodesTable, Error = StorageError>
+        + StorageInspect<RefCountsTable, Error = StorageError>
+        + StorageInspect<VersionsTable, Error = StorageError>,
+{
+    /// Creates an empty tree without versions hashing its nodes with `H`, over a
+    /// storage without versions.
+    pub fn new_with_hasher(storage: StorageType) -> Self {
+        Self {
+            tree: MerkleTree::new_with_hasher(VersionedNodes::new(storage)),
+            version: None,
+            phantom_tables: Default::default(),
+        }
+    }
+
+    /// Loads the tree at the committed `version` hashing its nodes with `H`.
+    pub fn load_with_hasher(
+        storage: StorageType,
+        version: Version,
+    ) -> Result<Self, VersionedMerkleTreeError<StorageError>> {
+        let record = StorageInspect::<VersionsTable>::get(&storage, &version)?
+            .ok_or(VersionedMerkleTreeError::VersionNotFound(version))?
+            .into_owned();
+        let tree =
+            MerkleTree::load_with_hasher(VersionedNodes::new(storage), &record.root)?;
+
+        Ok(Self {
+            tree,
+            version: Some(version),
+            phantom_tables: Default::default(),
+        })
+    }
+
+    /// The root of the committed `version`, or `None` if the version doesn't exist or
+    /// was pruned.
+    pub fn root_at(
+        &self,
+        version: Version,
+    ) -> Result<Option<Bytes32>, VersionedMerkleTreeError<StorageError>> {
+        let record = StorageInspect::<VersionsTable>::get(self.storage(), &version)?;
+        Ok(record.map(|record| record.root))
+    }
+
+    /// Returns `true` if the tree was updated since the last commit or load.
+    pub fn has_uncommitted_changes(
+        &self,
+    ) -> Result<bool, VersionedMerkleTreeError<StorageError>> {
+        let committed_root = match self.version {
+            Some(version) => self.root_at(version)?,
+            None => None,
+        };
+        let committed_root = committed_root.unwrap_or(*empty_sum());
+
+        Ok(!self.tree.storage().pending.is_empty() || self.root() != committed_root)
+    }
+
+    pub fn generate_proof(
+        &self,
+        key: &MerkleTreeKey,
+    ) -> Result<Proof, VersionedMerkleTreeError<StorageError>> {
+        Ok(self.tree.generate_proof(key)?)
+    }
+
+    pub fn generate_multiproof(
+        &self,
+        keys: &[MerkleTreeKey],
+    ) -> Result<MultiProof, VersionedMerkleTreeError<StorageError>> {
+        Ok(self.tree.generate_multiproof(keys)?)
+    }
+
+    /// The keys of the children of the node, or `None` if the node is a leaf.
+    fn children(
+        &self,
+        hash: &Bytes32,
+    ) -> Result<Option<[Bytes32; 2]>, VersionedMerkleTreeError<StorageError>> {
+        let primitive = StorageInspect::<NodesTable>::get(self.storage(), hash)?
+            .ok_or(MerkleTreeError::LoadError(*hash))?;
+        let prefix = primitive.prefix().map_err(|err| {
+            MerkleTreeError::DeserializeError(DeserializeError::from(err))
+        })?;
+
+        Ok(match prefix {
+            Prefix::Node => Some([*primitive.bytes_lo(), *primitive.bytes_hi()]),
+            Prefix::Leaf => None,
+        })
+    }
+}
+
+impl<NodesTable, RefCountsTable, VersionsTable, StorageType, StorageError, H>
+    VersionedMerkleTree<NodesTable, RefCountsTable, VersionsTable, StorageType, H>
+where
+    H: Hasher,
+    NodesTable: Mappable<Key = Bytes32, Value = Primitive, OwnedValue = Primitive>,
+    RefCountsTable: Mappable<Key = Bytes32, Value = u64, OwnedValue = u64>,
+    VersionsTable:
+        Mappable<Key = Version, Value = VersionRecord, OwnedValue = VersionRecord>,
+    StorageType: StorageMutate<NodesTable, Error = StorageError>
+        + StorageMutate<RefCountsTable, Error = StorageError>
+        + StorageMutate<VersionsTable, Error = StorageError>,
+{
+    pub fn update(
+        &mut self,
+        key: MerkleTreeKey,
+        data: &[u8],
+    ) -> Result<(), VersionedMerkleTreeError<StorageError>> {
+        Ok(self.tree.update(key, data)?)
+    }
+
+    pub fn delete(
+        &mut self,
+        key: MerkleTreeKey,
+    ) -> Result<(), VersionedMerkleTreeError<StorageError>> {
+        Ok(self.tree.delete(key)?)
+    }
+
+    /// See [`MerkleTree::update_batch`].
+    pub fn update_batch<I, D>(
+        &mut self,
+        set: I,
+    ) -> Result<(), VersionedMerkleTreeError<StorageError>>
+    where
+        I: IntoIterator<Item = (MerkleTreeKey, Option<D>)>,
+        D: AsRef<[u8]>,
+    {
+        Ok(self.tree.update_batch(set)?)
+    }
+
+    /// Commits the current root as the `version`, which must be greater than the
+    /// version of the tree, and returns the root. The version of the tree must be the
+    /// latest committed version, so that the versions form a single history. The nodes
+    /// created by the updates since the last commit that are not part of the tree are
+    /// removed.
+    pub fn commit(
+        &mut self,
+        version: Version,
+    ) -> Result<Bytes32, VersionedMerkleTreeError<StorageError>> {
+        if let Some(current) = self.version {
+            if version <= current {
+                return Err(VersionedMerkleTreeError::VersionNotIncreasing(
+                    version, current,
+                ))
+            }
+        }
+        if StorageInspect::<VersionsTable>::contains_key(self.storage(), &version)? {
+            return Err(VersionedMerkleTreeError::VersionExists(version))
+        }
+        let current = match self.version {
+            Some(current) => {
+                let record =
+                    StorageInspect::<VersionsTable>::get(self.storage(), &current)?
+                        .ok_or(VersionedMerkleTreeError::VersionNotFound(current))?
+                        .into_owned();
+                if record.next.is_some() {
+                    return Err(VersionedMerkleTreeError::VersionNotLatest(current))
+                }
+                Some((current, record))
+            }
+            None => None,
+        };
+
+        let root = self.root();
+        self.retain(&root)?;
+        self.remove_pending_nodes()?;
+
+        let record = VersionRecord {
+            root,
+            previous: self.version,
+            next: None,
+        };
+        StorageMutate::<VersionsTable>::insert(self.storage_mut(), &version, &record)?;
+        if let Some((current, mut record)) = current {
+            record.next = Some(version);
+            StorageMutate::<VersionsTable>::insert(
+                self.storage_mut(),
+                &current,
+                &record,
+            )?;
+        }
+        self.version = Some(version);
+
+        Ok(root)
+    }
+
+    /// Returns the storage, removing the nodes created by the updates since the last
+    /// commit or load. The uncommitted changes are lost.
+    pub fn into_storage(
+        mut self,
+    ) -> Result<StorageType, VersionedMerkleTreeError<StorageError>> {
+        self.remove_pending_nodes()?;
+        Ok(self.tree.into_storage().storage)
+    }
+
+    /// Removes the versions older than `version` preceding the version of the tree,
+    /// and the nodes that are not referenced by the remaining versions. The version of
+    /// the tree is never removed, so its proofs stay valid.
+    pub fn prune(
+        &mut self,
+        version: Version,
+    ) -> Result<(), VersionedMerkleTreeError<StorageError>> {
+        if self.has_uncommitted_changes()? {
+            return Err(VersionedMerkleTreeError::UncommittedChanges)
+        }
+        let Some(mut current) = self.version else {
+            return Ok(())
+        };
+
+        // Find the oldest version to keep, and detach it from the older versions.
+        let mut pruned = loop {
+            let mut record =
+                StorageInspect::<VersionsTable>::get(self.storage(), &current)?
+                    .ok_or(VersionedMerkleTreeError::VersionNotFound(current))?
+                    .into_owned();
+            match record.previous {
+                Some(previous) if previous >= version => current = previous,
+                Some(previous) => {
+                    record.previous = None;
+                    StorageMutate::<VersionsTable>::insert(
+                        self.storage_mut(),
+                        &current,
+                        &record,
+                    )?;
+                    break Some(previous)
+                }
+                None => break None,
+            }
+        };
+
+        while let Some(version) = pruned {
+            let Some(record) =
+                StorageMutate::<VersionsTable>::take(self.storage_mut(), &version)?
+            else {
+                break
+            };
+            self.release(&record.root)?;
+            pruned = record.previous;
+        }
+
+        Ok(())
+    }
+
+    fn storage_mut(&mut self) -> &mut StorageType {
+        &mut self.tree.storage_mut().storage
+    }
+
+    /// Removes the nodes inserted since the last commit or load that no version
+    /// references.
+    fn remove_pending_nodes(
+        &mut self,
+    ) -> Result<(), VersionedMerkleTreeError<StorageError>> {
+        let pending = mem::take(&mut self.tree.storage_mut().pending);
+        for hash in pending {
+            if !StorageInspect::<RefCountsTable>::contains_key(self.storage(), &hash)? {
+                StorageMutate::<NodesTable>::remove(self.storage_mut(), &hash)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Adds a reference to the node. A node referenced for the first time references
+    /// its children.
+    fn retain(
+        &mut self,
+        hash: &Bytes32,
+    ) -> Result<(), VersionedMerkleTreeError<StorageError>> {
+        if hash == empty_sum() {
+            return Ok(())
+        }
+
+        let count = StorageInspect::<RefCountsTable>::get(self.storage(), hash)?
+            .map(Cow::into_owned);
+        let new_count = count.unwrap_or_default().saturating_add(1);
+        StorageMutate::<RefCountsTable>::insert(self.storage_mut(), hash, &new_count)?;
+
+        if count.is_none() {
+            for child in self.children(hash)?.into_iter().flatten() {
+                self.retain(&child)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Removes a reference to the node. A node without references is removed, and
+    /// releases its children.
+    fn release(
+        &mut self,
+        hash: &Bytes32,
+    ) -> Result<(), VersionedMerkleTreeError<StorageError>> {
+        if hash == empty_sum() {
+            return Ok(())
+        }
+
+        let count = StorageInspect::<RefCountsTable>::get(self.storage(), hash)?
+            .ok_or(MerkleTreeError::LoadError(*hash))?
+            .into_owned();
+        if count > 1 {
+            #[allow(clippy::arithmetic_side_effects)] // count > 1
+            let new_count = count - 1;
+            StorageMutate::<RefCountsTable>::insert(
+                self.storage_mut(),
+                hash,
+                &new_count,
+            )?;
+            return Ok(())
+        }
+
+        let children = self.children(hash)?;
+        StorageMutate::<RefCountsTable>::remove(self.storage_mut(), hash)?;
+        StorageMutate::<NodesTable>::remove(self.storage_mut(), hash)?;
+        for child in children.into_iter().flatten() {
+            self.release(&child)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod test {
+    use super::*;
+    use crate::common::StorageMap;
+    use sha3::Keccak256;
+
+    #[derive(Debug)]
+    struct NodesTable;
+
+    impl Mappable for NodesTable {
+        type Key = Self::OwnedKey;
+        type OwnedKey = Bytes32;
+        type OwnedValue = Primitive;
+        type Value = Self::OwnedValue;
+    }
+
+    #[derive(Debug)]
+    struct RefCountsTable;
+
+    impl Mappable for RefCountsTable {
+        type Key = Self::OwnedKey;
+        type OwnedKey = Bytes32;
+        type OwnedValue = u64;
+        type Value = Self::OwnedValue;
+    }
+
+    #[derive(Debug)]
+    struct VersionsTable;
+
+    impl Mappable for VersionsTable {
+        type Key = Self::OwnedKey;
+        type OwnedKey = Version;
+        type OwnedValue = VersionRecord;
+        type Value = Self::OwnedValue;
+    }
+
+    #[derive(Debug, Default)]
+    struct TestStorage {
+        nodes: StorageMap<NodesTable>,
+        ref_counts: StorageMap<RefCountsTable>,
+        versions: StorageMap<VersionsTable>,
+    }
+
+    macro_rules! impl_storage {
+        ($table:ty, $field:ident) => {
+            impl StorageInspect<$table> for TestStorage {
+                type Error = core::convert::Infallible;
+
+                fn get(
+                    &self,
+                    key: &<$table as Mappable>::Key,
+                ) -> Result<Option<Cow<'_, <$table as Mappable>::OwnedValue>>, Self::Error>
+                {
+                    self.$field.get(key)
+                }
+
+                fn contains_key(
+                    &self,
+                    key: &<$table as Mappable>::Key,
+                ) -> Result<bool, Self::Error> {
+                    self.$field.contains_key(key)
+                }
+            }
+
+            impl StorageMutate<$table> for TestStorage {
+                fn replace(
+                    &mut self,
+                    key: &<$table as Mappable>::Key,
+                    value: &<$table as Mappable>::Value,
+                ) -> Result<Option<<$table as Mappable>::OwnedValue>, Self::Error> {
+                    self.$field.replace(key, value)
+                }
+
+                fn take(
+                    &mut self,
+                    key: &<$table as Mappable>::Key,
+                ) -> Result<Option<<$table as Mappable>::OwnedValue>, Self::Error> {
+                    self.$field.take(key)
+                }
+
+                fn apply_batch(
+                    &mut self,
+                    changes: &[(
+                        &<$table as Mappable>::Key,
+                        Option<&<$table as Mappable>::Value>,
+                    )],
+                ) -> Result<(), Self::Error> {
+                    self.$field.apply_batch(changes)
+                }
+            }
+        };
+    }
+
+    impl_storage!(NodesTable, nodes);
+    impl_storage!(RefCountsTable, ref_counts);
+    impl_storage!(VersionsTable, versions);
+
+    type TestTree =
+        VersionedMerkleTree<NodesTable, RefCountsTable, VersionsTable, TestStorage>;
+
+    fn key(n: u32) -> MerkleTreeKey {
+        MerkleTreeKey::new(n.to_be_bytes())
+    }
+
+    fn root_of(set: &[(u32, &[u8])]) -> Bytes32 {
+        let set = set.iter().map(|(n, data)| (key(*n), *data));
+        crate::sparse::in_memory::MerkleTree::root_from_set(set)
+    }
+
+    fn verify_inclusion(
+        proof: Proof,
+        root: &Bytes32,
+        key: &MerkleTreeKey,
+        value: &[u8],
+    ) -> bool {
+        match proof {
+            Proof::Inclusion(proof) => proof.verify(root, key, value),
+            Proof::Exclusion(_) => false,
+        }
+    }
+
+    fn node_count_of(set: &[(u32, &[u8])]) -> usize {
+        let mut storage = StorageMap::<NodesTable>::new();
+        let set = set.iter().map(|(n, data)| (key(*n), *data));
+        MerkleTree::from_set(&mut storage, set).unwrap();
+        storage.len()
+    }
+
+    #[test]
+    fn versioned_merkle_tree__load__restores_root_of_each_version() {
+        // Given
+        let mut tree = TestTree::new(TestStorage::default());
+        tree.update(key(0), b"DATA_0").unwrap();
+        tree.update(key(1), b"DATA_1").unwrap();
+        tree.commit(1).unwrap();
+        tree.update(key(0), b"DATA_2").unwrap();
+        tree.delete(key(1)).unwrap();
+        tree.commit(2).unwrap();
+        let storage = tree.into_storage().unwrap();
+
+        // When
+        let tree_1 = TestTree::load(storage, 1).unwrap();
+        let proof = tree_1.generate_proof(&key(1)).unwrap();
+        let storage = tree_1.into_storage().unwrap();
+        let tree_2 = TestTree::load(storage, 2).unwrap();
+
+        // Then
+        let root_1 = root_of(&[(0, b"DATA_0"), (1, b"DATA_1")]);
+        assert!(verify_inclusion(proof, &root_1, &key(1), b"DATA_1"));
+        assert_eq!(tree_2.root(), root_of(&[(0, b"DATA_2")]));
+        assert_eq!(tree_2.root_at(1).unwrap(), Some(root_1));
+        assert_eq!(tree_2.version(), Some(2));
+    }
+
+    #[test]
+    fn versioned_merkle_tree__load_with_hasher__restores_root_of_the_hasher() {
+        type KeccakTree = VersionedMerkleTree<
+            NodesTable,
+            RefCountsTable,
+            VersionsTable,
+            TestStorage,
+            Keccak256,
+        >;
+
+        // Given
+        let mut tree = KeccakTree::new_with_hasher(TestStorage::default());
+        tree.update(key(0), b"DATA_0").unwrap();
+        tree.update(key(1), b"DATA_1").unwrap();
+        tree.commit(1).unwrap();
+        let storage = tree.into_storage().unwrap();
+
+        // When
+        let tree = KeccakTree::load_with_hasher(storage, 1).unwrap();
+
+        // Then
+        let set = [(key(0), b"DATA_0"), (key(1), b"DATA_1")];
+        let expected_tree = MerkleTree::<NodesTable, _, Keccak256>::from_set_with_hasher(
+            StorageMap::<NodesTable>::new(),
+            set.into_iter(),
+        )
+        .unwrap();
+        assert_eq!(tree.root(), expected_tree.root());
+        assert_ne!(tree.root(), root_of(&[(0, b"DATA_0"), (1, b"DATA_1")]));
+    }
+
+    #[test]
+    fn versioned_merkle_tree__load__returns_error_for_unknown_version() {
+        // Given
+        let mut tree = TestTree::new(TestStorage::default());
+        tree.update(key(0), b"DATA_0").unwrap();
+        tree.commit(1).unwrap();
+
+        // When
+        let result = TestTree::load(tree.into_storage().unwrap(), 2);
+
+        // Then
+        assert!(matches!(
+            result,
+            Err(VersionedMerkleTreeError::VersionNotFound(2))
+        ));
+    }
+
+    #[test]
+    fn versioned_merkle_tree__commit__removes_intermediate_nodes() {
+        // Given
+        let set: Vec<(u32, &[u8])> = (0..10).map(|n| (n, &b"DATA"[..])).collect();
+        let mut tree = TestTree::new(TestStorage::default());
+
+        // When
+        for (n, data) in &set {
+            tree.update(key(*n), data).unwrap();
+        }
+        tree.commit(1).unwrap();
+
+        // Then
+        let node_count = node_count_of(&set);
+        assert_eq!(tree.storage().nodes.len(), node_count);
+        assert_eq!(tree.storage().ref_counts.len(), node_count);
+    }
+
+    #[test]
+    fn versioned_merkle_tree__commit__returns_error_for_version_not_increasing() {
+        // Given
+        let mut tree = TestTree::new(TestStorage::default());
+        tree.update(key(0), b"DATA_0").unwrap();
+        tree.commit(2).unwrap();
+        tree.update(key(1), b"DATA_1").unwrap();
+
+        // When
+        let result = tree.commit(2);
+
+        // Then
+        assert!(matches!(
+            result,
+            Err(VersionedMerkleTreeError::VersionNotIncreasing(2, 2))
+        ));
+    }
+
+    #[test]
+    fn versioned_merkle_tree__commit__returns_error_for_existing_version() {
+        // Given
+        let mut tree = TestTree::new(TestStorage::default());
+        tree.update(key(0), b"DATA_0").unwrap();
+        tree.commit(1).unwrap();
+        tree.update(key(0), b"DATA_1").unwrap();
+        tree.commit(2).unwrap();
+        let mut tree = TestTree::load(tree.into_storage().unwrap(), 1).unwrap();
+
+        // When
+        let result = tree.commit(2);
+
+        // Then
+        assert!(matches!(
+            result,
+            Err(VersionedMerkleTreeError::VersionExists(2))
+        ));
+    }
+
+    #[test]
+    fn versioned_merkle_tree__commit__returns_error_for_version_not_latest() {
+        // Given
+        let mut tree = TestTree::new(TestStorage::default());
+        tree.update(key(0), b"DATA_0").unwrap();
+        tree.commit(1).unwrap();
+        tree.update(key(0), b"DATA_1").unwrap();
+        tree.commit(2).unwrap();
+        let mut tree = TestTree::load(tree.into_storage().unwrap(), 1).unwrap();
+        tree.update(key(0), b"DATA_2").unwrap();
+
+        // When
+        let result = tree.commit(3);
+
+        // Then
+        assert!(matches!(
+            result,
+            Err(VersionedMerkleTreeError::VersionNotLatest(1))
+        ));
+        let storage = tree.into_storage().unwrap();
+        assert_eq!(storage.versions.len(), 2);
+        assert_eq!(storage.nodes.len(), 2);
+        assert_eq!(storage.ref_counts.len(), 2);
+    }
+
+    #[test]
+    fn versioned_merkle_tree__into_storage__removes_uncommitted_nodes() {
+        // Given
+        let set: [(u32, &[u8]); 2] = [(0, b"DATA_0"), (1, b"DATA_1")];
+        let mut tree = TestTree::new(TestStorage::default());
+        for (n, data) in set {
+            tree.update(key(n), data).unwrap();
+        }
+        tree.commit(1).unwrap();
+        for n in 2..10 {
+            tree.update(key(n), b"DATA").unwrap();
+        }
+
+        // When
+        let storage = tree.into_storage().unwrap();
+
+        // Then
+        assert_eq!(storage.nodes.len(), node_count_of(&set));
+        assert_eq!(storage.ref_counts.len(), node_count_of(&set));
+        let tree = TestTree::load(storage, 1).unwrap();
+        assert_eq!(tree.root(), root_of(&set));
+        assert!(!tree.has_uncommitted_changes().unwrap());
+    }
+
+    #[test]
+    fn versioned_merkle_tree__prune__keeps_only_nodes_of_remaining_versions() {
+        // Given
+        let mut tree = TestTree::new(TestStorage::default());
+        for version in 1..=10u32 {
+            for n in 0..10 {
+                let data = (version * n).to_be_bytes();
+                tree.update(key(n), &data).unwrap();
+            }
+            tree.delete(key(version)).unwrap();
+            tree.commit(version.into()).unwrap();
+        }
+
+        // When
+        tree.prune(10).unwrap();
+
+        // Then
+        let set: Vec<_> = (0..10u32).map(|n| (n, (10 * n).to_be_bytes())).collect();
+        let set: Vec<(u32, &[u8])> = set
+            .iter()
+            .filter(|(n, _)| *n != 10)
+            .map(|(n, data)| (*n, &data[..]))
+            .collect();
+        let node_count = node_count_of(&set);
+        assert_eq!(tree.root(), root_of(&set));
+        assert_eq!(tree.storage().nodes.len(), node_count);
+        assert_eq!(tree.storage().ref_counts.len(), node_count);
+        assert_eq!(tree.storage().versions.len(), 1);
+        assert_eq!(tree.root_at(9).unwrap(), None);
+    }
+
+    #[test]
+    fn versioned_merkle_tree__prune__keeps_versions_not_older_than_given_version() {
+        // Given
+        let mut tree = TestTree::new(TestStorage::default());
+        for version in 1..=5u64 {
+            tree.update(key(0), &version.to_be_bytes()).unwrap();
+            tree.commit(version).unwrap();
+        }
+        let root_3 = tree.root_at(3).unwrap();
+
+        // When
+        tree.prune(3).unwrap();
+
+        // Then
+        assert_eq!(tree.root_at(2).unwrap(), None);
+        assert_eq!(tree.root_at(3).unwrap(), root_3);
+        let tree = TestTree::load(tree.into_storage().unwrap(), 3).unwrap();
+        assert_eq!(Some(tree.root()), root_3);
+        assert_eq!(
+            tree.storage().versions.get(&3).unwrap().unwrap().previous,
+            None
+        );
+    }
+
+    #[test]
+    fn versioned_merkle_tree__prune__keeps_nodes_shared_with_reverted_value() {
+        // Given
+        let mut tree = TestTree::new(TestStorage::default());
+        tree.update(key(0), b"DATA_0").unwrap();
+        tree.update(key(1), b"DATA_1").unwrap();
+        tree.commit(1).unwrap();
+        tree.update(key(0), b"DATA_2").unwrap();
+        tree.commit(2).unwrap();
+        tree.update(key(0), b"DATA_0").unwrap();
+        tree.commit(3).unwrap();
+
+        // When
+        tree.prune(3).unwrap();
+
+        // Then
+        let set: [(u32, &[u8]); 2] = [(0, b"DATA_0"), (1, b"DATA_1")];
+        assert_eq!(tree.root(), root_of(&set));
+        assert_eq!(tree.storage().nodes.len(), node_count_of(&set));
+        let proof = tree.generate_proof(&key(0)).unwrap();
+        assert!(verify_inclusion(proof, &tree.root(), &key(0), b"DATA_0"));
+    }
+
+    #[test]
+    fn versioned_merkle_tree__prune__returns_error_for_uncommitted_changes() {
+        // Given
+        let mut tree = TestTree::new(TestStorage::default());
+        tree.update(key(0), b"DATA_0").unwrap();
+        tree.commit(1).unwrap();
+        tree.update(key(0), b"DATA_1").unwrap();
+
+        // When
+        let result = tree.prune(1);
+
+        // Then
+        assert!(matches!(
+            result,
+            Err(VersionedMerkleTreeError::UncommittedChanges)
+        ));
+    }
+}
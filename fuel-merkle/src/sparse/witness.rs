@@ -0,0 +1,452 @@
+use crate::{
+    common::{
+        path::{
+            Path,
+            Side,
+        },
+        Bytes32,
+        Hasher,
+    },
+    sparse::{
+        hash::{
+            calculate_leaf_hash,
+            calculate_node_hash,
+        },
+        proof::{
+            multiproof_root,
+            ExclusionLeaf,
+            MultiProof,
+            MultiProofLeaf,
+            MAX_DEPTH,
+        },
+        zero_sum,
+        MerkleTreeKey,
+    },
+};
+
+use alloc::{
+    collections::{
+        BTreeMap,
+        BTreeSet,
+    },
+    vec::Vec,
+};
+use core::{
+    fmt,
+    fmt::Debug,
+};
+use sha2::Sha256;
+
+#[derive(Debug, Clone, PartialEq, Eq, derive_more::Display)]
+pub enum UpdateWitnessError {
+    #[display(fmt = "the proof is not valid for the root and the values of the keys")]
+    InvalidProof,
+
+    #[display(fmt = "the key {} is not proven by the witness", "hex::encode(_0)")]
+    UnprovenKey(Bytes32),
+
+    #[display(
+        fmt = "the preimage of the side node {} is not in the witness",
+        "hex::encode(_0)"
+    )]
+    MissingPreimage(Bytes32),
+}
+
+/// The content of a side node of an [`UpdateWitness`], hashing to the side node.
+#[derive(Clone, Eq, PartialEq)]
+pub enum NodePreimage {
+    /// The side node is a leaf.
+    Leaf {
+        /// The leaf key.
+        key: Bytes32,
+        /// Hash of the value of the leaf.
+        value: Bytes32,
+    },
+    /// The side node is an internal node.
+    Node { left: Bytes32, right: Bytes32 },
+}
+
+impl NodePreimage {
+    fn hash<H: Hasher>(&self) -> Bytes32 {
+        match self {
+            NodePreimage::Leaf { key, value } => calculate_leaf_hash::<H>(key, value),
+            NodePreimage::Node { left, right } => calculate_node_hash::<H>(left, right),
+        }
+    }
+}
+
+impl Debug for NodePreimage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NodePreimage::Leaf { key, value } => f
+                .debug_struct("Leaf")
+                .field("Key", &hex::encode(key))
+                .field("Value", &hex::encode(value))
+                .finish(),
+            NodePreimage::Node { left, right } => f
+                .debug_struct("Node")
+                .field("Left", &hex::encode(left))
+                .field("Right", &hex::encode(right))
+                .finish(),
+        }
+    }
+}
+
+/// The data required to compute the root of a tree after updating or deleting some
+/// of its keys, without the tree.
+///
+/// The [`MultiProof`] proves the current values of the keys. Deleting a leaf moves
+/// its sibling up the tree if the sibling is a leaf, so the witness also contains the
+/// preimages of the side nodes that may become the only node of their subtree.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct UpdateWitness {
+    pub proof: MultiProof,
+    pub preimages: Vec<NodePreimage>,
+}
+
+impl UpdateWitness {
+    /// Computes the root of the tree with the `root` after applying the `updates`,
+    /// in order. The `keys` are the keys of the proof with their current values, as
+    /// in [`MultiProof::verify`]. An update with a `None` or empty value deletes the
+    /// key, as in [`MerkleTree::update`](crate::sparse::MerkleTree::update).
+    ///
+    /// Fails if the proof isn't valid against the `root`, an updated key isn't a key
+    /// of the proof, or a required preimage is missing.
+    pub fn update_root<V, U>(
+        &self,
+        root: &Bytes32,
+        keys: &[(MerkleTreeKey, Option<V>)],
+        updates: &[(MerkleTreeKey, Option<U>)],
+    ) -> Result<Bytes32, UpdateWitnessError>
+    where
+        V: AsRef<[u8]>,
+        U: AsRef<[u8]>,
+    {
+        self.update_root_with_hasher::<Sha256, V, U>(root, keys, updates)
+    }
+
+    /// Computes the updated root of a tree hashing its nodes with `H`, as in
+    /// [`update_root`](Self::update_root).
+    pub fn update_root_with_hasher<H, V, U>(
+        &self,
+        root: &Bytes32,
+        keys: &[(MerkleTreeKey, Option<V>)],
+        updates: &[(MerkleTreeKey, Option<U>)],
+    ) -> Result<Bytes32, UpdateWitnessError>
+    where
+        H: Hasher,
+        V: AsRef<[u8]>,
+        U: AsRef<[u8]>,
+    {
+        let hashes = self
+            .proof
+            .leaf_hashes::<H, V>(keys)
+            .ok_or(UpdateWitnessError::InvalidProof)?;
+
+        let mut side_nodes = Vec::new();
+        let mut proof_set = self.proof.proof_set.iter();
+        let computed = multiproof_root::<H, _, _>(hashes, |depth, position| {
+            let hash = *proof_set.next()?;
+            side_nodes.push((*position, depth, hash));
+            Some(hash)
+        });
+        if computed != Some(*root) || proof_set.next().is_some() {
+            return Err(UpdateWitnessError::InvalidProof)
+        }
+
+        let mut tree = PartialTree::new(side_nodes);
+        for (leaf, (key, value)) in self.proof.leaves.iter().zip(keys) {
+            match (leaf, value) {
+                (MultiProofLeaf::Inclusion { .. }, Some(value)) => {
+                    tree.insert(**key, calculate_leaf_hash::<H>(key, &H::hash(value)));
+                }
+                (
+                    MultiProofLeaf::Exclusion {
+                        leaf: ExclusionLeaf::Leaf(data),
+                        ..
+                    },
+                    _,
+                ) => {
+                    tree.insert(
+                        data.leaf_key,
+                        calculate_leaf_hash::<H>(&data.leaf_key, &data.leaf_value),
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        let proven = keys.iter().map(|(key, _)| **key).collect::<BTreeSet<_>>();
+        for (key, value) in updates {
+            if !proven.contains(&**key) {
+                return Err(UpdateWitnessError::UnprovenKey(**key))
+            }
+            match value.as_ref().map(AsRef::as_ref) {
+                Some(value) if !value.is_empty() => {
+                    tree.insert(**key, calculate_leaf_hash::<H>(key, &H::hash(value)));
+                }
+                _ => tree.remove(key),
+            }
+        }
+
+        let preimages = self
+            .preimages
+            .iter()
+            .map(|preimage| (preimage.hash::<H>(), preimage))
+            .collect::<BTreeMap<_, _>>();
+        tree.root::<H, _, _>(|hash| match preimages.get(hash) {
+            Some(NodePreimage::Leaf { .. }) => Ok(true),
+            Some(NodePreimage::Node { .. }) => Ok(false),
+            None => Err(UpdateWitnessError::MissingPreimage(*hash)),
+        })?
+        .ok_or(UpdateWitnessError::InvalidProof)
+    }
+}
+
+/// The `(position, depth, hash)` of side nodes of a multiproof.
+pub(crate) type SideNodes = Vec<(Bytes32, u32, Bytes32)>;
+
+/// The part of a tree known from a multiproof: the leaves on the paths of the keys
+/// and the side nodes of the paths, whose content is unknown.
+pub(crate) struct PartialTree {
+    /// The leaf hashes by key.
+    leaves: BTreeMap<Bytes32, Bytes32>,
+    /// The non-empty side nodes as `(position, depth, hash)`, ordered by position.
+    side_nodes: SideNodes,
+}
+
+/// A subtree of a [`PartialTree`].
+enum Subtree {
+    Empty,
+    Leaf(Bytes32),
+    Node(Bytes32),
+    /// A side node, which is either a leaf or a node.
+    SideNode(Bytes32),
+}
+
+impl Subtree {
+    fn hash(&self) -> &Bytes32 {
+        match self {
+            Subtree::Empty => zero_sum(),
+            Subtree::Leaf(hash) | Subtree::Node(hash) | Subtree::SideNode(hash) => hash,
+        }
+    }
+}
+
+impl PartialTree {
+    /// Creates a tree from the `(position, depth, hash)` of the side nodes, as
+    /// provided to [`multiproof_root`].
+    pub(crate) fn new(mut side_nodes: SideNodes) -> Self {
+        side_nodes.retain(|(_, _, hash)| hash != zero_sum());
+        side_nodes.sort();
+        Self {
+            leaves: BTreeMap::new(),
+            side_nodes,
+        }
+    }
+
+    pub(crate) fn insert(&mut self, key: Bytes32, hash: Bytes32) {
+        self.leaves.insert(key, hash);
+    }
+
+    pub(crate) fn remove(&mut self, key: &Bytes32) {
+        self.leaves.remove(key);
+    }
+
+    /// Computes the root of the tree. `is_leaf` tells whether a side node whose
+    /// sibling subtree is empty is a leaf, which then moves up the tree. Returns
+    /// `None` if the leaves and the side nodes overlap.
+    pub(crate) fn root<H, E, F>(&self, mut is_leaf: F) -> Result<Option<Bytes32>, E>
+    where
+        H: Hasher,
+        F: FnMut(&Bytes32) -> Result<bool, E>,
+    {
+        let leaves = self.leaves.iter().collect::<Vec<_>>();
+        let root = subtree::<H, _, _>(0, &leaves, &self.side_nodes, &mut is_leaf)?;
+        Ok(root.map(|root| *root.hash()))
+    }
+}
+
+fn subtree<H, E, F>(
+    depth: u32,
+    leaves: &[(&Bytes32, &Bytes32)],
+    side_nodes: &[(Bytes32, u32, Bytes32)],
+    is_leaf: &mut F,
+) -> Result<Option<Subtree>, E>
+where
+    H: Hasher,
+    F: FnMut(&Bytes32) -> Result<bool, E>,
+{
+    let subtree = match (leaves, side_nodes) {
+        ([], []) => Subtree::Empty,
+        ([(_, hash)], []) => Subtree::Leaf(**hash),
+        ([], [(_, side_depth, hash)]) if *side_depth == depth => Subtree::SideNode(*hash),
+        _ => {
+            if depth >= MAX_DEPTH
+                || side_nodes
+                    .iter()
+                    .any(|(_, side_depth, _)| *side_depth <= depth)
+            {
+                return Ok(None)
+            }
+
+            let is_left =
+                |key: &Bytes32| matches!(key.get_instruction(depth), Some(Side::Left));
+            let leaves_split = leaves.partition_point(|(key, _)| is_left(key));
+            let side_nodes_split =
+                side_nodes.partition_point(|(position, _, _)| is_left(position));
+            #[allow(clippy::arithmetic_side_effects)] // depth < MAX_DEPTH
+            let child_depth = depth + 1;
+
+            let Some(left) = subtree::<H, _, _>(
+                child_depth,
+                &leaves[..leaves_split],
+                &side_nodes[..side_nodes_split],
+                is_leaf,
+            )?
+            else {
+                return Ok(None)
+            };
+            let Some(right) = subtree::<H, _, _>(
+                child_depth,
+                &leaves[leaves_split..],
+                &side_nodes[side_nodes_split..],
+                is_leaf,
+            )?
+            else {
+                return Ok(None)
+            };
+
+            match (left, right) {
+                (Subtree::Empty, Subtree::Empty) => Subtree::Empty,
+                // A leaf without a sibling moves up the tree.
+                (Subtree::Empty, Subtree::Leaf(hash))
+                | (Subtree::Leaf(hash), Subtree::Empty) => Subtree::Leaf(hash),
+                (Subtree::Empty, Subtree::SideNode(hash))
+                | (Subtree::SideNode(hash), Subtree::Empty)
+                    if is_leaf(&hash)? =>
+                {
+                    Subtree::Leaf(hash)
+                }
+                (left, right) => {
+                    Subtree::Node(calculate_node_hash::<H>(left.hash(), right.hash()))
+                }
+            }
+        }
+    };
+
+    Ok(Some(subtree))
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod test {
+    use super::*;
+    use crate::{
+        common::sum,
+        sparse::in_memory::MerkleTree,
+    };
+
+    fn key(byte: u8) -> MerkleTreeKey {
+        MerkleTreeKey::new_without_hash([byte; 32])
+    }
+
+    #[test]
+    fn update_witness__update_root__moves_sibling_leaf_up_after_delete() {
+        // Given
+        let mut tree = MerkleTree::new();
+        tree.update(key(0x00), b"DATA_0");
+        tree.update(key(0xff), b"DATA_1");
+        let root = tree.root();
+        let witness = tree.generate_update_witness(&[key(0x00)]).unwrap();
+
+        // When
+        let updated_root = witness.update_root(
+            &root,
+            &[(key(0x00), Some(b"DATA_0"))],
+            &[(key(0x00), None::<&[u8]>)],
+        );
+
+        // Then
+        tree.delete(key(0x00));
+        assert_eq!(updated_root, Ok(tree.root()));
+        assert_eq!(
+            witness.preimages,
+            vec![NodePreimage::Leaf {
+                key: *key(0xff),
+                value: sum(b"DATA_1"),
+            }]
+        );
+    }
+
+    #[test]
+    fn update_witness__update_root__returns_empty_root_after_deleting_every_key() {
+        // Given
+        let mut tree = MerkleTree::new();
+        tree.update(key(0x00), b"DATA_0");
+        let root = tree.root();
+        let witness = tree.generate_update_witness(&[key(0x00)]).unwrap();
+
+        // When
+        let updated_root = witness.update_root(
+            &root,
+            &[(key(0x00), Some(b"DATA_0"))],
+            &[(key(0x00), Some(b""))],
+        );
+
+        // Then
+        assert_eq!(updated_root, Ok(*zero_sum()));
+    }
+
+    #[test]
+    fn update_witness__update_root__returns_error_for_missing_preimage() {
+        // Given
+        let mut tree = MerkleTree::new();
+        tree.update(key(0x00), b"DATA_0");
+        tree.update(key(0xff), b"DATA_1");
+        let root = tree.root();
+        let mut witness = tree.generate_update_witness(&[key(0x00)]).unwrap();
+        witness.preimages.clear();
+
+        // When
+        let updated_root = witness.update_root(
+            &root,
+            &[(key(0x00), Some(b"DATA_0"))],
+            &[(key(0x00), None::<&[u8]>)],
+        );
+
+        // Then
+        assert_eq!(
+            updated_root,
+            Err(UpdateWitnessError::MissingPreimage(calculate_leaf_hash::<
+                Sha256,
+            >(
+                &key(0xff),
+                &sum(b"DATA_1")
+            )))
+        );
+    }
+
+    #[test]
+    fn update_witness__update_root__returns_error_for_unproven_key() {
+        // Given
+        let mut tree = MerkleTree::new();
+        tree.update(key(0x00), b"DATA_0");
+        tree.update(key(0xff), b"DATA_1");
+        let root = tree.root();
+        let witness = tree.generate_update_witness(&[key(0x00)]).unwrap();
+
+        // When
+        let updated_root = witness.update_root(
+            &root,
+            &[(key(0x00), Some(b"DATA_0"))],
+            &[(key(0xff), Some(b"DATA_2"))],
+        );
+
+        // Then
+        assert_eq!(
+            updated_root,
+            Err(UpdateWitnessError::UnprovenKey(*key(0xff)))
+        );
+    }
+}
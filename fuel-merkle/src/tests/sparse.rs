@@ -2,6 +2,7 @@
 
 use crate::{
     common::{
+        sum,
         Bytes32,
         StorageMap,
     },
@@ -12,6 +13,7 @@ use crate::{
             ExclusionProof,
             Proof,
         },
+        witness::UpdateWitnessError,
         MerkleTree,
         MerkleTreeKey,
         Primitive,
@@ -30,13 +32,18 @@ use proptest::{
         hash_set,
         vec,
     },
+    option,
     prop_assert,
+    prop_assert_eq,
     prop_assume,
     prop_compose,
     proptest,
     strategy::Strategy,
 };
-use std::collections::HashSet;
+use std::collections::{
+    BTreeMap,
+    HashSet,
+};
 
 #[derive(Debug)]
 struct TestTable;
@@ -198,4 +205,189 @@ proptest! {
         prop_assert!(!exclusion_result);
         prop_assert!(inclusion_result != exclusion_result);
     }
+
+    #[test]
+    fn update_witness__update_root__returns_root_of_updated_tree(
+        (key_values, mut tree) in random_tree(1, 50),
+        included: usize,
+        excluded in hash_set(any::<Key>(), 0..5),
+        updates in vec((any::<usize>(), option::of(any::<Value>())), 0..20),
+    ) {
+        let root = tree.root();
+
+        // Given
+        let included = included % (key_values.len() + 1);
+        let mut keys = key_values[..included]
+            .iter()
+            .map(|(key, value)| (MerkleTreeKey::new(key), Some(*value)))
+            .collect::<Vec<_>>();
+        keys.extend(
+            excluded
+                .into_iter()
+                .filter(|key| !key_values.iter().any(|(k, _)| k == key))
+                .map(|key| (MerkleTreeKey::new(key), None)),
+        );
+        prop_assume!(!keys.is_empty());
+        let proven_keys = keys.iter().map(|(key, _)| *key).collect::<Vec<_>>();
+        let witness = tree.generate_update_witness(&proven_keys).expect("Infallible");
+        let updates = updates
+            .into_iter()
+            .map(|(index, value)| (proven_keys[index % proven_keys.len()], value))
+            .collect::<Vec<_>>();
+
+        // When
+        let updated_root = witness.update_root(&root, &keys, &updates);
+
+        // Then
+        for (key, value) in &updates {
+            match value {
+                Some(value) => tree.update(*key, value.as_ref()).unwrap(),
+                None => tree.delete(*key).unwrap(),
+            }
+        }
+        prop_assert_eq!(updated_root, Ok(tree.root()));
+    }
+
+    #[test]
+    fn update_witness__update_root__returns_root_of_tree_without_deleted_keys(
+        (key_values, mut tree) in random_tree(1, 50),
+        deleted: usize,
+    ) {
+        let root = tree.root();
+
+        // Given
+        let deleted = deleted % key_values.len() + 1;
+        let keys = key_values[..deleted]
+            .iter()
+            .map(|(key, value)| (MerkleTreeKey::new(key), Some(*value)))
+            .collect::<Vec<_>>();
+        let proven_keys = keys.iter().map(|(key, _)| *key).collect::<Vec<_>>();
+        let witness = tree.generate_update_witness(&proven_keys).expect("Infallible");
+        let updates = proven_keys
+            .iter()
+            .map(|key| (*key, None::<Value>))
+            .collect::<Vec<_>>();
+
+        // When
+        let updated_root = witness.update_root(&root, &keys, &updates);
+
+        // Then
+        for key in &proven_keys {
+            tree.delete(*key).unwrap();
+        }
+        prop_assert_eq!(updated_root, Ok(tree.root()));
+    }
+
+    #[test]
+    fn update_witness__update_root__returns_error_for_incorrect_value(
+        (key_values, tree) in random_tree(1, 50),
+        arb_num: usize,
+        value: Value,
+    ) {
+        let root = tree.root();
+
+        // Given
+        let (key, _) = key_values[arb_num % key_values.len()];
+        let key = MerkleTreeKey::new(key);
+        let witness = tree.generate_update_witness(&[key]).expect("Infallible");
+
+        // When
+        let updated_root = witness.update_root(&root, &[(key, Some(value))], &[(key, None::<Value>)]);
+
+        // Then
+        prop_assert_eq!(updated_root, Err(UpdateWitnessError::InvalidProof));
+    }
+
+    #[test]
+    fn merkle_tree__update_batch__yields_root_of_sequential_updates(
+        (key_values, mut tree) in random_tree(0, 100),
+        updates in vec((any::<Key>(), any::<usize>(), option::of(any::<Value>())), 0..50),
+    ) {
+        // Given
+        let updates = updates
+            .into_iter()
+            .map(|(key, index, value)| {
+                // Half of the updates are on the keys of the tree.
+                let key = match key_values.get(index % (2 * key_values.len() + 1)) {
+                    Some((key, _)) => *key,
+                    None => key,
+                };
+                (MerkleTreeKey::new(key), value)
+            })
+            .collect::<Vec<_>>();
+        let iter = key_values
+            .iter()
+            .map(|(key, value)| (MerkleTreeKey::new(key), value));
+        let mut expected_tree = MerkleTree::from_set(StorageMap::<TestTable>::new(), iter)
+            .expect("Unable to create Merkle tree");
+        for (key, value) in &updates {
+            match value {
+                Some(value) => expected_tree.update(*key, value.as_ref()).unwrap(),
+                None => expected_tree.delete(*key).unwrap(),
+            }
+        }
+
+        // When
+        tree.update_batch(updates).unwrap();
+
+        // Then
+        prop_assert_eq!(tree.root(), expected_tree.root());
+    }
+
+    #[test]
+    fn merkle_tree__leaves__yields_leaves_ordered_by_key((key_values, tree) in random_tree(0, 100)) {
+        // Given
+        let expected = key_values
+            .iter()
+            .map(|(key, value)| (*MerkleTreeKey::new(key), sum(value)))
+            .collect::<BTreeMap<_, _>>();
+
+        // When
+        let leaves = tree.leaves().collect::<Result<Vec<_>, _>>().unwrap();
+
+        // Then
+        prop_assert_eq!(leaves, expected.into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn range_proof__verify__returns_true_for_exactly_the_leaves_in_range(
+        (key_values, tree) in random_tree(0, 100),
+        bounds in [(any::<Key>(), any::<usize>()), (any::<Key>(), any::<usize>())],
+    ) {
+        let root = tree.root();
+
+        // Given
+        // Half of the bounds are keys of the tree.
+        let [start, end] = bounds.map(|(key, index)| {
+            let key = match key_values.get(index % (2 * key_values.len() + 1)) {
+                Some((key, _)) => *key,
+                None => key,
+            };
+            MerkleTreeKey::new(key)
+        });
+        let (start, end) = if *start <= *end { (start, end) } else { (end, start) };
+        let leaves = key_values
+            .iter()
+            .map(|(key, value)| (MerkleTreeKey::new(key), *value))
+            .filter(|(key, _)| (*start..=*end).contains(key))
+            .map(|leaf| (*leaf.0, leaf))
+            .collect::<BTreeMap<_, _>>()
+            .into_values()
+            .collect::<Vec<_>>();
+        let proof = tree.generate_range_proof(&start, &end).unwrap();
+
+        // When
+        let verification = proof.verify(&root, &start, &end, &leaves);
+        let verifications_without_one_leaf = (0..leaves.len())
+            .map(|index| {
+                let mut leaves = leaves.clone();
+                leaves.remove(index);
+                proof.verify(&root, &start, &end, &leaves)
+            })
+            .collect::<Vec<_>>();
+
+        // Then
+        prop_assert!(verification);
+        prop_assert!(verifications_without_one_leaf.iter().all(|verification| !verification));
+    }
 }
@@ -1,4 +1,7 @@
-use fuel_merkle::binary::verify;
+use fuel_merkle::binary::{
+    verify,
+    verify_consistency,
+};
 use serde::{
     Deserialize,
     Serialize,
@@ -68,3 +71,47 @@ impl ProofTest {
         Ok(())
     }
 }
+
+#[derive(Serialize, Deserialize)]
+pub struct ConsistencyProofTest {
+    pub name: String,
+    pub function_name: String,
+    pub description: String,
+    pub old_root: EncodedValue,
+    pub old_leaves_count: u64,
+    pub root: EncodedValue,
+    pub leaves_count: u64,
+    pub proof_set: Vec<EncodedValue>,
+    pub expected_verification: bool,
+}
+
+impl ConsistencyProofTest {
+    pub fn execute(self) -> Result<(), TestError> {
+        let old_root: Bytes32 =
+            self.old_root.into_bytes()?.as_slice().try_into().unwrap();
+        let root: Bytes32 = self.root.into_bytes()?.as_slice().try_into().unwrap();
+        let proof_set = self
+            .proof_set
+            .iter()
+            .cloned()
+            .map(|v| v.into_bytes().unwrap().as_slice().try_into().unwrap())
+            .collect::<Vec<Bytes32>>();
+        let verification = verify_consistency(
+            &old_root,
+            self.old_leaves_count,
+            &root,
+            self.leaves_count,
+            &proof_set,
+        );
+        let expected_verification = self.expected_verification;
+
+        if verification != expected_verification {
+            return Err(TestError::Failed(
+                self.name,
+                format!("Verification {verification} does not match expected verification {expected_verification}",),
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -2,14 +2,26 @@ use fuel_merkle::{
     common::Bytes32,
     sparse::{
         in_memory,
+        proof::{
+            ExclusionLeaf,
+            ExclusionLeafData,
+            MultiProof,
+            MultiProofLeaf,
+            Proof,
+        },
         MerkleTreeKey,
     },
 };
-use serde::Deserialize;
+use fuel_types::canonical;
+use serde::{
+    Deserialize,
+    Serialize,
+};
 use std::convert::TryInto;
 
 use crate::data::{
     EncodedValue,
+    Encoding,
     TestError,
 };
 
@@ -21,6 +33,8 @@ trait MerkleTreeTestAdaptor {
     fn update(&mut self, key: &Bytes32, data: &[u8]);
     fn delete(&mut self, key: &Bytes32);
     fn root(&self) -> Bytes32;
+    fn generate_proof(&self, key: &MerkleTreeKey) -> Option<Proof>;
+    fn generate_multiproof(&self, keys: &[MerkleTreeKey]) -> Option<MultiProof>;
 }
 
 #[derive(Deserialize)]
@@ -94,6 +108,202 @@ impl MerkleTreeTestAdaptor for InMemoryMerkleTreeTestAdaptor {
     fn root(&self) -> Bytes32 {
         self.tree.as_ref().root()
     }
+
+    fn generate_proof(&self, key: &MerkleTreeKey) -> Option<Proof> {
+        self.tree.as_ref().generate_proof(key)
+    }
+
+    fn generate_multiproof(&self, keys: &[MerkleTreeKey]) -> Option<MultiProof> {
+        self.tree.as_ref().generate_multiproof(keys)
+    }
+}
+
+fn decode_bytes32(value: EncodedValue) -> Result<Bytes32, TestError> {
+    value
+        .into_bytes()?
+        .try_into()
+        .map_err(|_| TestError::DecodingError)
+}
+
+/// A key of a multiproof, with its data if it is included in the tree.
+#[derive(Serialize, Deserialize)]
+pub struct MultiProofKey {
+    pub key: EncodedValue,
+    pub data: Option<EncodedValue>,
+}
+
+/// The expected proof of a key of a multiproof.
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MultiProofLeafData {
+    Inclusion {
+        depth: u32,
+    },
+    Exclusion {
+        depth: u32,
+        leaf_key: EncodedValue,
+        leaf_value: EncodedValue,
+    },
+    ExclusionPlaceholder {
+        depth: u32,
+    },
+}
+
+impl MultiProofLeafData {
+    pub fn from_leaf(leaf: &MultiProofLeaf) -> Self {
+        match leaf {
+            MultiProofLeaf::Inclusion { depth } => Self::Inclusion { depth: *depth },
+            MultiProofLeaf::Exclusion {
+                depth,
+                leaf: ExclusionLeaf::Leaf(data),
+            } => Self::Exclusion {
+                depth: *depth,
+                leaf_key: EncodedValue::from_raw(data.leaf_key, Encoding::Hex),
+                leaf_value: EncodedValue::from_raw(data.leaf_value, Encoding::Hex),
+            },
+            MultiProofLeaf::Exclusion {
+                depth,
+                leaf: ExclusionLeaf::Placeholder,
+            } => Self::ExclusionPlaceholder { depth: *depth },
+        }
+    }
+
+    fn into_leaf(self) -> Result<MultiProofLeaf, TestError> {
+        let leaf = match self {
+            Self::Inclusion { depth } => MultiProofLeaf::Inclusion { depth },
+            Self::Exclusion {
+                depth,
+                leaf_key,
+                leaf_value,
+            } => MultiProofLeaf::Exclusion {
+                depth,
+                leaf: ExclusionLeaf::Leaf(ExclusionLeafData {
+                    leaf_key: decode_bytes32(leaf_key)?,
+                    leaf_value: decode_bytes32(leaf_value)?,
+                }),
+            },
+            Self::ExclusionPlaceholder { depth } => MultiProofLeaf::Exclusion {
+                depth,
+                leaf: ExclusionLeaf::Placeholder,
+            },
+        };
+        Ok(leaf)
+    }
+}
+
+/// The expected multiproof of the keys against the tree built by the test steps.
+#[derive(Serialize, Deserialize)]
+pub struct MultiProofTest {
+    pub keys: Vec<MultiProofKey>,
+    pub leaves: Vec<MultiProofLeafData>,
+    pub proof_set: Vec<EncodedValue>,
+}
+
+impl MultiProofTest {
+    fn execute(
+        self,
+        name: String,
+        root: &Bytes32,
+        tree: &dyn MerkleTreeTestAdaptor,
+    ) -> Result<(), TestError> {
+        let entries = self
+            .keys
+            .into_iter()
+            .map(|MultiProofKey { key, data }| {
+                let key = MerkleTreeKey::new_without_hash(decode_bytes32(key)?);
+                let data = data.map(EncodedValue::into_bytes).transpose()?;
+                Ok((key, data))
+            })
+            .collect::<Result<Vec<_>, TestError>>()?;
+        let keys = entries.iter().map(|(key, _)| *key).collect::<Vec<_>>();
+        let expected = MultiProof {
+            leaves: self
+                .leaves
+                .into_iter()
+                .map(MultiProofLeafData::into_leaf)
+                .collect::<Result<_, _>>()?,
+            proof_set: self
+                .proof_set
+                .into_iter()
+                .map(decode_bytes32)
+                .collect::<Result<_, _>>()?,
+        };
+
+        let proof = tree.generate_multiproof(&keys).ok_or_else(|| {
+            TestError::Failed(name.clone(), "Failed to generate the multiproof".into())
+        })?;
+
+        if proof != expected {
+            return Err(TestError::Failed(
+                name,
+                format!("Multiproof {proof:?} does not match expected multiproof {expected:?}"),
+            ))
+        }
+
+        if !proof.verify(root, &entries) {
+            return Err(TestError::Failed(
+                name,
+                "Multiproof verification failed".into(),
+            ))
+        }
+
+        Ok(())
+    }
+}
+
+/// The expected canonical encoding of the proof of the key against the tree built
+/// by the test steps.
+#[derive(Serialize, Deserialize)]
+pub struct ProofTest {
+    pub key: EncodedValue,
+    pub data: Option<EncodedValue>,
+    pub encoded_proof: EncodedValue,
+}
+
+impl ProofTest {
+    fn execute(
+        self,
+        name: String,
+        root: &Bytes32,
+        tree: &dyn MerkleTreeTestAdaptor,
+    ) -> Result<(), TestError> {
+        let key = MerkleTreeKey::new_without_hash(decode_bytes32(self.key)?);
+        let data = self.data.map(EncodedValue::into_bytes).transpose()?;
+        let expected_encoded_proof = self.encoded_proof.into_bytes()?;
+
+        let proof = tree.generate_proof(&key).ok_or_else(|| {
+            TestError::Failed(name.clone(), "Failed to generate the proof".into())
+        })?;
+        let encoded_proof = canonical::Serialize::to_bytes(&proof);
+        if encoded_proof != expected_encoded_proof {
+            return Err(TestError::Failed(
+                name,
+                format!(
+                    "Encoded proof 0x{} does not match expected encoded proof 0x{}",
+                    hex::encode(encoded_proof),
+                    hex::encode(expected_encoded_proof)
+                ),
+            ))
+        }
+
+        let decoded_proof: Proof =
+            canonical::Deserialize::from_bytes(&expected_encoded_proof).map_err(
+                |err| TestError::Failed(name.clone(), format!("Decoding failed: {err}")),
+            )?;
+        let verification = match (&decoded_proof, data) {
+            (Proof::Inclusion(proof), Some(data)) => proof.verify(root, &key, &data),
+            (Proof::Exclusion(proof), None) => proof.verify(root, &key),
+            _ => false,
+        };
+        if decoded_proof != proof || !verification {
+            return Err(TestError::Failed(
+                name,
+                "Decoded proof verification failed".into(),
+            ))
+        }
+
+        Ok(())
+    }
 }
 
 #[derive(Deserialize)]
@@ -101,6 +311,8 @@ pub struct Test {
     name: String,
     expected_root: EncodedValue,
     steps: Vec<Step>,
+    multiproof: Option<MultiProofTest>,
+    proof: Option<ProofTest>,
 }
 
 impl Test {
@@ -115,10 +327,8 @@ impl Test {
         let root = tree.root();
         let expected_root: Bytes32 = self.expected_root.into_bytes()?.try_into().unwrap();
 
-        if root == expected_root {
-            Ok(())
-        } else {
-            Err(TestError::Failed(
+        if root != expected_root {
+            return Err(TestError::Failed(
                 self.name,
                 format!(
                     "Root 0x{} does not match expected root 0x{}",
@@ -127,5 +337,14 @@ impl Test {
                 ),
             ))
         }
+
+        if let Some(multiproof) = self.multiproof {
+            multiproof.execute(self.name.clone(), &root, &tree)?;
+        }
+
+        match self.proof {
+            Some(proof) => proof.execute(self.name, &root, &tree),
+            None => Ok(()),
+        }
     }
 }
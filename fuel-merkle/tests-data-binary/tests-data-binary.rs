@@ -4,7 +4,10 @@ use std::{
     path::Path,
 };
 
-use fuel_merkle_test_helpers::data::binary::ProofTest;
+use fuel_merkle_test_helpers::data::binary::{
+    ConsistencyProofTest,
+    ProofTest,
+};
 
 fn test(path: &Path) -> datatest_stable::Result<()> {
     let data_file = File::open(path)?;
@@ -12,4 +15,17 @@ fn test(path: &Path) -> datatest_stable::Result<()> {
     test.execute().map_err(|e| Box::new(e) as Box<dyn Error>)
 }
 
-datatest_stable::harness!(test, "./tests-data-binary/fixtures", r"^.*/*");
+fn test_consistency(path: &Path) -> datatest_stable::Result<()> {
+    let data_file = File::open(path)?;
+    let test: ConsistencyProofTest = serde_yaml::from_reader(data_file)?;
+    test.execute().map_err(|e| Box::new(e) as Box<dyn Error>)
+}
+
+datatest_stable::harness!(
+    test,
+    "./tests-data-binary/fixtures",
+    r"^.*/*",
+    test_consistency,
+    "./tests-data-binary/consistency-fixtures",
+    r"^.*/*",
+);
@@ -30,6 +30,13 @@ impl<'a, T: StorageInspect<Type> + ?Sized, Type: Mappable> StorageInspect<Type>
     fn contains_key(&self, key: &Type::Key) -> Result<bool, Self::Error> {
         <T as StorageInspect<Type>>::contains_key(self, key)
     }
+
+    fn get_many(
+        &self,
+        keys: &[&Type::Key],
+    ) -> Result<Vec<Option<Cow<'_, Type::OwnedValue>>>, Self::Error> {
+        <T as StorageInspect<Type>>::get_many(self, keys)
+    }
 }
 
 impl<'a, T: StorageInspect<Type> + ?Sized, Type: Mappable> StorageInspect<Type>
@@ -47,6 +54,13 @@ impl<'a, T: StorageInspect<Type> + ?Sized, Type: Mappable> StorageInspect<Type>
     fn contains_key(&self, key: &Type::Key) -> Result<bool, Self::Error> {
         <T as StorageInspect<Type>>::contains_key(self, key)
     }
+
+    fn get_many(
+        &self,
+        keys: &[&Type::Key],
+    ) -> Result<Vec<Option<Cow<'_, Type::OwnedValue>>>, Self::Error> {
+        <T as StorageInspect<Type>>::get_many(self, keys)
+    }
 }
 
 impl<'a, T: StorageMutate<Type> + ?Sized, Type: Mappable> StorageMutate<Type>
@@ -75,6 +89,13 @@ impl<'a, T: StorageMutate<Type> + ?Sized, Type: Mappable> StorageMutate<Type>
     fn take(&mut self, key: &Type::Key) -> Result<Option<Type::OwnedValue>, Self::Error> {
         <T as StorageMutate<Type>>::take(self, key)
     }
+
+    fn apply_batch(
+        &mut self,
+        changes: &[(&Type::Key, Option<&Type::Value>)],
+    ) -> Result<(), Self::Error> {
+        <T as StorageMutate<Type>>::apply_batch(self, changes)
+    }
 }
 
 impl<'a, T: StorageSize<Type> + ?Sized, Type: Mappable> StorageSize<Type> for &'a T {
@@ -182,6 +203,15 @@ impl<'a, T: StorageInspect<Type>, Type: Mappable> StorageRef<'a, T, Type> {
     pub fn contains_key(self, key: &Type::Key) -> Result<bool, T::Error> {
         self.0.contains_key(key)
     }
+
+    #[inline(always)]
+    #[allow(clippy::type_complexity)]
+    pub fn get_many(
+        self,
+        keys: &[&Type::Key],
+    ) -> Result<Vec<Option<Cow<'a, Type::OwnedValue>>>, T::Error> {
+        self.0.get_many(keys)
+    }
 }
 
 impl<'a, T, Type: Mappable> StorageRef<'a, T, Type> {
@@ -228,6 +258,17 @@ impl<'a, T: StorageInspect<Type>, Type: Mappable> StorageMut<'a, T, Type> {
     pub fn contains_key(self, key: &Type::Key) -> Result<bool, T::Error> {
         self.0.contains_key(key)
     }
+
+    #[inline(always)]
+    #[allow(clippy::type_complexity)]
+    pub fn get_many(
+        self,
+        keys: &[&Type::Key],
+    ) -> Result<Vec<Option<Cow<'a, Type::OwnedValue>>>, T::Error> {
+        // Workaround, because compiler doesn't convert the lifetime to `'a` by default.
+        let self_: &'a T = self.0;
+        self_.get_many(keys)
+    }
 }
 
 impl<'a, T, Type> StorageMut<'a, T, Type>
@@ -258,6 +299,14 @@ where
     pub fn take(self, key: &Type::Key) -> Result<Option<Type::OwnedValue>, T::Error> {
         StorageMutate::take(self.0, key)
     }
+
+    #[inline(always)]
+    pub fn apply_batch(
+        self,
+        changes: &[(&Type::Key, Option<&Type::Value>)],
+    ) -> Result<(), T::Error> {
+        StorageMutate::apply_batch(self.0, changes)
+    }
 }
 
 impl<'a, T, Type: Mappable> StorageMut<'a, T, Type> {
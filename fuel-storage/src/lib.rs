@@ -66,6 +66,18 @@ pub trait StorageInspect<Type: Mappable> {
 
     /// Return `true` if there is a `Key` mapping to a value in the storage.
     fn contains_key(&self, key: &Type::Key) -> Result<bool, Self::Error>;
+
+    /// Retrieve `Cow<Value>` for each of the `keys`, in the order of the keys.
+    ///
+    /// The default implementation calls `get` for each key. Storages able to read
+    /// several keys at once, like databases with batched reads, should override it.
+    #[allow(clippy::type_complexity)]
+    fn get_many(
+        &self,
+        keys: &[&Type::Key],
+    ) -> Result<Vec<Option<Cow<'_, Type::OwnedValue>>>, Self::Error> {
+        keys.iter().map(|key| self.get(key)).collect()
+    }
 }
 
 /// Base storage trait for Fuel infrastructure.
@@ -101,6 +113,26 @@ pub trait StorageMutate<Type: Mappable>: StorageInspect<Type> {
     /// Return `Ok(Some(Value))` if the value was present. If the key wasn't found, return
     /// `Ok(None)`.
     fn take(&mut self, key: &Type::Key) -> Result<Option<Type::OwnedValue>, Self::Error>;
+
+    /// Apply the `changes`: a change with `Some(Value)` appends the `Key->Value`
+    /// mapping to the storage, and a change with `None` removes the `Key->Value`
+    /// mapping from the storage.
+    ///
+    /// The default implementation calls `insert` or `remove` for each change, so a
+    /// failure may leave a part of the changes applied. Storages able to apply several
+    /// changes atomically, like databases with write batches, should override it.
+    fn apply_batch(
+        &mut self,
+        changes: &[(&Type::Key, Option<&Type::Value>)],
+    ) -> Result<(), Self::Error> {
+        for (key, value) in changes {
+            match value {
+                Some(value) => self.insert(key, value)?,
+                None => self.remove(key)?,
+            }
+        }
+        Ok(())
+    }
 }
 
 /// Base storage trait for Fuel infrastructure.
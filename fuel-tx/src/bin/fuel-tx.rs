@@ -0,0 +1,132 @@
+//! Command line tools for Fuel transactions.
+//!
+//! ```text
+//! fuel-tx explain <TX> --params <FILE> [--block-height <HEIGHT>] [--gas-price <PRICE>]
+//! fuel-tx schema <TYPE>
+//! ```
+//!
+//! - `TX`: the canonical-encoded transaction in hex, or `-` to read it from stdin.
+//! - `--params`: the consensus parameters of the chain as JSON.
+//! - `TYPE`: one of `transaction`, `input`, `output`, `receipt` or
+//!   `consensus_parameters`. The command prints the JSON Schema of the [`fuel_tx::json`]
+//!   representation of the type.
+
+use std::{
+    env,
+    fs,
+    io::{
+        self,
+        Read,
+    },
+    process::ExitCode,
+};
+
+use fuel_tx::{
+    explain::explain,
+    json,
+    ConsensusParameters,
+    Input,
+    Output,
+    Receipt,
+    Transaction,
+};
+use fuel_types::canonical::Deserialize;
+
+const USAGE: &str = "Usage: fuel-tx explain <TX> --params <FILE> [--block-height \
+                     <HEIGHT>] [--gas-price <PRICE>]\n       fuel-tx schema <TYPE>";
+
+fn main() -> ExitCode {
+    match run(env::args().skip(1).collect()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: Vec<String>) -> Result<(), String> {
+    let mut args = args.into_iter();
+    match args.next().as_deref() {
+        Some("explain") => {}
+        Some("schema") => return schema(args),
+        Some("-h" | "--help") => {
+            println!("{USAGE}");
+            return Ok(())
+        }
+        _ => return Err(USAGE.to_string()),
+    }
+
+    let mut tx = None;
+    let mut params = None;
+    let mut block_height = 0u32;
+    let mut gas_price = 0;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--params" => {
+                let path = args.next().ok_or(USAGE)?;
+                let json =
+                    fs::read(&path).map_err(|e| format!("Failed to read {path}: {e}"))?;
+                params = serde_json::from_slice::<ConsensusParameters>(&json)
+                    .map(Some)
+                    .map_err(|e| format!("Invalid consensus parameters: {e}"))?;
+            }
+            "--block-height" => {
+                block_height = args
+                    .next()
+                    .and_then(|height| height.parse().ok())
+                    .ok_or_else(|| format!("Invalid block height\n{USAGE}"))?;
+            }
+            "--gas-price" => {
+                gas_price = args
+                    .next()
+                    .and_then(|price| price.parse().ok())
+                    .ok_or_else(|| format!("Invalid gas price\n{USAGE}"))?;
+            }
+            _ if tx.is_none() => tx = Some(arg),
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+
+    let params = params.ok_or(USAGE)?;
+    let tx = match tx.as_deref() {
+        Some("-") => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| format!("Failed to read stdin: {e}"))?;
+            input
+        }
+        Some(tx) => tx.to_string(),
+        None => return Err(USAGE.to_string()),
+    };
+    let tx = tx.trim();
+    let bytes = hex::decode(tx.strip_prefix("0x").unwrap_or(tx))
+        .map_err(|e| format!("Invalid hex: {e}"))?;
+    let tx = Transaction::from_bytes(&bytes)
+        .map_err(|e| format!("Invalid transaction: {e:?}"))?;
+
+    print!("{}", explain(&tx, &params, block_height.into(), gas_price));
+
+    Ok(())
+}
+
+fn schema(mut args: impl Iterator<Item = String>) -> Result<(), String> {
+    let schema = match (args.next().as_deref(), args.next()) {
+        (Some("transaction"), None) => json::schema::<Transaction>(),
+        (Some("input"), None) => json::schema::<Input>(),
+        (Some("output"), None) => json::schema::<Output>(),
+        (Some("receipt"), None) => json::schema::<Receipt>(),
+        (Some("consensus_parameters"), None) => json::schema::<ConsensusParameters>(),
+        _ => return Err(USAGE.to_string()),
+    }
+    .map_err(|e| format!("Failed to generate the schema: {e}"))?;
+
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&schema).expect("the schema is valid JSON")
+    );
+
+    Ok(())
+}
@@ -5,6 +5,8 @@ use crate::{
         field::{
             self,
             BytecodeWitnessIndex,
+            Expiration,
+            FeePayer,
             Maturity,
             Tip,
             Witnesses,
@@ -39,7 +41,10 @@ use crate::{
 
 use crate::{
     Cacheable,
+    FeeEstimate,
+    FeeEstimateError,
     Signable,
+    SignerType,
 };
 
 use crate::{
@@ -69,6 +74,13 @@ use fuel_types::{
     Word,
 };
 
+mod fund;
+
+pub use fund::{
+    CoinSelection,
+    FundError,
+};
+
 pub trait BuildableAloc
 where
     Self: Default + Clone + Executable + Chargeable + field::Policies + Into<Transaction>,
@@ -371,6 +383,20 @@ impl<Tx: Buildable> TransactionBuilder<Tx> {
         self
     }
 
+    pub fn expiration(&mut self, expiration: BlockHeight) -> &mut Self {
+        self.tx.set_expiration(expiration);
+
+        self
+    }
+
+    /// Sets the input at `input_index` as the only payer of the transaction fee, with
+    /// its refund going to the change output at `change_index`.
+    pub fn fee_payer(&mut self, input_index: u16, change_index: u16) -> &mut Self {
+        self.tx.set_fee_payer(Some((input_index, change_index)));
+
+        self
+    }
+
     pub fn witness_limit(&mut self, witness_limit: Word) -> &mut Self {
         self.tx.set_witness_limit(witness_limit);
 
@@ -472,6 +498,14 @@ impl<Tx: Buildable> TransactionBuilder<Tx> {
         self
     }
 
+    /// Registers a signing key and returns the index of its witness.
+    ///
+    /// Inputs added with [`Self::add_input`] or [`Self::fund`] that refer to this
+    /// witness are signed when the transaction is finalized.
+    pub fn add_signer(&mut self, secret: SecretKey) -> u16 {
+        self.upsert_secret(secret)
+    }
+
     /// Adds a secret to the builder, and adds a corresponding witness if it's a new entry
     fn upsert_secret(&mut self, secret_key: SecretKey) -> u16 {
         let witness_len = u16::try_from(self.witnesses().len())
@@ -511,6 +545,26 @@ impl<Tx: Buildable> TransactionBuilder<Tx> {
 
         tx
     }
+
+    /// Estimates the size and the fee bounds of the transaction at `gas_price` once it
+    /// is signed by the builder's keys and the external `signers`.
+    ///
+    /// See [`Chargeable::estimate_fee`].
+    pub fn estimate_fee(
+        &self,
+        signers: &[(u16, SignerType)],
+        gas_price: Word,
+    ) -> Result<FeeEstimate, FeeEstimateError>
+    where
+        Tx: fuel_types::canonical::Serialize,
+    {
+        self.tx.estimate_fee(
+            self.params.gas_costs(),
+            self.params.fee_params(),
+            signers,
+            gas_price,
+        )
+    }
 }
 
 impl<Tx: field::Outputs> TransactionBuilder<Tx> {
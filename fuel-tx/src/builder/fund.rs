@@ -0,0 +1,823 @@
+use super::{
+    Buildable,
+    TransactionBuilder,
+};
+use crate::{
+    field::{
+        self,
+        FeePayer,
+        MaxFeeLimit,
+    },
+    FeeEstimateError,
+    Input,
+    Output,
+};
+
+use alloc::{
+    collections::BTreeMap,
+    vec::Vec,
+};
+use fuel_types::{
+    canonical::Serialize,
+    Address,
+    AssetId,
+    Word,
+};
+
+/// The maximum number of fee estimations done by [`TransactionBuilder::fund`].
+///
+/// Every estimation can only increase the fee, so it converges quickly in practice.
+const MAX_FUND_ITERATIONS: usize = 16;
+
+/// The strategy used to pick the inputs of an asset while funding a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoinSelection {
+    /// Picks the largest inputs first.
+    LargestFirst,
+    /// Picks as few inputs as possible, then swaps them for smaller ones to leave a
+    /// small change. The change isn't guaranteed to be the smallest possible.
+    MinimizeInputs,
+    /// Picks random inputs until the target is covered, then keeps adding random
+    /// inputs while they bring the total closer to twice the target. This produces
+    /// change outputs of a size useful for later transactions.
+    #[cfg(feature = "random")]
+    RandomImprove {
+        /// Seed of the random selection.
+        seed: u64,
+    },
+}
+
+/// The error returned by [`TransactionBuilder::fund`].
+#[derive(Debug, Clone, PartialEq, Eq, derive_more::Display)]
+#[non_exhaustive]
+pub enum FundError {
+    /// The available inputs don't cover the outputs and the fee.
+    #[display(
+        fmt = "Insufficient funds of {asset_id}: required {required}, available {available}"
+    )]
+    InsufficientFunds {
+        /// The missing asset.
+        asset_id: AssetId,
+        /// The amount still required after the transaction inputs.
+        required: Word,
+        /// The total amount of the available inputs.
+        available: Word,
+    },
+    /// The fee estimation of the funded transaction failed.
+    #[display(fmt = "The fee estimation failed: {_0}")]
+    FeeEstimate(FeeEstimateError),
+    /// The fee or the balances overflow.
+    #[display(fmt = "The fee or the balances overflow")]
+    BalanceOverflow,
+    /// The fee kept increasing with the selected inputs.
+    #[display(fmt = "The fee didn't converge")]
+    FeeNotConverged,
+    /// The `FeePayer` policy doesn't point at a coin or a message coin of the base
+    /// asset in the transaction.
+    #[display(fmt = "The fee payer input {index} isn't a coin of the base asset")]
+    FeePayerNotFound {
+        /// The input index of the policy.
+        index: u16,
+    },
+    /// The available predicate input at `index` has no `predicate_gas_used`, so the fee
+    /// of its predicate can't be accounted.
+    #[display(fmt = "The predicate gas of the available input {index} isn't estimated")]
+    PredicateGasNotEstimated {
+        /// The position of the input in the available inputs.
+        index: usize,
+    },
+    /// The fee payer input doesn't cover the fee on its own.
+    #[display(
+        fmt = "Insufficient fee payer input: required {required}, available {available}"
+    )]
+    InsufficientFeePayer {
+        /// The fee of the funded transaction.
+        required: Word,
+        /// The amount of the fee payer input.
+        available: Word,
+    },
+}
+
+impl<Tx> TransactionBuilder<Tx>
+where
+    Tx: Buildable + field::Outputs + Serialize,
+{
+    /// Adds inputs from `available` to cover the coin outputs and the fee of the
+    /// transaction at `gas_price`, adds an [`Output::Change`] for every funded asset
+    /// without one, and sets the `MaxFee` policy.
+    ///
+    /// The fee is computed with [`Chargeable::estimate_fee`] and the gas costs of the
+    /// builder's consensus parameters. Since the selected inputs and change outputs
+    /// increase the fee, the selection is repeated until the fee converges.
+    ///
+    /// Only coins and message coins are used. Inputs already in the transaction are
+    /// skipped. Signed inputs must refer to a witness of the builder; empty witnesses
+    /// are accounted as signatures. The fee of predicate inputs is computed from their
+    /// `predicate_gas_used`, which must be estimated beforehand, like with
+    /// `estimate_predicates` of `fuel-vm`; otherwise
+    /// [`FundError::PredicateGasNotEstimated`] is returned.
+    ///
+    /// If the `FeePayer` policy is set, the fee payer input pays the fee and isn't
+    /// counted towards the outputs, so it must already be in the transaction and cover
+    /// the fee on its own. Otherwise [`FundError::FeePayerNotFound`] or
+    /// [`FundError::InsufficientFeePayer`] is returned.
+    ///
+    /// [`Chargeable::estimate_fee`]: crate::Chargeable::estimate_fee
+    pub fn fund(
+        &mut self,
+        available: impl Iterator<Item = Input>,
+        strategy: CoinSelection,
+        gas_price: Word,
+    ) -> Result<&mut Self, FundError> {
+        let base_asset_id = *self.params.base_asset_id();
+
+        let fee_payer_amount = self
+            .tx
+            .fee_payer()
+            .map(|index| {
+                self.tx
+                    .fee_payer_input()
+                    .filter(|input| {
+                        is_spendable(input)
+                            && input.asset_id(&base_asset_id) == Some(&base_asset_id)
+                    })
+                    .map(amount)
+                    .ok_or(FundError::FeePayerNotFound { index })
+            })
+            .transpose()?;
+
+        let mut candidates = BTreeMap::<AssetId, Vec<Input>>::new();
+        for (index, input) in available.enumerate() {
+            if !is_spendable(&input)
+                || self.tx.inputs().iter().any(|i| same_source(i, &input))
+            {
+                continue
+            }
+
+            if input.predicate_gas_used() == Some(0) {
+                return Err(FundError::PredicateGasNotEstimated { index })
+            }
+
+            if let Some(asset_id) = input.asset_id(&base_asset_id) {
+                candidates.entry(*asset_id).or_default().push(input);
+            }
+        }
+
+        let mut max_fee = 0;
+        for _ in 0..MAX_FUND_ITERATIONS {
+            let mut tx = self.tx.clone();
+            tx.set_max_fee_limit(max_fee);
+
+            for (asset_id, required) in missing_balances(&tx, &base_asset_id, max_fee)? {
+                let candidates = candidates.get(&asset_id).map(Vec::as_slice);
+                let candidates = candidates.unwrap_or_default();
+
+                let selected =
+                    strategy.select(candidates, required).ok_or_else(|| {
+                        let available = candidates
+                            .iter()
+                            .filter_map(Input::amount)
+                            .fold(0, Word::saturating_add);
+
+                        FundError::InsufficientFunds {
+                            asset_id,
+                            required,
+                            available,
+                        }
+                    })?;
+
+                tx.inputs_mut().extend(selected);
+            }
+
+            add_change_outputs(&mut tx, &base_asset_id);
+
+            let fee = tx
+                .estimate_fee(
+                    self.params.gas_costs(),
+                    self.params.fee_params(),
+                    &[],
+                    gas_price,
+                )
+                .map_err(FundError::FeeEstimate)?
+                .max_fee();
+
+            if fee <= max_fee {
+                if let Some(available) = fee_payer_amount.filter(|a| *a < max_fee) {
+                    return Err(FundError::InsufficientFeePayer {
+                        required: max_fee,
+                        available,
+                    })
+                }
+
+                self.tx = tx;
+                return Ok(self)
+            }
+
+            max_fee = fee;
+        }
+
+        Err(FundError::FeeNotConverged)
+    }
+}
+
+impl CoinSelection {
+    /// Selects inputs from `candidates` whose total amount is at least `target`.
+    ///
+    /// Returns `None` if all the candidates together don't cover the target.
+    pub fn select(&self, candidates: &[Input], target: Word) -> Option<Vec<Input>> {
+        let mut sorted = candidates.to_vec();
+        sorted.sort_by_key(|input| core::cmp::Reverse(amount(input)));
+
+        let selected = match self {
+            Self::LargestFirst => largest_first(&sorted, target)?,
+            Self::MinimizeInputs => minimize_inputs(&sorted, target)?,
+            #[cfg(feature = "random")]
+            Self::RandomImprove { seed } => random_improve(&sorted, target, *seed)?,
+        };
+
+        Some(selected.into_iter().cloned().collect())
+    }
+}
+
+/// Coins and message coins, the inputs that can pay for outputs and fees.
+fn is_spendable(input: &Input) -> bool {
+    input.is_coin() || input.is_message_coin_signed() || input.is_message_coin_predicate()
+}
+
+fn amount(input: &Input) -> Word {
+    input.amount().unwrap_or_default()
+}
+
+fn total<'a>(inputs: impl IntoIterator<Item = &'a Input>) -> Word {
+    inputs.into_iter().map(amount).fold(0, Word::saturating_add)
+}
+
+/// `candidates` must be sorted by decreasing amount.
+fn largest_first(candidates: &[Input], target: Word) -> Option<Vec<&Input>> {
+    let mut sum: Word = 0;
+    let mut selected = Vec::new();
+
+    for input in candidates {
+        if sum >= target {
+            break
+        }
+        sum = sum.saturating_add(amount(input));
+        selected.push(input);
+    }
+
+    (sum >= target).then_some(selected)
+}
+
+/// `candidates` must be sorted by decreasing amount.
+///
+/// The largest inputs give the minimal number of inputs. Each of them is then swapped,
+/// from the smallest to the largest, with the smallest unselected input that keeps the
+/// target covered.
+fn minimize_inputs(candidates: &[Input], target: Word) -> Option<Vec<&Input>> {
+    let count = largest_first(candidates, target)?.len();
+    let (selected, unselected) = candidates.split_at(count);
+    let mut selected: Vec<&Input> = selected.iter().collect();
+    let mut sum = total(selected.iter().copied());
+
+    // The amount required from a swapped input never decreases from one swap to the
+    // next, so every swap can pick among the unselected inputs larger than the
+    // previously swapped one.
+    let mut available = unselected.len();
+    for input in selected.iter_mut().rev() {
+        let others = sum.saturating_sub(amount(input));
+        let required = target.saturating_sub(others);
+        let covering = unselected
+            .get(..available)
+            .unwrap_or_default()
+            .partition_point(|candidate| amount(candidate) >= required);
+        let Some(position) = covering.checked_sub(1) else {
+            break
+        };
+
+        *input = &unselected[position];
+        sum = others.saturating_add(amount(input));
+        available = position;
+    }
+
+    selected.reverse();
+    Some(selected)
+}
+
+#[cfg(feature = "random")]
+fn random_improve(candidates: &[Input], target: Word, seed: u64) -> Option<Vec<&Input>> {
+    use rand::{
+        seq::SliceRandom,
+        SeedableRng,
+    };
+
+    if total(candidates) < target {
+        return None
+    }
+
+    let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+    let mut shuffled: Vec<&Input> = candidates.iter().collect();
+    shuffled.shuffle(&mut rng);
+
+    let mut sum: Word = 0;
+    let mut selected = Vec::new();
+    let mut rest = shuffled.into_iter();
+
+    for input in rest.by_ref() {
+        if sum >= target {
+            break
+        }
+        sum = sum.saturating_add(amount(input));
+        selected.push(input);
+    }
+
+    let ideal = target.saturating_mul(2);
+    let limit = target.saturating_mul(3);
+
+    for input in rest {
+        let improved = sum.saturating_add(amount(input));
+
+        if improved <= limit && improved.abs_diff(ideal) < sum.abs_diff(ideal) {
+            sum = improved;
+            selected.push(input);
+        }
+    }
+
+    Some(selected)
+}
+
+/// Whether both inputs spend the same coin or message.
+fn same_source(a: &Input, b: &Input) -> bool {
+    match (a.utxo_id(), b.utxo_id()) {
+        (Some(a), Some(b)) => a == b,
+        _ => a.nonce().is_some() && a.nonce() == b.nonce(),
+    }
+}
+
+/// The amount of every asset missing to cover the coin outputs and `max_fee`. The
+/// fee of a transaction with a fee payer is paid by the fee payer input, which is left
+/// out of the balances.
+fn missing_balances<Tx>(
+    tx: &Tx,
+    base_asset_id: &AssetId,
+    max_fee: Word,
+) -> Result<BTreeMap<AssetId, Word>, FundError>
+where
+    Tx: Buildable + field::Outputs,
+{
+    let fee_payer = tx.fee_payer().map(usize::from);
+
+    let mut required = BTreeMap::<AssetId, Word>::new();
+    if fee_payer.is_none() {
+        required.insert(*base_asset_id, max_fee);
+    }
+
+    for output in tx.outputs() {
+        if let Output::Coin {
+            amount, asset_id, ..
+        } = output
+        {
+            let balance = required.entry(*asset_id).or_default();
+            *balance = balance
+                .checked_add(*amount)
+                .ok_or(FundError::BalanceOverflow)?;
+        }
+    }
+
+    for (index, input) in tx.inputs().iter().enumerate() {
+        if !is_spendable(input) || Some(index) == fee_payer {
+            continue
+        }
+
+        if let Some(balance) = input
+            .asset_id(base_asset_id)
+            .and_then(|asset_id| required.get_mut(asset_id))
+        {
+            *balance = balance.saturating_sub(amount(input));
+        }
+    }
+
+    required.retain(|_, missing| *missing > 0);
+
+    Ok(required)
+}
+
+/// Adds an [`Output::Change`] to the owner of the first input of every asset without
+/// one. The fee payer input and its change output are left out.
+fn add_change_outputs<Tx>(tx: &mut Tx, base_asset_id: &AssetId)
+where
+    Tx: Buildable + field::Outputs,
+{
+    let fee_payer = tx.fee_payer().map(usize::from);
+    let fee_payer_change = tx.fee_payer_change().map(usize::from);
+    let mut owners = BTreeMap::<AssetId, Address>::new();
+
+    for (index, input) in tx.inputs().iter().enumerate() {
+        if !is_spendable(input) || Some(index) == fee_payer {
+            continue
+        }
+
+        let owner = input.input_owner().or_else(|| input.recipient());
+        if let (Some(asset_id), Some(owner)) = (input.asset_id(base_asset_id), owner) {
+            owners.entry(*asset_id).or_insert(*owner);
+        }
+    }
+
+    for (asset_id, owner) in owners {
+        let has_change = tx.outputs().iter().enumerate().any(|(index, output)| {
+            Some(index) != fee_payer_change
+                && output.is_change()
+                && output.asset_id() == Some(&asset_id)
+        });
+
+        if !has_change {
+            tx.outputs_mut().push(Output::change(owner, 0, asset_id));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        field::{
+            Inputs,
+            Outputs,
+        },
+        ConsensusParameters,
+        Finalizable,
+        FormatValidityChecks,
+        Script,
+        TransactionFee,
+        TxPointer,
+    };
+    use fuel_asm::{
+        op,
+        RegId,
+    };
+    use fuel_crypto::SecretKey;
+    use rand::{
+        rngs::StdRng,
+        Rng,
+        SeedableRng,
+    };
+
+    const GAS_PRICE: Word = 10;
+    const PREDICATE_GAS_USED: Word = 1_000;
+
+    fn coins(rng: &mut StdRng, amounts: &[Word], asset_id: AssetId) -> Vec<Input> {
+        predicate_coins(rng, amounts, asset_id, PREDICATE_GAS_USED)
+    }
+
+    fn predicate_coins(
+        rng: &mut StdRng,
+        amounts: &[Word],
+        asset_id: AssetId,
+        predicate_gas_used: Word,
+    ) -> Vec<Input> {
+        let predicate = op::ret(RegId::ONE).to_bytes().to_vec();
+        amounts
+            .iter()
+            .map(|amount| {
+                Input::coin_predicate(
+                    rng.gen(),
+                    Input::predicate_owner(&predicate),
+                    *amount,
+                    asset_id,
+                    TxPointer::default(),
+                    predicate_gas_used,
+                    predicate.clone(),
+                    vec![],
+                )
+            })
+            .collect()
+    }
+
+    fn assert_funded(builder: &TransactionBuilder<Script>) {
+        let tx = builder.finalize();
+        let params = builder.get_params();
+        let base_asset_id = params.base_asset_id();
+
+        let fee = TransactionFee::checked_from_tx(
+            params.gas_costs(),
+            params.fee_params(),
+            &tx,
+            GAS_PRICE,
+        )
+        .unwrap();
+        assert!(fee.max_fee() <= tx.max_fee_limit());
+
+        let balances = missing_balances(&tx, base_asset_id, tx.max_fee_limit()).unwrap();
+        assert_eq!(balances, BTreeMap::new());
+
+        let changes = tx.outputs().iter().filter(|o| o.is_change()).count();
+        let assets = tx
+            .inputs()
+            .iter()
+            .filter_map(|i| i.asset_id(base_asset_id))
+            .collect::<alloc::collections::BTreeSet<_>>();
+        assert_eq!(changes, assets.len());
+    }
+
+    #[test]
+    fn fund_covers_outputs_and_fee() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let base_asset_id = *ConsensusParameters::standard().base_asset_id();
+        let asset_id: AssetId = rng.gen();
+
+        let mut available = coins(rng, &[10, 20, 1_000_000, 30], base_asset_id);
+        available.extend(coins(rng, &[5, 7, 9], asset_id));
+
+        let mut builder = TransactionBuilder::script(vec![], vec![]);
+        builder.add_output(Output::coin(rng.gen(), 15, asset_id));
+        builder
+            .fund(
+                available.into_iter(),
+                CoinSelection::LargestFirst,
+                GAS_PRICE,
+            )
+            .expect("The transaction should be funded");
+
+        assert_funded(&builder);
+        assert_eq!(builder.inputs().len(), 3);
+    }
+
+    #[test]
+    fn fund_signs_selected_inputs() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let secret = SecretKey::random(rng);
+        let owner = Input::owner(&secret.public_key());
+        let params = ConsensusParameters::standard();
+
+        let mut builder = TransactionBuilder::script(vec![], vec![]);
+        let witness_index = builder.add_signer(secret);
+        let available = (0..20).map(|_| {
+            Input::coin_signed(
+                rng.gen(),
+                owner,
+                1_000,
+                *params.base_asset_id(),
+                TxPointer::default(),
+                witness_index,
+            )
+        });
+        builder
+            .fund(available, CoinSelection::LargestFirst, GAS_PRICE)
+            .expect("The transaction should be funded");
+
+        assert_funded(&builder);
+        builder
+            .finalize()
+            .check(Default::default(), &params)
+            .expect("The transaction should be valid");
+    }
+
+    #[test]
+    fn fund_converges_when_inputs_increase_the_fee() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let base_asset_id = *ConsensusParameters::standard().base_asset_id();
+        let available = coins(rng, &[50_000; 200], base_asset_id);
+        let mut params = ConsensusParameters::standard();
+        params.set_fee_params(params.fee_params().with_gas_price_factor(1));
+
+        let mut builder = TransactionBuilder::script(vec![], vec![]);
+        builder
+            .with_params(params)
+            .script_gas_limit(0)
+            .fund(
+                available.into_iter(),
+                CoinSelection::LargestFirst,
+                GAS_PRICE,
+            )
+            .expect("The transaction should be funded");
+
+        assert_funded(&builder);
+        assert!(builder.inputs().len() > 1);
+    }
+
+    #[test]
+    fn fund_fails_without_enough_funds() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let asset_id: AssetId = rng.gen();
+        let base_asset_id = *ConsensusParameters::standard().base_asset_id();
+
+        let mut available = coins(rng, &[1_000_000], base_asset_id);
+        available.extend(coins(rng, &[5, 7], asset_id));
+
+        let mut builder = TransactionBuilder::script(vec![], vec![]);
+        builder.add_output(Output::coin(rng.gen(), 15, asset_id));
+        let result = builder.fund(
+            available.into_iter(),
+            CoinSelection::LargestFirst,
+            GAS_PRICE,
+        );
+
+        assert_eq!(
+            result.err(),
+            Some(FundError::InsufficientFunds {
+                asset_id,
+                required: 15,
+                available: 12,
+            })
+        );
+    }
+
+    #[test]
+    fn fund_skips_inputs_already_in_the_transaction() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let base_asset_id = *ConsensusParameters::standard().base_asset_id();
+        let available = coins(rng, &[1_000_000], base_asset_id);
+
+        let mut builder = TransactionBuilder::script(vec![], vec![]);
+        builder.add_input(available[0].clone());
+        builder
+            .fund(
+                available.into_iter(),
+                CoinSelection::LargestFirst,
+                GAS_PRICE,
+            )
+            .expect("The existing input covers the fee");
+
+        assert_funded(&builder);
+        assert_eq!(builder.inputs().len(), 1);
+    }
+
+    #[test]
+    fn fund_accounts_for_the_predicate_gas() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let predicate_gas_used = 100_000;
+        let mut params = ConsensusParameters::standard();
+        params.set_fee_params(params.fee_params().with_gas_price_factor(1));
+        let base_asset_id = *params.base_asset_id();
+        let available = predicate_coins(
+            rng,
+            &[10_000_000, 10_000_000],
+            base_asset_id,
+            predicate_gas_used,
+        );
+
+        let mut builder = TransactionBuilder::script(vec![], vec![]);
+        builder
+            .with_params(params.clone())
+            .script_gas_limit(0)
+            .fund(
+                available.into_iter(),
+                CoinSelection::LargestFirst,
+                GAS_PRICE,
+            )
+            .expect("The transaction should be funded");
+
+        assert_funded(&builder);
+        let tx = builder.finalize();
+        assert!(tx.max_fee_limit() > predicate_gas_used * GAS_PRICE);
+        tx.check(Default::default(), &params)
+            .expect("The transaction should be valid");
+    }
+
+    #[test]
+    fn fund_fails_with_predicates_without_gas() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let base_asset_id = *ConsensusParameters::standard().base_asset_id();
+        let mut available = coins(rng, &[1_000_000], base_asset_id);
+        available.extend(predicate_coins(rng, &[1_000_000], base_asset_id, 0));
+
+        let mut builder = TransactionBuilder::script(vec![], vec![]);
+        let result = builder.fund(
+            available.into_iter(),
+            CoinSelection::LargestFirst,
+            GAS_PRICE,
+        );
+
+        assert_eq!(
+            result.err(),
+            Some(FundError::PredicateGasNotEstimated { index: 1 })
+        );
+    }
+
+    #[test]
+    fn fund_fails_without_fee_payer_input() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let base_asset_id = *ConsensusParameters::standard().base_asset_id();
+        let available = coins(rng, &[1_000_000], base_asset_id);
+
+        let mut builder = TransactionBuilder::script(vec![], vec![]);
+        builder.fee_payer(0, 0);
+        let result = builder.fund(
+            available.clone().into_iter(),
+            CoinSelection::LargestFirst,
+            GAS_PRICE,
+        );
+        assert_eq!(result.err(), Some(FundError::FeePayerNotFound { index: 0 }));
+
+        let asset_id = rng.gen();
+        builder.add_input(coins(rng, &[1_000_000], asset_id).remove(0));
+        let result = builder.fund(
+            available.into_iter(),
+            CoinSelection::LargestFirst,
+            GAS_PRICE,
+        );
+        assert_eq!(result.err(), Some(FundError::FeePayerNotFound { index: 0 }));
+    }
+
+    #[test]
+    fn fund_fails_if_fee_payer_input_doesnt_cover_the_fee() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let mut params = ConsensusParameters::standard();
+        params.set_fee_params(params.fee_params().with_gas_price_factor(1));
+        let base_asset_id = *params.base_asset_id();
+        let available = coins(rng, &[1_000_000], base_asset_id);
+        let fee_payer = coins(rng, &[1], base_asset_id).remove(0);
+
+        let mut builder = TransactionBuilder::script(vec![], vec![]);
+        builder
+            .with_params(params)
+            .add_output(Output::change(
+                *fee_payer.input_owner().unwrap(),
+                0,
+                base_asset_id,
+            ))
+            .add_input(fee_payer)
+            .fee_payer(0, 0);
+        let err = builder
+            .fund(
+                available.into_iter(),
+                CoinSelection::LargestFirst,
+                GAS_PRICE,
+            )
+            .err();
+
+        assert!(
+            matches!(
+                err,
+                Some(FundError::InsufficientFeePayer { required, available: 1 }) if required > 1
+            ),
+            "{err:?}"
+        );
+    }
+
+    #[test]
+    fn minimize_inputs_leaves_a_small_change() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let asset_id = rng.gen();
+        let candidates = coins(rng, &[10, 100, 50, 60, 40], asset_id);
+
+        let amounts =
+            |selected: Vec<Input>| -> Vec<Word> { selected.iter().map(amount).collect() };
+
+        let largest = CoinSelection::LargestFirst.select(&candidates, 55).unwrap();
+        let minimal = CoinSelection::MinimizeInputs
+            .select(&candidates, 55)
+            .unwrap();
+        assert_eq!(amounts(largest), vec![100]);
+        assert_eq!(amounts(minimal), vec![60]);
+
+        let largest = CoinSelection::LargestFirst
+            .select(&candidates, 150)
+            .unwrap();
+        let minimal = CoinSelection::MinimizeInputs
+            .select(&candidates, 150)
+            .unwrap();
+        assert_eq!(amounts(largest), vec![100, 60]);
+        assert_eq!(amounts(minimal), vec![50, 100]);
+
+        assert_eq!(CoinSelection::MinimizeInputs.select(&candidates, 261), None);
+    }
+
+    #[test]
+    fn minimize_inputs_scales_to_many_candidates() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let asset_id = rng.gen();
+        let amounts: Vec<Word> = (0..10_000).map(|_| rng.gen_range(1..1_000)).collect();
+        let candidates = coins(rng, &amounts, asset_id);
+        let target = 100_000;
+
+        let largest = CoinSelection::LargestFirst
+            .select(&candidates, target)
+            .unwrap();
+        let minimal = CoinSelection::MinimizeInputs
+            .select(&candidates, target)
+            .unwrap();
+
+        assert_eq!(minimal.len(), largest.len());
+        assert!(total(&minimal) >= target);
+        assert!(total(&minimal) <= total(&largest));
+    }
+
+    #[test]
+    #[cfg(feature = "random")]
+    fn random_improve_aims_at_twice_the_target() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let asset_id = rng.gen();
+        let candidates = coins(rng, &[10; 100], asset_id);
+
+        for seed in 0..10 {
+            let strategy = CoinSelection::RandomImprove { seed };
+            let selected = strategy.select(&candidates, 100).unwrap();
+
+            assert_eq!(total(&selected), 200);
+            assert_eq!(strategy.select(&candidates, 100), Some(selected));
+        }
+
+        let strategy = CoinSelection::RandomImprove { seed: 0 };
+        assert_eq!(strategy.select(&candidates, 1001), None);
+    }
+}
@@ -0,0 +1,123 @@
+//! Stable JSON representation of the fuel-tx types.
+//!
+//! The default serde representation follows the layout of the Rust types and is
+//! inconvenient for JSON-RPC clients. This module encodes the same serde data model
+//! with a few rules that make the result independent of the Rust layout:
+//!
+//! - Integers wider than 32 bits, like [`Word`](crate::Word), are decimal strings, so
+//!   they survive clients that parse numbers as IEEE 754 doubles.
+//! - Byte arrays and byte vectors are lowercase hex strings prefixed with `0x`.
+//! - Enum variants are objects with a `type` field holding the name of the variant. The
+//!   fields of struct variants and of structs wrapped by newtype variants are placed next
+//!   to `type`; any other payload is placed under `value`.
+//! - Fields that don't exist for a variant, like the predicate of a signed coin, are
+//!   omitted.
+//! - The fields of the `body` of a transaction are placed next to the other fields of the
+//!   transaction.
+//! - The policies are an object holding the values of the set policies under their names,
+//!   like `max_fee`; the unset policies are omitted.
+//! - Witnesses are the hex strings of their data.
+//!
+//! The encoding is deterministic: equal values produce equal JSON documents.
+//! [`schema`] generates the JSON Schema of a type, and the
+//! schemas of [`Transaction`](crate::Transaction), [`Input`](crate::Input),
+//! [`Output`](crate::Output), [`Receipt`](crate::Receipt) and
+//! [`ConsensusParameters`](crate::ConsensusParameters) are available in the `schema`
+//! directory of the crate.
+
+mod de;
+mod layout;
+mod schema;
+mod ser;
+
+#[cfg(test)]
+mod tests;
+
+pub use schema::schema;
+
+use alloc::string::{
+    String,
+    ToString,
+};
+use core::fmt;
+use serde::{
+    de::DeserializeOwned,
+    Serialize,
+};
+use serde_json::Value;
+
+/// The error of the JSON encoding.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error(String);
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl serde::de::StdError for Error {}
+
+impl serde::ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+impl serde::de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+/// Encodes the `value` into its JSON representation.
+pub fn to_value<T>(value: &T) -> Result<Value, Error>
+where
+    T: Serialize + ?Sized,
+{
+    value.serialize(ser::Encoder).map(ser::Encoded::into_value)
+}
+
+/// Encodes the `value` into a compact JSON document.
+pub fn to_string<T>(value: &T) -> Result<String, Error>
+where
+    T: Serialize + ?Sized,
+{
+    serde_json::to_string(&to_value(value)?).map_err(serde::ser::Error::custom)
+}
+
+/// Decodes the value from its JSON representation.
+pub fn from_value<T>(value: Value) -> Result<T, Error>
+where
+    T: DeserializeOwned,
+{
+    T::deserialize(de::Decoder(value))
+}
+
+/// Decodes the value from a JSON document.
+pub fn from_str<T>(json: &str) -> Result<T, Error>
+where
+    T: DeserializeOwned,
+{
+    from_value(serde_json::from_str(json).map_err(serde::de::Error::custom)?)
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(bytes.len().saturating_mul(2).saturating_add(2));
+    hex.push_str("0x");
+    hex.push_str(&hex::encode(bytes));
+    hex
+}
+
+fn decode_hex(hex: &str) -> Result<alloc::vec::Vec<u8>, Error> {
+    let digits = hex
+        .strip_prefix("0x")
+        .filter(|digits| !digits.bytes().any(|c| c.is_ascii_uppercase()))
+        .ok_or_else(|| {
+            Error(alloc::format!(
+                "expected a lowercase 0x-prefixed hex string, got `{hex}`"
+            ))
+        })?;
+    hex::decode(digits)
+        .map_err(|e| Error(alloc::format!("invalid hex string `{hex}`: {e}")))
+}
@@ -0,0 +1,566 @@
+use super::{
+    decode_hex,
+    layout,
+    Error,
+};
+use alloc::{
+    string::String,
+    vec,
+    vec::Vec,
+};
+use serde::de::{
+    self,
+    DeserializeSeed,
+    Error as _,
+    IntoDeserializer,
+    Unexpected,
+    Visitor,
+};
+use serde_json::{
+    Map,
+    Value,
+};
+
+/// Implements the methods of [`PayloadDecoder`] that read the payload from `value`.
+macro_rules! forward_to_value {
+    ($($method:ident)*) => {$(
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+            Decoder(self.0.value()?).$method(visitor)
+        }
+    )*};
+}
+
+fn unexpected(value: &Value) -> Unexpected<'_> {
+    match value {
+        Value::Null => Unexpected::Unit,
+        Value::Bool(v) => Unexpected::Bool(*v),
+        Value::Number(v) => match (v.as_u64(), v.as_i64(), v.as_f64()) {
+            (Some(v), _, _) => Unexpected::Unsigned(v),
+            (_, Some(v), _) => Unexpected::Signed(v),
+            (_, _, Some(v)) => Unexpected::Float(v),
+            _ => Unexpected::Other("number"),
+        },
+        Value::String(v) => Unexpected::Str(v),
+        Value::Array(_) => Unexpected::Seq,
+        Value::Object(_) => Unexpected::Map,
+    }
+}
+
+/// Parses a decimal string produced by the encoder, rejecting the other notations
+/// accepted by [`str::parse`], like leading zeros or a `+` sign.
+fn parse_decimal<T>(value: Value, expected: &'static str) -> Result<T, Error>
+where
+    T: core::str::FromStr + alloc::string::ToString,
+{
+    match value {
+        Value::String(string) => string
+            .parse::<T>()
+            .ok()
+            .filter(|number| number.to_string() == string)
+            .ok_or_else(|| Error::invalid_value(Unexpected::Str(&string), &expected)),
+        value => Err(Error::invalid_type(unexpected(&value), &expected)),
+    }
+}
+
+pub(super) struct Decoder(pub(super) Value);
+
+impl Decoder {
+    fn number<'de, V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Value::Number(number) => match (number.as_u64(), number.as_i64()) {
+                (Some(v), _) => visitor.visit_u64(v),
+                (_, Some(v)) => visitor.visit_i64(v),
+                _ => Err(Error::invalid_type(
+                    unexpected(&Value::Number(number)),
+                    &visitor,
+                )),
+            },
+            value => Err(Error::invalid_type(unexpected(&value), &visitor)),
+        }
+    }
+
+    fn seq<'de, V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Value::Array(items) => visit_seq(items, visitor),
+            Value::String(hex) => visit_seq(
+                decode_hex(&hex)?.into_iter().map(Value::from).collect(),
+                visitor,
+            ),
+            value => Err(Error::invalid_type(unexpected(&value), &visitor)),
+        }
+    }
+}
+
+fn visit_seq<'de, V: Visitor<'de>>(
+    items: Vec<Value>,
+    visitor: V,
+) -> Result<V::Value, Error> {
+    let len = items.len();
+    let mut seq = SeqDecoder(items.into_iter());
+    let value = visitor.visit_seq(&mut seq)?;
+    if seq.0.len() != 0 {
+        return Err(Error::invalid_length(len, &"fewer elements in the array"))
+    }
+    Ok(value)
+}
+
+fn visit_struct<'de, V: Visitor<'de>>(
+    mut object: Map<String, Value>,
+    fields: &'static [&'static str],
+    visitor: V,
+) -> Result<V::Value, Error> {
+    if fields.contains(&layout::FLATTENED_FIELD) {
+        object = layout::unflatten(object, fields)?;
+    }
+    if let Some(key) = object.keys().find(|key| !fields.contains(&key.as_str())) {
+        return Err(Error::unknown_field(key, fields))
+    }
+    // The fields that don't exist for a variant are omitted by the encoder.
+    for field in fields {
+        object.entry(*field).or_insert(Value::Null);
+    }
+    visitor.visit_map(MapDecoder {
+        entries: object.into_iter(),
+        value: None,
+    })
+}
+
+impl<'de> de::Deserializer<'de> for Decoder {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Value::Null => visitor.visit_unit(),
+            Value::Bool(v) => visitor.visit_bool(v),
+            Value::Number(_) => self.number(visitor),
+            Value::String(v) => visitor.visit_string(v),
+            Value::Array(items) => visit_seq(items, visitor),
+            Value::Object(object) => visitor.visit_map(MapDecoder {
+                entries: object.into_iter(),
+                value: None,
+            }),
+        }
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Value::Bool(v) => visitor.visit_bool(v),
+            value => Err(Error::invalid_type(unexpected(&value), &visitor)),
+        }
+    }
+
+    fn deserialize_i8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.number(visitor)
+    }
+
+    fn deserialize_i16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.number(visitor)
+    }
+
+    fn deserialize_i32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.number(visitor)
+    }
+
+    fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_i64(parse_decimal(self.0, "a decimal string")?)
+    }
+
+    fn deserialize_i128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_i128(parse_decimal(self.0, "a decimal string")?)
+    }
+
+    fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.number(visitor)
+    }
+
+    fn deserialize_u16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.number(visitor)
+    }
+
+    fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.number(visitor)
+    }
+
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_u64(parse_decimal(self.0, "a decimal string")?)
+    }
+
+    fn deserialize_u128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visitor.visit_u128(parse_decimal(self.0, "a decimal string")?)
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_f64(visitor)
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Value::Number(number) => match number.as_f64() {
+                Some(v) => visitor.visit_f64(v),
+                None => Err(Error::invalid_type(
+                    unexpected(&Value::Number(number)),
+                    &visitor,
+                )),
+            },
+            value => Err(Error::invalid_type(unexpected(&value), &visitor)),
+        }
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Value::String(v) => visitor.visit_string(v),
+            value => Err(Error::invalid_type(unexpected(&value), &visitor)),
+        }
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_byte_buf(visitor)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match self.0 {
+            Value::String(hex) => visitor.visit_byte_buf(decode_hex(&hex)?),
+            value => Err(Error::invalid_type(unexpected(&value), &visitor)),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Value::Null => visitor.visit_none(),
+            value => visitor.visit_some(Decoder(value)),
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Value::Null => visitor.visit_unit(),
+            value => Err(Error::invalid_type(unexpected(&value), &visitor)),
+        }
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.seq(visitor)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        match self.0 {
+            Value::Object(object) => visitor.visit_map(MapDecoder {
+                entries: object.into_iter(),
+                value: None,
+            }),
+            value => Err(Error::invalid_type(unexpected(&value), &visitor)),
+        }
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match (self.0, fields) {
+            (value, [field]) if layout::is_transparent(name) => {
+                let mut object = Map::new();
+                object.insert((*field).into(), value);
+                layout::check_bytes(name, &object)?;
+                visit_struct(object, fields, visitor)
+            }
+            (Value::Object(object), _) if name == layout::POLICIES => {
+                visit_struct(layout::decode_policies(object)?, fields, visitor)
+            }
+            (Value::Object(object), _) => {
+                layout::check_bytes(name, &object)?;
+                visit_struct(object, fields, visitor)
+            }
+            (value, _) => Err(Error::invalid_type(unexpected(&value), &visitor)),
+        }
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        _: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let mut object = match self.0 {
+            Value::Object(object) => object,
+            value => return Err(Error::invalid_type(unexpected(&value), &visitor)),
+        };
+        match object.remove("type") {
+            Some(Value::String(variant)) => {
+                visitor.visit_enum(EnumDecoder { variant, object })
+            }
+            Some(value) => {
+                Err(Error::invalid_type(unexpected(&value), &"a variant name"))
+            }
+            None => Err(Error::missing_field("type")),
+        }
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+struct SeqDecoder(vec::IntoIter<Value>);
+
+impl<'de> de::SeqAccess<'de> for SeqDecoder {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        self.0
+            .next()
+            .map(|value| seed.deserialize(Decoder(value)))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.0.len())
+    }
+}
+
+struct MapDecoder {
+    entries: <Map<String, Value> as IntoIterator>::IntoIter,
+    value: Option<Value>,
+}
+
+impl<'de> de::MapAccess<'de> for MapDecoder {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        match self.entries.next() {
+            Some((key, value)) => {
+                self.value = Some(value);
+                seed.deserialize(Decoder(Value::String(key))).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Error> {
+        let value = self
+            .value
+            .take()
+            .ok_or_else(|| Error::custom("map value without a key"))?;
+        seed.deserialize(Decoder(value))
+    }
+}
+
+struct EnumDecoder {
+    variant: String,
+    object: Map<String, Value>,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumDecoder {
+    type Error = Error;
+    type Variant = VariantDecoder;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, VariantDecoder), Error> {
+        layout::check_bytes(&self.variant, &self.object)?;
+        let variant = seed.deserialize(self.variant.into_deserializer())?;
+        Ok((variant, VariantDecoder(self.object)))
+    }
+}
+
+struct VariantDecoder(Map<String, Value>);
+
+impl VariantDecoder {
+    /// Takes the payload stored under `value`, which must be the only field.
+    fn value(mut self) -> Result<Value, Error> {
+        let value = self
+            .0
+            .remove("value")
+            .ok_or(Error::missing_field("value"))?;
+        match self.0.keys().next() {
+            Some(key) => Err(Error::unknown_field(key, &["type", "value"])),
+            None => Ok(value),
+        }
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for VariantDecoder {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        match self.0.keys().next() {
+            Some(key) => Err(Error::unknown_field(key, &["type"])),
+            None => Ok(()),
+        }
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, Error> {
+        seed.deserialize(PayloadDecoder(self))
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_tuple(Decoder(self.value()?), len, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visit_struct(self.0, fields, visitor)
+    }
+}
+
+/// Decodes the payload of a newtype variant. Structs and maps are merged into the
+/// tagged object, any other payload is stored under `value`.
+struct PayloadDecoder(VariantDecoder);
+
+impl<'de> de::Deserializer<'de> for PayloadDecoder {
+    type Error = Error;
+
+    forward_to_value! {
+        deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64
+        deserialize_i128 deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64
+        deserialize_u128 deserialize_f32 deserialize_f64 deserialize_char deserialize_str
+        deserialize_string deserialize_bytes deserialize_byte_buf deserialize_option
+        deserialize_unit deserialize_seq deserialize_identifier deserialize_ignored_any
+    }
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        Decoder(self.0.value()?).deserialize_any(visitor)
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        de::VariantAccess::unit_variant(self.0)?;
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        Decoder(Value::Object((self.0).0)).deserialize_map(visitor)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        if layout::is_transparent(name) {
+            return Decoder(self.0.value()?).deserialize_struct(name, fields, visitor)
+        }
+        Decoder(Value::Object((self.0).0)).deserialize_struct(name, fields, visitor)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        Decoder(self.0.value()?).deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        Decoder(self.0.value()?).deserialize_tuple_struct(name, len, visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        Decoder(self.0.value()?).deserialize_enum(name, variants, visitor)
+    }
+}
@@ -0,0 +1,171 @@
+//! The rules that detach the JSON layout from the layout of the Rust types. The
+//! encoder, the decoder and the schema apply them to the structs by their serde name.
+
+use super::Error;
+use crate::policies::PolicyType;
+use alloc::{
+    format,
+    string::{
+        String,
+        ToString,
+    },
+    vec::Vec,
+};
+use serde::de::Error as _;
+use serde_json::{
+    Map,
+    Value,
+};
+use strum::IntoEnumIterator;
+
+/// The field whose struct is merged into the enclosing object.
+pub(super) const FLATTENED_FIELD: &str = "body";
+
+/// The structs encoded as the value of their only field.
+const TRANSPARENT_STRUCTS: &[&str] = &["Witness"];
+
+/// The byte vector fields, keyed by the serde name of their struct or struct variant.
+/// They are encoded as hex strings, including the empty ones. The schema generation
+/// fails on a byte vector field missing from the list.
+const BYTE_FIELDS: &[(&str, &str)] = &[
+    ("Coin", "predicate"),
+    ("Coin", "predicate_data"),
+    ("LogData", "data"),
+    ("Message", "data"),
+    ("Message", "predicate"),
+    ("Message", "predicate_data"),
+    ("MessageOut", "data"),
+    ("ReturnData", "data"),
+    ("ScriptBody", "script"),
+    ("ScriptBody", "script_data"),
+    ("UploadSubsection", "subsection"),
+    ("Witness", "data"),
+];
+
+/// The struct encoded as an object that holds the value of each set policy under the
+/// name of the policy.
+pub(super) const POLICIES: &str = "Policies";
+
+const POLICIES_BITS: &str = "bits";
+const POLICIES_VALUES: &str = "values";
+
+/// Whether the struct is encoded as the value of its only field.
+pub(super) fn is_transparent(name: &str) -> bool {
+    TRANSPARENT_STRUCTS.contains(&name)
+}
+
+/// Whether the field of the struct or struct variant is a byte vector.
+pub(super) fn is_bytes(name: &str, field: &str) -> bool {
+    BYTE_FIELDS.contains(&(name, field))
+}
+
+/// Checks that the byte vector fields of the struct or struct variant are hex strings.
+/// The fields that don't exist for a variant, or hold `None`, are `null`.
+pub(super) fn check_bytes(name: &str, object: &Map<String, Value>) -> Result<(), Error> {
+    for (key, value) in object {
+        if is_bytes(name, key) && !matches!(value, Value::String(_) | Value::Null) {
+            return Err(Error::custom(format!(
+                "the field `{key}` of `{name}` must be a hex string"
+            )))
+        }
+    }
+    Ok(())
+}
+
+/// The name of the policy in the encoded [`Policies`](crate::policies::Policies).
+pub(super) fn policy_name(policy_type: PolicyType) -> &'static str {
+    match policy_type {
+        PolicyType::Tip => "tip",
+        PolicyType::WitnessLimit => "witness_limit",
+        PolicyType::Maturity => "maturity",
+        PolicyType::MaxFee => "max_fee",
+        PolicyType::Expiration => "expiration",
+        PolicyType::FeePayer => "fee_payer",
+    }
+}
+
+/// Merges the `body` of a struct into the struct.
+pub(super) fn flatten(object: &mut Map<String, Value>, body: Value) -> Result<(), Error> {
+    let Value::Object(body) = body else {
+        return Err(Error::custom(format!(
+            "the field `{FLATTENED_FIELD}` must be an object to be flattened"
+        )))
+    };
+    for (key, value) in body {
+        if object.contains_key(&key) {
+            return Err(Error::custom(format!(
+                "the field `{key}` of the `{FLATTENED_FIELD}` conflicts with the \
+                 enclosing struct"
+            )))
+        }
+        object.insert(key, value);
+    }
+    Ok(())
+}
+
+/// Moves the fields that don't belong to the struct into its `body`.
+pub(super) fn unflatten(
+    object: Map<String, Value>,
+    fields: &'static [&'static str],
+) -> Result<Map<String, Value>, Error> {
+    if object.contains_key(FLATTENED_FIELD) {
+        return Err(Error::unknown_field(FLATTENED_FIELD, fields))
+    }
+    let (mut object, body): (Map<_, _>, Map<_, _>) = object
+        .into_iter()
+        .partition(|(key, _)| fields.contains(&key.as_str()));
+    object.insert(FLATTENED_FIELD.to_string(), Value::Object(body));
+    Ok(object)
+}
+
+/// Converts the serde fields of [`Policies`](crate::policies::Policies), the bits and
+/// the array of values, into the values of the set policies keyed by name.
+pub(super) fn encode_policies(
+    mut object: Map<String, Value>,
+) -> Result<Map<String, Value>, Error> {
+    let bits = object
+        .remove(POLICIES_BITS)
+        .and_then(|bits| bits.as_u64())
+        .ok_or_else(|| Error::custom("the policies bits must be a number"))?;
+    let Some(Value::Array(mut values)) = object.remove(POLICIES_VALUES) else {
+        return Err(Error::custom("the policy values must be an array"))
+    };
+
+    PolicyType::iter()
+        .filter(|policy_type| bits & u64::from(policy_type.bit().bits()) != 0)
+        .map(|policy_type| {
+            let value = values
+                .get_mut(policy_type.index())
+                .map(Value::take)
+                .ok_or_else(|| Error::custom("missing policy value"))?;
+            Ok((policy_name(policy_type).to_string(), value))
+        })
+        .collect()
+}
+
+/// Converts the set policies keyed by name back into the serde fields of
+/// [`Policies`](crate::policies::Policies). The values of the unset policies are zero.
+pub(super) fn decode_policies(
+    mut object: Map<String, Value>,
+) -> Result<Map<String, Value>, Error> {
+    let mut bits = 0u32;
+    let mut values = PolicyType::iter()
+        .map(|_| Value::String("0".to_string()))
+        .collect::<Vec<_>>();
+    for policy_type in PolicyType::iter() {
+        if let Some(value) = object.remove(policy_name(policy_type)) {
+            bits |= policy_type.bit().bits();
+            if let Some(slot) = values.get_mut(policy_type.index()) {
+                *slot = value;
+            }
+        }
+    }
+    if let Some(key) = object.keys().next() {
+        return Err(Error::custom(format!("unknown policy `{key}`")))
+    }
+
+    let mut fields = Map::new();
+    fields.insert(POLICIES_BITS.to_string(), bits.into());
+    fields.insert(POLICIES_VALUES.to_string(), Value::Array(values));
+    Ok(fields)
+}
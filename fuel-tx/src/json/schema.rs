@@ -0,0 +1,879 @@
+//! Generation of the JSON Schema by tracing the [`Deserialize`] implementation.
+//!
+//! The tracer is a deserializer that records the requested serde types while it
+//! feeds the visitors with default values. Every run explores one more variant of
+//! each enum, so the type is traced until all the variants of the reachable enums
+//! are known.
+
+use super::{
+    layout,
+    Error,
+};
+use crate::policies::PolicyType;
+use alloc::{
+    boxed::Box,
+    collections::BTreeMap,
+    format,
+    string::{
+        String,
+        ToString,
+    },
+    vec,
+    vec::Vec,
+};
+use serde::de::{
+    self,
+    DeserializeOwned,
+    DeserializeSeed,
+    Error as _,
+    IntoDeserializer,
+    Visitor,
+};
+use serde_json::{
+    json,
+    Map,
+    Value,
+};
+use strum::IntoEnumIterator;
+
+/// Generates the JSON Schema of the JSON representation of `T`.
+///
+/// Enums are defined under `$defs` and referenced by name; the other types are
+/// inlined. The schema follows the 2020-12 draft.
+pub fn schema<T>() -> Result<Value, Error>
+where
+    T: DeserializeOwned,
+{
+    let mut registry = Registry::default();
+    let format = loop {
+        let explored = registry.explored();
+        let mut format = Format::Unknown;
+        T::deserialize(Tracer {
+            registry: &mut registry,
+            format: &mut format,
+        })?;
+        if registry.is_complete() {
+            break format
+        }
+        if registry.explored() == explored {
+            let missing = registry
+                .enums
+                .iter()
+                .flat_map(|(name, entry)| {
+                    entry
+                        .variants
+                        .iter()
+                        .zip(&entry.formats)
+                        .filter(|(_, format)| format.is_none())
+                        .map(move |(variant, _)| format!("{name}::{variant}"))
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            return Err(Error::custom(format!(
+                "failed to trace the variants {missing}"
+            )))
+        }
+    };
+
+    let mut root = Map::new();
+    root.insert(
+        "$schema".to_string(),
+        "https://json-schema.org/draft/2020-12/schema".into(),
+    );
+    if let Format::Struct(name, _) | Format::Enum(name) = &format {
+        root.insert("title".to_string(), (*name).into());
+    }
+    if let Value::Object(schema) = format.schema()? {
+        root.extend(schema);
+    }
+    let defs = registry
+        .enums
+        .iter()
+        .map(|(name, entry)| Ok((name.to_string(), entry.schema()?)))
+        .collect::<Result<Map<_, _>, Error>>()?;
+    root.insert("$defs".to_string(), Value::Object(defs));
+
+    Ok(Value::Object(root))
+}
+
+enum Format {
+    Unknown,
+    Bool,
+    Integer {
+        min: i128,
+        max: i128,
+    },
+    Decimal {
+        signed: bool,
+    },
+    Float,
+    String,
+    Bytes {
+        len: Option<usize>,
+    },
+    /// A unit struct, omitted by the encoder.
+    Absent,
+    Unit,
+    Option(Box<Format>),
+    Seq(Box<Format>),
+    Tuple(Vec<Format>),
+    Map(Box<Format>),
+    Struct(&'static str, Vec<(&'static str, Format)>),
+    Enum(&'static str),
+}
+
+enum VariantFormat {
+    Unit,
+    Newtype(Format),
+    Tuple(Vec<Format>),
+    Struct(Vec<(&'static str, Format)>),
+}
+
+#[derive(Default)]
+struct Registry {
+    enums: BTreeMap<&'static str, EnumEntry>,
+}
+
+struct EnumEntry {
+    variants: &'static [&'static str],
+    formats: Vec<Option<VariantFormat>>,
+}
+
+impl Registry {
+    fn explored(&self) -> usize {
+        self.enums
+            .values()
+            .flat_map(|entry| entry.formats.iter())
+            .filter(|format| format.is_some())
+            .count()
+    }
+
+    fn is_complete(&self) -> bool {
+        self.enums
+            .values()
+            .all(|entry| entry.formats.iter().all(Option::is_some))
+    }
+
+    /// Chooses the variant of the enum to trace: the first unexplored variant, or
+    /// the first variant that leads to an enum with unexplored variants.
+    fn next_variant(
+        &mut self,
+        name: &'static str,
+        variants: &'static [&'static str],
+    ) -> Result<usize, Error> {
+        let entry = self.enums.entry(name).or_insert_with(|| EnumEntry {
+            variants,
+            formats: variants.iter().map(|_| None).collect(),
+        });
+        if entry.variants != variants {
+            return Err(Error::custom(format!("different enums are named `{name}`")))
+        }
+        if let Some(index) = entry.formats.iter().position(Option::is_none) {
+            return Ok(index)
+        }
+
+        let entry = &self.enums[name];
+        let mut visiting = vec![name];
+        Ok(entry
+            .formats
+            .iter()
+            .flatten()
+            .position(|format| !self.is_variant_complete(format, &mut visiting))
+            .unwrap_or_default())
+    }
+
+    fn is_enum_complete(
+        &self,
+        name: &'static str,
+        visiting: &mut Vec<&'static str>,
+    ) -> bool {
+        if visiting.contains(&name) {
+            return true
+        }
+        let Some(entry) = self.enums.get(name) else {
+            return false
+        };
+        visiting.push(name);
+        let complete = entry.formats.iter().all(|format| match format {
+            Some(format) => self.is_variant_complete(format, visiting),
+            None => false,
+        });
+        visiting.pop();
+        complete
+    }
+
+    fn is_variant_complete(
+        &self,
+        format: &VariantFormat,
+        visiting: &mut Vec<&'static str>,
+    ) -> bool {
+        match format {
+            VariantFormat::Unit => true,
+            VariantFormat::Newtype(format) => self.is_format_complete(format, visiting),
+            VariantFormat::Tuple(formats) => formats
+                .iter()
+                .all(|format| self.is_format_complete(format, visiting)),
+            VariantFormat::Struct(fields) => fields
+                .iter()
+                .all(|(_, format)| self.is_format_complete(format, visiting)),
+        }
+    }
+
+    fn is_format_complete(
+        &self,
+        format: &Format,
+        visiting: &mut Vec<&'static str>,
+    ) -> bool {
+        match format {
+            Format::Option(format) | Format::Seq(format) | Format::Map(format) => {
+                self.is_format_complete(format, visiting)
+            }
+            Format::Tuple(formats) => formats
+                .iter()
+                .all(|format| self.is_format_complete(format, visiting)),
+            Format::Struct(_, fields) => fields
+                .iter()
+                .all(|(_, format)| self.is_format_complete(format, visiting)),
+            Format::Enum(name) => self.is_enum_complete(name, visiting),
+            _ => true,
+        }
+    }
+}
+
+fn object(properties: Map<String, Value>, required: Vec<String>) -> Value {
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    })
+}
+
+/// The properties and the required properties of the fields of the struct or struct
+/// variant `owner`.
+fn fields_schema(
+    owner: &str,
+    fields: &[(&'static str, Format)],
+) -> Result<(Map<String, Value>, Vec<String>), Error> {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    for (name, format) in fields {
+        match format {
+            Format::Absent => continue,
+            Format::Struct(body, fields) if *name == layout::FLATTENED_FIELD => {
+                let (body_properties, body_required) = struct_schema(body, fields)?;
+                properties.extend(body_properties);
+                required.extend(body_required);
+            }
+            _ if *name == layout::FLATTENED_FIELD => {
+                return Err(Error::custom(format!(
+                    "the field `{name}` must be a struct to be flattened"
+                )))
+            }
+            format => {
+                properties.insert(name.to_string(), field_schema(owner, name, format)?);
+                required.push(name.to_string());
+            }
+        }
+    }
+    Ok((properties, required))
+}
+
+/// The schema of the field of the struct or struct variant `owner`. The encoder only
+/// writes the byte vectors listed in the layout as hex strings, so the other byte
+/// vectors are rejected.
+fn field_schema(owner: &str, name: &str, format: &Format) -> Result<Value, Error> {
+    let bytes = match format {
+        Format::Option(format) => format.is_byte_vector(),
+        format => format.is_byte_vector(),
+    };
+    match (bytes, layout::is_bytes(owner, name)) {
+        (true, false) => Err(Error::custom(format!(
+            "the byte vector field `{name}` of `{owner}` isn't listed in the JSON layout"
+        ))),
+        (false, true) => Err(Error::custom(format!(
+            "the field `{name}` of `{owner}` is listed in the JSON layout, but isn't a \
+             byte vector"
+        ))),
+        (true, true) => {
+            let bytes = json!({ "type": "string", "pattern": "^0x([0-9a-f]{2})*$" });
+            Ok(match format {
+                Format::Option(_) => json!({ "oneOf": [{ "type": "null" }, bytes] }),
+                _ => bytes,
+            })
+        }
+        (false, false) => format.schema(),
+    }
+}
+
+/// The properties and the required properties of a struct encoded as an object.
+fn struct_schema(
+    name: &'static str,
+    fields: &[(&'static str, Format)],
+) -> Result<(Map<String, Value>, Vec<String>), Error> {
+    if name == layout::POLICIES {
+        let value = Format::Decimal { signed: false }.schema()?;
+        let properties = PolicyType::iter()
+            .map(|policy_type| {
+                (layout::policy_name(policy_type).to_string(), value.clone())
+            })
+            .collect();
+        return Ok((properties, Vec::new()))
+    }
+    fields_schema(name, fields)
+}
+
+fn tuple_schema(formats: &[Format]) -> Result<Value, Error> {
+    if !formats.is_empty() && formats.iter().all(Format::is_byte) {
+        return Format::Bytes {
+            len: Some(formats.len()),
+        }
+        .schema()
+    }
+    let items = formats
+        .iter()
+        .map(Format::schema)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(json!({
+        "type": "array",
+        "prefixItems": items,
+        "minItems": formats.len(),
+        "maxItems": formats.len(),
+    }))
+}
+
+impl Format {
+    fn schema(&self) -> Result<Value, Error> {
+        Ok(match self {
+            Format::Unknown => {
+                return Err(Error::custom("the type can't be traced"));
+            }
+            Format::Bool => json!({ "type": "boolean" }),
+            Format::Integer { min, max } => {
+                json!({ "type": "integer", "minimum": min, "maximum": max })
+            }
+            Format::Decimal { signed: false } => {
+                json!({ "type": "string", "pattern": "^(0|[1-9][0-9]*)$" })
+            }
+            Format::Decimal { signed: true } => {
+                json!({ "type": "string", "pattern": "^(0|-?[1-9][0-9]*)$" })
+            }
+            Format::Float => json!({ "type": "number" }),
+            Format::String => json!({ "type": "string" }),
+            Format::Bytes { len: None } => {
+                return Err(Error::custom(
+                    "byte vectors are only supported as fields listed in the JSON layout",
+                ))
+            }
+            Format::Bytes { len: Some(len) } => {
+                let digits = len.saturating_mul(2);
+                json!({ "type": "string", "pattern": format!("^0x[0-9a-f]{{{digits}}}$") })
+            }
+            Format::Absent | Format::Unit => json!({ "type": "null" }),
+            Format::Option(format) => {
+                json!({ "oneOf": [{ "type": "null" }, format.schema()?] })
+            }
+            Format::Seq(format) => json!({ "type": "array", "items": format.schema()? }),
+            Format::Tuple(formats) => tuple_schema(formats)?,
+            Format::Map(format) => {
+                json!({ "type": "object", "additionalProperties": format.schema()? })
+            }
+            Format::Struct(name, fields) => match fields.as_slice() {
+                [(field, format)] if layout::is_transparent(name) => {
+                    field_schema(name, field, format)?
+                }
+                _ => {
+                    let (properties, required) = struct_schema(name, fields)?;
+                    object(properties, required)
+                }
+            },
+            Format::Enum(name) => json!({ "$ref": format!("#/$defs/{name}") }),
+        })
+    }
+
+    /// Whether the elements of a sequence with this format form a hex string.
+    fn is_byte(&self) -> bool {
+        matches!(self, Format::Integer { min: 0, max: 255 })
+    }
+
+    fn is_byte_vector(&self) -> bool {
+        matches!(self, Format::Bytes { len: None })
+    }
+}
+
+impl EnumEntry {
+    fn schema(&self) -> Result<Value, Error> {
+        let variants = self
+            .variants
+            .iter()
+            .zip(&self.formats)
+            .map(|(variant, format)| {
+                let format = format
+                    .as_ref()
+                    .ok_or_else(|| Error::custom("the variant isn't traced"))?;
+                let mut properties = Map::new();
+                properties.insert("type".to_string(), json!({ "const": variant }));
+                let mut required = vec!["type".to_string()];
+                let (fields, fields_required) = match format {
+                    VariantFormat::Unit | VariantFormat::Newtype(Format::Absent) => {
+                        (Map::new(), Vec::new())
+                    }
+                    VariantFormat::Newtype(Format::Struct(name, fields))
+                        if !layout::is_transparent(name) =>
+                    {
+                        struct_schema(name, fields)?
+                    }
+                    VariantFormat::Struct(fields) => fields_schema(variant, fields)?,
+                    VariantFormat::Newtype(Format::Map(format)) => {
+                        return Ok(json!({
+                            "type": "object",
+                            "properties": properties,
+                            "required": required,
+                            "additionalProperties": format.schema()?,
+                        }))
+                    }
+                    VariantFormat::Newtype(format) => {
+                        let mut fields = Map::new();
+                        fields.insert("value".to_string(), format.schema()?);
+                        (fields, vec!["value".to_string()])
+                    }
+                    VariantFormat::Tuple(formats) => {
+                        let mut fields = Map::new();
+                        fields.insert("value".to_string(), tuple_schema(formats)?);
+                        (fields, vec!["value".to_string()])
+                    }
+                };
+                properties.extend(fields);
+                required.extend(fields_required);
+                Ok(object(properties, required))
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok(json!({ "oneOf": variants }))
+    }
+}
+
+struct Tracer<'a> {
+    registry: &'a mut Registry,
+    format: &'a mut Format,
+}
+
+impl Tracer<'_> {
+    fn integer<T: Into<i128>>(self, min: T, max: T) {
+        *self.format = Format::Integer {
+            min: min.into(),
+            max: max.into(),
+        };
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Tracer<'_> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _: V) -> Result<V::Value, Error> {
+        Err(Error::custom(
+            "self-describing types can't be traced, use explicit serde types",
+        ))
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        *self.format = Format::Bool;
+        visitor.visit_bool(false)
+    }
+
+    fn deserialize_i8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.integer(i8::MIN, i8::MAX);
+        visitor.visit_i8(0)
+    }
+
+    fn deserialize_i16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.integer(i16::MIN, i16::MAX);
+        visitor.visit_i16(0)
+    }
+
+    fn deserialize_i32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.integer(i32::MIN, i32::MAX);
+        visitor.visit_i32(0)
+    }
+
+    fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        *self.format = Format::Decimal { signed: true };
+        visitor.visit_i64(0)
+    }
+
+    fn deserialize_i128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        *self.format = Format::Decimal { signed: true };
+        visitor.visit_i128(0)
+    }
+
+    fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.integer(u8::MIN, u8::MAX);
+        visitor.visit_u8(0)
+    }
+
+    fn deserialize_u16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.integer(u16::MIN, u16::MAX);
+        visitor.visit_u16(0)
+    }
+
+    fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.integer(u32::MIN, u32::MAX);
+        visitor.visit_u32(0)
+    }
+
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        *self.format = Format::Decimal { signed: false };
+        visitor.visit_u64(0)
+    }
+
+    fn deserialize_u128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        *self.format = Format::Decimal { signed: false };
+        visitor.visit_u128(0)
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        *self.format = Format::Float;
+        visitor.visit_f32(0.0)
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        *self.format = Format::Float;
+        visitor.visit_f64(0.0)
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        *self.format = Format::String;
+        visitor.visit_char('\0')
+    }
+
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        *self.format = Format::String;
+        visitor.visit_string(String::new())
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_byte_buf(visitor)
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        *self.format = Format::Bytes { len: None };
+        visitor.visit_byte_buf(Vec::new())
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let mut format = Format::Unknown;
+        let value = visitor.visit_some(Tracer {
+            registry: self.registry,
+            format: &mut format,
+        })?;
+        *self.format = Format::Option(Box::new(format));
+        Ok(value)
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        *self.format = Format::Unit;
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        *self.format = Format::Absent;
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let mut formats = vec![Format::Unknown];
+        let value = visitor.visit_seq(SeqTracer {
+            registry: self.registry,
+            formats: &mut formats,
+            index: 0,
+        })?;
+        let format = formats.pop().unwrap_or(Format::Unknown);
+        *self.format = if format.is_byte() {
+            Format::Bytes { len: None }
+        } else {
+            Format::Seq(Box::new(format))
+        };
+        Ok(value)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let mut formats = (0..len).map(|_| Format::Unknown).collect::<Vec<_>>();
+        let value = visitor.visit_seq(SeqTracer {
+            registry: self.registry,
+            formats: &mut formats,
+            index: 0,
+        })?;
+        *self.format = Format::Tuple(formats);
+        Ok(value)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_tuple(len, visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let mut formats = vec![Format::Unknown, Format::Unknown];
+        let value = visitor.visit_map(MapTracer {
+            registry: self.registry,
+            formats: &mut formats,
+            keys: None,
+            index: 0,
+        })?;
+        let format = formats.pop().unwrap_or(Format::Unknown);
+        *self.format = Format::Map(Box::new(format));
+        Ok(value)
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let mut formats = fields.iter().map(|_| Format::Unknown).collect::<Vec<_>>();
+        let value = visitor.visit_map(MapTracer {
+            registry: self.registry,
+            formats: &mut formats,
+            keys: Some(fields),
+            index: 0,
+        })?;
+        *self.format =
+            Format::Struct(name, fields.iter().copied().zip(formats).collect());
+        Ok(value)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let index = self.registry.next_variant(name, variants)?;
+        *self.format = Format::Enum(name);
+        visitor.visit_enum(EnumTracer {
+            registry: self.registry,
+            name,
+            index,
+        })
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        self.deserialize_string(visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_unit()
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+struct SeqTracer<'a> {
+    registry: &'a mut Registry,
+    formats: &'a mut [Format],
+    index: usize,
+}
+
+impl<'de> de::SeqAccess<'de> for SeqTracer<'_> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        let Some(format) = self.formats.get_mut(self.index) else {
+            return Ok(None)
+        };
+        self.index = self.index.saturating_add(1);
+        seed.deserialize(Tracer {
+            registry: self.registry,
+            format,
+        })
+        .map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.formats.len().saturating_sub(self.index))
+    }
+}
+
+/// Traces the entries of a map or the fields of a struct. `formats` holds the key
+/// and the value formats of a map, or the value formats of the struct fields.
+struct MapTracer<'a> {
+    registry: &'a mut Registry,
+    formats: &'a mut [Format],
+    keys: Option<&'static [&'static str]>,
+    index: usize,
+}
+
+impl<'de> de::MapAccess<'de> for MapTracer<'_> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        match self.keys {
+            Some(keys) => match keys.get(self.index) {
+                Some(key) => seed.deserialize(key.into_deserializer()).map(Some),
+                None => Ok(None),
+            },
+            None if self.index == 0 => {
+                let [format, ..] = self.formats else {
+                    return Ok(None)
+                };
+                seed.deserialize(Tracer {
+                    registry: self.registry,
+                    format,
+                })
+                .map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Error> {
+        let index = match self.keys {
+            Some(_) => self.index,
+            None => 1,
+        };
+        let format = self
+            .formats
+            .get_mut(index)
+            .ok_or_else(|| Error::custom("map value without a key"))?;
+        self.index = self.index.saturating_add(1);
+        seed.deserialize(Tracer {
+            registry: self.registry,
+            format,
+        })
+    }
+}
+
+struct EnumTracer<'a> {
+    registry: &'a mut Registry,
+    name: &'static str,
+    index: usize,
+}
+
+impl EnumTracer<'_> {
+    fn record(self, format: VariantFormat) {
+        if let Some(entry) = self.registry.enums.get_mut(self.name) {
+            if let Some(slot @ None) = entry.formats.get_mut(self.index) {
+                *slot = Some(format);
+            }
+        }
+    }
+}
+
+impl<'de, 'a> de::EnumAccess<'de> for EnumTracer<'a> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self), Error> {
+        let variant = self
+            .registry
+            .enums
+            .get(self.name)
+            .and_then(|entry| entry.variants.get(self.index))
+            .ok_or_else(|| Error::custom("unknown variant"))?;
+        let variant = seed.deserialize((*variant).into_deserializer())?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for EnumTracer<'_> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        self.record(VariantFormat::Unit);
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, Error> {
+        let mut format = Format::Unknown;
+        let value = seed.deserialize(Tracer {
+            registry: &mut *self.registry,
+            format: &mut format,
+        })?;
+        self.record(VariantFormat::Newtype(format));
+        Ok(value)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let mut formats = (0..len).map(|_| Format::Unknown).collect::<Vec<_>>();
+        let value = visitor.visit_seq(SeqTracer {
+            registry: &mut *self.registry,
+            formats: &mut formats,
+            index: 0,
+        })?;
+        self.record(VariantFormat::Tuple(formats));
+        Ok(value)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let mut formats = fields.iter().map(|_| Format::Unknown).collect::<Vec<_>>();
+        let value = visitor.visit_map(MapTracer {
+            registry: &mut *self.registry,
+            formats: &mut formats,
+            keys: Some(fields),
+            index: 0,
+        })?;
+        self.record(VariantFormat::Struct(
+            fields.iter().copied().zip(formats).collect(),
+        ));
+        Ok(value)
+    }
+}
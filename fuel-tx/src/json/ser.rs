@@ -0,0 +1,522 @@
+use super::{
+    encode_hex,
+    layout,
+    Error,
+};
+use alloc::{
+    string::{
+        String,
+        ToString,
+    },
+    vec::Vec,
+};
+use core::fmt::Display;
+use serde::{
+    ser::{
+        self,
+        Error as _,
+    },
+    Serialize,
+};
+use serde_json::{
+    Map,
+    Number,
+    Value,
+};
+
+/// The intermediate result of the encoding. It keeps the information required by the
+/// enclosing value that is lost in a [`Value`].
+pub(super) enum Encoded {
+    /// A `u8`. A non-empty sequence of bytes is encoded as a hex string.
+    Byte(u8),
+    /// A struct or a map, which is merged into the enclosing tagged union.
+    Object(Map<String, Value>),
+    /// A unit struct, which is omitted from the enclosing struct.
+    Absent,
+    Value(Value),
+}
+
+impl Encoded {
+    pub(super) fn into_value(self) -> Value {
+        match self {
+            Encoded::Byte(byte) => byte.into(),
+            Encoded::Object(object) => Value::Object(object),
+            Encoded::Absent => Value::Null,
+            Encoded::Value(value) => value,
+        }
+    }
+}
+
+fn string(value: impl Display) -> Result<Encoded, Error> {
+    Ok(Encoded::Value(Value::String(value.to_string())))
+}
+
+fn tagged(variant: &'static str, mut object: Map<String, Value>) -> Result<Value, Error> {
+    if object
+        .insert("type".to_string(), Value::String(variant.to_string()))
+        .is_some()
+    {
+        return Err(Error::custom(alloc::format!(
+            "the field `type` of the variant `{variant}` conflicts with the tag"
+        )))
+    }
+    Ok(Value::Object(object))
+}
+
+fn tagged_value(variant: &'static str, value: Value) -> Result<Encoded, Error> {
+    let mut object = Map::new();
+    object.insert("value".to_string(), value);
+    tagged(variant, object).map(Encoded::Value)
+}
+
+pub(super) struct Encoder;
+
+impl ser::Serializer for Encoder {
+    type Error = Error;
+    type Ok = Encoded;
+    type SerializeMap = MapEncoder;
+    type SerializeSeq = SeqEncoder;
+    type SerializeStruct = MapEncoder;
+    type SerializeStructVariant = MapEncoder;
+    type SerializeTuple = SeqEncoder;
+    type SerializeTupleStruct = SeqEncoder;
+    type SerializeTupleVariant = SeqEncoder;
+
+    fn serialize_bool(self, v: bool) -> Result<Encoded, Error> {
+        Ok(Encoded::Value(v.into()))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Encoded, Error> {
+        Ok(Encoded::Value(v.into()))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Encoded, Error> {
+        Ok(Encoded::Value(v.into()))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Encoded, Error> {
+        Ok(Encoded::Value(v.into()))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Encoded, Error> {
+        string(v)
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Encoded, Error> {
+        string(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Encoded, Error> {
+        Ok(Encoded::Byte(v))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Encoded, Error> {
+        Ok(Encoded::Value(v.into()))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Encoded, Error> {
+        Ok(Encoded::Value(v.into()))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Encoded, Error> {
+        string(v)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Encoded, Error> {
+        string(v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Encoded, Error> {
+        self.serialize_f64(v.into())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Encoded, Error> {
+        Number::from_f64(v)
+            .map(|number| Encoded::Value(Value::Number(number)))
+            .ok_or_else(|| Error::custom("non-finite floats can't be encoded"))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Encoded, Error> {
+        string(v)
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Encoded, Error> {
+        string(v)
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Encoded, Error> {
+        Ok(Encoded::Value(Value::String(encode_hex(v))))
+    }
+
+    fn serialize_none(self) -> Result<Encoded, Error> {
+        Ok(Encoded::Value(Value::Null))
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Encoded, Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value
+            .serialize(Encoder)
+            .map(|encoded| Encoded::Value(encoded.into_value()))
+    }
+
+    fn serialize_unit(self) -> Result<Encoded, Error> {
+        Ok(Encoded::Value(Value::Null))
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<Encoded, Error> {
+        Ok(Encoded::Absent)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<Encoded, Error> {
+        tagged(variant, Map::new()).map(Encoded::Value)
+    }
+
+    fn serialize_newtype_struct<T>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<Encoded, Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(Encoder)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Encoded, Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        match value.serialize(Encoder)? {
+            Encoded::Object(object) => tagged(variant, object).map(Encoded::Value),
+            Encoded::Absent => tagged(variant, Map::new()).map(Encoded::Value),
+            encoded => tagged_value(variant, encoded.into_value()),
+        }
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SeqEncoder, Error> {
+        Ok(SeqEncoder {
+            variant: None,
+            items: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SeqEncoder, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        len: usize,
+    ) -> Result<SeqEncoder, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SeqEncoder, Error> {
+        Ok(SeqEncoder {
+            variant: Some(variant),
+            items: Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<MapEncoder, Error> {
+        Ok(MapEncoder {
+            name: None,
+            variant: None,
+            object: Map::new(),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(self, name: &'static str, _: usize) -> Result<MapEncoder, Error> {
+        Ok(MapEncoder {
+            name: Some(name),
+            variant: None,
+            object: Map::new(),
+            key: None,
+        })
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        _: usize,
+    ) -> Result<MapEncoder, Error> {
+        Ok(MapEncoder {
+            name: None,
+            variant: Some(variant),
+            object: Map::new(),
+            key: None,
+        })
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+pub(super) struct SeqEncoder {
+    variant: Option<&'static str>,
+    items: Vec<Encoded>,
+}
+
+impl SeqEncoder {
+    fn push<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.items.push(value.serialize(Encoder)?);
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Encoded, Error> {
+        let bytes = self
+            .items
+            .iter()
+            .map(|item| match item {
+                Encoded::Byte(byte) => Some(*byte),
+                _ => None,
+            })
+            .collect::<Option<Vec<u8>>>()
+            .filter(|bytes| !bytes.is_empty());
+        let value = match bytes {
+            Some(bytes) => Value::String(encode_hex(&bytes)),
+            None => {
+                Value::Array(self.items.into_iter().map(Encoded::into_value).collect())
+            }
+        };
+
+        match self.variant {
+            Some(variant) => tagged_value(variant, value),
+            None => Ok(Encoded::Value(value)),
+        }
+    }
+}
+
+impl ser::SerializeSeq for SeqEncoder {
+    type Error = Error;
+    type Ok = Encoded;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Encoded, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for SeqEncoder {
+    type Error = Error;
+    type Ok = Encoded;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Encoded, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for SeqEncoder {
+    type Error = Error;
+    type Ok = Encoded;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Encoded, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for SeqEncoder {
+    type Error = Error;
+    type Ok = Encoded;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Encoded, Error> {
+        self.finish()
+    }
+}
+
+pub(super) struct MapEncoder {
+    /// The name of the struct, or `None` for maps and struct variants.
+    name: Option<&'static str>,
+    variant: Option<&'static str>,
+    object: Map<String, Value>,
+    key: Option<String>,
+}
+
+impl MapEncoder {
+    fn insert<T>(&mut self, key: String, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        match value.serialize(Encoder)? {
+            Encoded::Absent => Ok(()),
+            encoded => self.insert_value(key, encoded.into_value()),
+        }
+    }
+
+    fn insert_value(&mut self, key: String, value: Value) -> Result<(), Error> {
+        if self.object.insert(key, value).is_some() {
+            return Err(Error::custom("duplicate key"))
+        }
+        Ok(())
+    }
+
+    fn insert_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        if key == layout::FLATTENED_FIELD {
+            let body = value.serialize(Encoder)?.into_value();
+            return layout::flatten(&mut self.object, body)
+        }
+        let is_bytes = self
+            .name
+            .or(self.variant)
+            .is_some_and(|name| layout::is_bytes(name, key));
+        if is_bytes {
+            return self.insert_bytes(key, value)
+        }
+        self.insert(key.to_string(), value)
+    }
+
+    /// Inserts a byte vector, whose sequence of bytes is a hex string unless empty.
+    fn insert_bytes<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let value = match value.serialize(Encoder)? {
+            Encoded::Absent => return Ok(()),
+            Encoded::Value(Value::Array(items)) if items.is_empty() => {
+                Value::String(encode_hex(&[]))
+            }
+            encoded => encoded.into_value(),
+        };
+        self.insert_value(key.to_string(), value)
+    }
+
+    fn finish(self) -> Result<Encoded, Error> {
+        match (self.name, self.variant) {
+            (_, Some(variant)) => tagged(variant, self.object).map(Encoded::Value),
+            (Some(layout::POLICIES), None) => {
+                layout::encode_policies(self.object).map(Encoded::Object)
+            }
+            (Some(name), None) if layout::is_transparent(name) => {
+                let mut fields = self.object.into_iter();
+                match (fields.next(), fields.next()) {
+                    (Some((_, value)), None) => Ok(Encoded::Value(value)),
+                    _ => Err(Error::custom(alloc::format!(
+                        "the struct `{name}` must have a single field"
+                    ))),
+                }
+            }
+            (_, None) => Ok(Encoded::Object(self.object)),
+        }
+    }
+}
+
+impl ser::SerializeMap for MapEncoder {
+    type Error = Error;
+    type Ok = Encoded;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let key = match key.serialize(Encoder)?.into_value() {
+            Value::String(key) => key,
+            _ => return Err(Error::custom("map keys must be encoded as strings")),
+        };
+        self.key = Some(key);
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| Error::custom("map value without a key"))?;
+        self.insert(key, value)
+    }
+
+    fn end(self) -> Result<Encoded, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStruct for MapEncoder {
+    type Error = Error;
+    type Ok = Encoded;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.insert_field(key, value)
+    }
+
+    fn end(self) -> Result<Encoded, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for MapEncoder {
+    type Error = Error;
+    type Ok = Encoded;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.insert_field(key, value)
+    }
+
+    fn end(self) -> Result<Encoded, Error> {
+        self.finish()
+    }
+}
@@ -0,0 +1,314 @@
+use super::*;
+use crate::{
+    policies::Policies,
+    test_helper::TransactionFactory,
+    Blob,
+    ConsensusParameters,
+    Create,
+    Input,
+    Mint,
+    Output,
+    Receipt,
+    Script,
+    Transaction,
+    TxPointer,
+    Upgrade,
+    Upload,
+    UtxoId,
+};
+use alloc::{
+    format,
+    vec,
+};
+use fuel_types::{
+    Address,
+    AssetId,
+    Bytes32,
+};
+use serde_json::json;
+
+fn hex32(byte: u8) -> String {
+    format!("0x{}", hex::encode([byte; 32]))
+}
+
+fn script() -> Transaction {
+    Transaction::script(
+        1_000_000,
+        vec![0x24, 0x04, 0x00, 0x00],
+        vec![],
+        Policies::new().with_max_fee(100),
+        vec![Input::coin_signed(
+            UtxoId::new(Bytes32::new([1; 32]), 2),
+            Address::new([3; 32]),
+            u64::MAX,
+            AssetId::new([4; 32]),
+            TxPointer::new(5.into(), 6),
+            0,
+        )],
+        vec![Output::change(
+            Address::new([3; 32]),
+            0,
+            AssetId::new([4; 32]),
+        )],
+        vec![vec![0xaa, 0xbb].into()],
+    )
+    .into()
+}
+
+fn assert_round_trip<T>(value: &T)
+where
+    T: serde::Serialize + DeserializeOwned + fuel_types::canonical::Serialize,
+{
+    let json = to_value(value).expect("Failed to encode JSON");
+    let decoded: T = from_value(json.clone()).expect("Failed to decode JSON");
+
+    assert_eq!(decoded.to_bytes(), value.to_bytes());
+    assert_eq!(to_value(&decoded).expect("Failed to encode JSON"), json);
+}
+
+#[test]
+fn encodes_transaction_with_tagged_unions_hex_bytes_and_decimal_words() {
+    let expected = json!({
+        "type": "Script",
+        "script_gas_limit": "1000000",
+        "receipts_root": hex32(0),
+        "script": "0x24040000",
+        "script_data": "0x",
+        "policies": { "max_fee": "100" },
+        "inputs": [{
+            "type": "CoinSigned",
+            "utxo_id": { "tx_id": hex32(1), "output_index": 2 },
+            "owner": hex32(3),
+            "amount": "18446744073709551615",
+            "asset_id": hex32(4),
+            "tx_pointer": { "block_height": 5, "tx_index": 6 },
+            "witness_index": 0,
+        }],
+        "outputs": [{
+            "type": "Change",
+            "to": hex32(3),
+            "amount": "0",
+            "asset_id": hex32(4),
+        }],
+        "witnesses": ["0xaabb"],
+    });
+
+    assert_eq!(to_value(&script()), Ok(expected.clone()));
+    assert_eq!(from_str::<Transaction>(&expected.to_string()), Ok(script()));
+}
+
+#[test]
+fn encodes_newtype_variant_payloads_under_value() {
+    let receipt =
+        Receipt::script_result(crate::ScriptExecutionResult::GenericFailure(7), 10);
+
+    assert_eq!(
+        to_string(&receipt),
+        Ok(r#"{"gas_used":"10","result":{"type":"GenericFailure","value":"7"},"type":"ScriptResult"}"#.into())
+    );
+}
+
+#[test]
+fn encoding_is_deterministic() {
+    let tx = script();
+
+    assert_eq!(to_string(&tx), to_string(&tx.clone()));
+    assert_eq!(
+        to_string(&from_str::<Transaction>(&to_string(&tx).unwrap()).unwrap()),
+        to_string(&tx)
+    );
+}
+
+#[test]
+fn decoding_rejects_non_canonical_values() {
+    let valid = to_value(&script()).unwrap();
+    type Mutation = fn(&mut Value);
+
+    let cases: [(&str, Mutation); 13] = [
+        ("word as a number", |tx| {
+            tx["inputs"][0]["amount"] = json!(10)
+        }),
+        ("word with leading zeros", |tx| {
+            tx["inputs"][0]["amount"] = json!("010")
+        }),
+        ("word with a sign", |tx| {
+            tx["inputs"][0]["amount"] = json!("+10")
+        }),
+        ("hex without prefix", |tx| {
+            tx["inputs"][0]["owner"] = json!(hex32(3).trim_start_matches("0x"))
+        }),
+        ("uppercase hex", |tx| {
+            tx["inputs"][0]["owner"] =
+                json!(hex32(0xab).to_uppercase().replace("0X", "0x"))
+        }),
+        ("short hex", |tx| tx["inputs"][0]["owner"] = json!("0x0303")),
+        ("bytes as an array", |tx| {
+            tx["script"] = json!([0x24, 4, 0, 0])
+        }),
+        ("witness as an array", |tx| {
+            tx["witnesses"][0] = json!([0xaa, 0xbb])
+        }),
+        ("unknown field", |tx| {
+            tx["inputs"][0]["owner_id"] = json!(hex32(3))
+        }),
+        ("nested body", |tx| {
+            let script = tx.as_object_mut().unwrap().remove("script").unwrap();
+            tx["body"] = json!({ "script": script });
+        }),
+        ("unknown policy", |tx| {
+            tx["policies"]["gas_price"] = json!("1")
+        }),
+        ("unknown variant", |tx| tx["type"] = json!("Scripts")),
+        ("missing tag", |tx| {
+            tx.as_object_mut().unwrap().remove("type");
+        }),
+    ];
+
+    for (case, mutate) in cases {
+        let mut tx = valid.clone();
+        mutate(&mut tx);
+        assert!(from_value::<Transaction>(tx).is_err(), "{case}");
+    }
+}
+
+#[test]
+fn encodes_empty_byte_vectors_as_hex() {
+    let receipt = Receipt::return_data(Default::default(), 0, 0, 0, vec![]);
+
+    let json = to_value(&receipt).unwrap();
+
+    assert_eq!(json["data"], json!("0x"));
+    assert_eq!(
+        to_value(&from_value::<Receipt>(json).unwrap()).unwrap()["data"],
+        "0x"
+    );
+    assert_eq!(to_value(&crate::Witness::default()), Ok(json!("0x")));
+}
+
+#[test]
+fn default_serde_encoding_is_unchanged() {
+    let witness = crate::Witness::from(vec![0xaa, 0xbb]);
+
+    assert_eq!(
+        serde_json::to_value(witness).unwrap(),
+        json!({ "data": [0xaa, 0xbb] })
+    );
+}
+
+#[test]
+fn transactions_round_trip() {
+    for tx in TransactionFactory::<_, Script>::from_seed(3493).take(10) {
+        assert_round_trip(&Transaction::from(tx.0));
+    }
+    for tx in TransactionFactory::<_, Create>::from_seed(3493).take(10) {
+        assert_round_trip(&Transaction::from(tx.0));
+    }
+    for tx in TransactionFactory::<_, Mint>::from_seed(3493).take(10) {
+        assert_round_trip(&Transaction::from(tx));
+    }
+    for tx in TransactionFactory::<_, Upgrade>::from_seed(3493).take(10) {
+        assert_round_trip(&Transaction::from(tx.0));
+    }
+    for tx in TransactionFactory::<_, Upload>::from_seed(3493).take(10) {
+        assert_round_trip(&Transaction::from(tx.0));
+    }
+    for tx in TransactionFactory::<_, Blob>::from_seed(3493).take(10) {
+        assert_round_trip(&Transaction::from(tx.0));
+    }
+}
+
+#[test]
+fn consensus_parameters_round_trip() {
+    for params in [
+        ConsensusParameters::default(),
+        ConsensusParameters::standard(),
+    ] {
+        let json = to_value(&params).expect("Failed to encode JSON");
+
+        assert_eq!(from_value(json), Ok(params));
+    }
+}
+
+#[test]
+fn schema_files_are_up_to_date() {
+    fn assert_schema<T: DeserializeOwned>(name: &str, file: &str) {
+        let schema = schema::<T>().expect("Failed to generate the schema");
+        let schema = serde_json::to_string_pretty(&schema).unwrap();
+
+        assert_eq!(
+            schema.trim_end(),
+            file.trim_end(),
+            "`schema/{name}.json` is outdated, regenerate it with \
+             `cargo run -p fuel-tx --features explain -- schema {name}`"
+        );
+    }
+
+    assert_schema::<Transaction>(
+        "transaction",
+        include_str!("../../schema/transaction.json"),
+    );
+    assert_schema::<Input>("input", include_str!("../../schema/input.json"));
+    assert_schema::<Output>("output", include_str!("../../schema/output.json"));
+    assert_schema::<Receipt>("receipt", include_str!("../../schema/receipt.json"));
+    assert_schema::<ConsensusParameters>(
+        "consensus_parameters",
+        include_str!("../../schema/consensus_parameters.json"),
+    );
+}
+
+#[test]
+fn schema_describes_the_encoding() {
+    let schema = schema::<Transaction>().unwrap();
+    let coin_signed = &schema["$defs"]["Input"]["oneOf"][0];
+
+    assert_eq!(
+        coin_signed["properties"]["type"],
+        json!({ "const": "CoinSigned" })
+    );
+    assert_eq!(
+        coin_signed["properties"]["amount"],
+        json!({ "type": "string", "pattern": "^(0|[1-9][0-9]*)$" })
+    );
+    assert_eq!(
+        coin_signed["properties"]["owner"],
+        json!({ "type": "string", "pattern": "^0x[0-9a-f]{64}$" })
+    );
+    assert!(coin_signed["properties"].get("predicate").is_none());
+}
+
+#[test]
+fn schema_rejects_byte_vectors_missing_from_the_layout() {
+    // Every type with a schema is traced down to its byte vectors, which must all be
+    // hex encoded by the layout.
+    for schema in [
+        schema::<Transaction>(),
+        schema::<Input>(),
+        schema::<Output>(),
+        schema::<Receipt>(),
+        schema::<ConsensusParameters>(),
+    ] {
+        assert!(schema.is_ok(), "{schema:?}");
+    }
+
+    #[derive(serde::Deserialize)]
+    #[allow(dead_code)]
+    struct Unlisted {
+        data: Vec<u8>,
+    }
+
+    #[derive(serde::Deserialize)]
+    #[allow(dead_code)]
+    struct Coin {
+        predicate: u64,
+    }
+
+    assert_eq!(
+        schema::<Unlisted>().unwrap_err().to_string(),
+        "the byte vector field `data` of `Unlisted` isn't listed in the JSON layout"
+    );
+    assert_eq!(
+        schema::<Coin>().unwrap_err().to_string(),
+        "the field `predicate` of `Coin` is listed in the JSON layout, but isn't a byte \
+         vector"
+    );
+}
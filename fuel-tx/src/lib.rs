@@ -54,6 +54,9 @@ mod receipt;
 #[cfg(feature = "alloc")]
 mod transaction;
 
+#[cfg(feature = "serde")]
+pub mod json;
+
 #[cfg(test)]
 mod tests;
 
@@ -63,19 +66,30 @@ pub mod test_helper;
 #[cfg(feature = "test-helpers")]
 pub use builder::{
     Buildable,
+    CoinSelection,
     Finalizable,
+    FundError,
     TransactionBuilder,
 };
 
 #[cfg(feature = "alloc")]
 pub use receipt::{
+    LogDecodeError,
+    LogDecoder,
+    LogDecoders,
     Receipt,
+    ReceiptBloom,
+    ReceiptFilter,
+    ReceiptProof,
+    ReceiptRepr,
+    ReceiptsWithDepth,
     ScriptExecutionResult,
 };
 
 #[cfg(feature = "alloc")]
 pub use transaction::{
     consensus_parameters,
+    explain,
     field,
     input,
     input::Input,
@@ -98,14 +112,20 @@ pub use transaction::{
     CreateMetadata,
     DependentCost,
     Executable,
+    FeeEstimate,
+    FeeEstimateError,
     FeeParameters,
     FormatValidityChecks,
     GasCosts,
     GasCostsValues,
     Mint,
+    PartiallySignedError,
+    PartiallySignedTransaction,
     PredicateParameters,
     Script,
     ScriptParameters,
+    SignerHint,
+    SignerType,
     StorageSlot,
     Transaction,
     TransactionFee,
@@ -18,10 +18,26 @@ use fuel_types::{
     Word,
 };
 
+mod bloom;
+mod logs;
+mod proof;
+mod query;
 mod receipt_repr;
 mod script_result;
 
 use crate::input::message::compute_message_id;
+pub use bloom::ReceiptBloom;
+pub use logs::{
+    LogDecodeError,
+    LogDecoder,
+    LogDecoders,
+};
+pub use proof::ReceiptProof;
+pub use query::{
+    ReceiptFilter,
+    ReceiptsWithDepth,
+};
+pub use receipt_repr::ReceiptRepr;
 pub use script_result::ScriptExecutionResult;
 
 #[derive(Clone, Derivative)]
@@ -0,0 +1,302 @@
+use super::{
+    query::emitter,
+    Receipt,
+};
+use fuel_crypto::Hasher;
+use fuel_types::{
+    canonical::{
+        Deserialize,
+        Serialize,
+    },
+    ContractId,
+    Word,
+};
+
+/// A bloom filter of the contracts and logs of the receipts of a transaction.
+///
+/// Light clients check whether a transaction may have logs of a contract without
+/// downloading its receipts. A negative answer is certain, a positive answer requires
+/// checking the receipts, for example with a [`ReceiptProof`](super::ReceiptProof).
+///
+/// The filter has 2048 bits. Each item sets 3 bits, selected by the first 6 bytes of
+/// its SHA-256 hash, read as 3 big-endian `u16`s modulo 2048. The items are:
+/// - The non-zero contract ids that emitted a receipt, were called, received a transfer
+///   or minted or burned a coin.
+/// - The contract id followed by the big-endian log id (`rb`) of each `Log` and `LogData`
+///   receipt. The logs of the script use the zeroed contract id.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReceiptBloom(
+    #[cfg_attr(feature = "serde", serde(with = "serde_bits"))] [u8; ReceiptBloom::SIZE],
+);
+
+impl ReceiptBloom {
+    const HASHES: usize = 3;
+    /// The size of the filter in bytes.
+    pub const SIZE: usize = 256;
+
+    /// Creates an empty filter.
+    pub const fn new() -> Self {
+        Self([0; Self::SIZE])
+    }
+
+    /// Creates the filter of the receipts of a transaction.
+    pub fn from_receipts<'a, I>(receipts: I) -> Self
+    where
+        I: IntoIterator<Item = &'a Receipt>,
+    {
+        let mut bloom = Self::new();
+        receipts
+            .into_iter()
+            .for_each(|receipt| bloom.insert_receipt(receipt));
+        bloom
+    }
+
+    /// Adds the contracts and the log of the `receipt` to the filter.
+    pub fn insert_receipt(&mut self, receipt: &Receipt) {
+        let emitter = emitter(receipt);
+        let contracts = [emitter.as_ref(), receipt.to()];
+        contracts
+            .into_iter()
+            .flatten()
+            .filter(|id| **id != ContractId::zeroed())
+            .for_each(|id| self.insert(id.as_ref()));
+
+        if let (Some(id), Some(log_id)) = (emitter, receipt.rb()) {
+            self.insert(&log_key(&id, log_id));
+        }
+    }
+
+    /// Adds the items of `other` to the filter, for example to compute the filter of a
+    /// block from the filters of its transactions.
+    pub fn accrue(&mut self, other: &Self) {
+        self.0
+            .iter_mut()
+            .zip(other.0.iter())
+            .for_each(|(byte, other)| *byte |= other);
+    }
+
+    /// Returns `false` if no receipt involves the contract.
+    pub fn may_contain_contract(&self, contract_id: &ContractId) -> bool {
+        self.contains(contract_id.as_ref())
+    }
+
+    /// Returns `false` if the contract emitted no log with the `log_id`.
+    pub fn may_contain_log(&self, contract_id: &ContractId, log_id: Word) -> bool {
+        self.contains(&log_key(contract_id, log_id))
+    }
+
+    /// Returns `true` if no item was added to the filter.
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(|byte| *byte == 0)
+    }
+
+    fn insert(&mut self, item: &[u8]) {
+        for (byte, mask) in Self::bits(item) {
+            self.0[byte] |= mask;
+        }
+    }
+
+    fn contains(&self, item: &[u8]) -> bool {
+        Self::bits(item)
+            .into_iter()
+            .all(|(byte, mask)| self.0[byte] & mask != 0)
+    }
+
+    /// The byte index and the mask of the bits of the item.
+    fn bits(item: &[u8]) -> [(usize, u8); Self::HASHES] {
+        let hash = Hasher::hash(item);
+        let mut bits = [(0, 0); Self::HASHES];
+        for (bit, chunk) in bits.iter_mut().zip(hash.chunks_exact(2)) {
+            // 2048 bits of the filter, the 11 lower bits of the `u16`
+            let index = u16::from_be_bytes([chunk[0], chunk[1]]) & 0x07ff;
+            *bit = (usize::from(index >> 3), 1 << (index & 0x07));
+        }
+        bits
+    }
+}
+
+fn log_key(contract_id: &ContractId, log_id: Word) -> [u8; 40] {
+    let mut key = [0; 40];
+    key[..ContractId::LEN].copy_from_slice(contract_id.as_ref());
+    key[ContractId::LEN..].copy_from_slice(&log_id.to_be_bytes());
+    key
+}
+
+impl Default for ReceiptBloom {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl core::fmt::Debug for ReceiptBloom {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("ReceiptBloom(")?;
+        self.0.iter().try_for_each(|byte| write!(f, "{byte:02x}"))?;
+        f.write_str(")")
+    }
+}
+
+impl From<[u8; ReceiptBloom::SIZE]> for ReceiptBloom {
+    fn from(bytes: [u8; ReceiptBloom::SIZE]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<ReceiptBloom> for [u8; ReceiptBloom::SIZE] {
+    fn from(bloom: ReceiptBloom) -> Self {
+        bloom.0
+    }
+}
+
+impl AsRef<[u8]> for ReceiptBloom {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// The filter is a hex string in human-readable formats and bytes otherwise, like the
+/// `fuel-types` arrays, since serde derives arrays only up to 32 elements.
+#[cfg(feature = "serde")]
+mod serde_bits {
+    use super::ReceiptBloom;
+    use alloc::{
+        string::String,
+        vec::Vec,
+    };
+    use serde::{
+        de::Error,
+        Deserialize,
+        Deserializer,
+        Serializer,
+    };
+
+    pub fn serialize<S>(
+        bits: &[u8; ReceiptBloom::SIZE],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&hex::encode(bits))
+        } else {
+            serializer.serialize_bytes(bits)
+        }
+    }
+
+    pub fn deserialize<'de, D>(
+        deserializer: D,
+    ) -> Result<[u8; ReceiptBloom::SIZE], D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut bits = [0; ReceiptBloom::SIZE];
+        if deserializer.is_human_readable() {
+            let s = String::deserialize(deserializer)?;
+            let s = s.strip_prefix("0x").unwrap_or(&s);
+            hex::decode_to_slice(s, &mut bits).map_err(D::Error::custom)?;
+        } else {
+            let bytes = Vec::<u8>::deserialize(deserializer)?;
+            if bytes.len() != ReceiptBloom::SIZE {
+                return Err(D::Error::invalid_length(bytes.len(), &"256 bytes"))
+            }
+            bits.copy_from_slice(&bytes);
+        }
+        Ok(bits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    const A: ContractId = ContractId::new([0xa; 32]);
+    const B: ContractId = ContractId::new([0xb; 32]);
+    const C: ContractId = ContractId::new([0xc; 32]);
+
+    #[test]
+    fn contains_the_contracts_and_logs_of_the_receipts() {
+        let receipts = [
+            Receipt::call(
+                ContractId::zeroed(),
+                A,
+                0,
+                Default::default(),
+                0,
+                0,
+                0,
+                0,
+                0,
+            ),
+            Receipt::log_data(A, 0, 7, 0, 0, 0, vec![1, 2]),
+            Receipt::transfer(A, B, 1, Default::default(), 0, 0),
+            Receipt::log(ContractId::zeroed(), 0, 9, 0, 0, 0, 0),
+        ];
+        let bloom = ReceiptBloom::from_receipts(&receipts);
+
+        assert!(bloom.may_contain_contract(&A));
+        assert!(bloom.may_contain_contract(&B));
+        assert!(bloom.may_contain_log(&A, 7));
+        assert!(bloom.may_contain_log(&ContractId::zeroed(), 9));
+
+        assert!(!bloom.may_contain_contract(&C));
+        assert!(!bloom.may_contain_contract(&ContractId::zeroed()));
+        assert!(!bloom.may_contain_log(&A, 9));
+        assert!(!bloom.may_contain_log(&B, 7));
+    }
+
+    #[test]
+    fn accrue_is_the_union_of_the_filters() {
+        let a = ReceiptBloom::from_receipts(&[Receipt::log(A, 0, 1, 0, 0, 0, 0)]);
+        let b = ReceiptBloom::from_receipts(&[Receipt::log(B, 0, 2, 0, 0, 0, 0)]);
+        let mut block = ReceiptBloom::new();
+        block.accrue(&a);
+        block.accrue(&b);
+
+        assert_eq!(
+            block,
+            ReceiptBloom::from_receipts(&[
+                Receipt::log(A, 0, 1, 0, 0, 0, 0),
+                Receipt::log(B, 0, 2, 0, 0, 0, 0),
+            ])
+        );
+        assert!(block.may_contain_log(&A, 1));
+        assert!(block.may_contain_log(&B, 2));
+        assert!(!block.may_contain_log(&A, 2));
+    }
+
+    #[test]
+    fn empty_filter_contains_nothing() {
+        let bloom = ReceiptBloom::from_receipts(&[Receipt::script_result(
+            crate::ScriptExecutionResult::Success,
+            0,
+        )]);
+
+        assert!(bloom.is_empty());
+        assert!(!bloom.may_contain_contract(&A));
+    }
+
+    #[test]
+    fn canonical_encoding_is_the_raw_bits() {
+        let bloom = ReceiptBloom::from_receipts(&[Receipt::log(A, 0, 1, 0, 0, 0, 0)]);
+        let bytes = bloom.to_bytes();
+
+        assert_eq!(bytes.as_slice(), bloom.as_ref());
+        assert_eq!(ReceiptBloom::from_bytes(&bytes), Ok(bloom));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_roundtrip() {
+        let bloom = ReceiptBloom::from_receipts(&[Receipt::log(A, 0, 1, 0, 0, 0, 0)]);
+
+        let json = serde_json::to_string(&bloom).unwrap();
+        assert_eq!(json, alloc::format!("\"{}\"", hex::encode(bloom)));
+        assert_eq!(serde_json::from_str::<ReceiptBloom>(&json).unwrap(), bloom);
+
+        let bytes = postcard::to_allocvec(&bloom).unwrap();
+        assert_eq!(postcard::from_bytes::<ReceiptBloom>(&bytes).unwrap(), bloom);
+    }
+}
@@ -0,0 +1,243 @@
+use super::{
+    query::emitter,
+    Receipt,
+};
+use alloc::{
+    boxed::Box,
+    collections::BTreeMap,
+};
+use fuel_types::{
+    canonical,
+    ContractId,
+    Word,
+};
+
+/// Decodes the data of the `LogData` receipts with a log id into a typed value.
+///
+/// Closures decoding bytes implement the trait.
+pub trait LogDecoder {
+    /// The decoded value.
+    type Log;
+    /// The error of the decoding, for example of the ABI decoding of the data.
+    type Error;
+
+    /// Decodes the data of a `LogData` receipt.
+    fn decode(&self, data: &[u8]) -> Result<Self::Log, Self::Error>;
+}
+
+impl<F, T, E> LogDecoder for F
+where
+    F: Fn(&[u8]) -> Result<T, E>,
+{
+    type Error = E;
+    type Log = T;
+
+    fn decode(&self, data: &[u8]) -> Result<T, E> {
+        self(data)
+    }
+}
+
+/// The error returned by [`LogDecoders::decode`], with the error `E` of the decoders.
+#[derive(Debug, PartialEq, Eq, derive_more::Display)]
+#[display(bound = "E: core::fmt::Debug")]
+#[non_exhaustive]
+pub enum LogDecodeError<E = canonical::Error> {
+    /// The `LogData` receipt was decoded without its data.
+    #[display(fmt = "The receipt doesn't have the log data")]
+    MissingData,
+    /// The decoder failed to decode the data.
+    #[display(fmt = "Failed to decode the log data: {_0:?}")]
+    Decode(E),
+}
+
+/// The [`LogDecoder`]s of the logs of several contracts, registered per contract id and
+/// log id.
+///
+/// All the decoders produce the same type, usually an enum of the events an indexer is
+/// interested in, and fail with the same error `E`.
+///
+/// ```
+/// use fuel_tx::{
+///     ContractId,
+///     LogDecoders,
+///     Receipt,
+/// };
+/// use fuel_types::canonical::Deserialize;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Event {
+///     Deposit(u64),
+/// }
+///
+/// let contract = ContractId::from([1; 32]);
+/// let mut decoders = LogDecoders::new();
+/// decoders.register(contract, 7, |data: &[u8]| u64::from_bytes(data).map(Event::Deposit));
+///
+/// let receipts = [Receipt::log_data(contract, 0, 7, 0, 0, 0, 100u64.to_be_bytes().to_vec())];
+/// let events: Vec<_> = decoders.decode_all(&receipts).collect();
+///
+/// assert_eq!(events, [Ok(Event::Deposit(100))]);
+/// ```
+pub struct LogDecoders<T, E = canonical::Error> {
+    decoders: BTreeMap<(ContractId, Word), Box<dyn LogDecoder<Log = T, Error = E>>>,
+}
+
+impl<T, E> Default for LogDecoders<T, E> {
+    fn default() -> Self {
+        Self {
+            decoders: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T, E> LogDecoders<T, E> {
+    /// Creates an empty set of decoders.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the decoder of the logs with the `log_id` emitted by the contract,
+    /// replacing the previous one. The logs of the script are emitted by the zeroed
+    /// contract id.
+    pub fn register<D>(&mut self, contract_id: ContractId, log_id: Word, decoder: D)
+    where
+        D: LogDecoder<Log = T, Error = E> + 'static,
+    {
+        self.decoders
+            .insert((contract_id, log_id), Box::new(decoder));
+    }
+
+    /// Returns `true` if a decoder is registered for the contract and log id.
+    pub fn contains(&self, contract_id: &ContractId, log_id: Word) -> bool {
+        self.decoders.contains_key(&(*contract_id, log_id))
+    }
+
+    /// Decodes the `receipt` with its registered decoder. Returns `None` if the receipt
+    /// isn't a `LogData` or no decoder is registered for it.
+    pub fn decode(&self, receipt: &Receipt) -> Option<Result<T, LogDecodeError<E>>> {
+        let Receipt::LogData { rb, data, .. } = receipt else {
+            return None
+        };
+        let decoder = self.decoders.get(&(emitter(receipt)?, *rb))?;
+
+        Some(match data {
+            Some(data) => decoder.decode(data).map_err(LogDecodeError::Decode),
+            None => Err(LogDecodeError::MissingData),
+        })
+    }
+
+    /// Decodes, in order, the logs of the receipts with a registered decoder.
+    pub fn decode_all<'a>(
+        &'a self,
+        receipts: &'a [Receipt],
+    ) -> impl Iterator<Item = Result<T, LogDecodeError<E>>> + 'a {
+        receipts.iter().filter_map(|receipt| self.decode(receipt))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{
+        vec,
+        vec::Vec,
+    };
+    use fuel_types::canonical::Deserialize;
+
+    const A: ContractId = ContractId::new([0xa; 32]);
+    const B: ContractId = ContractId::new([0xb; 32]);
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum Event {
+        Amount(Word),
+        Flag(bool),
+    }
+
+    fn decoders() -> LogDecoders<Event> {
+        let mut decoders = LogDecoders::new();
+        decoders.register(A, 1, |data: &[u8]| {
+            Word::from_bytes(data).map(Event::Amount)
+        });
+        decoders.register(B, 1, |data: &[u8]| match data {
+            [0] => Ok(Event::Flag(false)),
+            [1] => Ok(Event::Flag(true)),
+            _ => Err(canonical::Error::Unknown("invalid flag")),
+        });
+        decoders
+    }
+
+    #[test]
+    fn decode_uses_the_decoder_of_the_contract_and_log_id() {
+        let decoders = decoders();
+        let amount = Receipt::log_data(A, 0, 1, 0, 0, 0, 5u64.to_be_bytes().to_vec());
+        let flag = Receipt::log_data(B, 0, 1, 0, 0, 0, vec![1]);
+
+        assert_eq!(decoders.decode(&amount), Some(Ok(Event::Amount(5))));
+        assert_eq!(decoders.decode(&flag), Some(Ok(Event::Flag(true))));
+    }
+
+    #[test]
+    fn decode_skips_unregistered_logs() {
+        let decoders = decoders();
+
+        assert!(!decoders.contains(&A, 2));
+        assert_eq!(
+            decoders.decode(&Receipt::log_data(A, 0, 2, 0, 0, 0, vec![])),
+            None
+        );
+        assert_eq!(decoders.decode(&Receipt::log(A, 0, 1, 0, 0, 0, 0)), None);
+        assert_eq!(decoders.decode(&Receipt::ret(A, 0, 0, 0)), None);
+    }
+
+    #[test]
+    fn decode_reports_errors() {
+        let decoders = decoders();
+        let invalid = Receipt::log_data(B, 0, 1, 0, 0, 0, vec![2]);
+        let without_data =
+            Receipt::log_data_with_len(A, 0, 1, 0, 8, Default::default(), 0, 0, None);
+
+        assert_eq!(
+            decoders.decode(&invalid),
+            Some(Err(LogDecodeError::Decode(canonical::Error::Unknown(
+                "invalid flag"
+            ))))
+        );
+        assert_eq!(
+            decoders.decode(&without_data),
+            Some(Err(LogDecodeError::MissingData))
+        );
+    }
+
+    #[test]
+    fn decode_all_returns_the_registered_logs_in_order() {
+        let decoders = decoders();
+        let receipts = [
+            Receipt::log_data(B, 0, 1, 0, 0, 0, vec![0]),
+            Receipt::log_data(A, 0, 3, 0, 0, 0, vec![]),
+            Receipt::log_data(A, 0, 1, 0, 0, 0, 7u64.to_be_bytes().to_vec()),
+        ];
+        let events: Vec<_> = decoders.decode_all(&receipts).collect();
+
+        assert_eq!(events, [Ok(Event::Flag(false)), Ok(Event::Amount(7))]);
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum AbiError {
+        UnexpectedLength(usize),
+    }
+
+    #[test]
+    fn decode_reports_the_errors_of_the_decoders() {
+        let mut decoders = LogDecoders::<Event, AbiError>::new();
+        decoders.register(A, 1, |data: &[u8]| match data {
+            [flag] => Ok(Event::Flag(*flag != 0)),
+            _ => Err(AbiError::UnexpectedLength(data.len())),
+        });
+        let invalid = Receipt::log_data(A, 0, 1, 0, 0, 0, vec![0, 1]);
+
+        assert_eq!(
+            decoders.decode(&invalid),
+            Some(Err(LogDecodeError::Decode(AbiError::UnexpectedLength(2))))
+        );
+    }
+}
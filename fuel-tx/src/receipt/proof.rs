@@ -0,0 +1,156 @@
+use super::Receipt;
+use alloc::vec::Vec;
+use fuel_merkle::binary::{
+    self,
+    in_memory,
+    root_calculator::MerkleRootCalculator,
+};
+use fuel_types::{
+    canonical::{
+        Deserialize,
+        Serialize,
+    },
+    Bytes32,
+};
+
+impl Receipt {
+    /// Computes the receipts root of a script transaction: the root of the binary
+    /// Merkle tree of the canonical encodings of the receipts.
+    pub fn receipts_root<'a, I>(receipts: I) -> Bytes32
+    where
+        I: IntoIterator<Item = &'a Receipt>,
+    {
+        let mut tree = MerkleRootCalculator::new();
+        for receipt in receipts {
+            tree.push(receipt.to_bytes().as_slice());
+        }
+        tree.root().into()
+    }
+}
+
+/// The Merkle inclusion proof of a receipt against the receipts root of its
+/// transaction, computed by [`Receipt::receipts_root`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Deserialize, Serialize)]
+pub struct ReceiptProof {
+    index: u64,
+    receipts_count: u64,
+    proof_set: Vec<Bytes32>,
+}
+
+impl ReceiptProof {
+    /// Creates the proof of the receipt at `index` among the `receipts` of a
+    /// transaction. Returns `None` if `index` is out of bounds.
+    pub fn new(receipts: &[Receipt], index: usize) -> Option<Self> {
+        let mut tree = in_memory::MerkleTree::new();
+        for receipt in receipts {
+            tree.push(receipt.to_bytes().as_slice());
+        }
+        let index = u64::try_from(index).ok()?;
+        let (_, proof_set) = tree.prove(index)?;
+
+        Some(Self {
+            index,
+            receipts_count: u64::try_from(receipts.len()).ok()?,
+            proof_set: proof_set.into_iter().map(Into::into).collect(),
+        })
+    }
+
+    /// The index of the proven receipt.
+    pub const fn index(&self) -> u64 {
+        self.index
+    }
+
+    /// The number of receipts of the transaction.
+    pub const fn receipts_count(&self) -> u64 {
+        self.receipts_count
+    }
+
+    /// The side nodes of the path from the receipt to the root, from the leaf up.
+    pub fn proof_set(&self) -> &[Bytes32] {
+        &self.proof_set
+    }
+
+    /// Returns `true` if the `receipt` is the receipt at [`Self::index`] of the
+    /// transaction with the `receipts_root`.
+    pub fn verify(&self, receipts_root: &Bytes32, receipt: &Receipt) -> bool {
+        let proof_set = self.proof_set.iter().map(|node| **node).collect();
+
+        binary::verify(
+            receipts_root,
+            &receipt.to_bytes(),
+            &proof_set,
+            self.index,
+            self.receipts_count,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ScriptExecutionResult;
+    use alloc::vec;
+    use fuel_types::ContractId;
+
+    fn receipts(count: u8) -> Vec<Receipt> {
+        (0..count)
+            .map(|i| {
+                Receipt::log_data(ContractId::new([i; 32]), 0, i.into(), 0, 0, 0, vec![i])
+            })
+            .collect()
+    }
+
+    #[test]
+    fn receipts_root_of_no_receipts_is_the_empty_root() {
+        assert_eq!(
+            Receipt::receipts_root(&[]),
+            Bytes32::from(MerkleRootCalculator::new().root())
+        );
+    }
+
+    #[test]
+    fn proofs_of_every_receipt_verify() {
+        for count in 1..=17 {
+            let receipts = receipts(count);
+            let root = Receipt::receipts_root(&receipts);
+
+            for (index, receipt) in receipts.iter().enumerate() {
+                let proof = ReceiptProof::new(&receipts, index).expect("Index in bounds");
+
+                assert_eq!(proof.index(), index as u64);
+                assert_eq!(proof.receipts_count(), u64::from(count));
+                assert!(proof.verify(&root, receipt), "{index} of {count}");
+            }
+        }
+    }
+
+    #[test]
+    fn proof_fails_for_another_receipt_or_root() {
+        let receipts = receipts(5);
+        let root = Receipt::receipts_root(&receipts);
+        let proof = ReceiptProof::new(&receipts, 2).unwrap();
+
+        assert!(!proof.verify(&root, &receipts[3]));
+        assert!(!proof.verify(
+            &root,
+            &Receipt::script_result(ScriptExecutionResult::Success, 0)
+        ));
+        assert!(!proof.verify(&Receipt::receipts_root(&receipts[..4]), &receipts[2]));
+    }
+
+    #[test]
+    fn proof_of_out_of_bounds_index_is_none() {
+        assert_eq!(ReceiptProof::new(&receipts(3), 3), None);
+        assert_eq!(ReceiptProof::new(&[], 0), None);
+    }
+
+    #[test]
+    fn proof_round_trips_canonical_encoding() {
+        let receipts = receipts(6);
+        let proof = ReceiptProof::new(&receipts, 4).unwrap();
+
+        assert_eq!(ReceiptProof::from_bytes(&proof.to_bytes()), Ok(proof));
+    }
+}
@@ -0,0 +1,306 @@
+use super::{
+    receipt_repr::ReceiptRepr,
+    Receipt,
+};
+use alloc::vec::Vec;
+use core::slice;
+use fuel_types::{
+    ContractId,
+    Word,
+};
+
+/// Iterates the receipts of a transaction together with the nested call depth at
+/// which they were emitted.
+///
+/// The script runs at depth `0`. A `Call` receipt is emitted by the caller and every
+/// following receipt is emitted by the callee at the next depth, until the `Return` or
+/// `ReturnData` receipt of the callee. The `ScriptResult` receipt is always at depth
+/// `0`.
+#[derive(Debug, Clone)]
+pub struct ReceiptsWithDepth<'a> {
+    receipts: slice::Iter<'a, Receipt>,
+    depth: usize,
+}
+
+impl<'a> ReceiptsWithDepth<'a> {
+    pub fn new(receipts: &'a [Receipt]) -> Self {
+        Self {
+            receipts: receipts.iter(),
+            depth: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for ReceiptsWithDepth<'a> {
+    type Item = (usize, &'a Receipt);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let receipt = self.receipts.next()?;
+        let depth = match receipt {
+            Receipt::ScriptResult { .. } => 0,
+            _ => self.depth,
+        };
+
+        self.depth = match receipt {
+            Receipt::Call { .. } => depth.saturating_add(1),
+            Receipt::Return { .. } | Receipt::ReturnData { .. } => {
+                depth.saturating_sub(1)
+            }
+            _ => depth,
+        };
+
+        Some((depth, receipt))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.receipts.size_hint()
+    }
+}
+
+/// Selects receipts by the contract that emitted them, their kind, their log id and
+/// their nested call depth. An unset criterion matches every receipt.
+///
+/// ```
+/// use fuel_tx::{
+///     ContractId,
+///     Receipt,
+///     ReceiptFilter,
+///     ReceiptRepr,
+/// };
+///
+/// let contract = ContractId::from([1; 32]);
+/// let receipts = [
+///     Receipt::call(ContractId::zeroed(), contract, 0, Default::default(), 0, 0, 0, 0, 0),
+///     Receipt::log_data(contract, 0, 42, 0, 0, 0, vec![1, 2, 3]),
+///     Receipt::ret(contract, 0, 0, 0),
+/// ];
+///
+/// let filter = ReceiptFilter::new()
+///     .with_contract_id(contract)
+///     .with_kind(ReceiptRepr::LogData)
+///     .with_log_id(42)
+///     .with_depth(1);
+/// let logs: Vec<_> = filter.filter(&receipts).collect();
+///
+/// assert_eq!(logs, [&receipts[1]]);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ReceiptFilter {
+    contract_id: Option<ContractId>,
+    kinds: Vec<ReceiptRepr>,
+    log_id: Option<Word>,
+    depth: Option<usize>,
+}
+
+impl ReceiptFilter {
+    /// Creates a filter matching every receipt.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Matches the receipts emitted by the contract. The receipts emitted by the script
+    /// have the zeroed contract id. `ScriptResult` and `MessageOut` receipts don't
+    /// have a contract id and never match.
+    pub fn with_contract_id(mut self, contract_id: ContractId) -> Self {
+        self.contract_id = Some(contract_id);
+        self
+    }
+
+    /// Matches the receipts of the kind. Called several times, matches the receipts of
+    /// any of the kinds.
+    pub fn with_kind(mut self, kind: ReceiptRepr) -> Self {
+        if !self.kinds.contains(&kind) {
+            self.kinds.push(kind);
+        }
+        self
+    }
+
+    /// Matches the `Log` and `LogData` receipts with the log id, the value of their
+    /// `rb` register.
+    pub fn with_log_id(mut self, log_id: Word) -> Self {
+        self.log_id = Some(log_id);
+        self
+    }
+
+    /// Matches the receipts emitted at the nested call depth, as defined by
+    /// [`ReceiptsWithDepth`].
+    pub fn with_depth(mut self, depth: usize) -> Self {
+        self.depth = Some(depth);
+        self
+    }
+
+    /// Returns `true` if the `receipt`, emitted at the call `depth`, matches the
+    /// filter.
+    pub fn matches(&self, receipt: &Receipt, depth: usize) -> bool {
+        (self.contract_id.is_none() || self.contract_id == emitter(receipt))
+            && (self.kinds.is_empty() || self.kinds.contains(&ReceiptRepr::from(receipt)))
+            && (self.log_id.is_none() || self.log_id == receipt.rb())
+            && (self.depth.is_none() || self.depth == Some(depth))
+    }
+
+    /// Returns the matching receipts, in order, from the receipts of a transaction.
+    pub fn filter<'a>(
+        &'a self,
+        receipts: &'a [Receipt],
+    ) -> impl Iterator<Item = &'a Receipt> + 'a {
+        ReceiptsWithDepth::new(receipts)
+            .filter(|(depth, receipt)| self.matches(receipt, *depth))
+            .map(|(_, receipt)| receipt)
+    }
+}
+
+/// The contract that emitted the receipt, zeroed for the script.
+pub(super) fn emitter(receipt: &Receipt) -> Option<ContractId> {
+    match receipt {
+        Receipt::ScriptResult { .. } | Receipt::MessageOut { .. } => None,
+        Receipt::Mint { contract_id, .. } | Receipt::Burn { contract_id, .. } => {
+            Some(*contract_id)
+        }
+        Receipt::Call { id, .. }
+        | Receipt::Return { id, .. }
+        | Receipt::ReturnData { id, .. }
+        | Receipt::Panic { id, .. }
+        | Receipt::Revert { id, .. }
+        | Receipt::Log { id, .. }
+        | Receipt::LogData { id, .. }
+        | Receipt::Transfer { id, .. }
+        | Receipt::TransferOut { id, .. } => Some(*id),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ScriptExecutionResult;
+    use alloc::vec;
+
+    const A: ContractId = ContractId::new([0xa; 32]);
+    const B: ContractId = ContractId::new([0xb; 32]);
+
+    fn receipts() -> Vec<Receipt> {
+        let call = |id, to| Receipt::call(id, to, 0, Default::default(), 0, 0, 0, 0, 0);
+        vec![
+            call(ContractId::zeroed(), A),
+            Receipt::log_data(A, 0, 1, 0, 0, 0, vec![1]),
+            call(A, B),
+            Receipt::log_data(B, 0, 2, 0, 0, 0, vec![2]),
+            Receipt::ret(B, 0, 0, 0),
+            Receipt::log(A, 0, 1, 0, 0, 0, 0),
+            Receipt::ret(A, 0, 0, 0),
+            Receipt::log(ContractId::zeroed(), 0, 1, 0, 0, 0, 0),
+            Receipt::ret(ContractId::zeroed(), 0, 0, 0),
+            Receipt::script_result(ScriptExecutionResult::Success, 0),
+        ]
+    }
+
+    fn indices(filter: &ReceiptFilter, receipts: &[Receipt]) -> Vec<usize> {
+        ReceiptsWithDepth::new(receipts)
+            .enumerate()
+            .filter(|(_, (depth, receipt))| filter.matches(receipt, *depth))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    #[test]
+    fn receipts_with_depth_follows_calls_and_returns() {
+        let depths: Vec<_> = ReceiptsWithDepth::new(&receipts())
+            .map(|(depth, _)| depth)
+            .collect();
+
+        assert_eq!(depths, [0, 1, 1, 2, 2, 1, 1, 0, 0, 0]);
+    }
+
+    #[test]
+    fn receipts_with_depth_puts_script_result_at_the_top_level() {
+        let receipts = [
+            Receipt::call(
+                ContractId::zeroed(),
+                A,
+                0,
+                Default::default(),
+                0,
+                0,
+                0,
+                0,
+                0,
+            ),
+            Receipt::revert(A, 0, 0, 0),
+            Receipt::script_result(ScriptExecutionResult::Revert, 0),
+        ];
+        let depths: Vec<_> = ReceiptsWithDepth::new(&receipts)
+            .map(|(depth, _)| depth)
+            .collect();
+
+        assert_eq!(depths, [0, 1, 0]);
+    }
+
+    #[test]
+    fn empty_filter_matches_every_receipt() {
+        let receipts = receipts();
+
+        assert_eq!(
+            ReceiptFilter::new().filter(&receipts).count(),
+            receipts.len()
+        );
+    }
+
+    #[test]
+    fn filter_by_contract_id() {
+        let receipts = receipts();
+
+        assert_eq!(
+            indices(&ReceiptFilter::new().with_contract_id(A), &receipts),
+            [1, 2, 5, 6]
+        );
+        assert_eq!(
+            indices(
+                &ReceiptFilter::new().with_contract_id(ContractId::zeroed()),
+                &receipts
+            ),
+            [0, 7, 8]
+        );
+    }
+
+    #[test]
+    fn filter_by_kinds() {
+        let receipts = receipts();
+        let filter = ReceiptFilter::new()
+            .with_kind(ReceiptRepr::Log)
+            .with_kind(ReceiptRepr::LogData);
+
+        assert_eq!(indices(&filter, &receipts), [1, 3, 5, 7]);
+    }
+
+    #[test]
+    fn filter_by_log_id() {
+        let receipts = receipts();
+
+        assert_eq!(
+            indices(&ReceiptFilter::new().with_log_id(1), &receipts),
+            [1, 5, 7]
+        );
+    }
+
+    #[test]
+    fn filter_by_depth() {
+        let receipts = receipts();
+
+        assert_eq!(
+            indices(&ReceiptFilter::new().with_depth(2), &receipts),
+            [3, 4]
+        );
+    }
+
+    #[test]
+    fn filter_combines_criteria() {
+        let receipts = receipts();
+        let filter = ReceiptFilter::new()
+            .with_contract_id(A)
+            .with_kind(ReceiptRepr::Log)
+            .with_log_id(1)
+            .with_depth(1);
+
+        assert_eq!(indices(&filter, &receipts), [5]);
+        assert_eq!(filter.filter(&receipts).collect::<Vec<_>>(), [&receipts[5]]);
+    }
+}
@@ -75,6 +75,17 @@ where
 
         assert_eq!(*data, d_p);
         assert_eq!(data.size(), d_p.size());
+
+        let json = crate::json::to_value(data).expect("Failed to encode JSON");
+        let d_j: T =
+            crate::json::from_value(json.clone()).expect("Failed to decode JSON");
+
+        assert_eq!(&d_j, data);
+        assert_eq!(d_j.to_bytes(), data.to_bytes());
+        assert_eq!(
+            crate::json::to_value(&d_j).expect("Failed to encode JSON"),
+            json
+        );
     }
 }
 
@@ -647,6 +658,63 @@ fn transaction_serde_serialization_deserialization() {
     )]);
 }
 
+/// A script transaction with the legacy policies, encoded before `Expiration` and
+/// `FeePayer` were added.
+fn legacy_script() -> Transaction {
+    Transaction::script(
+        1_000,
+        vec![0x24, 0x04, 0x00, 0x00],
+        vec![0xaa, 0xbb],
+        Policies::new()
+            .with_tip(1)
+            .with_maturity(2.into())
+            .with_max_fee(100),
+        vec![Input::coin_signed(
+            UtxoId::new(Bytes32::new([1; 32]), 2),
+            Address::new([3; 32]),
+            500,
+            AssetId::new([4; 32]),
+            TxPointer::new(5.into(), 6),
+            0,
+        )],
+        vec![Output::change(
+            Address::new([7; 32]),
+            0,
+            AssetId::new([4; 32]),
+        )],
+        vec![Witness::from(vec![8u8; 4])],
+    )
+    .into()
+}
+
+#[test]
+fn transaction_postcard_decodes_legacy_encoding() {
+    let bytes = hex::decode(
+        "00e8070000000000000000000000000000000000000000000000000000000000000000042404000002aabb0d0100026401000101010101010101010101010101010101010101010101010101010101010101020303030303030303030303030303030303030303030303030303030303030303f403040404040404040404040404040404040404040404040404040404040404040405060001020707070707070707070707070707070707070707070707070707070707070707000404040404040404040404040404040404040404040404040404040404040404010408080808",
+    )
+    .unwrap();
+
+    let tx: Transaction =
+        postcard::from_bytes(&bytes).expect("Should decode the legacy transaction");
+
+    assert_eq!(tx, legacy_script());
+    assert_eq!(postcard::to_allocvec(&tx).unwrap(), bytes);
+}
+
+#[test]
+fn transaction_bincode_decodes_legacy_encoding() {
+    let bytes = hex::decode(
+        "00000000e80300000000000000000000000000000000000000000000000000000000000000000000000000000400000000000000240400000200000000000000aabb0d0000000100000000000000000000000000000002000000000000006400000000000000010000000000000000000000010101010101010101010101010101010101010101010101010101010101010102000303030303030303030303030303030303030303030303030303030303030303f401000000000000040404040404040404040404040404040404040404040404040404040404040405000000060000000100000000000000020000000707070707070707070707070707070707070707070707070707070707070707000000000000000004040404040404040404040404040404040404040404040404040404040404040100000000000000040000000000000008080808",
+    )
+    .unwrap();
+
+    let tx: Transaction =
+        bincode::deserialize(&bytes).expect("Should decode the legacy transaction");
+
+    assert_eq!(tx, legacy_script());
+    assert_eq!(bincode::serialize(&tx).unwrap(), bytes);
+}
+
 #[test]
 fn create_input_data_offset() {
     let rng = &mut StdRng::seed_from_u64(8586);
@@ -550,7 +550,10 @@ fn transaction_with_duplicate_coin_inputs_is_invalid() {
         .check_without_signatures(Default::default(), &ConsensusParameters::standard())
         .expect_err("Expected checkable failure");
 
-    assert_eq!(err, ValidityError::DuplicateInputUtxoId { utxo_id });
+    assert_eq!(
+        err,
+        ValidityError::DuplicateInputUtxoId { index: 1, utxo_id }
+    );
 }
 
 #[test]
@@ -587,7 +590,13 @@ fn transaction_with_duplicate_message_inputs_is_invalid() {
         )
         .expect_err("Expected checkable failure");
 
-    assert_eq!(err, ValidityError::DuplicateMessageInputId { message_id });
+    assert_eq!(
+        err,
+        ValidityError::DuplicateMessageInputId {
+            index: 2,
+            message_id
+        }
+    );
 }
 
 #[test]
@@ -619,7 +628,13 @@ fn transaction_with_duplicate_contract_inputs_is_invalid() {
         .check_without_signatures(Default::default(), &ConsensusParameters::standard())
         .expect_err("Expected checkable failure");
 
-    assert_eq!(err, ValidityError::DuplicateInputContractId { contract_id });
+    assert_eq!(
+        err,
+        ValidityError::DuplicateInputContractId {
+            index: 2,
+            contract_id
+        }
+    );
 }
 
 #[test]
@@ -123,6 +123,254 @@ fn maturity() {
     assert_eq!(ValidityError::TransactionMaturity, err);
 }
 
+#[test]
+fn expiration() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+
+    let block_height = 1000.into();
+
+    TransactionBuilder::script(generate_bytes(rng), generate_bytes(rng))
+        .expiration(block_height)
+        .add_random_fee_input()
+        .finalize()
+        .check(block_height, &test_params())
+        .expect("Failed to validate script");
+
+    TransactionBuilder::create(rng.gen(), rng.gen(), vec![])
+        .expiration(block_height)
+        .add_random_fee_input()
+        .add_contract_created()
+        .finalize()
+        .check(block_height, &test_params())
+        .expect("Failed to validate tx create");
+
+    let err = Transaction::script(
+        Default::default(),
+        vec![],
+        vec![],
+        Policies::new().with_expiration(999.into()).with_max_fee(0),
+        vec![],
+        vec![],
+        vec![],
+    )
+    .check(block_height, &test_params())
+    .expect_err("Expected erroneous transaction");
+
+    assert_eq!(ValidityError::TransactionExpiration, err);
+
+    let err = Transaction::create(
+        0,
+        Policies::new().with_expiration(999.into()).with_max_fee(0),
+        rng.gen(),
+        vec![],
+        vec![],
+        vec![],
+        vec![rng.gen()],
+    )
+    .check(block_height, &test_params())
+    .expect_err("Expected erroneous transaction");
+
+    assert_eq!(ValidityError::TransactionExpiration, err);
+}
+
+fn fee_payer_script(
+    rng: &mut StdRng,
+    fee_payer: Input,
+    change_to: Address,
+) -> TransactionBuilder<Script> {
+    let secret = SecretKey::random(rng);
+    let mut builder = TransactionBuilder::script(vec![], vec![]);
+    builder
+        .add_unsigned_coin_input(secret, rng.gen(), 100, AssetId::BASE, rng.gen())
+        .add_input(fee_payer)
+        .add_output(Output::change(rng.gen(), 0, AssetId::BASE))
+        .add_output(Output::change(change_to, 0, AssetId::BASE))
+        .fee_payer(1, 1);
+    builder
+}
+
+#[test]
+fn script__check__fee_payer_with_separate_change_is_valid() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let block_height = 1000.into();
+
+    let predicate = vec![0x01];
+    let owner = Input::predicate_owner(&predicate);
+    let fee_payer = Input::coin_predicate(
+        rng.gen(),
+        owner,
+        1000,
+        AssetId::BASE,
+        rng.gen(),
+        0,
+        predicate,
+        vec![],
+    );
+
+    fee_payer_script(rng, fee_payer, owner)
+        .finalize()
+        .check(block_height, &test_params())
+        .expect("Failed to validate transaction");
+}
+
+#[test]
+fn script__check__fee_payer_input_index_out_of_bounds() {
+    let block_height = 1000.into();
+
+    let err = TransactionBuilder::script(vec![], vec![])
+        .add_random_fee_input()
+        .fee_payer(1, 0)
+        .finalize()
+        .check(block_height, &test_params())
+        .expect_err("Expected erroneous transaction");
+
+    assert_eq!(err, ValidityError::TransactionFeePayerInputIndexBounds);
+}
+
+#[test]
+fn script__check__fee_payer_input_must_be_base_asset_coin() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let block_height = 1000.into();
+
+    let predicate = vec![0x01];
+    let owner = Input::predicate_owner(&predicate);
+    let fee_payer = Input::coin_predicate(
+        rng.gen(),
+        owner,
+        1000,
+        rng.gen(),
+        rng.gen(),
+        0,
+        predicate,
+        vec![],
+    );
+
+    let err = fee_payer_script(rng, fee_payer, owner)
+        .finalize()
+        .check(block_height, &test_params())
+        .expect_err("Expected erroneous transaction");
+
+    assert_eq!(err, ValidityError::TransactionFeePayerInvalidInput);
+}
+
+#[test]
+fn script__check__fee_payer_change_index_out_of_bounds() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let block_height = 1000.into();
+
+    let predicate = vec![0x01];
+    let owner = Input::predicate_owner(&predicate);
+    let fee_payer = Input::coin_predicate(
+        rng.gen(),
+        owner,
+        1000,
+        AssetId::BASE,
+        rng.gen(),
+        0,
+        predicate,
+        vec![],
+    );
+
+    let err = fee_payer_script(rng, fee_payer, owner)
+        .fee_payer(1, 2)
+        .finalize()
+        .check(block_height, &test_params())
+        .expect_err("Expected erroneous transaction");
+
+    assert_eq!(err, ValidityError::TransactionFeePayerChangeIndexBounds);
+}
+
+#[test]
+fn script__check__fee_payer_change_must_go_to_its_owner() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let block_height = 1000.into();
+
+    let predicate = vec![0x01];
+    let owner = Input::predicate_owner(&predicate);
+    let fee_payer = Input::coin_predicate(
+        rng.gen(),
+        owner,
+        1000,
+        AssetId::BASE,
+        rng.gen(),
+        0,
+        predicate,
+        vec![],
+    );
+
+    let change_to = rng.gen();
+    let errors = fee_payer_script(rng, fee_payer, change_to)
+        .finalize()
+        .check_all_without_signatures(block_height, &test_params())
+        .expect_err("Expected erroneous transaction");
+
+    assert_eq!(
+        errors,
+        vec![ValidityError::TransactionFeePayerInvalidChange]
+    );
+}
+
+#[test]
+fn script__check__fee_payer_change_must_be_base_asset_change() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let block_height = 1000.into();
+
+    let predicate = vec![0x01];
+    let owner = Input::predicate_owner(&predicate);
+    let fee_payer = Input::coin_predicate(
+        rng.gen(),
+        owner,
+        1000,
+        AssetId::BASE,
+        rng.gen(),
+        0,
+        predicate,
+        vec![],
+    );
+
+    let err = fee_payer_script(rng, fee_payer, owner)
+        .fee_payer(1, 0)
+        .finalize()
+        .check(block_height, &test_params())
+        .expect_err("Expected erroneous transaction");
+
+    assert_eq!(err, ValidityError::TransactionFeePayerInvalidChange);
+}
+
+#[test]
+fn script__check__fee_payer_and_spender_with_the_same_owner_is_valid() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+    let block_height = 1000.into();
+
+    // Both inputs are owned by the same predicate, and both change outputs go to it
+    let predicate = vec![0x01];
+    let owner = Input::predicate_owner(&predicate);
+    let mut coin = |amount| {
+        Input::coin_predicate(
+            rng.gen(),
+            owner,
+            amount,
+            AssetId::BASE,
+            rng.gen(),
+            0,
+            predicate.clone(),
+            vec![],
+        )
+    };
+    let spender = coin(100);
+    let fee_payer = coin(1000);
+
+    TransactionBuilder::script(vec![], vec![])
+        .add_input(spender)
+        .add_input(fee_payer)
+        .add_output(Output::change(owner, 0, AssetId::BASE))
+        .add_output(Output::change(owner, 0, AssetId::BASE))
+        .fee_payer(1, 1)
+        .finalize()
+        .check(block_height, &test_params())
+        .expect("Failed to validate transaction");
+}
+
 #[test]
 fn script__check__not_set_witness_limit_success() {
     // Given
@@ -505,7 +753,10 @@ fn output_change_asset_id() {
         .expect_err("Expected erroneous transaction");
 
     assert_eq!(
-        ValidityError::TransactionOutputChangeAssetIdDuplicated(a),
+        ValidityError::TransactionOutputChangeAssetIdDuplicated {
+            index: 1,
+            asset_id: a
+        },
         err
     );
 
@@ -521,7 +772,8 @@ fn output_change_asset_id() {
 
     assert!(matches!(
         err,
-        ValidityError::TransactionOutputChangeAssetIdNotFound(asset_id) if asset_id == c
+        ValidityError::TransactionOutputChangeAssetIdNotFound { index: 1, asset_id }
+            if asset_id == c
     ));
 
     let err = TransactionBuilder::script(generate_bytes(rng), generate_bytes(rng))
@@ -536,7 +788,8 @@ fn output_change_asset_id() {
 
     assert!(matches!(
         err,
-        ValidityError::TransactionOutputCoinAssetIdNotFound(asset_id) if asset_id == c
+        ValidityError::TransactionOutputCoinAssetIdNotFound { index: 1, asset_id }
+            if asset_id == c
     ));
 }
 
@@ -783,7 +1036,10 @@ fn create__check__cannot_have_multiple_change_outputs() {
 
     assert_eq!(
         err,
-        ValidityError::TransactionOutputChangeAssetIdDuplicated(AssetId::BASE)
+        ValidityError::TransactionOutputChangeAssetIdDuplicated {
+            index: 1,
+            asset_id: AssetId::BASE
+        }
     );
 }
 
@@ -1147,6 +1403,136 @@ fn mint() {
     assert_eq!(err, ValidityError::TransactionMintIncorrectBlockHeight);
 }
 
+#[test]
+fn script__check_all__reports_every_violation() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+
+    let block_height = 1000.into();
+    let change_asset: AssetId = rng.gen();
+
+    let tx = Transaction::script(
+        TX_PARAMS.max_gas_per_tx() + 1,
+        vec![],
+        vec![],
+        Policies::new().with_maturity(1001.into()).with_max_fee(0),
+        vec![Input::coin_signed(
+            rng.gen(),
+            rng.gen(),
+            rng.gen(),
+            AssetId::BASE,
+            rng.gen(),
+            1,
+        )],
+        vec![Output::change(rng.gen(), 0, change_asset)],
+        vec![],
+    );
+
+    let err = tx
+        .check_without_signatures(block_height, &test_params())
+        .expect_err("Expected erroneous transaction");
+    let errors = tx
+        .check_all_without_signatures(block_height, &test_params())
+        .expect_err("Expected erroneous transaction");
+
+    assert_eq!(errors.first(), Some(&err));
+    assert_eq!(
+        errors,
+        vec![
+            ValidityError::TransactionMaxGasExceeded,
+            ValidityError::TransactionMaturity,
+            ValidityError::InputWitnessIndexBounds { index: 0 },
+            ValidityError::TransactionOutputChangeAssetIdNotFound {
+                index: 0,
+                asset_id: change_asset
+            },
+        ]
+    );
+}
+
+#[test]
+fn script__check_all__reports_the_index_of_every_violation() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+
+    let block_height = 1000.into();
+    let utxo_id = rng.gen();
+    let foreign_asset: AssetId = rng.gen();
+    let coin = Input::coin_signed(utxo_id, rng.gen(), 100, AssetId::BASE, rng.gen(), 0);
+
+    let errors = TransactionBuilder::script(vec![], vec![])
+        .max_fee_limit(0)
+        .add_input(coin.clone())
+        .add_input(coin)
+        .add_witness(rng.gen())
+        .add_output(Output::change(rng.gen(), 0, AssetId::BASE))
+        .add_output(Output::coin(rng.gen(), 1, foreign_asset))
+        .add_output(Output::change(rng.gen(), 0, AssetId::BASE))
+        .add_output(Output::change(rng.gen(), 0, foreign_asset))
+        .finalize()
+        .check_all_without_signatures(block_height, &test_params())
+        .expect_err("Expected erroneous transaction");
+
+    assert_eq!(
+        errors,
+        vec![
+            ValidityError::TransactionOutputChangeAssetIdDuplicated {
+                index: 2,
+                asset_id: AssetId::BASE
+            },
+            ValidityError::DuplicateInputUtxoId { index: 1, utxo_id },
+            ValidityError::TransactionOutputCoinAssetIdNotFound {
+                index: 1,
+                asset_id: foreign_asset
+            },
+            ValidityError::TransactionOutputChangeAssetIdNotFound {
+                index: 3,
+                asset_id: foreign_asset
+            },
+        ]
+    );
+}
+
+#[test]
+fn script__check_all__accepts_valid_transaction() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+
+    let block_height = 1000.into();
+
+    TransactionBuilder::script(generate_bytes(rng), generate_bytes(rng))
+        .add_random_fee_input()
+        .finalize()
+        .check_all(block_height, &test_params())
+        .expect("Failed to validate transaction");
+}
+
+#[test]
+fn mint__check_all__reports_every_violation() {
+    let rng = &mut StdRng::seed_from_u64(8586);
+
+    let block_height: fuel_types::BlockHeight = 1000.into();
+
+    let errors = TransactionBuilder::mint(
+        block_height,
+        rng.gen(),
+        rng.gen(),
+        rng.gen(),
+        rng.gen(),
+        rng.gen(),
+        rng.gen(),
+    )
+    .finalize()
+    .check_all(block_height.succ().unwrap(), &test_params())
+    .expect_err("Expected erroneous transaction");
+
+    assert_eq!(
+        errors,
+        vec![
+            ValidityError::TransactionMintIncorrectBlockHeight,
+            ValidityError::TransactionMintIncorrectOutputIndex,
+            ValidityError::TransactionMintNonBaseAsset,
+        ]
+    );
+}
+
 mod inputs {
     use super::*;
     use itertools::Itertools;
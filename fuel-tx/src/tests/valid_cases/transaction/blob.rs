@@ -305,7 +305,10 @@ fn check__fail_if_output_change_asset_id_is_duplicated() {
 
     // Then
     assert_eq!(
-        Err(ValidityError::TransactionOutputChangeAssetIdDuplicated(a)),
+        Err(ValidityError::TransactionOutputChangeAssetIdDuplicated {
+            index: 1,
+            asset_id: a
+        }),
         result
     );
 }
@@ -326,7 +329,10 @@ fn check__fail_if_output_asset_id_not_in_inputs() {
 
     // Then
     assert_eq!(
-        Err(ValidityError::TransactionOutputChangeAssetIdNotFound(c)),
+        Err(ValidityError::TransactionOutputChangeAssetIdNotFound {
+            index: 0,
+            asset_id: c
+        }),
         result
     );
 }
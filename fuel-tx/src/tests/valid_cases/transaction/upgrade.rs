@@ -258,7 +258,10 @@ fn output_change_asset_id_duplicated_output() {
 
     // Then
     assert_eq!(
-        Err(ValidityError::TransactionOutputChangeAssetIdDuplicated(a)),
+        Err(ValidityError::TransactionOutputChangeAssetIdDuplicated {
+            index: 1,
+            asset_id: a
+        }),
         result
     );
 }
@@ -279,7 +282,10 @@ fn output_change_asset_id_foreign_asset() {
 
     // Then
     assert_eq!(
-        Err(ValidityError::TransactionOutputChangeAssetIdNotFound(c)),
+        Err(ValidityError::TransactionOutputChangeAssetIdNotFound {
+            index: 0,
+            asset_id: c
+        }),
         result
     );
 }
@@ -53,8 +53,10 @@ mod types;
 mod validity;
 
 mod id;
+mod partially_signed;
 
 pub mod consensus_parameters;
+pub mod explain;
 pub mod policies;
 
 pub use consensus_parameters::{
@@ -70,9 +72,17 @@ pub use consensus_parameters::{
 };
 pub use fee::{
     Chargeable,
+    FeeEstimate,
+    FeeEstimateError,
+    SignerType,
     TransactionFee,
 };
 pub use metadata::Cacheable;
+pub use partially_signed::{
+    PartiallySignedError,
+    PartiallySignedTransaction,
+    SignerHint,
+};
 pub use repr::TransactionRepr;
 pub use types::*;
 pub use validity::{
@@ -747,7 +757,11 @@ pub mod field {
         Word,
     };
 
-    use crate::policies::PolicyType;
+    use crate::policies::{
+        fee_payer_indices,
+        fee_payer_value,
+        PolicyType,
+    };
     use alloc::vec::Vec;
     use core::ops::{
         Deref,
@@ -820,6 +834,83 @@ pub mod field {
         }
     }
 
+    pub trait Expiration {
+        /// Returns the last block height at which the transaction can be included,
+        /// or `BlockHeight::new(u32::MAX)` if the policy is not set.
+        fn expiration(&self) -> BlockHeight;
+        fn set_expiration(&mut self, value: BlockHeight);
+    }
+
+    impl<T: Policies + ?Sized> Expiration for T {
+        #[inline(always)]
+        fn expiration(&self) -> BlockHeight {
+            self.policies()
+                .get(PolicyType::Expiration)
+                .map(|value| u32::try_from(value).unwrap_or(u32::MAX))
+                .unwrap_or(u32::MAX)
+                .into()
+        }
+
+        #[inline(always)]
+        fn set_expiration(&mut self, block_height: BlockHeight) {
+            self.policies_mut()
+                .set(PolicyType::Expiration, Some(*block_height.deref() as u64))
+        }
+    }
+
+    pub trait FeePayer {
+        /// Returns the index of the input paying the fee, if the policy is set.
+        fn fee_payer(&self) -> Option<u16>;
+        /// Returns the index of the change output receiving the refund of the fee
+        /// payer, if the policy is set.
+        fn fee_payer_change(&self) -> Option<u16>;
+        /// Sets the indices of the input paying the fee and of the change output
+        /// receiving its refund.
+        fn set_fee_payer(&mut self, indices: Option<(u16, u16)>);
+
+        /// Returns the input paying the fee, if the policy is set and points at an
+        /// existing input.
+        fn fee_payer_input(&self) -> Option<&Input>;
+
+        /// Returns the output receiving the refund of the fee payer, if the policy is
+        /// set and points at an existing output.
+        fn fee_payer_change_output(&self) -> Option<&Output>;
+    }
+
+    impl<T: Policies + Inputs + Outputs + ?Sized> FeePayer for T {
+        #[inline(always)]
+        fn fee_payer(&self) -> Option<u16> {
+            self.policies()
+                .get(PolicyType::FeePayer)
+                .map(|value| fee_payer_indices(value).0)
+        }
+
+        #[inline(always)]
+        fn fee_payer_change(&self) -> Option<u16> {
+            self.policies()
+                .get(PolicyType::FeePayer)
+                .map(|value| fee_payer_indices(value).1)
+        }
+
+        #[inline(always)]
+        fn set_fee_payer(&mut self, indices: Option<(u16, u16)>) {
+            self.policies_mut().set(
+                PolicyType::FeePayer,
+                indices.map(|(input_index, change_index)| {
+                    fee_payer_value(input_index, change_index)
+                }),
+            )
+        }
+
+        fn fee_payer_input(&self) -> Option<&Input> {
+            self.inputs().get(self.fee_payer()? as usize)
+        }
+
+        fn fee_payer_change_output(&self) -> Option<&Output> {
+            self.outputs().get(self.fee_payer_change()? as usize)
+        }
+    }
+
     pub trait MaxFeeLimit {
         fn max_fee_limit(&self) -> Word;
         fn set_max_fee_limit(&mut self, value: Word);
@@ -2777,6 +2777,32 @@ impl From<GasCostsValuesV4> for GasCostsValues {
 #[cfg(test)]
 mod tests {
     use crate::DependentCost;
+    use fuel_asm::{
+        semantics::GasCost,
+        Opcode,
+    };
+
+    use super::GasCostsValuesV4;
+
+    #[test]
+    fn opcode_semantics_refer_to_existing_gas_costs() {
+        let costs = serde_json::to_value(GasCostsValuesV4::free()).unwrap();
+
+        for opcode in (0..=u8::MAX).filter_map(|byte| Opcode::try_from(byte).ok()) {
+            match opcode.semantics().gas {
+                GasCost::Base(key) => {
+                    assert!(costs[key].is_u64(), "{opcode:?} uses unknown cost {key}")
+                }
+                GasCost::Dependent { key, .. } => {
+                    assert!(
+                        costs[key].is_object(),
+                        "{opcode:?} uses unknown dependent cost {key}"
+                    )
+                }
+                GasCost::External => {}
+            }
+        }
+    }
 
     #[test]
     fn light_operation_gas_cost_resolves_correctly() {
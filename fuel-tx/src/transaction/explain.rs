@@ -0,0 +1,680 @@
+//! Human-readable explanation of a transaction.
+//!
+//! [`explain`] lists every field of a transaction together with the values derived
+//! from them: the transaction id, predicate roots, message ids, signers of the
+//! witnesses, ids of the created contracts and the fee. It also runs the format
+//! validity checks, so the explanation points at the problems of invalid
+//! transactions.
+
+use crate::{
+    field::{
+        BytecodeRoot,
+        BytecodeWitnessIndex,
+        InputContract,
+        Inputs,
+        MintAmount,
+        MintAssetId,
+        MintGasPrice,
+        OutputContract,
+        Outputs,
+        Policies,
+        ProofSet,
+        ReceiptsRoot,
+        Salt,
+        Script as ScriptField,
+        ScriptData,
+        ScriptGasLimit,
+        StorageSlots,
+        SubsectionIndex,
+        SubsectionsNumber,
+        TxPointer,
+        UpgradePurpose as UpgradePurposeField,
+        Witnesses,
+    },
+    policies::PolicyType,
+    BlobIdExt,
+    Cacheable,
+    Chargeable,
+    ConsensusParameters,
+    CreateMetadata,
+    FormatValidityChecks,
+    Input,
+    Output,
+    Transaction,
+    TransactionFee,
+    TransactionRepr,
+    TxId,
+    UniqueIdentifier,
+    UpgradePurpose,
+    ValidityError,
+    Witness,
+};
+use alloc::{
+    format,
+    string::{
+        String,
+        ToString,
+    },
+    vec,
+    vec::Vec,
+};
+use core::fmt;
+use fuel_crypto::Signature;
+use fuel_types::{
+    canonical::Serialize,
+    AssetId,
+    BlobId,
+    BlockHeight,
+    Word,
+};
+use strum::IntoEnumIterator;
+
+/// A named value of a transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field {
+    /// The name of the field.
+    pub name: &'static str,
+    /// The formatted value.
+    pub value: String,
+    /// Whether the value is derived from the transaction rather than encoded in it.
+    pub computed: bool,
+}
+
+impl Field {
+    fn new(name: &'static str, value: impl fmt::Display) -> Self {
+        Self {
+            name,
+            value: value.to_string(),
+            computed: false,
+        }
+    }
+
+    fn computed(name: &'static str, value: impl fmt::Display) -> Self {
+        Self {
+            name,
+            value: value.to_string(),
+            computed: true,
+        }
+    }
+}
+
+/// An input, output or witness of a transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// The variant of the entry.
+    pub kind: &'static str,
+    /// The fields of the entry.
+    pub fields: Vec<Field>,
+}
+
+/// The explanation of a transaction, see [`explain`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    /// The type of the transaction.
+    pub kind: TransactionRepr,
+    /// The id of the transaction on the chain of the consensus parameters.
+    pub id: TxId,
+    /// The size of the canonical encoding.
+    pub size: usize,
+    /// The fields specific to the type of the transaction.
+    pub body: Vec<Field>,
+    /// The policies set in the transaction.
+    pub policies: Vec<Field>,
+    /// The inputs.
+    pub inputs: Vec<Entry>,
+    /// The outputs.
+    pub outputs: Vec<Entry>,
+    /// The witnesses.
+    pub witnesses: Vec<Entry>,
+    /// The gas price used to compute the fee.
+    pub gas_price: Word,
+    /// The fee at `gas_price`, if the transaction is chargeable and the fee doesn't
+    /// overflow.
+    pub fee: Option<TransactionFee>,
+    /// The result of [`FormatValidityChecks::check_without_signatures`].
+    pub validity: Result<(), ValidityError>,
+    /// The result of [`FormatValidityChecks::check_signatures`].
+    pub signatures: Result<(), ValidityError>,
+}
+
+/// Explains `tx` for the chain of `params`, at `block_height` and `gas_price`.
+pub fn explain(
+    tx: &Transaction,
+    params: &ConsensusParameters,
+    block_height: BlockHeight,
+    gas_price: Word,
+) -> Explanation {
+    // The validity checks rely on the metadata, which may be missing or outdated.
+    let mut tx = tx.clone();
+    let validity = tx
+        .precompute(&params.chain_id())
+        .and_then(|_| tx.check_without_signatures(block_height, params));
+    let tx = &tx;
+
+    let id = tx.id(&params.chain_id());
+    let base_asset_id = params.base_asset_id();
+
+    let mut explanation = Explanation {
+        kind: TransactionRepr::from(tx),
+        id,
+        size: tx.size(),
+        body: vec![],
+        policies: vec![],
+        inputs: vec![],
+        outputs: vec![],
+        witnesses: vec![],
+        gas_price,
+        fee: None,
+        validity,
+        signatures: tx.check_signatures(&params.chain_id()),
+    };
+
+    match tx {
+        Transaction::Script(tx) => {
+            explanation.body = vec![
+                Field::new("script_gas_limit", tx.script_gas_limit()),
+                Field::new("receipts_root", format!("{:#x}", tx.receipts_root())),
+                Field::new("script", bytes(tx.script())),
+                Field::new("script_data", bytes(tx.script_data())),
+            ];
+            explanation.chargeable(tx, params, base_asset_id);
+        }
+        Transaction::Create(tx) => {
+            explanation.body = vec![
+                Field::new("bytecode_witness_index", tx.bytecode_witness_index()),
+                Field::new("salt", format!("{:#x}", tx.salt())),
+                Field::new("storage_slots", tx.storage_slots().len()),
+            ];
+            explanation.chargeable(tx, params, base_asset_id);
+
+            if let Ok(metadata) = CreateMetadata::compute(tx) {
+                explanation.body.extend([
+                    Field::computed(
+                        "contract_root",
+                        format!("{:#x}", metadata.contract_root),
+                    ),
+                    Field::computed("state_root", format!("{:#x}", metadata.state_root)),
+                    Field::computed(
+                        "contract_id",
+                        format!("{:#x}", metadata.contract_id),
+                    ),
+                ]);
+
+                for (output, entry) in tx.outputs().iter().zip(&mut explanation.outputs) {
+                    if let Output::ContractCreated {
+                        contract_id,
+                        state_root,
+                    } = output
+                    {
+                        entry.fields.extend([
+                            Field::computed(
+                                "expected_contract_id",
+                                matches(&metadata.contract_id, contract_id),
+                            ),
+                            Field::computed(
+                                "expected_state_root",
+                                matches(&metadata.state_root, state_root),
+                            ),
+                        ]);
+                    }
+                }
+            }
+        }
+        Transaction::Mint(tx) => {
+            explanation.body = vec![
+                Field::new("tx_pointer", tx.tx_pointer()),
+                Field::new("mint_amount", tx.mint_amount()),
+                Field::new("mint_asset_id", format!("{:#x}", tx.mint_asset_id())),
+                Field::new("gas_price", tx.gas_price()),
+            ];
+            explanation.inputs = vec![input(
+                &Input::Contract(tx.input_contract().clone()),
+                base_asset_id,
+            )];
+            explanation.outputs = vec![output(&Output::Contract(*tx.output_contract()))];
+        }
+        Transaction::Upgrade(tx) => {
+            explanation.body = match tx.upgrade_purpose() {
+                UpgradePurpose::ConsensusParameters {
+                    witness_index,
+                    checksum,
+                } => {
+                    let mut body = vec![
+                        Field::new("upgrade_purpose", "ConsensusParameters"),
+                        Field::new("witness_index", witness_index),
+                        Field::new("checksum", format!("{checksum:#x}")),
+                    ];
+                    if let Some(witness) = tx.witnesses().get(*witness_index as usize) {
+                        let actual = fuel_crypto::Hasher::hash(witness.as_ref());
+                        body.push(Field::computed(
+                            "witness_checksum",
+                            matches(&actual, checksum),
+                        ));
+                    }
+                    body
+                }
+                UpgradePurpose::StateTransition { root } => vec![
+                    Field::new("upgrade_purpose", "StateTransition"),
+                    Field::new("root", format!("{root:#x}")),
+                ],
+            };
+            explanation.chargeable(tx, params, base_asset_id);
+        }
+        Transaction::Upload(tx) => {
+            explanation.body = vec![
+                Field::new("root", format!("{:#x}", tx.bytecode_root())),
+                Field::new("witness_index", tx.bytecode_witness_index()),
+                Field::new("subsection_index", tx.subsection_index()),
+                Field::new("subsections_number", tx.subsections_number()),
+                Field::new("proof_set", tx.proof_set().len()),
+            ];
+            explanation.chargeable(tx, params, base_asset_id);
+        }
+        Transaction::Blob(tx) => {
+            explanation.body = vec![
+                Field::new("id", format!("{:#x}", tx.body.id)),
+                Field::new("witness_index", tx.body.witness_index),
+            ];
+            if let Some(witness) = tx.witnesses().get(tx.body.witness_index as usize) {
+                let actual = BlobId::compute(witness.as_ref());
+                explanation.body.push(Field::computed(
+                    "witness_blob_id",
+                    matches(&actual, &tx.body.id),
+                ));
+            }
+            explanation.chargeable(tx, params, base_asset_id);
+        }
+    }
+
+    explanation
+}
+
+impl Explanation {
+    fn chargeable<Tx>(
+        &mut self,
+        tx: &Tx,
+        params: &ConsensusParameters,
+        base_asset_id: &AssetId,
+    ) where
+        Tx: Chargeable + Inputs + Outputs + Witnesses + Policies,
+    {
+        let policies = tx.policies();
+        self.policies = PolicyType::iter()
+            .filter_map(|policy| {
+                let name = match policy {
+                    PolicyType::Tip => "tip",
+                    PolicyType::WitnessLimit => "witness_limit",
+                    PolicyType::Maturity => "maturity",
+                    PolicyType::MaxFee => "max_fee",
+                    PolicyType::Expiration => "expiration",
+                    PolicyType::FeePayer => "fee_payer",
+                };
+                policies.get(policy).map(|value| Field::new(name, value))
+            })
+            .collect();
+
+        self.inputs = tx
+            .inputs()
+            .iter()
+            .map(|i| input(i, base_asset_id))
+            .collect();
+        self.outputs = tx.outputs().iter().map(output).collect();
+        self.witnesses = tx
+            .witnesses()
+            .iter()
+            .map(|w| witness(w, &self.id))
+            .collect();
+        self.fee = TransactionFee::checked_from_tx(
+            params.gas_costs(),
+            params.fee_params(),
+            tx,
+            self.gas_price,
+        );
+    }
+}
+
+fn input(input: &Input, base_asset_id: &AssetId) -> Entry {
+    let kind = match input {
+        Input::CoinSigned(_) => "CoinSigned",
+        Input::CoinPredicate(_) => "CoinPredicate",
+        Input::Contract(_) => "Contract",
+        Input::MessageCoinSigned(_) => "MessageCoinSigned",
+        Input::MessageCoinPredicate(_) => "MessageCoinPredicate",
+        Input::MessageDataSigned(_) => "MessageDataSigned",
+        Input::MessageDataPredicate(_) => "MessageDataPredicate",
+    };
+
+    let mut fields = vec![];
+    if let Some(utxo_id) = input.utxo_id() {
+        fields.push(Field::new("utxo_id", utxo_id));
+    }
+    if let Some(owner) = input.input_owner() {
+        fields.push(Field::new("owner", format!("{owner:#x}")));
+    }
+    if let Some(sender) = input.sender() {
+        fields.push(Field::new("sender", format!("{sender:#x}")));
+    }
+    if let Some(recipient) = input.recipient() {
+        fields.push(Field::new("recipient", format!("{recipient:#x}")));
+    }
+    if let Some(amount) = input.amount() {
+        fields.push(Field::new("amount", amount));
+    }
+    if let Some(asset_id) = input.asset_id(base_asset_id) {
+        fields.push(Field::new("asset_id", format!("{asset_id:#x}")));
+    }
+    if let Some(nonce) = input.nonce() {
+        fields.push(Field::new("nonce", format!("{nonce:#x}")));
+    }
+    if let Some(tx_pointer) = input.tx_pointer() {
+        fields.push(Field::new("tx_pointer", tx_pointer));
+    }
+    if let Some(contract_id) = input.contract_id() {
+        fields.push(Field::new("contract_id", format!("{contract_id:#x}")));
+    }
+    if let Some(balance_root) = input.balance_root() {
+        fields.push(Field::new("balance_root", format!("{balance_root:#x}")));
+    }
+    if let Some(state_root) = input.state_root() {
+        fields.push(Field::new("state_root", format!("{state_root:#x}")));
+    }
+    if let Some(witness_index) = input.witness_index() {
+        fields.push(Field::new("witness_index", witness_index));
+    }
+    if let Some(data) = input.input_data() {
+        fields.push(Field::new("data", bytes(data)));
+    }
+    if let Some((predicate, predicate_data, predicate_gas_used)) = input.predicate() {
+        fields.extend([
+            Field::new("predicate_gas_used", predicate_gas_used),
+            Field::new("predicate", bytes(predicate)),
+            Field::new("predicate_data", bytes(predicate_data)),
+        ]);
+
+        let root = Input::predicate_owner(predicate);
+        let owner = input.input_owner().or(input.recipient());
+        fields.push(Field::computed(
+            "predicate_root",
+            match owner {
+                Some(owner) => matches(&root, owner),
+                None => format!("{root:#x}"),
+            },
+        ));
+    }
+    if let Some(message_id) = input.message_id() {
+        fields.push(Field::computed("message_id", format!("{message_id:#x}")));
+    }
+
+    Entry { kind, fields }
+}
+
+fn output(output: &Output) -> Entry {
+    let kind = match output {
+        Output::Coin { .. } => "Coin",
+        Output::Contract(_) => "Contract",
+        Output::Change { .. } => "Change",
+        Output::Variable { .. } => "Variable",
+        Output::ContractCreated { .. } => "ContractCreated",
+    };
+
+    let mut fields = vec![];
+    if let Some(to) = output.to() {
+        fields.push(Field::new("to", format!("{to:#x}")));
+    }
+    if let Some(amount) = output.amount() {
+        fields.push(Field::new("amount", amount));
+    }
+    if let Some(asset_id) = output.asset_id() {
+        fields.push(Field::new("asset_id", format!("{asset_id:#x}")));
+    }
+    if let Some(input_index) = output.input_index() {
+        fields.push(Field::new("input_index", input_index));
+    }
+    if let Some(contract_id) = output.contract_id() {
+        fields.push(Field::new("contract_id", format!("{contract_id:#x}")));
+    }
+    if let Some(balance_root) = output.balance_root() {
+        fields.push(Field::new("balance_root", format!("{balance_root:#x}")));
+    }
+    if let Some(state_root) = output.state_root() {
+        fields.push(Field::new("state_root", format!("{state_root:#x}")));
+    }
+
+    Entry { kind, fields }
+}
+
+fn witness(witness: &Witness, id: &TxId) -> Entry {
+    let mut fields = vec![Field::new("data", bytes(witness.as_ref()))];
+
+    if witness.as_ref().len() == Signature::LEN {
+        let signer = witness
+            .recover_witness(id, 0)
+            .map(|signer| format!("{signer:#x}"))
+            .unwrap_or_else(|_| "not a valid signature".to_string());
+        fields.push(Field::computed("signer", signer));
+    }
+
+    Entry {
+        kind: "Witness",
+        fields,
+    }
+}
+
+fn bytes(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(bytes.len().saturating_mul(2));
+    for byte in bytes.iter().take(32) {
+        hex.push_str(&format!("{byte:02x}"));
+    }
+    if bytes.len() > 32 {
+        hex.push_str("...");
+    }
+
+    format!("{} bytes 0x{hex}", bytes.len())
+}
+
+fn matches<T>(computed: &T, encoded: &T) -> String
+where
+    T: fmt::LowerHex + PartialEq,
+{
+    if computed == encoded {
+        format!("{computed:#x} (matches)")
+    } else {
+        format!("{computed:#x} (MISMATCH)")
+    }
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn fields(
+            f: &mut fmt::Formatter<'_>,
+            fields: &[Field],
+            indent: &str,
+        ) -> fmt::Result {
+            for field in fields {
+                let marker = if field.computed { " (computed)" } else { "" };
+                writeln!(f, "{indent}{}{marker}: {}", field.name, field.value)?;
+            }
+            Ok(())
+        }
+
+        fn entries(
+            f: &mut fmt::Formatter<'_>,
+            title: &str,
+            entries: &[Entry],
+        ) -> fmt::Result {
+            writeln!(f, "{title} ({})", entries.len())?;
+            for (index, entry) in entries.iter().enumerate() {
+                writeln!(f, "  [{index}] {}", entry.kind)?;
+                fields(f, &entry.fields, "      ")?;
+            }
+            Ok(())
+        }
+
+        writeln!(f, "{:?} transaction", self.kind)?;
+        writeln!(f, "  id (computed): {:#x}", self.id)?;
+        writeln!(f, "  size: {} bytes", self.size)?;
+        fields(f, &self.body, "  ")?;
+
+        writeln!(f, "Policies ({})", self.policies.len())?;
+        fields(f, &self.policies, "  ")?;
+        entries(f, "Inputs", &self.inputs)?;
+        entries(f, "Outputs", &self.outputs)?;
+        entries(f, "Witnesses", &self.witnesses)?;
+
+        if let Some(fee) = &self.fee {
+            writeln!(f, "Fee at gas price {}", self.gas_price)?;
+            writeln!(f, "  min_gas: {}", fee.min_gas())?;
+            writeln!(f, "  max_gas: {}", fee.max_gas())?;
+            writeln!(f, "  min_fee: {}", fee.min_fee())?;
+            writeln!(f, "  max_fee: {}", fee.max_fee())?;
+        }
+
+        writeln!(f, "Validity")?;
+        match &self.validity {
+            Ok(()) => writeln!(f, "  format: ok")?,
+            Err(e) => writeln!(f, "  format: {e:?}")?,
+        }
+        match &self.signatures {
+            Ok(()) => writeln!(f, "  signatures: ok")?,
+            Err(e) => writeln!(f, "  signatures: {e:?}")?,
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        field::Witnesses,
+        Contract,
+        Finalizable,
+        TransactionBuilder,
+    };
+    use fuel_crypto::SecretKey;
+    use rand::{
+        rngs::StdRng,
+        Rng,
+        SeedableRng,
+    };
+
+    fn field<'a>(fields: &'a [Field], name: &str) -> &'a Field {
+        fields
+            .iter()
+            .find(|field| field.name == name)
+            .unwrap_or_else(|| panic!("Missing field {name}"))
+    }
+
+    #[test]
+    fn explain_computes_derived_values() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let params = ConsensusParameters::standard();
+        let secret = SecretKey::random(rng);
+        let predicate = vec![1, 2, 3];
+
+        let tx = TransactionBuilder::script(vec![], vec![])
+            .max_fee_limit(1_000)
+            .add_unsigned_coin_input(
+                secret,
+                rng.gen(),
+                1_000,
+                *params.base_asset_id(),
+                Default::default(),
+            )
+            .add_input(Input::message_coin_predicate(
+                rng.gen(),
+                Input::predicate_owner(&predicate),
+                1_000,
+                rng.gen(),
+                0,
+                predicate,
+                vec![],
+            ))
+            .finalize_as_transaction();
+
+        let explanation = explain(&tx, &params, 0u32.into(), 1);
+
+        assert_eq!(explanation.kind, TransactionRepr::Script);
+        assert_eq!(explanation.id, tx.id(&params.chain_id()));
+        assert_eq!(explanation.validity, Ok(()));
+        assert_eq!(explanation.signatures, Ok(()));
+        assert!(explanation.fee.is_some());
+
+        let signer = field(&explanation.witnesses[0].fields, "signer");
+        let owner = Input::owner(&secret.public_key());
+        assert!(signer.computed);
+        assert_eq!(signer.value, format!("{owner:#x}"));
+
+        let message = &explanation.inputs[1];
+        assert_eq!(message.kind, "MessageCoinPredicate");
+        assert!(field(&message.fields, "predicate_root")
+            .value
+            .ends_with("(matches)"));
+        assert!(field(&message.fields, "message_id").computed);
+    }
+
+    #[test]
+    fn explain_checks_created_contract_id() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let params = ConsensusParameters::standard();
+        let bytecode = vec![0; 64];
+        let salt = rng.gen();
+
+        let contract = Contract::from(bytecode.clone());
+        let state_root = Contract::default_state_root();
+        let contract_id = contract.id(&salt, &contract.root(), &state_root);
+
+        let mut tx = TransactionBuilder::create(bytecode.into(), salt, vec![])
+            .add_random_fee_input()
+            .add_output(Output::contract_created(contract_id, state_root))
+            .finalize();
+        let explanation = explain(&tx.clone().into(), &params, 0u32.into(), 1);
+
+        let created = &explanation.outputs[0];
+        assert!(field(&created.fields, "expected_contract_id")
+            .value
+            .ends_with("(matches)"));
+        assert_eq!(explanation.validity, Ok(()));
+
+        tx.witnesses_mut()[0] = vec![1; 64].into();
+        let explanation = explain(&tx.into(), &params, 0u32.into(), 1);
+
+        let created = &explanation.outputs[0];
+        assert!(field(&created.fields, "expected_contract_id")
+            .value
+            .ends_with("(MISMATCH)"));
+        assert_eq!(
+            explanation.validity,
+            Err(
+                ValidityError::TransactionCreateOutputContractCreatedDoesntMatch {
+                    index: 0
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn explain_displays_every_section() {
+        let tx = TransactionBuilder::script(vec![], vec![])
+            .add_random_fee_input()
+            .finalize_as_transaction();
+
+        let text =
+            explain(&tx, &ConsensusParameters::standard(), 0u32.into(), 1).to_string();
+
+        for section in [
+            "Script transaction",
+            "id (computed)",
+            "Policies",
+            "Inputs (1)",
+            "Outputs (0)",
+            "Witnesses (1)",
+            "Fee at gas price 1",
+            "format: ok",
+            "signatures: ok",
+        ] {
+            assert!(text.contains(section), "Missing {section} in:\n{text}");
+        }
+    }
+}
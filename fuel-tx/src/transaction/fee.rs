@@ -21,6 +21,11 @@ use crate::{
     FeeParameters,
     GasCosts,
     Input,
+    Witness,
+};
+use alloc::{
+    collections::BTreeMap,
+    vec,
 };
 use fuel_asm::Word;
 use fuel_types::canonical::Serialize;
@@ -110,6 +115,84 @@ impl TransactionFee {
     }
 }
 
+/// The type of the key producing the witness of a signer.
+///
+/// Signed inputs are always verified with a `secp256k1` signature. The other types are
+/// verified by predicates reading the witness.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SignerType {
+    /// A compact `secp256k1` signature.
+    Secp256k1,
+    /// A compact `secp256r1` signature.
+    Secp256r1,
+    /// The public key followed by the `ed25519` signature, since the public key can't be
+    /// recovered from the signature.
+    Ed25519,
+}
+
+impl SignerType {
+    /// The size of the witness data produced by the signer.
+    pub const fn witness_size(&self) -> usize {
+        match self {
+            Self::Secp256k1 | Self::Secp256r1 => 64,
+            Self::Ed25519 => 96,
+        }
+    }
+}
+
+/// The size and the fee bounds of a transaction once its missing witnesses are provided.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeeEstimate {
+    pub(crate) size: usize,
+    pub(crate) fee: TransactionFee,
+}
+
+impl FeeEstimate {
+    /// The size of the signed transaction in bytes.
+    pub const fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The fee of the signed transaction.
+    pub const fn fee(&self) -> TransactionFee {
+        self.fee
+    }
+
+    /// The minimum fee of the signed transaction.
+    pub const fn min_fee(&self) -> Word {
+        self.fee.min_fee
+    }
+
+    /// The maximum fee of the signed transaction. It is the lower bound of the
+    /// `MaxFee` policy.
+    pub const fn max_fee(&self) -> Word {
+        self.fee.max_fee
+    }
+}
+
+/// The error returned by [`Chargeable::estimate_fee`].
+#[derive(Debug, Clone, PartialEq, Eq, derive_more::Display)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub enum FeeEstimateError {
+    /// A signer or a signed input refers to a witness the transaction doesn't have.
+    #[display(fmt = "The witness {_0} doesn't exist")]
+    WitnessIndexBounds(u16),
+    /// The witnesses of the signed transaction exceed the `WitnessLimit` policy.
+    #[display(fmt = "The witnesses size {size} exceeds the witness limit {limit}")]
+    WitnessLimitExceeded {
+        /// The `WitnessLimit` policy.
+        limit: Word,
+        /// The size of the witnesses of the signed transaction.
+        size: Word,
+    },
+    /// The fee overflows.
+    #[display(fmt = "The fee overflows")]
+    FeeOverflow,
+}
+
 fn gas_to_fee(gas: Word, gas_price: Word, factor: Word) -> u128 {
     let total_price = (gas as u128)
         .checked_mul(gas_price as u128)
@@ -280,4 +363,264 @@ pub trait Chargeable: field::Inputs + field::Witnesses + field::Policies {
 
     /// Used for accounting purposes when charging for metadata creation.
     fn gas_used_by_metadata(&self, gas_costs: &GasCosts) -> Word;
+
+    /// Estimates the size and the fee bounds of the transaction at `gas_price` once
+    /// its missing witnesses are provided.
+    ///
+    /// Every empty witness is replaced by a placeholder of the size produced by its
+    /// signer: the type from `signers` by witness index, or [`SignerType::Secp256k1`]
+    /// for the witnesses of signed inputs. Witnesses that are already set are kept.
+    ///
+    /// The `predicate_gas_used` of the predicate inputs must already be estimated.
+    fn estimate_fee(
+        &self,
+        gas_costs: &GasCosts,
+        fee: &FeeParameters,
+        signers: &[(u16, SignerType)],
+        gas_price: Word,
+    ) -> Result<FeeEstimate, FeeEstimateError>
+    where
+        Self: Clone + Serialize + Sized,
+    {
+        let mut tx = self.clone();
+
+        let mut placeholders = BTreeMap::new();
+        for witness_index in tx.inputs().iter().filter_map(Input::witness_index) {
+            placeholders.insert(witness_index, SignerType::Secp256k1);
+        }
+        placeholders.extend(signers.iter().copied());
+
+        for (witness_index, signer) in placeholders {
+            let witness = tx
+                .witnesses_mut()
+                .get_mut(usize::from(witness_index))
+                .ok_or(FeeEstimateError::WitnessIndexBounds(witness_index))?;
+
+            if witness.as_vec().is_empty() {
+                *witness = Witness::from(vec![0; signer.witness_size()]);
+            }
+        }
+
+        if let Some(limit) = tx.policies().get(PolicyType::WitnessLimit) {
+            let size = tx.witnesses().size_dynamic() as Word;
+            if size > limit {
+                return Err(FeeEstimateError::WitnessLimitExceeded { limit, size })
+            }
+        }
+
+        let fee = TransactionFee::checked_from_tx(gas_costs, fee, &tx, gas_price)
+            .ok_or(FeeEstimateError::FeeOverflow)?;
+
+        Ok(FeeEstimate {
+            size: tx.size(),
+            fee,
+        })
+    }
+}
+
+#[cfg(all(test, feature = "test-helpers"))]
+mod tests {
+    use super::*;
+    use crate::{
+        field::Witnesses,
+        ConsensusParameters,
+        Finalizable,
+        Script,
+        TransactionBuilder,
+        TxPointer,
+    };
+    use fuel_crypto::SecretKey;
+    use rand::{
+        rngs::StdRng,
+        Rng,
+        SeedableRng,
+    };
+
+    const GAS_PRICE: Word = 10;
+
+    fn predicate_input(rng: &mut StdRng) -> Input {
+        Input::coin_predicate(
+            rng.gen(),
+            rng.gen(),
+            1000,
+            rng.gen(),
+            rng.gen(),
+            100,
+            vec![1],
+            vec![],
+        )
+    }
+
+    fn fee_of(tx: &Script) -> TransactionFee {
+        let params = ConsensusParameters::standard();
+        TransactionFee::checked_from_tx(
+            params.gas_costs(),
+            params.fee_params(),
+            tx,
+            GAS_PRICE,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn estimate_fee_matches_the_signed_transaction() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+
+        let mut builder = TransactionBuilder::script(vec![], vec![]);
+        builder
+            .add_unsigned_coin_input(
+                SecretKey::random(rng),
+                rng.gen(),
+                1000,
+                rng.gen(),
+                rng.gen(),
+            )
+            .add_unsigned_message_input(
+                SecretKey::random(rng),
+                rng.gen(),
+                rng.gen(),
+                1000,
+                vec![],
+            );
+
+        let estimate = builder.estimate_fee(&[], GAS_PRICE).unwrap();
+        let signed = builder.finalize();
+
+        assert_eq!(estimate.size(), signed.size());
+        assert_eq!(estimate.fee(), fee_of(&signed));
+        assert!(estimate.min_fee() <= estimate.max_fee());
+    }
+
+    #[test]
+    fn estimate_fee_accounts_for_external_signers() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+
+        for signer in [
+            SignerType::Secp256k1,
+            SignerType::Secp256r1,
+            SignerType::Ed25519,
+        ] {
+            let mut builder = TransactionBuilder::script(vec![], vec![]);
+            builder
+                .add_input(predicate_input(rng))
+                .add_witness(Witness::default());
+
+            let estimate = builder.estimate_fee(&[(0, signer)], GAS_PRICE).unwrap();
+
+            let mut signed = builder.finalize();
+            signed.witnesses_mut()[0] = vec![7; signer.witness_size()].into();
+
+            assert_eq!(estimate.size(), signed.size());
+            assert_eq!(estimate.fee(), fee_of(&signed));
+        }
+    }
+
+    #[test]
+    fn estimate_fee_keeps_provided_witnesses() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+
+        let mut builder = TransactionBuilder::script(vec![], vec![]);
+        builder
+            .add_input(predicate_input(rng))
+            .add_witness(vec![7; 200].into());
+
+        let estimate = builder
+            .estimate_fee(&[(0, SignerType::Ed25519)], GAS_PRICE)
+            .unwrap();
+
+        assert_eq!(estimate.size(), builder.finalize().size());
+    }
+
+    #[test]
+    fn estimate_fee_uses_the_witness_limit() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+
+        let mut builder = TransactionBuilder::script(vec![], vec![]);
+        builder
+            .add_unsigned_coin_input(
+                SecretKey::random(rng),
+                rng.gen(),
+                1000,
+                rng.gen(),
+                rng.gen(),
+            )
+            .witness_limit(1000);
+
+        let estimate = builder.estimate_fee(&[], GAS_PRICE).unwrap();
+        let signed = builder.finalize();
+
+        assert_eq!(estimate.fee(), fee_of(&signed));
+        assert!(estimate.fee().max_gas() > estimate.fee().min_gas());
+    }
+
+    #[test]
+    fn estimate_fee_fails_if_witness_limit_is_exceeded() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+
+        let mut builder = TransactionBuilder::script(vec![], vec![]);
+        builder
+            .add_input(predicate_input(rng))
+            .add_witness(Witness::default())
+            .witness_limit(10);
+
+        let err = builder
+            .estimate_fee(&[(0, SignerType::Ed25519)], GAS_PRICE)
+            .unwrap_err();
+
+        assert_eq!(
+            err,
+            FeeEstimateError::WitnessLimitExceeded {
+                limit: 10,
+                // The length prefix and the padded data of the witness
+                size: 8 + 96,
+            }
+        );
+    }
+
+    #[test]
+    fn estimate_fee_fails_for_missing_witness() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+
+        let mut builder = TransactionBuilder::script(vec![], vec![]);
+        builder.add_input(predicate_input(rng));
+
+        let err = builder
+            .estimate_fee(&[(0, SignerType::Secp256r1)], GAS_PRICE)
+            .unwrap_err();
+
+        assert_eq!(err, FeeEstimateError::WitnessIndexBounds(0));
+    }
+
+    #[test]
+    fn estimate_fee_accounts_for_builder_signers() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let secret = SecretKey::random(rng);
+        let mut builder = TransactionBuilder::script(vec![], vec![]);
+        let witness_index = builder.add_signer(secret);
+        builder.add_input(Input::coin_signed(
+            rng.gen(),
+            Input::owner(&secret.public_key()),
+            1_000_000,
+            rng.gen(),
+            TxPointer::default(),
+            witness_index,
+        ));
+
+        let unsigned = builder.finalize_without_signature();
+        let signed = builder.finalize();
+        let params = builder.get_params();
+        let estimate = |tx: &Script| {
+            tx.estimate_fee(params.gas_costs(), params.fee_params(), &[], GAS_PRICE)
+                .unwrap()
+        };
+
+        let estimated = estimate(&unsigned);
+
+        assert_eq!(estimated.size(), signed.size());
+        assert_eq!(estimated, estimate(&signed));
+        assert!(
+            unsigned.max_gas(params.gas_costs(), params.fee_params())
+                < signed.max_gas(params.gas_costs(), params.fee_params())
+        );
+    }
 }
@@ -0,0 +1,893 @@
+//! Transactions being signed by several parties.
+//!
+//! A [`PartiallySignedTransaction`] carries an unsigned or partially signed
+//! transaction together with [`SignerHint`]s telling every party which witnesses they
+//! have to fill. Each party signs its own copy, the copies are combined, and the result
+//! is finalized once all the expected signatures are present.
+
+use crate::{
+    field::{
+        Inputs,
+        Witnesses,
+    },
+    input::{
+        coin::CoinSigned,
+        message::{
+            MessageCoinSigned,
+            MessageDataSigned,
+        },
+    },
+    FormatValidityChecks,
+    Input,
+    Transaction,
+    UniqueIdentifier,
+    ValidityError,
+    Witness,
+};
+use alloc::vec::Vec;
+use core::mem;
+use fuel_crypto::{
+    Message,
+    PublicKey,
+    SecretKey,
+    Signature,
+};
+use fuel_types::{
+    canonical::{
+        self,
+        Deserialize,
+    },
+    Address,
+    ChainId,
+};
+
+/// Tells which signer authorizes an input, and where its signature goes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(fuel_types::canonical::Deserialize, fuel_types::canonical::Serialize)]
+pub struct SignerHint {
+    /// The index of the input authorized by the signature.
+    pub input_index: u16,
+    /// The address of the signer, see [`Input::owner`].
+    pub signer: Address,
+    /// The index of the witness holding the signature.
+    pub witness_index: u16,
+}
+
+/// The error returned while building, combining or finalizing a
+/// [`PartiallySignedTransaction`].
+#[derive(Debug, Clone, PartialEq, Eq, derive_more::Display)]
+#[non_exhaustive]
+pub enum PartiallySignedError {
+    /// `Mint` transactions have no signed inputs.
+    #[display(fmt = "Mint transactions can't be signed")]
+    UnsupportedTransaction,
+    /// A hint refers to an input the transaction doesn't have.
+    #[display(fmt = "The input {index} doesn't exist")]
+    InputIndexBounds {
+        /// The index of the input.
+        index: u16,
+    },
+    /// A hint of a signed input names another signer than the owner of the input.
+    #[display(fmt = "The signer of the input {index} is not its owner")]
+    SignerMismatch {
+        /// The index of the input.
+        index: u16,
+    },
+    /// A hint of a signed input names another witness than the one of the input.
+    #[display(fmt = "The witness of the input {index} is not the one of its hint")]
+    WitnessMismatch {
+        /// The index of the input.
+        index: u16,
+    },
+    /// A hint or a signature refers to a witness the transaction doesn't have.
+    #[display(fmt = "The witness {index} doesn't exist")]
+    WitnessIndexBounds {
+        /// The index of the witness.
+        index: u16,
+    },
+    /// The combined transactions differ in more than their witnesses.
+    #[display(fmt = "The transactions to combine are different")]
+    TransactionMismatch,
+    /// The combined transactions have different non-empty values of a witness.
+    #[display(fmt = "The witness {index} has conflicting values")]
+    WitnessConflict {
+        /// The index of the witness.
+        index: u16,
+    },
+    /// There is no transaction to merge.
+    #[display(fmt = "No transaction to merge")]
+    NothingToMerge,
+    /// The finalized transaction is missing a signature or has an invalid one.
+    #[display(fmt = "{_0}")]
+    Validity(ValidityError),
+}
+
+impl From<ValidityError> for PartiallySignedError {
+    fn from(e: ValidityError) -> Self {
+        Self::Validity(e)
+    }
+}
+
+/// A transaction with the hints required to collect the signatures of several
+/// parties.
+///
+/// Signatures don't change the transaction id, so every party can sign its own copy.
+/// The copies are then put together with [`PartiallySignedTransaction::combine`] or
+/// [`PartiallySignedTransaction::merge`].
+///
+/// Decoding checks the hints like [`PartiallySignedTransaction::add_hint`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(try_from = "serde_parts::RawPartiallySignedTransaction")
+)]
+#[derive(fuel_types::canonical::Serialize)]
+pub struct PartiallySignedTransaction {
+    tx: Transaction,
+    hints: Vec<SignerHint>,
+}
+
+impl PartiallySignedTransaction {
+    /// Wraps `tx` with a hint for each of its signed inputs.
+    ///
+    /// The witnesses referred to by the inputs must already be in the transaction.
+    /// Inputs authorized by signatures in other ways, like predicates checking
+    /// signatures, are described with [`Self::add_hint`].
+    pub fn new<T>(tx: T) -> Result<Self, PartiallySignedError>
+    where
+        T: Into<Transaction>,
+    {
+        let tx = tx.into();
+        let inputs = inputs(&tx).ok_or(PartiallySignedError::UnsupportedTransaction)?;
+
+        let hints = inputs
+            .iter()
+            .enumerate()
+            .filter_map(|(index, input)| {
+                let (owner, witness_index) = signed_input(input)?;
+                Some((index, owner, witness_index))
+            })
+            .map(|(index, signer, witness_index)| {
+                let input_index = u16::try_from(index).map_err(|_| {
+                    PartiallySignedError::InputIndexBounds { index: u16::MAX }
+                })?;
+
+                Ok(SignerHint {
+                    input_index,
+                    signer,
+                    witness_index,
+                })
+            })
+            .collect::<Result<Vec<_>, PartiallySignedError>>()?;
+
+        Self::from_parts(tx, hints)
+    }
+
+    /// Wraps `tx` with the `hints`, checked and sorted by [`Self::add_hint`].
+    fn from_parts(
+        tx: Transaction,
+        hints: Vec<SignerHint>,
+    ) -> Result<Self, PartiallySignedError> {
+        let mut pst = Self { tx, hints };
+        pst.check_hints()?;
+
+        Ok(pst)
+    }
+
+    /// Adds the hints again with [`Self::add_hint`].
+    fn check_hints(&mut self) -> Result<(), PartiallySignedError> {
+        if inputs(&self.tx).is_none() {
+            return Err(PartiallySignedError::UnsupportedTransaction)
+        }
+
+        let hints = mem::take(&mut self.hints);
+        self.hints.reserve(hints.len());
+        for hint in hints {
+            self.add_hint(hint)?;
+        }
+
+        Ok(())
+    }
+
+    /// The transaction being signed.
+    pub const fn transaction(&self) -> &Transaction {
+        &self.tx
+    }
+
+    /// The signatures expected by the transaction.
+    pub fn hints(&self) -> &[SignerHint] {
+        &self.hints
+    }
+
+    /// The hints whose witness is still empty.
+    pub fn missing_signatures(&self) -> impl Iterator<Item = &SignerHint> {
+        let witnesses = witnesses(&self.tx).unwrap_or_default();
+
+        self.hints.iter().filter(|hint| {
+            match witnesses.get(hint.witness_index as usize) {
+                Some(witness) => witness.as_vec().is_empty(),
+                None => true,
+            }
+        })
+    }
+
+    /// Expects a signature of `hint.signer` in the witness `hint.witness_index`.
+    ///
+    /// The signer and the witness of a signed input must be the owner and the witness
+    /// of the input.
+    pub fn add_hint(
+        &mut self,
+        hint: SignerHint,
+    ) -> Result<&mut Self, PartiallySignedError> {
+        self.check_hint(&hint)?;
+
+        if let Err(position) = self.hints.binary_search(&hint) {
+            self.hints.insert(position, hint);
+        }
+
+        Ok(self)
+    }
+
+    fn check_hint(&self, hint: &SignerHint) -> Result<(), PartiallySignedError> {
+        let inputs = inputs(&self.tx).unwrap_or_default();
+        let witnesses = witnesses(&self.tx).unwrap_or_default();
+
+        let input = inputs.get(hint.input_index as usize).ok_or(
+            PartiallySignedError::InputIndexBounds {
+                index: hint.input_index,
+            },
+        )?;
+
+        if hint.witness_index as usize >= witnesses.len() {
+            return Err(PartiallySignedError::WitnessIndexBounds {
+                index: hint.witness_index,
+            })
+        }
+
+        if let Some((owner, witness_index)) = signed_input(input) {
+            if owner != hint.signer {
+                return Err(PartiallySignedError::SignerMismatch {
+                    index: hint.input_index,
+                })
+            }
+
+            if witness_index != hint.witness_index {
+                return Err(PartiallySignedError::WitnessMismatch {
+                    index: hint.input_index,
+                })
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Signs every witness expected from the owner of `secret`, and returns the number
+    /// of signed witnesses.
+    pub fn sign(&mut self, secret: &SecretKey, chain_id: &ChainId) -> usize {
+        let signer = Input::owner(&PublicKey::from(secret));
+        let id = self.tx.id(chain_id);
+        let signature = Signature::sign(secret, Message::from_bytes_ref(&id));
+
+        let mut indexes = self
+            .hints
+            .iter()
+            .filter(|hint| hint.signer == signer)
+            .map(|hint| hint.witness_index)
+            .collect::<Vec<_>>();
+        indexes.sort_unstable();
+        indexes.dedup();
+
+        if let Some(witnesses) = witnesses_mut(&mut self.tx) {
+            for index in &indexes {
+                if let Some(witness) = witnesses.get_mut(*index as usize) {
+                    *witness = signature.as_ref().into();
+                }
+            }
+        }
+
+        indexes.len()
+    }
+
+    /// Sets the witness `witness_index` to a signature produced outside of this
+    /// transaction, like by a hardware wallet.
+    ///
+    /// The signature is only verified by [`Self::finalize`].
+    pub fn add_signature(
+        &mut self,
+        witness_index: u16,
+        signature: Signature,
+    ) -> Result<&mut Self, PartiallySignedError> {
+        let witness = witnesses_mut(&mut self.tx)
+            .and_then(|witnesses| witnesses.get_mut(witness_index as usize))
+            .ok_or(PartiallySignedError::WitnessIndexBounds {
+                index: witness_index,
+            })?;
+        *witness = signature.as_ref().into();
+
+        Ok(self)
+    }
+
+    /// Adds the witnesses and the hints of `other` to this transaction.
+    ///
+    /// Both transactions must be equal apart from their witnesses. A witness empty on
+    /// one side takes the value of the other side, and non-empty witnesses must be
+    /// equal. On error, this transaction is unchanged.
+    pub fn combine(&mut self, other: &Self) -> Result<&mut Self, PartiallySignedError> {
+        let ours = witnesses(&self.tx).unwrap_or_default();
+        let theirs = witnesses(&other.tx).unwrap_or_default();
+
+        if ours.len() != theirs.len()
+            || without_witnesses(&self.tx) != without_witnesses(&other.tx)
+        {
+            return Err(PartiallySignedError::TransactionMismatch)
+        }
+
+        let combined = ours
+            .iter()
+            .zip(theirs)
+            .enumerate()
+            .map(|(index, (ours, theirs))| {
+                match (ours.as_vec().is_empty(), theirs.as_vec().is_empty()) {
+                    (_, true) => Ok(ours.clone()),
+                    (true, false) => Ok(theirs.clone()),
+                    (false, false) if ours == theirs => Ok(ours.clone()),
+                    (false, false) => Err(PartiallySignedError::WitnessConflict {
+                        index: u16::try_from(index).unwrap_or(u16::MAX),
+                    }),
+                }
+            })
+            .collect::<Result<Vec<Witness>, _>>()?;
+
+        // The hints only depend on the inputs and the number of witnesses, which are
+        // the same on both sides, so they are checked before anything is changed.
+        for hint in &other.hints {
+            self.check_hint(hint)?;
+        }
+
+        if let Some(witnesses) = witnesses_mut(&mut self.tx) {
+            *witnesses = combined;
+        }
+
+        for hint in &other.hints {
+            if let Err(position) = self.hints.binary_search(hint) {
+                self.hints.insert(position, *hint);
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Combines all the copies of a transaction signed by different parties.
+    pub fn merge<I>(psts: I) -> Result<Self, PartiallySignedError>
+    where
+        I: IntoIterator<Item = Self>,
+    {
+        let mut psts = psts.into_iter();
+        let mut merged = psts.next().ok_or(PartiallySignedError::NothingToMerge)?;
+
+        for pst in psts {
+            merged.combine(&pst)?;
+        }
+
+        Ok(merged)
+    }
+
+    /// Verifies that every hinted witness holds a valid signature of its signer, and
+    /// that all the signed inputs are signed, then returns the final transaction.
+    pub fn finalize(
+        self,
+        chain_id: &ChainId,
+    ) -> Result<Transaction, PartiallySignedError> {
+        let inputs =
+            inputs(&self.tx).ok_or(PartiallySignedError::UnsupportedTransaction)?;
+        let witnesses = witnesses(&self.tx).unwrap_or_default();
+        let id = self.tx.id(chain_id);
+
+        for hint in &self.hints {
+            let index = hint.input_index as usize;
+            if index >= inputs.len() {
+                return Err(PartiallySignedError::InputIndexBounds {
+                    index: hint.input_index,
+                })
+            }
+
+            let witness = witnesses
+                .get(hint.witness_index as usize)
+                .ok_or(ValidityError::InputWitnessIndexBounds { index })?;
+
+            if witness.recover_witness(&id, index)? != hint.signer {
+                return Err(ValidityError::InputInvalidSignature { index }.into())
+            }
+        }
+
+        self.tx.check_signatures(chain_id)?;
+
+        Ok(self.tx)
+    }
+}
+
+impl Deserialize for PartiallySignedTransaction {
+    fn decode_static<I: canonical::Input + ?Sized>(
+        buffer: &mut I,
+    ) -> Result<Self, canonical::Error> {
+        let tx = Transaction::decode_static(buffer)?;
+        let hints = Vec::<SignerHint>::decode_static(buffer)?;
+        Ok(Self { tx, hints })
+    }
+
+    fn decode_dynamic<I: canonical::Input + ?Sized>(
+        &mut self,
+        buffer: &mut I,
+    ) -> Result<(), canonical::Error> {
+        self.tx.decode_dynamic(buffer)?;
+        self.hints.decode_dynamic(buffer)?;
+
+        self.check_hints()
+            .map_err(|_| canonical::Error::Unknown("Invalid signer hints"))
+    }
+}
+
+/// Deserializes a [`PartiallySignedTransaction`] through
+/// [`PartiallySignedTransaction::add_hint`].
+#[cfg(feature = "serde")]
+mod serde_parts {
+    use super::{
+        PartiallySignedError,
+        PartiallySignedTransaction,
+        SignerHint,
+    };
+    use crate::Transaction;
+    use alloc::vec::Vec;
+
+    #[derive(serde::Deserialize)]
+    #[serde(rename = "PartiallySignedTransaction")]
+    pub struct RawPartiallySignedTransaction {
+        tx: Transaction,
+        hints: Vec<SignerHint>,
+    }
+
+    impl TryFrom<RawPartiallySignedTransaction> for PartiallySignedTransaction {
+        type Error = PartiallySignedError;
+
+        fn try_from(raw: RawPartiallySignedTransaction) -> Result<Self, Self::Error> {
+            Self::from_parts(raw.tx, raw.hints)
+        }
+    }
+}
+
+/// The owner and the witness index of a signed input.
+fn signed_input(input: &Input) -> Option<(Address, u16)> {
+    match input {
+        Input::CoinSigned(CoinSigned {
+            owner,
+            witness_index,
+            ..
+        })
+        | Input::MessageCoinSigned(MessageCoinSigned {
+            recipient: owner,
+            witness_index,
+            ..
+        })
+        | Input::MessageDataSigned(MessageDataSigned {
+            recipient: owner,
+            witness_index,
+            ..
+        }) => Some((*owner, *witness_index)),
+        _ => None,
+    }
+}
+
+fn inputs(tx: &Transaction) -> Option<&[Input]> {
+    match tx {
+        Transaction::Script(tx) => Some(tx.inputs()),
+        Transaction::Create(tx) => Some(tx.inputs()),
+        Transaction::Upgrade(tx) => Some(tx.inputs()),
+        Transaction::Upload(tx) => Some(tx.inputs()),
+        Transaction::Blob(tx) => Some(tx.inputs()),
+        Transaction::Mint(_) => None,
+    }
+}
+
+fn witnesses(tx: &Transaction) -> Option<&[Witness]> {
+    match tx {
+        Transaction::Script(tx) => Some(tx.witnesses()),
+        Transaction::Create(tx) => Some(tx.witnesses()),
+        Transaction::Upgrade(tx) => Some(tx.witnesses()),
+        Transaction::Upload(tx) => Some(tx.witnesses()),
+        Transaction::Blob(tx) => Some(tx.witnesses()),
+        Transaction::Mint(_) => None,
+    }
+}
+
+fn witnesses_mut(tx: &mut Transaction) -> Option<&mut Vec<Witness>> {
+    match tx {
+        Transaction::Script(tx) => Some(tx.witnesses_mut()),
+        Transaction::Create(tx) => Some(tx.witnesses_mut()),
+        Transaction::Upgrade(tx) => Some(tx.witnesses_mut()),
+        Transaction::Upload(tx) => Some(tx.witnesses_mut()),
+        Transaction::Blob(tx) => Some(tx.witnesses_mut()),
+        Transaction::Mint(_) => None,
+    }
+}
+
+fn without_witnesses(tx: &Transaction) -> Transaction {
+    let mut tx = tx.clone();
+    if let Some(witnesses) = witnesses_mut(&mut tx) {
+        witnesses.clear();
+    }
+    tx
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ConsensusParameters,
+        Finalizable,
+        TransactionBuilder,
+    };
+    use fuel_types::canonical::Serialize;
+    use rand::{
+        rngs::StdRng,
+        Rng,
+        SeedableRng,
+    };
+
+    struct Parties {
+        alice: SecretKey,
+        bob: SecretKey,
+        pst: PartiallySignedTransaction,
+    }
+
+    fn parties() -> Parties {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let alice = SecretKey::random(rng);
+        let bob = SecretKey::random(rng);
+        let base_asset_id = *ConsensusParameters::standard().base_asset_id();
+
+        let mut builder = TransactionBuilder::script(vec![], vec![]);
+        builder.max_fee_limit(0);
+        for secret in [&alice, &bob] {
+            builder.add_unsigned_coin_input(
+                *secret,
+                rng.gen(),
+                1_000,
+                base_asset_id,
+                Default::default(),
+            );
+        }
+        let tx = builder.finalize_without_signature();
+        let pst = PartiallySignedTransaction::new(tx).unwrap();
+
+        Parties { alice, bob, pst }
+    }
+
+    fn chain_id() -> ChainId {
+        ConsensusParameters::standard().chain_id()
+    }
+
+    #[test]
+    fn new_hints_signed_inputs() {
+        let Parties { alice, bob, pst } = parties();
+
+        let signers = pst
+            .hints()
+            .iter()
+            .map(|hint| hint.signer)
+            .collect::<Vec<_>>();
+        assert_eq!(signers.len(), 2);
+        assert!(signers.contains(&Input::owner(&alice.public_key())));
+        assert!(signers.contains(&Input::owner(&bob.public_key())));
+        assert_eq!(pst.missing_signatures().count(), 2);
+    }
+
+    #[test]
+    fn merged_signatures_finalize() {
+        let Parties { alice, bob, pst } = parties();
+        let chain_id = chain_id();
+
+        let mut signed_by_alice = pst.clone();
+        assert_eq!(signed_by_alice.sign(&alice, &chain_id), 1);
+        let mut signed_by_bob = pst;
+        assert_eq!(signed_by_bob.sign(&bob, &chain_id), 1);
+
+        let pst = PartiallySignedTransaction::merge([signed_by_alice, signed_by_bob])
+            .expect("The copies should be combined");
+        assert_eq!(pst.missing_signatures().count(), 0);
+
+        let tx = pst.finalize(&chain_id).expect("All the inputs are signed");
+        tx.check(Default::default(), &ConsensusParameters::standard())
+            .expect("The transaction should be valid");
+    }
+
+    #[test]
+    fn finalize_fails_on_missing_signature() {
+        let Parties { alice, mut pst, .. } = parties();
+        let chain_id = chain_id();
+        pst.sign(&alice, &chain_id);
+
+        let missing = *pst.missing_signatures().next().unwrap();
+        let result = pst.finalize(&chain_id);
+
+        assert_eq!(
+            result,
+            Err(PartiallySignedError::Validity(
+                ValidityError::InputInvalidSignature {
+                    index: missing.input_index as usize
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn finalize_verifies_hints_of_predicates() {
+        let rng = &mut StdRng::seed_from_u64(2322);
+        let signer = SecretKey::random(rng);
+        let chain_id = chain_id();
+
+        let predicate = vec![1];
+        let owner = Input::predicate_owner(&predicate);
+        let tx = TransactionBuilder::script(vec![], vec![])
+            .add_input(Input::coin_predicate(
+                rng.gen(),
+                owner,
+                1_000,
+                rng.gen(),
+                Default::default(),
+                0,
+                predicate,
+                vec![],
+            ))
+            .add_witness(Witness::default())
+            .finalize_without_signature();
+        let mut pst = PartiallySignedTransaction::new(tx).unwrap();
+        assert!(pst.hints().is_empty());
+
+        let hint = SignerHint {
+            input_index: 0,
+            signer: Input::owner(&signer.public_key()),
+            witness_index: 0,
+        };
+        pst.add_hint(hint).unwrap();
+        assert_eq!(
+            pst.clone().finalize(&chain_id),
+            Err(PartiallySignedError::Validity(
+                ValidityError::InputInvalidSignature { index: 0 }
+            ))
+        );
+
+        let id = pst.transaction().id(&chain_id);
+        let signature = Signature::sign(&signer, Message::from_bytes_ref(&id));
+        pst.add_signature(0, signature).unwrap();
+        assert!(pst.finalize(&chain_id).is_ok());
+    }
+
+    #[test]
+    fn add_hint_checks_bounds() {
+        let Parties { mut pst, .. } = parties();
+        let hint = pst.hints()[0];
+
+        assert_eq!(
+            pst.add_hint(SignerHint {
+                input_index: 2,
+                ..hint
+            })
+            .err(),
+            Some(PartiallySignedError::InputIndexBounds { index: 2 })
+        );
+        assert_eq!(
+            pst.add_hint(SignerHint {
+                witness_index: 2,
+                ..hint
+            })
+            .err(),
+            Some(PartiallySignedError::WitnessIndexBounds { index: 2 })
+        );
+        assert_eq!(
+            pst.add_signature(2, Signature::default()).err(),
+            Some(PartiallySignedError::WitnessIndexBounds { index: 2 })
+        );
+
+        pst.add_hint(hint).unwrap();
+        assert_eq!(pst.hints().len(), 2);
+    }
+
+    #[test]
+    fn add_hint_rejects_signer_other_than_the_owner() {
+        let Parties {
+            alice,
+            bob,
+            mut pst,
+        } = parties();
+        let alice = Input::owner(&alice.public_key());
+        let bob = Input::owner(&bob.public_key());
+        let hint = *pst
+            .hints()
+            .iter()
+            .find(|hint| hint.signer == alice)
+            .unwrap();
+
+        assert_eq!(
+            pst.add_hint(SignerHint {
+                signer: bob,
+                ..hint
+            })
+            .err(),
+            Some(PartiallySignedError::SignerMismatch {
+                index: hint.input_index
+            })
+        );
+        assert_eq!(pst.hints().len(), 2);
+    }
+
+    #[test]
+    fn add_hint_rejects_witness_other_than_the_inputs() {
+        let Parties {
+            alice,
+            bob,
+            mut pst,
+        } = parties();
+        let alice = Input::owner(&alice.public_key());
+        let bob = Input::owner(&bob.public_key());
+        let alice_hint = *pst
+            .hints()
+            .iter()
+            .find(|hint| hint.signer == alice)
+            .unwrap();
+        let bob_hint = *pst.hints().iter().find(|hint| hint.signer == bob).unwrap();
+
+        assert_eq!(
+            pst.add_hint(SignerHint {
+                witness_index: bob_hint.witness_index,
+                ..alice_hint
+            })
+            .err(),
+            Some(PartiallySignedError::WitnessMismatch {
+                index: alice_hint.input_index
+            })
+        );
+        assert_eq!(pst.hints().len(), 2);
+    }
+
+    #[test]
+    fn combine_rejects_conflicts_and_different_transactions() {
+        let Parties { alice, bob, pst } = parties();
+        let chain_id = chain_id();
+        let index = pst.hints()[0].witness_index;
+
+        let mut ours = pst.clone();
+        ours.add_signature(index, Signature::default()).unwrap();
+        let mut theirs = pst.clone();
+        theirs.sign(&alice, &chain_id);
+        theirs.sign(&bob, &chain_id);
+        assert_eq!(
+            ours.combine(&theirs).err(),
+            Some(PartiallySignedError::WitnessConflict { index })
+        );
+
+        let other = parties_with_other_amount();
+        assert_eq!(
+            pst.clone().combine(&other).err(),
+            Some(PartiallySignedError::TransactionMismatch)
+        );
+
+        assert_eq!(
+            PartiallySignedTransaction::merge([]).err(),
+            Some(PartiallySignedError::NothingToMerge)
+        );
+    }
+
+    #[test]
+    fn combine_leaves_the_transaction_unchanged_on_invalid_hints() {
+        let Parties { alice, pst, .. } = parties();
+        let mut theirs = pst.clone();
+        theirs.sign(&alice, &chain_id());
+        // Decoded or added hints are checked, so only a tampered copy holds this one
+        theirs.hints.push(SignerHint {
+            input_index: u16::MAX,
+            ..theirs.hints[0]
+        });
+
+        let mut ours = pst.clone();
+        assert_eq!(
+            ours.combine(&theirs).err(),
+            Some(PartiallySignedError::InputIndexBounds { index: u16::MAX })
+        );
+        assert_eq!(ours, pst);
+    }
+
+    fn parties_with_other_amount() -> PartiallySignedTransaction {
+        let mut tx = parties().pst.tx;
+        if let Some(Input::CoinSigned(coin)) = tx
+            .as_script_mut()
+            .and_then(|tx| tx.inputs_mut().first_mut())
+        {
+            coin.amount = 1;
+        }
+        PartiallySignedTransaction::new(tx).unwrap()
+    }
+
+    #[test]
+    fn mint_is_unsupported() {
+        let tx = Transaction::mint(
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+        );
+
+        assert_eq!(
+            PartiallySignedTransaction::new(tx).err(),
+            Some(PartiallySignedError::UnsupportedTransaction)
+        );
+    }
+
+    #[test]
+    fn encodings_round_trip() {
+        let Parties { alice, mut pst, .. } = parties();
+        pst.sign(&alice, &chain_id());
+
+        let bytes = pst.to_bytes();
+        let decoded = PartiallySignedTransaction::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded, pst);
+
+        let json = serde_json::to_string(&pst).unwrap();
+        let decoded: PartiallySignedTransaction = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, pst);
+    }
+
+    #[test]
+    fn decoding_rejects_hints_refused_by_add_hint() {
+        let Parties { bob, pst, .. } = parties();
+        let bob = Input::owner(&bob.public_key());
+        let invalid_hints = [
+            SignerHint {
+                signer: bob,
+                ..pst.hints()[0]
+            },
+            SignerHint {
+                input_index: 2,
+                ..pst.hints()[0]
+            },
+            SignerHint {
+                witness_index: 2,
+                ..pst.hints()[0]
+            },
+        ];
+
+        for hint in invalid_hints {
+            let tampered = PartiallySignedTransaction {
+                tx: pst.tx.clone(),
+                hints: vec![hint],
+            };
+
+            assert_eq!(
+                PartiallySignedTransaction::from_bytes(&tampered.to_bytes()),
+                Err(canonical::Error::Unknown("Invalid signer hints"))
+            );
+            let json = serde_json::to_string(&tampered).unwrap();
+            assert!(serde_json::from_str::<PartiallySignedTransaction>(&json).is_err());
+        }
+    }
+
+    #[test]
+    fn decoding_sorts_the_hints() {
+        let Parties { pst, .. } = parties();
+        let unsorted = PartiallySignedTransaction {
+            tx: pst.tx.clone(),
+            hints: pst.hints().iter().rev().copied().collect(),
+        };
+
+        let decoded =
+            PartiallySignedTransaction::from_bytes(&unsorted.to_bytes()).unwrap();
+        assert_eq!(decoded, pst);
+
+        let json = serde_json::to_string(&unsorted).unwrap();
+        let decoded: PartiallySignedTransaction = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, pst);
+    }
+}
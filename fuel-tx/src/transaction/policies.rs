@@ -33,11 +33,18 @@ bitflags::bitflags! {
         const Maturity = 1 << 2;
         /// If set, the max fee is present in the policies.
         const MaxFee = 1 << 3;
+        /// If set, the expiration is present in the policies.
+        const Expiration = 1 << 4;
+        /// If set, the fee payer is present in the policies.
+        const FeePayer = 1 << 5;
     }
 }
 
 /// The helper enum to make user-friendly API for [`Policies::set`] and [`Policies::get`]
 /// methods.
+///
+/// A new policy only needs a new variant here and a new bit in [`PoliciesBits`]; the
+/// position of its value in [`Policies`] is derived from the bit.
 #[derive(
     Clone,
     Copy,
@@ -54,16 +61,14 @@ pub enum PolicyType {
     WitnessLimit,
     Maturity,
     MaxFee,
+    Expiration,
+    FeePayer,
 }
 
 impl PolicyType {
+    /// The index of the policy's value in the policies array.
     pub const fn index(&self) -> usize {
-        match self {
-            PolicyType::Tip => 0,
-            PolicyType::WitnessLimit => 1,
-            PolicyType::Maturity => 2,
-            PolicyType::MaxFee => 3,
-        }
+        self.bit().bits().trailing_zeros() as usize
     }
 
     pub const fn bit(&self) -> PoliciesBits {
@@ -72,16 +77,47 @@ impl PolicyType {
             PolicyType::WitnessLimit => PoliciesBits::WitnessLimit,
             PolicyType::Maturity => PoliciesBits::Maturity,
             PolicyType::MaxFee => PoliciesBits::MaxFee,
+            PolicyType::Expiration => PoliciesBits::Expiration,
+            PolicyType::FeePayer => PoliciesBits::FeePayer,
+        }
+    }
+
+    /// The maximum value of the policy. Block heights must fit into `u32`, as well as
+    /// the pair of `u16` indices of the fee payer.
+    pub const fn max_value(&self) -> Word {
+        match self {
+            PolicyType::Maturity | PolicyType::Expiration | PolicyType::FeePayer => {
+                u32::MAX as Word
+            }
+            PolicyType::Tip | PolicyType::WitnessLimit | PolicyType::MaxFee => Word::MAX,
         }
     }
 }
 
+/// Packs the indices of the `FeePayer` policy into its value: the index of the input
+/// paying the fee goes into the lowest two bytes, and the index of the change output
+/// receiving its refund into the next two.
+pub fn fee_payer_value(input_index: u16, change_index: u16) -> Word {
+    let [input_0, input_1] = input_index.to_le_bytes();
+    let [change_0, change_1] = change_index.to_le_bytes();
+    Word::from_le_bytes([input_0, input_1, change_0, change_1, 0, 0, 0, 0])
+}
+
+/// Unpacks the value of the `FeePayer` policy into the index of the input paying the
+/// fee and the index of the change output receiving its refund.
+pub fn fee_payer_indices(value: Word) -> (u16, u16) {
+    let [input_0, input_1, change_0, change_1, ..] = value.to_le_bytes();
+    (
+        u16::from_le_bytes([input_0, input_1]),
+        u16::from_le_bytes([change_0, change_1]),
+    )
+}
+
 /// The total number of policies.
 pub const POLICIES_NUMBER: usize = PoliciesBits::all().bits().count_ones() as usize;
 
 /// Container for managing policies.
 #[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Hash)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "typescript", wasm_bindgen::prelude::wasm_bindgen)]
 pub struct Policies {
     /// A bitmask that indicates what policies are set.
@@ -138,6 +174,22 @@ impl Policies {
         self
     }
 
+    /// Sets the `expiration` policy.
+    pub fn with_expiration(mut self, expiration: BlockHeight) -> Self {
+        self.set(PolicyType::Expiration, Some(*expiration.deref() as u64));
+        self
+    }
+
+    /// Sets the `fee_payer` policy to the index of the input paying the fee and the
+    /// index of the change output receiving its refund.
+    pub fn with_fee_payer(mut self, input_index: u16, change_index: u16) -> Self {
+        self.set(
+            PolicyType::FeePayer,
+            Some(fee_payer_value(input_index, change_index)),
+        );
+        self
+    }
+
     /// Returns a policy's value if the corresponding bit is set.
     pub fn get(&self, policy_type: PolicyType) -> Option<Word> {
         if self.bits.contains(policy_type.bit()) {
@@ -180,13 +232,17 @@ impl Policies {
             return false;
         }
 
-        if let Some(maturity) = self.get(PolicyType::Maturity) {
-            if maturity > u32::MAX as u64 {
-                return false;
-            }
-        }
+        self.values_are_in_range()
+    }
 
-        true
+    /// Returns `true` if all set policies don't exceed their [`PolicyType::max_value`].
+    fn values_are_in_range(&self) -> bool {
+        use strum::IntoEnumIterator;
+        PolicyType::iter().all(|policy_type| {
+            self.get(policy_type)
+                .map(|value| value <= policy_type.max_value())
+                .unwrap_or(true)
+        })
     }
 
     /// Helper function to generate values arrays based on the `PoliciesBits`.
@@ -247,10 +303,8 @@ impl Deserialize for Policies {
             }
         }
 
-        if let Some(maturity) = self.get(PolicyType::Maturity) {
-            if maturity > u32::MAX as u64 {
-                return Err(Error::Unknown("The maturity in more than `u32::MAX`"));
-            }
+        if !self.values_are_in_range() {
+            return Err(Error::Unknown("The policy value is out of range"));
         }
 
         Ok(())
@@ -269,15 +323,251 @@ impl Distribution<Policies> for Standard {
             values: Policies::values_for_bitmask(bits, values),
         };
 
-        if policies.get(PolicyType::Maturity).is_some() {
-            let maturity: u32 = rng.gen();
-            policies.set(PolicyType::Maturity, Some(maturity as u64));
+        use strum::IntoEnumIterator;
+        for policy_type in PolicyType::iter() {
+            if policies.is_set(policy_type) {
+                let value = rng.gen_range(0..=policy_type.max_value());
+                policies.set(policy_type, Some(value));
+            }
         }
 
         policies
     }
 }
 
+/// Serializes the policy values in a layout picked by the set policies. While only the
+/// policies that existed before `Expiration` and `FeePayer` are set, the values keep
+/// their original layout, so such policies encode as before in the formats that aren't
+/// self-describing, like `postcard` and `bincode`. Self-describing formats also accept
+/// shorter arrays of values, as long as they cover every set policy.
+#[cfg(feature = "serde")]
+mod serde_policies {
+    use super::{
+        Policies,
+        PoliciesBits,
+        PolicyType,
+        POLICIES_NUMBER,
+    };
+    use core::fmt;
+    use fuel_types::Word;
+    use serde::{
+        de::{
+            self,
+            DeserializeSeed,
+            MapAccess,
+            SeqAccess,
+            Visitor,
+        },
+        ser::{
+            SerializeStruct,
+            SerializeTuple,
+        },
+        Deserialize,
+        Deserializer,
+        Serialize,
+        Serializer,
+    };
+
+    const FIELDS: &[&str] = &["bits", "values"];
+
+    /// The versions of the layout of the serialized policy values.
+    #[derive(Clone, Copy)]
+    enum ValuesLayout {
+        /// The values of `Tip`, `WitnessLimit`, `Maturity` and `MaxFee`.
+        V1,
+        /// The values of all policies.
+        V2,
+    }
+
+    impl ValuesLayout {
+        const V1_POLICIES: PoliciesBits = PoliciesBits::Tip
+            .union(PoliciesBits::WitnessLimit)
+            .union(PoliciesBits::Maturity)
+            .union(PoliciesBits::MaxFee);
+
+        /// The oldest layout that has a value for every set policy.
+        fn for_bits(bits: PoliciesBits) -> Self {
+            if Self::V1_POLICIES.contains(bits) {
+                Self::V1
+            } else {
+                Self::V2
+            }
+        }
+
+        /// The number of values in the layout.
+        const fn len(self) -> usize {
+            match self {
+                Self::V1 => Self::V1_POLICIES.bits().count_ones() as usize,
+                Self::V2 => POLICIES_NUMBER,
+            }
+        }
+    }
+
+    /// The values serialized as a tuple of the length of their layout.
+    struct Values<'a>(&'a [Word]);
+
+    impl Serialize for Values<'_> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            let mut tuple = serializer.serialize_tuple(self.0.len())?;
+            for value in self.0 {
+                tuple.serialize_element(value)?;
+            }
+            tuple.end()
+        }
+    }
+
+    impl Serialize for Policies {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            let len = ValuesLayout::for_bits(self.bits).len();
+            let mut state = serializer.serialize_struct("Policies", FIELDS.len())?;
+            state.serialize_field("bits", &self.bits)?;
+            state.serialize_field("values", &Values(&self.values[..len]))?;
+            state.end()
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Policies {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_struct("Policies", FIELDS, PoliciesVisitor)
+        }
+    }
+
+    #[derive(serde::Deserialize)]
+    #[serde(field_identifier, rename_all = "lowercase")]
+    enum Field {
+        Bits,
+        Values,
+        #[serde(other)]
+        Ignore,
+    }
+
+    struct PoliciesVisitor;
+
+    impl<'de> Visitor<'de> for PoliciesVisitor {
+        type Value = Policies;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("struct Policies")
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let bits: PoliciesBits = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+            // The formats that aren't self-describing only know how many values to
+            // read from the layout of the set policies.
+            let values = seq
+                .next_element_seed(ValuesLayout::for_bits(bits))?
+                .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+            policies(bits, values)
+        }
+
+        fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+        where
+            A: MapAccess<'de>,
+        {
+            let mut bits = None;
+            let mut values = None;
+            while let Some(field) = map.next_key()? {
+                match field {
+                    Field::Bits => {
+                        if bits.is_some() {
+                            return Err(de::Error::duplicate_field("bits"))
+                        }
+                        bits = Some(map.next_value()?);
+                    }
+                    Field::Values => {
+                        if values.is_some() {
+                            return Err(de::Error::duplicate_field("values"))
+                        }
+                        values = Some(map.next_value_seed(ValuesLayout::V2)?);
+                    }
+                    Field::Ignore => {
+                        map.next_value::<de::IgnoredAny>()?;
+                    }
+                }
+            }
+            let bits = bits.ok_or_else(|| de::Error::missing_field("bits"))?;
+            let values = values.ok_or_else(|| de::Error::missing_field("values"))?;
+            policies(bits, values)
+        }
+    }
+
+    /// Checks that the deserialized values cover every set policy.
+    fn policies<E>(
+        bits: PoliciesBits,
+        (values, len): ([Word; POLICIES_NUMBER], usize),
+    ) -> Result<Policies, E>
+    where
+        E: de::Error,
+    {
+        use strum::IntoEnumIterator;
+        if PolicyType::iter()
+            .filter(|policy_type| bits.contains(policy_type.bit()))
+            .any(|policy_type| policy_type.index() >= len)
+        {
+            return Err(E::custom("The policy values don't cover all set policies"))
+        }
+
+        Ok(Policies { bits, values })
+    }
+
+    /// Deserializes at most as many values as the layout has, along with the number of
+    /// values present in the input.
+    impl<'de> DeserializeSeed<'de> for ValuesLayout {
+        type Value = ([Word; POLICIES_NUMBER], usize);
+
+        fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_tuple(self.len(), self)
+        }
+    }
+
+    impl<'de> Visitor<'de> for ValuesLayout {
+        type Value = ([Word; POLICIES_NUMBER], usize);
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(formatter, "at most {} policy values", self.len())
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let mut values = [0; POLICIES_NUMBER];
+            for (len, value) in values.iter_mut().take(self.len()).enumerate() {
+                match seq.next_element()? {
+                    Some(next) => *value = next,
+                    None => return Ok((values, len)),
+                }
+            }
+
+            if seq.next_element::<de::IgnoredAny>()?.is_some() {
+                return Err(de::Error::invalid_length(
+                    self.len().saturating_add(1),
+                    &self,
+                ))
+            }
+
+            Ok((values, self.len()))
+        }
+    }
+}
+
 #[cfg(feature = "typescript")]
 pub mod typescript {
     use wasm_bindgen::prelude::*;
@@ -325,7 +615,9 @@ pub mod typescript {
 #[test]
 fn values_for_bitmask_produces_expected_values() {
     const MAX_BITMASK: u32 = 1 << POLICIES_NUMBER;
-    const VALUES: [Word; POLICIES_NUMBER] = [0x1000001, 0x2000001, 0x3000001, 0x4000001];
+    const VALUES: [Word; POLICIES_NUMBER] = [
+        0x1000001, 0x2000001, 0x3000001, 0x4000001, 0x5000001, 0x6001,
+    ];
 
     // Given
     let mut set = hashbrown::HashSet::new();
@@ -344,7 +636,9 @@ fn values_for_bitmask_produces_expected_values() {
 #[test]
 fn canonical_serialization_deserialization_for_any_combination_of_values_works() {
     const MAX_BITMASK: u32 = 1 << POLICIES_NUMBER;
-    const VALUES: [Word; POLICIES_NUMBER] = [0x1000001, 0x2000001, 0x3000001, 0x4000001];
+    const VALUES: [Word; POLICIES_NUMBER] = [
+        0x1000001, 0x2000001, 0x3000001, 0x4000001, 0x5000001, 0x6001,
+    ];
 
     for bitmask in 0..MAX_BITMASK {
         let bits =
@@ -383,3 +677,153 @@ fn canonical_serialization_deserialization_for_any_combination_of_values_works()
         );
     }
 }
+
+#[test]
+fn policy_type_index_follows_bit_position() {
+    use strum::IntoEnumIterator;
+
+    for (index, policy_type) in PolicyType::iter().enumerate() {
+        assert_eq!(policy_type.index(), index);
+        assert_eq!(
+            PoliciesBits::all().iter().nth(index),
+            Some(policy_type.bit())
+        );
+    }
+}
+
+#[test]
+fn decode_fails_for_expiration_above_u32_max() {
+    let mut policies = Policies::new();
+    policies.set(PolicyType::Expiration, Some(u32::MAX as u64 + 1));
+    assert!(!policies.is_valid());
+
+    let bytes = policies.to_bytes();
+    let result = Policies::from_bytes(&bytes);
+
+    assert!(result.is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_deserialization_accepts_values_without_newer_policies() {
+    // Given
+    let json = r#"{"bits":"Tip | MaxFee","values":[1,0,0,4]}"#;
+
+    // When
+    let policies: Policies =
+        serde_json::from_str(json).expect("Should deserialize without error");
+
+    // Then
+    assert_eq!(policies, Policies::new().with_tip(1).with_max_fee(4));
+    assert_eq!(
+        serde_json::to_string(&policies).unwrap(),
+        r#"{"bits":"Tip | MaxFee","values":[1,0,0,4]}"#
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_deserialization_fails_for_values_not_covering_set_policies() {
+    // Given
+    let json = r#"{"bits":"Tip | Expiration","values":[1,0,0,4]}"#;
+
+    // When
+    let result = serde_json::from_str::<Policies>(json);
+
+    // Then
+    assert!(result.is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_deserialization_fails_for_too_many_values() {
+    let json = r#"{"bits":"Tip","values":[1,0,0,0,0,0,0]}"#;
+
+    let result = serde_json::from_str::<Policies>(json);
+
+    assert!(result.is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_serialization_keeps_values_of_newer_policies() {
+    // Given
+    let policies = Policies::new().with_tip(1).with_expiration(5.into());
+
+    // When
+    let json = serde_json::to_string(&policies).unwrap();
+
+    // Then
+    assert_eq!(
+        json,
+        r#"{"bits":"Tip | Expiration","values":[1,0,0,0,5,0]}"#
+    );
+    assert_eq!(serde_json::from_str::<Policies>(&json).unwrap(), policies);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn postcard_decodes_legacy_policies() {
+    // The encoding from before `Expiration` and `FeePayer` were added.
+    let bytes = [0x0d, 1, 0, 2, 100];
+    let expected = Policies::new()
+        .with_tip(1)
+        .with_maturity(2.into())
+        .with_max_fee(100);
+
+    let policies: Policies =
+        postcard::from_bytes(&bytes).expect("Should decode legacy policies");
+
+    assert_eq!(policies, expected);
+    assert_eq!(postcard::to_allocvec(&policies).unwrap(), bytes);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn bincode_decodes_legacy_policies() {
+    // The encoding from before `Expiration` and `FeePayer` were added.
+    let bytes = hex::decode(
+        "0d0000000100000000000000000000000000000002000000000000006400000000000000",
+    )
+    .unwrap();
+    let expected = Policies::new()
+        .with_tip(1)
+        .with_maturity(2.into())
+        .with_max_fee(100);
+
+    let policies: Policies =
+        bincode::deserialize(&bytes).expect("Should decode legacy policies");
+
+    assert_eq!(policies, expected);
+    assert_eq!(bincode::serialize(&policies).unwrap(), bytes);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn postcard_and_bincode_round_trip_any_combination_of_policies() {
+    const MAX_BITMASK: u32 = 1 << POLICIES_NUMBER;
+    const VALUES: [Word; POLICIES_NUMBER] = [
+        0x1000001, 0x2000001, 0x3000001, 0x4000001, 0x5000001, 0x6001,
+    ];
+
+    for bitmask in 0..MAX_BITMASK {
+        let bits =
+            PoliciesBits::from_bits(bitmask).expect("Should construct a valid bits");
+        let policies = Policies {
+            bits,
+            values: Policies::values_for_bitmask(bits, VALUES),
+        };
+
+        let postcard_bytes = postcard::to_allocvec(&policies).unwrap();
+        let bincode_bytes = bincode::serialize(&policies).unwrap();
+
+        assert_eq!(
+            postcard::from_bytes::<Policies>(&postcard_bytes).unwrap(),
+            policies
+        );
+        assert_eq!(
+            bincode::deserialize::<Policies>(&bincode_bytes).unwrap(),
+            policies
+        );
+    }
+}
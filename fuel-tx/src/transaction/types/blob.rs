@@ -8,6 +8,7 @@ use crate::{
             ChargeableTransaction,
             UniqueFormatValidityChecks,
         },
+        validity::Violations,
         Chargeable,
     },
     ConsensusParameters,
@@ -93,6 +94,7 @@ impl UniqueFormatValidityChecks for Blob {
     fn check_unique_rules(
         &self,
         consensus_params: &ConsensusParameters,
+        violations: &mut Violations,
     ) -> Result<(), ValidityError> {
         let index = self.body.witness_index as usize;
         let witness = self
@@ -102,7 +104,7 @@ impl UniqueFormatValidityChecks for Blob {
 
         // Verify that blob id is correct
         if BlobId::compute(witness.as_ref()) != self.body.id {
-            return Err(ValidityError::TransactionBlobIdVerificationFailed);
+            violations.report(ValidityError::TransactionBlobIdVerificationFailed)?;
         }
 
         self.inputs
@@ -111,15 +113,15 @@ impl UniqueFormatValidityChecks for Blob {
             .try_for_each(|(index, input)| {
                 if let Some(asset_id) = input.asset_id(consensus_params.base_asset_id()) {
                     if asset_id != consensus_params.base_asset_id() {
-                        return Err(
+                        violations.report(
                             ValidityError::TransactionInputContainsNonBaseAssetId {
                                 index,
                             },
-                        );
+                        )?;
                     }
                 }
 
-                match input {
+                violations.check(match input {
                     Input::Contract(_) => {
                         Err(ValidityError::TransactionInputContainsContract { index })
                     }
@@ -127,36 +129,40 @@ impl UniqueFormatValidityChecks for Blob {
                         Err(ValidityError::TransactionInputContainsMessageData { index })
                     }
                     _ => Ok(()),
-                }
+                })
             })?;
 
         self.outputs
             .iter()
             .enumerate()
-            .try_for_each(|(index, output)| match output {
-                Output::Contract(_) => {
-                    Err(ValidityError::TransactionOutputContainsContract { index })
-                }
+            .try_for_each(|(index, output)| {
+                violations.check(match output {
+                    Output::Contract(_) => {
+                        Err(ValidityError::TransactionOutputContainsContract { index })
+                    }
 
-                Output::Variable { .. } => {
-                    Err(ValidityError::TransactionOutputContainsVariable { index })
-                }
+                    Output::Variable { .. } => {
+                        Err(ValidityError::TransactionOutputContainsVariable { index })
+                    }
 
-                Output::Change { asset_id, .. } => {
-                    if asset_id != consensus_params.base_asset_id() {
-                        Err(ValidityError::TransactionChangeChangeUsesNotBaseAsset {
+                    Output::Change { asset_id, .. } => {
+                        if asset_id != consensus_params.base_asset_id() {
+                            Err(ValidityError::TransactionChangeChangeUsesNotBaseAsset {
+                                index,
+                            })
+                        } else {
+                            Ok(())
+                        }
+                    }
+
+                    Output::ContractCreated { .. } => {
+                        Err(ValidityError::TransactionOutputContainsContractCreated {
                             index,
                         })
-                    } else {
-                        Ok(())
                     }
-                }
-
-                Output::ContractCreated { .. } => {
-                    Err(ValidityError::TransactionOutputContainsContractCreated { index })
-                }
 
-                Output::Coin { .. } => Ok(()),
+                    Output::Coin { .. } => Ok(()),
+                })
             })?;
 
         Ok(())
@@ -13,6 +13,7 @@ use crate::{
         validity::{
             check_common_part,
             FormatValidityChecks,
+            Violations,
         },
         Chargeable,
     },
@@ -125,9 +126,53 @@ pub(crate) trait UniqueFormatValidityChecks {
     fn check_unique_rules(
         &self,
         consensus_params: &ConsensusParameters,
+        violations: &mut Violations,
     ) -> Result<(), ValidityError>;
 }
 
+fn check_signatures_with<Tx>(
+    tx: &Tx,
+    chain_id: &ChainId,
+    violations: &mut Violations,
+) -> Result<(), ValidityError>
+where
+    Tx: UniqueIdentifier + Inputs + Witnesses,
+{
+    let id = tx.id(chain_id);
+
+    // There will be at most len(witnesses) signatures to cache
+    let mut recovery_cache = Some(HashMap::with_capacity(tx.witnesses().len()));
+
+    tx.inputs()
+        .iter()
+        .enumerate()
+        .try_for_each(|(index, input)| {
+            violations.check(input.check_signature(
+                index,
+                &id,
+                tx.witnesses(),
+                &mut recovery_cache,
+            ))
+        })?;
+
+    Ok(())
+}
+
+fn check_without_signatures_with<Tx>(
+    tx: &Tx,
+    block_height: BlockHeight,
+    consensus_params: &ConsensusParameters,
+    violations: &mut Violations,
+) -> Result<(), ValidityError>
+where
+    Tx: Serialize + Chargeable + Outputs + UniqueFormatValidityChecks,
+{
+    check_common_part(tx, block_height, consensus_params, violations)?;
+    tx.check_unique_rules(consensus_params, violations)?;
+
+    Ok(())
+}
+
 impl<Body, MetadataBody> FormatValidityChecks
     for ChargeableTransaction<Body, MetadataBody>
 where
@@ -139,19 +184,7 @@ where
     Self: UniqueFormatValidityChecks,
 {
     fn check_signatures(&self, chain_id: &ChainId) -> Result<(), ValidityError> {
-        let id = self.id(chain_id);
-
-        // There will be at most len(witnesses) signatures to cache
-        let mut recovery_cache = Some(HashMap::with_capacity(self.witnesses().len()));
-
-        self.inputs()
-            .iter()
-            .enumerate()
-            .try_for_each(|(index, input)| {
-                input.check_signature(index, &id, self.witnesses(), &mut recovery_cache)
-            })?;
-
-        Ok(())
+        check_signatures_with(self, chain_id, &mut Violations::default())
     }
 
     fn check_without_signatures(
@@ -159,10 +192,33 @@ where
         block_height: BlockHeight,
         consensus_params: &ConsensusParameters,
     ) -> Result<(), ValidityError> {
-        check_common_part(self, block_height, consensus_params)?;
-        self.check_unique_rules(consensus_params)?;
+        check_without_signatures_with(
+            self,
+            block_height,
+            consensus_params,
+            &mut Violations::default(),
+        )
+    }
+
+    fn check_all_signatures(&self, chain_id: &ChainId) -> Result<(), Vec<ValidityError>> {
+        Violations::collect(|violations| {
+            check_signatures_with(self, chain_id, violations)
+        })
+    }
 
-        Ok(())
+    fn check_all_without_signatures(
+        &self,
+        block_height: BlockHeight,
+        consensus_params: &ConsensusParameters,
+    ) -> Result<(), Vec<ValidityError>> {
+        Violations::collect(|violations| {
+            check_without_signatures_with(
+                self,
+                block_height,
+                consensus_params,
+                violations,
+            )
+        })
     }
 }
 
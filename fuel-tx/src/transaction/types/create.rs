@@ -11,6 +11,7 @@ use crate::{
             ChargeableTransaction,
             UniqueFormatValidityChecks,
         },
+        validity::Violations,
     },
     Chargeable,
     ConsensusParameters,
@@ -130,6 +131,7 @@ impl UniqueFormatValidityChecks for Create {
     fn check_unique_rules(
         &self,
         consensus_params: &ConsensusParameters,
+        violations: &mut Violations,
     ) -> Result<(), ValidityError> {
         let contract_params = consensus_params.contract_params();
         let base_asset_id = consensus_params.base_asset_id();
@@ -141,13 +143,13 @@ impl UniqueFormatValidityChecks for Create {
             .ok_or(ValidityError::TransactionCreateBytecodeWitnessIndex)?;
 
         if bytecode_witness_len > contract_params.contract_max_size() {
-            return Err(ValidityError::TransactionCreateBytecodeLen);
+            violations.report(ValidityError::TransactionCreateBytecodeLen)?;
         }
 
         // Restrict to subset of u16::MAX, allowing this to be increased in the future
         // in a non-breaking way.
         if self.body.storage_slots.len() as u64 > contract_params.max_storage_slots() {
-            return Err(ValidityError::TransactionCreateStorageSlotMax);
+            violations.report(ValidityError::TransactionCreateStorageSlotMax)?;
         }
 
         // Verify storage slots are sorted
@@ -158,7 +160,7 @@ impl UniqueFormatValidityChecks for Create {
             .windows(2)
             .all(|s| s[0] < s[1])
         {
-            return Err(ValidityError::TransactionCreateStorageSlotOrder);
+            violations.report(ValidityError::TransactionCreateStorageSlotOrder)?;
         }
 
         self.inputs
@@ -167,20 +169,22 @@ impl UniqueFormatValidityChecks for Create {
             .try_for_each(|(index, input)| {
                 if let Some(asset_id) = input.asset_id(consensus_params.base_asset_id()) {
                     if asset_id != consensus_params.base_asset_id() {
-                        return Err(
+                        violations.report(
                             ValidityError::TransactionInputContainsNonBaseAssetId {
                                 index,
                             },
-                        );
+                        )?;
                     }
                 }
 
                 match input {
-                    Input::Contract(_) => {
-                        Err(ValidityError::TransactionInputContainsContract { index })
-                    }
+                    Input::Contract(_) => violations.report(
+                        ValidityError::TransactionInputContainsContract { index },
+                    ),
                     Input::MessageDataSigned(_) | Input::MessageDataPredicate(_) => {
-                        Err(ValidityError::TransactionInputContainsMessageData { index })
+                        violations.report(
+                            ValidityError::TransactionInputContainsMessageData { index },
+                        )
                     }
                     _ => Ok(()),
                 }
@@ -203,17 +207,18 @@ impl UniqueFormatValidityChecks for Create {
             .iter()
             .enumerate()
             .try_for_each(|(index, output)| match output {
-                Output::Contract(_) => {
-                    Err(ValidityError::TransactionOutputContainsContract { index })
-                }
+                Output::Contract(_) => violations.report(
+                    ValidityError::TransactionOutputContainsContract { index },
+                ),
 
-                Output::Variable { .. } => {
-                    Err(ValidityError::TransactionOutputContainsVariable { index })
-                }
+                Output::Variable { .. } => violations.report(
+                    ValidityError::TransactionOutputContainsVariable { index },
+                ),
 
-                Output::Change { asset_id, .. } if asset_id != base_asset_id => {
-                    Err(ValidityError::TransactionChangeChangeUsesNotBaseAsset { index })
-                }
+                Output::Change { asset_id, .. } if asset_id != base_asset_id => violations
+                    .report(ValidityError::TransactionChangeChangeUsesNotBaseAsset {
+                        index,
+                    }),
 
                 Output::ContractCreated {
                     contract_id,
@@ -221,7 +226,9 @@ impl UniqueFormatValidityChecks for Create {
                 } if contract_id != &contract_id_calculated
                     || state_root != &state_root_calculated =>
                     {
-                        Err(
+                        contract_created = true;
+
+                        violations.report(
                             ValidityError::TransactionCreateOutputContractCreatedDoesntMatch {
                                 index,
                             },
@@ -231,11 +238,9 @@ impl UniqueFormatValidityChecks for Create {
                 // TODO: Output::ContractCreated { contract_id, state_root } if
                 // contract_id == &id && state_root == &storage_root
                 //  maybe move from `fuel-vm` to here
-                Output::ContractCreated { .. } if contract_created => {
-                    Err(ValidityError::TransactionCreateOutputContractCreatedMultiple {
-                        index,
-                    })
-                }
+                Output::ContractCreated { .. } if contract_created => violations.report(
+                    ValidityError::TransactionCreateOutputContractCreatedMultiple { index },
+                ),
 
                 Output::ContractCreated { .. } => {
                     contract_created = true;
@@ -247,7 +252,8 @@ impl UniqueFormatValidityChecks for Create {
             })?;
 
         if !contract_created {
-            return Err(ValidityError::TransactionOutputDoesntContainContractCreated);
+            violations
+                .report(ValidityError::TransactionOutputDoesntContainContractCreated)?;
         }
 
         Ok(())
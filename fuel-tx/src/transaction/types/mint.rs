@@ -6,6 +6,7 @@ use crate::{
         validity::{
             check_size,
             FormatValidityChecks,
+            Violations,
         },
     },
     ConsensusParameters,
@@ -26,6 +27,8 @@ use fuel_types::ChainId;
 
 use fuel_types::canonical::Serialize;
 
+use alloc::vec::Vec;
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub(crate) struct MintMetadata {
     pub id: Bytes32,
@@ -99,19 +102,44 @@ impl FormatValidityChecks for Mint {
         block_height: BlockHeight,
         consensus_params: &ConsensusParameters,
     ) -> Result<(), ValidityError> {
-        check_size(self, consensus_params.tx_params())?;
+        self.check_without_signatures_with(
+            block_height,
+            consensus_params,
+            &mut Violations::default(),
+        )
+    }
+
+    fn check_all_without_signatures(
+        &self,
+        block_height: BlockHeight,
+        consensus_params: &ConsensusParameters,
+    ) -> Result<(), Vec<ValidityError>> {
+        Violations::collect(|violations| {
+            self.check_without_signatures_with(block_height, consensus_params, violations)
+        })
+    }
+}
+
+impl Mint {
+    fn check_without_signatures_with(
+        &self,
+        block_height: BlockHeight,
+        consensus_params: &ConsensusParameters,
+        violations: &mut Violations,
+    ) -> Result<(), ValidityError> {
+        violations.check(check_size(self, consensus_params.tx_params()))?;
 
         if self.tx_pointer().block_height() != block_height {
-            return Err(ValidityError::TransactionMintIncorrectBlockHeight);
+            violations.report(ValidityError::TransactionMintIncorrectBlockHeight)?;
         }
 
         if self.output_contract.input_index != 0 {
-            return Err(ValidityError::TransactionMintIncorrectOutputIndex);
+            violations.report(ValidityError::TransactionMintIncorrectOutputIndex)?;
         }
 
         // It is temporary check until https://github.com/FuelLabs/fuel-core/issues/1205
         if &self.mint_asset_id != consensus_params.base_asset_id() {
-            return Err(ValidityError::TransactionMintNonBaseAsset);
+            violations.report(ValidityError::TransactionMintNonBaseAsset)?;
         }
 
         Ok(())
@@ -15,6 +15,7 @@ use crate::{
             ChargeableTransaction,
             UniqueFormatValidityChecks,
         },
+        validity::Violations,
         Chargeable,
     },
     ConsensusParameters,
@@ -113,23 +114,24 @@ impl UniqueFormatValidityChecks for Script {
     fn check_unique_rules(
         &self,
         consensus_params: &ConsensusParameters,
+        violations: &mut Violations,
     ) -> Result<(), ValidityError> {
         let script_params = consensus_params.script_params();
         if self.body.script.len() as u64 > script_params.max_script_length() {
-            Err(ValidityError::TransactionScriptLength)?;
+            violations.report(ValidityError::TransactionScriptLength)?;
         }
 
         if self.body.script_data.len() as u64 > script_params.max_script_data_length() {
-            Err(ValidityError::TransactionScriptDataLength)?;
+            violations.report(ValidityError::TransactionScriptDataLength)?;
         }
 
         self.outputs
             .iter()
             .enumerate()
             .try_for_each(|(index, output)| match output {
-                Output::ContractCreated { .. } => {
-                    Err(ValidityError::TransactionOutputContainsContractCreated { index })
-                }
+                Output::ContractCreated { .. } => violations.report(
+                    ValidityError::TransactionOutputContainsContractCreated { index },
+                ),
                 _ => Ok(()),
             })?;
 
@@ -7,6 +7,7 @@ use crate::{
             ChargeableTransaction,
             UniqueFormatValidityChecks,
         },
+        validity::Violations,
         Chargeable,
     },
     ConsensusParameters,
@@ -180,18 +181,19 @@ impl UniqueFormatValidityChecks for Upgrade {
     fn check_unique_rules(
         &self,
         consensus_params: &ConsensusParameters,
+        violations: &mut Violations,
     ) -> Result<(), ValidityError> {
         // At least one of inputs must be owned by the privileged address.
-        self.inputs
-            .iter()
-            .find(|input| {
-                if let Some(owner) = input.input_owner() {
-                    owner == consensus_params.privileged_address()
-                } else {
-                    false
-                }
-            })
-            .ok_or(ValidityError::TransactionUpgradeNoPrivilegedAddress)?;
+        let privileged_input = self.inputs.iter().find(|input| {
+            if let Some(owner) = input.input_owner() {
+                owner == consensus_params.privileged_address()
+            } else {
+                false
+            }
+        });
+        if privileged_input.is_none() {
+            violations.report(ValidityError::TransactionUpgradeNoPrivilegedAddress)?;
+        }
 
         // We verify validity of the `UpgradePurpose` in the
         // `UpgradeMetadata::compute`.
@@ -199,7 +201,7 @@ impl UniqueFormatValidityChecks for Upgrade {
 
         if let Some(metadata) = self.metadata.as_ref() {
             if metadata.body != calculated_metadata {
-                return Err(ValidityError::TransactionMetadataMismatch);
+                violations.report(ValidityError::TransactionMetadataMismatch)?;
             }
         }
 
@@ -210,15 +212,15 @@ impl UniqueFormatValidityChecks for Upgrade {
             .try_for_each(|(index, input)| {
                 if let Some(asset_id) = input.asset_id(consensus_params.base_asset_id()) {
                     if asset_id != consensus_params.base_asset_id() {
-                        return Err(
+                        violations.report(
                             ValidityError::TransactionInputContainsNonBaseAssetId {
                                 index,
                             },
-                        );
+                        )?;
                     }
                 }
 
-                match input {
+                violations.check(match input {
                     Input::Contract(_) => {
                         Err(ValidityError::TransactionInputContainsContract { index })
                     }
@@ -226,32 +228,38 @@ impl UniqueFormatValidityChecks for Upgrade {
                         Err(ValidityError::TransactionInputContainsMessageData { index })
                     }
                     _ => Ok(()),
-                }
+                })
             })?;
 
         // The upgrade transaction can't create a contract.
         self.outputs
             .iter()
             .enumerate()
-            .try_for_each(|(index, output)| match output {
-                Output::Contract(_) => {
-                    Err(ValidityError::TransactionOutputContainsContract { index })
-                }
+            .try_for_each(|(index, output)| {
+                violations.check(match output {
+                    Output::Contract(_) => {
+                        Err(ValidityError::TransactionOutputContainsContract { index })
+                    }
 
-                Output::Variable { .. } => {
-                    Err(ValidityError::TransactionOutputContainsVariable { index })
-                }
+                    Output::Variable { .. } => {
+                        Err(ValidityError::TransactionOutputContainsVariable { index })
+                    }
 
-                Output::Change { asset_id, .. }
-                    if asset_id != consensus_params.base_asset_id() =>
-                {
-                    Err(ValidityError::TransactionChangeChangeUsesNotBaseAsset { index })
-                }
+                    Output::Change { asset_id, .. }
+                        if asset_id != consensus_params.base_asset_id() =>
+                    {
+                        Err(ValidityError::TransactionChangeChangeUsesNotBaseAsset {
+                            index,
+                        })
+                    }
 
-                Output::ContractCreated { .. } => {
-                    Err(ValidityError::TransactionOutputContainsContractCreated { index })
-                }
-                _ => Ok(()),
+                    Output::ContractCreated { .. } => {
+                        Err(ValidityError::TransactionOutputContainsContractCreated {
+                            index,
+                        })
+                    }
+                    _ => Ok(()),
+                })
             })?;
 
         Ok(())
@@ -8,6 +8,7 @@ use crate::{
             ChargeableTransaction,
             UniqueFormatValidityChecks,
         },
+        validity::Violations,
         Chargeable,
     },
     ConsensusParameters,
@@ -181,11 +182,13 @@ impl UniqueFormatValidityChecks for Upload {
     fn check_unique_rules(
         &self,
         consensus_params: &ConsensusParameters,
+        violations: &mut Violations,
     ) -> Result<(), ValidityError> {
         if self.body.subsections_number
             > consensus_params.tx_params().max_bytecode_subsections()
         {
-            return Err(ValidityError::TransactionUploadTooManyBytecodeSubsections);
+            violations
+                .report(ValidityError::TransactionUploadTooManyBytecodeSubsections)?;
         }
 
         let index = self.body.witness_index as usize;
@@ -212,7 +215,7 @@ impl UniqueFormatValidityChecks for Upload {
         );
 
         if !result {
-            return Err(ValidityError::TransactionUploadRootVerificationFailed);
+            violations.report(ValidityError::TransactionUploadRootVerificationFailed)?;
         }
 
         self.inputs
@@ -221,15 +224,15 @@ impl UniqueFormatValidityChecks for Upload {
             .try_for_each(|(index, input)| {
                 if let Some(asset_id) = input.asset_id(consensus_params.base_asset_id()) {
                     if asset_id != consensus_params.base_asset_id() {
-                        return Err(
+                        violations.report(
                             ValidityError::TransactionInputContainsNonBaseAssetId {
                                 index,
                             },
-                        );
+                        )?;
                     }
                 }
 
-                match input {
+                violations.check(match input {
                     Input::Contract(_) => {
                         Err(ValidityError::TransactionInputContainsContract { index })
                     }
@@ -237,31 +240,37 @@ impl UniqueFormatValidityChecks for Upload {
                         Err(ValidityError::TransactionInputContainsMessageData { index })
                     }
                     _ => Ok(()),
-                }
+                })
             })?;
 
         self.outputs
             .iter()
             .enumerate()
-            .try_for_each(|(index, output)| match output {
-                Output::Contract(_) => {
-                    Err(ValidityError::TransactionOutputContainsContract { index })
-                }
+            .try_for_each(|(index, output)| {
+                violations.check(match output {
+                    Output::Contract(_) => {
+                        Err(ValidityError::TransactionOutputContainsContract { index })
+                    }
 
-                Output::Variable { .. } => {
-                    Err(ValidityError::TransactionOutputContainsVariable { index })
-                }
+                    Output::Variable { .. } => {
+                        Err(ValidityError::TransactionOutputContainsVariable { index })
+                    }
 
-                Output::Change { asset_id, .. }
-                    if asset_id != consensus_params.base_asset_id() =>
-                {
-                    Err(ValidityError::TransactionChangeChangeUsesNotBaseAsset { index })
-                }
+                    Output::Change { asset_id, .. }
+                        if asset_id != consensus_params.base_asset_id() =>
+                    {
+                        Err(ValidityError::TransactionChangeChangeUsesNotBaseAsset {
+                            index,
+                        })
+                    }
 
-                Output::ContractCreated { .. } => {
-                    Err(ValidityError::TransactionOutputContainsContractCreated { index })
-                }
-                _ => Ok(()),
+                    Output::ContractCreated { .. } => {
+                        Err(ValidityError::TransactionOutputContainsContractCreated {
+                            index,
+                        })
+                    }
+                    _ => Ok(()),
+                })
             })?;
 
         Ok(())
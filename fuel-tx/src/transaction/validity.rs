@@ -1,5 +1,9 @@
 use crate::{
-    field::Maturity,
+    field::{
+        Expiration,
+        FeePayer,
+        Maturity,
+    },
     input::{
         coin::{
             CoinPredicate,
@@ -29,7 +33,11 @@ use crate::{
     Transaction,
     Witness,
 };
-use core::hash::Hash;
+use alloc::{
+    collections::BTreeSet,
+    vec,
+    vec::Vec,
+};
 use fuel_types::{
     canonical,
     canonical::Serialize,
@@ -39,7 +47,6 @@ use fuel_types::{
     ChainId,
 };
 use hashbrown::HashMap;
-use itertools::Itertools;
 
 mod error;
 
@@ -264,6 +271,47 @@ pub trait FormatValidityChecks {
         block_height: BlockHeight,
         consensus_params: &ConsensusParameters,
     ) -> Result<(), ValidityError>;
+
+    /// Performs the same checks as [`Self::check`], but returns all the violations
+    /// instead of the first one.
+    fn check_all(
+        &self,
+        block_height: BlockHeight,
+        consensus_params: &ConsensusParameters,
+    ) -> Result<(), Vec<ValidityError>> {
+        let mut errors = self
+            .check_all_without_signatures(block_height, consensus_params)
+            .err()
+            .unwrap_or_default();
+        errors.extend(
+            self.check_all_signatures(&consensus_params.chain_id())
+                .err()
+                .unwrap_or_default(),
+        );
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Performs the same checks as [`Self::check_signatures`], but returns all the
+    /// violations instead of the first one.
+    fn check_all_signatures(&self, chain_id: &ChainId) -> Result<(), Vec<ValidityError>> {
+        self.check_signatures(chain_id).map_err(|e| vec![e])
+    }
+
+    /// Performs the same checks as [`Self::check_without_signatures`], but returns all
+    /// the violations instead of the first one.
+    fn check_all_without_signatures(
+        &self,
+        block_height: BlockHeight,
+        consensus_params: &ConsensusParameters,
+    ) -> Result<(), Vec<ValidityError>> {
+        self.check_without_signatures(block_height, consensus_params)
+            .map_err(|e| vec![e])
+    }
 }
 
 impl FormatValidityChecks for Transaction {
@@ -300,6 +348,44 @@ impl FormatValidityChecks for Transaction {
             Self::Blob(tx) => tx.check_without_signatures(block_height, consensus_params),
         }
     }
+
+    fn check_all_signatures(&self, chain_id: &ChainId) -> Result<(), Vec<ValidityError>> {
+        match self {
+            Self::Script(tx) => tx.check_all_signatures(chain_id),
+            Self::Create(tx) => tx.check_all_signatures(chain_id),
+            Self::Mint(tx) => tx.check_all_signatures(chain_id),
+            Self::Upgrade(tx) => tx.check_all_signatures(chain_id),
+            Self::Upload(tx) => tx.check_all_signatures(chain_id),
+            Self::Blob(tx) => tx.check_all_signatures(chain_id),
+        }
+    }
+
+    fn check_all_without_signatures(
+        &self,
+        block_height: BlockHeight,
+        consensus_params: &ConsensusParameters,
+    ) -> Result<(), Vec<ValidityError>> {
+        match self {
+            Self::Script(tx) => {
+                tx.check_all_without_signatures(block_height, consensus_params)
+            }
+            Self::Create(tx) => {
+                tx.check_all_without_signatures(block_height, consensus_params)
+            }
+            Self::Mint(tx) => {
+                tx.check_all_without_signatures(block_height, consensus_params)
+            }
+            Self::Upgrade(tx) => {
+                tx.check_all_without_signatures(block_height, consensus_params)
+            }
+            Self::Upload(tx) => {
+                tx.check_all_without_signatures(block_height, consensus_params)
+            }
+            Self::Blob(tx) => {
+                tx.check_all_without_signatures(block_height, consensus_params)
+            }
+        }
+    }
 }
 
 /// Validates the size of the transaction in bytes. Transactions cannot exceed
@@ -321,6 +407,7 @@ pub(crate) fn check_common_part<T>(
     tx: &T,
     block_height: BlockHeight,
     consensus_params: &ConsensusParameters,
+    violations: &mut Violations,
 ) -> Result<(), ValidityError>
 where
     T: canonical::Serialize + Chargeable + field::Outputs,
@@ -331,42 +418,46 @@ where
     let gas_costs = consensus_params.gas_costs();
     let fee_params = consensus_params.fee_params();
 
-    check_size(tx, tx_params)?;
+    violations.check(check_size(tx, tx_params))?;
 
     if !tx.policies().is_valid() {
-        Err(ValidityError::TransactionPoliciesAreInvalid)?
+        violations.report(ValidityError::TransactionPoliciesAreInvalid)?
     }
 
     if let Some(witness_limit) = tx.policies().get(PolicyType::WitnessLimit) {
         let witness_size = tx.witnesses().size_dynamic();
         if witness_size as u64 > witness_limit {
-            Err(ValidityError::TransactionWitnessLimitExceeded)?
+            violations.report(ValidityError::TransactionWitnessLimitExceeded)?
         }
     }
 
     let max_gas = tx.max_gas(gas_costs, fee_params);
     if max_gas > tx_params.max_gas_per_tx() {
-        Err(ValidityError::TransactionMaxGasExceeded)?
+        violations.report(ValidityError::TransactionMaxGasExceeded)?
     }
 
     if !tx.policies().is_set(PolicyType::MaxFee) {
-        Err(ValidityError::TransactionMaxFeeNotSet)?
+        violations.report(ValidityError::TransactionMaxFeeNotSet)?
     };
 
     if tx.maturity() > block_height {
-        Err(ValidityError::TransactionMaturity)?;
+        violations.report(ValidityError::TransactionMaturity)?;
+    }
+
+    if tx.expiration() < block_height {
+        violations.report(ValidityError::TransactionExpiration)?;
     }
 
     if tx.inputs().len() > tx_params.max_inputs() as usize {
-        Err(ValidityError::TransactionInputsMax)?
+        violations.report(ValidityError::TransactionInputsMax)?
     }
 
     if tx.outputs().len() > tx_params.max_outputs() as usize {
-        Err(ValidityError::TransactionOutputsMax)?
+        violations.report(ValidityError::TransactionOutputsMax)?
     }
 
     if tx.witnesses().len() > tx_params.max_witnesses() as usize {
-        Err(ValidityError::TransactionWitnessesMax)?
+        violations.report(ValidityError::TransactionWitnessesMax)?
     }
 
     let any_spendable_input = tx.inputs().iter().find(|input| match input {
@@ -380,53 +471,104 @@ where
     });
 
     if any_spendable_input.is_none() {
-        Err(ValidityError::NoSpendableInput)?
-    }
-
-    tx.input_asset_ids_unique(base_asset_id)
-        .try_for_each(|input_asset_id| {
-            // check for duplicate change outputs
-            if tx
-                .outputs()
-                .iter()
-                .filter_map(|output| match output {
-                    Output::Change { asset_id, .. } if input_asset_id == asset_id => {
-                        Some(())
-                    }
-                    _ => None,
-                })
-                .count()
-                > 1
-            {
-                return Err(ValidityError::TransactionOutputChangeAssetIdDuplicated(
-                    *input_asset_id,
-                ));
+        violations.report(ValidityError::NoSpendableInput)?
+    }
+
+    if tx.fee_payer().is_some() {
+        match tx.fee_payer_input() {
+            None => {
+                violations.report(ValidityError::TransactionFeePayerInputIndexBounds)?
             }
+            Some(
+                Input::CoinSigned(CoinSigned { asset_id, .. })
+                | Input::CoinPredicate(CoinPredicate { asset_id, .. }),
+            ) if asset_id == base_asset_id => {}
+            Some(Input::MessageCoinSigned(_) | Input::MessageCoinPredicate(_)) => {}
+            Some(_) => {
+                violations.report(ValidityError::TransactionFeePayerInvalidInput)?
+            }
+        }
 
-            Ok(())
+        // The refund must go back to the owner of the fee payer input. An invalid
+        // fee payer input is already reported above.
+        let owner = tx
+            .fee_payer_input()
+            .and_then(|input| input.input_owner().or_else(|| input.recipient()));
+        match tx.fee_payer_change_output() {
+            None => {
+                violations.report(ValidityError::TransactionFeePayerChangeIndexBounds)?
+            }
+            Some(Output::Change { to, asset_id, .. })
+                if asset_id == base_asset_id
+                    && (owner.is_none() || owner == Some(to)) => {}
+            Some(_) => {
+                violations.report(ValidityError::TransactionFeePayerInvalidChange)?
+            }
+        }
+    }
+
+    let fee_payer_change = tx.fee_payer_change().map(usize::from);
+
+    // Check for duplicate change outputs, the change of the fee payer is separate
+    // from the change of the other inputs
+    let change_asset_ids = tx
+        .outputs()
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| Some(*index) != fee_payer_change)
+        .filter_map(|(index, output)| match output {
+            Output::Change { asset_id, .. } => Some((index, asset_id)),
+            _ => None,
+        })
+        .filter(|(_, asset_id)| {
+            tx.input_asset_ids_unique(base_asset_id)
+                .any(|input_asset_id| input_asset_id == *asset_id)
+        });
+
+    for (index, asset_id) in duplicates(change_asset_ids) {
+        violations.report(ValidityError::TransactionOutputChangeAssetIdDuplicated {
+            index,
+            asset_id: *asset_id,
         })?;
+    }
 
     // Check for duplicated input utxo id
-    let duplicated_utxo_id = tx
-        .inputs()
-        .iter()
-        .filter_map(|i| i.is_coin().then(|| i.utxo_id()).flatten());
+    let duplicated_utxo_id = tx.inputs().iter().enumerate().filter_map(|(index, i)| {
+        i.is_coin()
+            .then(|| i.utxo_id())
+            .flatten()
+            .map(|utxo_id| (index, utxo_id))
+    });
 
-    if let Some(utxo_id) = next_duplicate(duplicated_utxo_id).copied() {
-        return Err(ValidityError::DuplicateInputUtxoId { utxo_id });
+    for (index, utxo_id) in duplicates(duplicated_utxo_id) {
+        violations.report(ValidityError::DuplicateInputUtxoId {
+            index,
+            utxo_id: *utxo_id,
+        })?;
     }
 
     // Check for duplicated input contract id
-    let duplicated_contract_id = tx.inputs().iter().filter_map(Input::contract_id);
-
-    if let Some(contract_id) = next_duplicate(duplicated_contract_id).copied() {
-        return Err(ValidityError::DuplicateInputContractId { contract_id });
+    let duplicated_contract_id =
+        tx.inputs().iter().enumerate().filter_map(|(index, i)| {
+            i.contract_id().map(|contract_id| (index, contract_id))
+        });
+
+    for (index, contract_id) in duplicates(duplicated_contract_id) {
+        violations.report(ValidityError::DuplicateInputContractId {
+            index,
+            contract_id: *contract_id,
+        })?;
     }
 
     // Check for duplicated input message id
-    let duplicated_message_id = tx.inputs().iter().filter_map(Input::message_id);
-    if let Some(message_id) = next_duplicate(duplicated_message_id) {
-        return Err(ValidityError::DuplicateMessageInputId { message_id });
+    let duplicated_message_id =
+        tx.inputs().iter().enumerate().filter_map(|(index, i)| {
+            i.message_id().map(|message_id| (index, message_id))
+        });
+
+    for (index, message_id) in duplicates(duplicated_message_id) {
+        violations
+            .report(ValidityError::DuplicateMessageInputId { index, message_id })?;
     }
 
     // Validate the inputs without checking signature
@@ -434,28 +576,31 @@ where
         .iter()
         .enumerate()
         .try_for_each(|(index, input)| {
-            input.check_without_signature(
+            violations.check(input.check_without_signature(
                 index,
                 tx.outputs(),
                 tx.witnesses(),
                 predicate_params,
-            )
+            ))
         })?;
 
     tx.outputs()
         .iter()
         .enumerate()
         .try_for_each(|(index, output)| {
-            output.check(index, tx.inputs())?;
+            violations.check(output.check(index, tx.inputs()))?;
 
             if let Output::Change { asset_id, .. } = output {
                 if !tx
                     .input_asset_ids(base_asset_id)
                     .any(|input_asset_id| input_asset_id == asset_id)
                 {
-                    return Err(ValidityError::TransactionOutputChangeAssetIdNotFound(
-                        *asset_id,
-                    ));
+                    violations.report(
+                        ValidityError::TransactionOutputChangeAssetIdNotFound {
+                            index,
+                            asset_id: *asset_id,
+                        },
+                    )?;
                 }
             }
 
@@ -464,9 +609,12 @@ where
                     .input_asset_ids(base_asset_id)
                     .any(|input_asset_id| input_asset_id == asset_id)
                 {
-                    return Err(ValidityError::TransactionOutputCoinAssetIdNotFound(
-                        *asset_id,
-                    ));
+                    violations.report(
+                        ValidityError::TransactionOutputCoinAssetIdNotFound {
+                            index,
+                            asset_id: *asset_id,
+                        },
+                    )?;
                 }
             }
 
@@ -476,26 +624,72 @@ where
     Ok(())
 }
 
-// TODO https://github.com/FuelLabs/fuel-tx/issues/148
-pub(crate) fn next_duplicate<U>(iter: impl Iterator<Item = U>) -> Option<U>
-where
-    U: PartialEq + Ord + Copy + Hash,
-{
-    #[cfg(not(feature = "std"))]
-    {
-        iter.sorted()
-            .as_slice()
-            .windows(2)
-            .filter_map(|u| (u[0] == u[1]).then(|| u[0]))
-            .next()
+/// Receives the violations found by the validity checks.
+///
+/// By default, the first violation aborts the checks. In the collecting mode, the
+/// violations are recorded and the checks go on. The checks still abort on the errors
+/// that prevent the next rules from being checked, like a missing witness.
+#[derive(Debug, Default)]
+pub(crate) struct Violations {
+    collected: Option<Vec<ValidityError>>,
+}
+
+impl Violations {
+    /// Creates a sink recording all the violations.
+    pub(crate) fn collecting() -> Self {
+        Self {
+            collected: Some(Vec::new()),
+        }
     }
 
-    #[cfg(feature = "std")]
+    /// Reports a violation, returning it if the checks should abort.
+    pub(crate) fn report(&mut self, error: ValidityError) -> Result<(), ValidityError> {
+        match &mut self.collected {
+            Some(collected) => {
+                collected.push(error);
+                Ok(())
+            }
+            None => Err(error),
+        }
+    }
+
+    /// Reports the violation of `result`, if any.
+    pub(crate) fn check(
+        &mut self,
+        result: Result<(), ValidityError>,
+    ) -> Result<(), ValidityError> {
+        result.or_else(|error| self.report(error))
+    }
+
+    /// Runs `checks` in the collecting mode and returns all the violations.
+    pub(crate) fn collect<F>(checks: F) -> Result<(), Vec<ValidityError>>
+    where
+        F: FnOnce(&mut Self) -> Result<(), ValidityError>,
     {
-        iter.duplicates().next()
+        let mut violations = Self::collecting();
+        let result = checks(&mut violations);
+
+        let mut collected = violations.collected.unwrap_or_default();
+        collected.extend(result.err());
+
+        if collected.is_empty() {
+            Ok(())
+        } else {
+            Err(collected)
+        }
     }
 }
 
+// TODO https://github.com/FuelLabs/fuel-tx/issues/148
+/// The indexed values equal to the value of a previous item, with their index.
+pub(crate) fn duplicates<U>(iter: impl Iterator<Item = (usize, U)>) -> Vec<(usize, U)>
+where
+    U: Ord + Copy,
+{
+    let mut seen = BTreeSet::new();
+    iter.filter(|(_, u)| !seen.insert(*u)).collect()
+}
+
 #[cfg(feature = "typescript")]
 mod typescript {
     use crate::{
@@ -38,13 +38,22 @@ pub enum ValidityError {
     InputMessageDataLength {
         index: usize,
     },
+    #[display(fmt = "The input {index} spends the duplicated coin {utxo_id:?}")]
     DuplicateInputUtxoId {
+        /// The index of the input repeating the coin of a previous input.
+        index: usize,
         utxo_id: UtxoId,
     },
+    #[display(fmt = "The input {index} spends the duplicated message {message_id}")]
     DuplicateMessageInputId {
+        /// The index of the input repeating the message of a previous input.
+        index: usize,
         message_id: MessageId,
     },
+    #[display(fmt = "The input {index} uses the duplicated contract {contract_id}")]
     DuplicateInputContractId {
+        /// The index of the input repeating the contract of a previous input.
+        index: usize,
         contract_id: ContractId,
     },
     OutputContractInputIndex {
@@ -120,15 +129,44 @@ pub enum ValidityError {
     TransactionPoliciesAreInvalid,
     TransactionNoGasPricePolicy,
     TransactionMaturity,
+    /// The transaction's expiration height is below the current block height.
+    TransactionExpiration,
+    /// The `FeePayer` policy points at a non-existent input.
+    TransactionFeePayerInputIndexBounds,
+    /// The `FeePayer` policy points at an input that isn't a base asset coin or a
+    /// message coin.
+    TransactionFeePayerInvalidInput,
+    /// The `FeePayer` policy points at a non-existent change output.
+    TransactionFeePayerChangeIndexBounds,
+    /// The `FeePayer` policy points at an output that isn't a base asset change
+    /// output to the owner (or the recipient of the message) of the fee payer input.
+    TransactionFeePayerInvalidChange,
     TransactionMaxFeeNotSet,
     TransactionInputsMax,
     TransactionOutputsMax,
     TransactionWitnessesMax,
-    TransactionOutputChangeAssetIdDuplicated(AssetId),
-    TransactionOutputChangeAssetIdNotFound(AssetId),
+    /// The change output is not the first change output of its asset.
+    #[display(fmt = "The change output {index} duplicates the change of {asset_id}")]
+    TransactionOutputChangeAssetIdDuplicated {
+        /// The index of the change output.
+        index: usize,
+        asset_id: AssetId,
+    },
+    /// The change output is for an asset that doesn't exist in the inputs.
+    #[display(fmt = "The change output {index} has no input of {asset_id}")]
+    TransactionOutputChangeAssetIdNotFound {
+        /// The index of the change output.
+        index: usize,
+        asset_id: AssetId,
+    },
     /// This error happens when a transaction attempts to create a coin output for an
     /// asset type that doesn't exist in the coin inputs.
-    TransactionOutputCoinAssetIdNotFound(AssetId),
+    #[display(fmt = "The coin output {index} has no input of {asset_id}")]
+    TransactionOutputCoinAssetIdNotFound {
+        /// The index of the coin output.
+        index: usize,
+        asset_id: AssetId,
+    },
     /// The transaction doesn't provide enough input amount of the native chain asset to
     /// cover all potential execution fees
     #[display(
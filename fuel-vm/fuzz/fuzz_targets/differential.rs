@@ -0,0 +1,14 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use fuel_vm::differential::{
+    check_program,
+    Program,
+};
+
+fuzz_target!(|program: Program| {
+    if let Err(mismatch) = check_program(&program.0) {
+        panic!("{:?} in {:?}", mismatch, program);
+    }
+});
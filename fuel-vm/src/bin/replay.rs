@@ -0,0 +1,95 @@
+//! Replays a transaction against a snapshot of the chain state.
+//!
+//! ```text
+//! fuel-vm-replay <TX> <CONSENSUS_PARAMETERS> [STATE] [--gas-price <PRICE>]
+//! ```
+//!
+//! - `TX`: the canonical-encoded transaction, as raw bytes or hex.
+//! - `CONSENSUS_PARAMETERS`: JSON, or the `Upgrade` transaction encoding as raw bytes or
+//!   hex.
+//! - `STATE`: a [`StorageSnapshot`](fuel_vm::storage::snapshot::StorageSnapshot) as JSON,
+//!   or canonical-encoded as raw bytes or hex. Defaults to an empty state.
+
+use std::{
+    env,
+    fs,
+    process::ExitCode,
+};
+
+use fuel_vm::{
+    replay::{
+        decode_consensus_parameters,
+        decode_snapshot,
+        decode_transaction,
+        replay,
+        ReplayError,
+    },
+    storage::MemoryStorage,
+};
+
+const USAGE: &str =
+    "Usage: fuel-vm-replay <TX> <CONSENSUS_PARAMETERS> [STATE] [--gas-price <PRICE>]";
+
+fn main() -> ExitCode {
+    match run(env::args().skip(1).collect()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: Vec<String>) -> Result<(), String> {
+    let mut files = Vec::new();
+    let mut gas_price = 0;
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--gas-price" => {
+                gas_price = args
+                    .next()
+                    .and_then(|price| price.parse().ok())
+                    .ok_or_else(|| format!("Invalid gas price\n{USAGE}"))?;
+            }
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return Ok(())
+            }
+            _ => files.push(arg),
+        }
+    }
+
+    let read =
+        |path: &String| fs::read(path).map_err(|e| format!("Failed to read {path}: {e}"));
+
+    let (tx, params, state) = match files.as_slice() {
+        [tx, params] => (read(tx)?, read(params)?, None),
+        [tx, params, state] => (read(tx)?, read(params)?, Some(read(state)?)),
+        _ => return Err(USAGE.to_string()),
+    };
+
+    let tx = decode_transaction(&tx).map_err(|e| e.to_string())?;
+    let params = decode_consensus_parameters(&params).map_err(|e| e.to_string())?;
+    let state = match state {
+        Some(state) => decode_snapshot(&state).map_err(|e| e.to_string())?,
+        None => MemoryStorage::default()
+            .snapshot()
+            .map_err(|e| e.to_string())?,
+    };
+
+    let replay = replay(tx, &params, &state, gas_price).map_err(|e| match &e {
+        ReplayError::Execution { receipts, .. } if !receipts.is_empty() => {
+            let receipts = receipts
+                .iter()
+                .map(|receipt| format!("\n  {receipt:?}"))
+                .collect::<String>();
+            format!("{e}\n\nReceipts:{receipts}")
+        }
+        _ => e.to_string(),
+    })?;
+    print!("{replay}");
+
+    Ok(())
+}
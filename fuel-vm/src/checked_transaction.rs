@@ -9,6 +9,7 @@
 
 use fuel_tx::{
     Create,
+    FeeEstimateError,
     Mint,
     Script,
     Transaction,
@@ -285,6 +286,8 @@ pub enum CheckError {
         /// The max fee calculated from the gas price and gas used by the transaction.
         max_fee_from_gas_price: Word,
     },
+    /// The fee estimation failed.
+    FeeEstimate(FeeEstimateError),
 }
 
 /// Performs checks for a transaction
@@ -2057,6 +2060,70 @@ mod tests {
             .finalize()
     }
 
+    #[test]
+    fn estimate_fee_with_predicates__matches_the_estimated_signed_transaction() {
+        use crate::checked_transaction::builder::TransactionBuilderExt;
+        use fuel_tx::{
+            field::Inputs,
+            SignerType,
+        };
+
+        let rng = &mut StdRng::seed_from_u64(2322u64);
+        let gas_price = 10;
+        let params = ConsensusParameters::standard();
+        let predicate = vec![op::ret(1)].into_iter().collect::<Vec<u8>>();
+        let owner = Input::predicate_owner(&predicate);
+
+        let mut builder = TransactionBuilder::script(vec![], vec![]);
+        builder
+            .with_params(params.clone())
+            .add_input(Input::coin_predicate(
+                rng.gen(),
+                owner,
+                1000,
+                AssetId::default(),
+                rng.gen(),
+                0,
+                predicate,
+                vec![],
+            ))
+            .add_unsigned_coin_input(
+                SecretKey::random(rng),
+                rng.gen(),
+                1000,
+                AssetId::default(),
+                rng.gen(),
+            )
+            .add_witness(Witness::default());
+
+        let estimate = builder
+            .estimate_fee_with_predicates(
+                &[(1, SignerType::Secp256r1)],
+                gas_price,
+                MemoryInstance::new(),
+            )
+            .expect("Should estimate the fee");
+
+        let mut tx = builder.finalize();
+        tx.estimate_predicates(
+            &CheckPredicateParams::from(&params),
+            MemoryInstance::new(),
+        )
+        .expect("Should estimate predicates");
+        assert!(tx.inputs()[0].predicate_gas_used().unwrap() > 0);
+        tx.witnesses_mut()[1] = vec![0; SignerType::Secp256r1.witness_size()].into();
+
+        let fee = TransactionFee::checked_from_tx(
+            params.gas_costs(),
+            params.fee_params(),
+            &tx,
+            gas_price,
+        )
+        .unwrap();
+        assert_eq!(estimate.fee(), fee);
+        assert_eq!(estimate.size(), tx.size());
+    }
+
     // used when proptesting to avoid expensive crypto signatures
     fn predicate_tx(
         rng: &mut StdRng,
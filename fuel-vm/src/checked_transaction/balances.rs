@@ -1,5 +1,8 @@
 use fuel_tx::{
-    field,
+    field::{
+        self,
+        FeePayer,
+    },
     input::{
         coin::{
             CoinPredicate,
@@ -32,14 +35,34 @@ pub(crate) fn initial_free_balances<T>(
 where
     T: Chargeable + field::Inputs + field::Outputs,
 {
+    let fee_payer = tx.fee_payer().map(usize::from);
     let (mut non_retryable_balances, retryable_balance) =
-        add_up_input_balances(tx, base_asset_id).ok_or(ValidityError::BalanceOverflow)?;
+        add_up_input_balances(tx, base_asset_id, fee_payer)
+            .ok_or(ValidityError::BalanceOverflow)?;
 
     let max_fee = tx
         .policies()
         .get(PolicyType::MaxFee)
         .ok_or(ValidityError::TransactionMaxFeeNotSet)?;
-    deduct_max_fee_from_base_asset(&mut non_retryable_balances, base_asset_id, max_fee)?;
+    match tx.fee_payer_input() {
+        // The fee payer input covers the fee alone and isn't spendable by the
+        // transaction. Its remainder is refunded to its own change output.
+        Some(input) => {
+            let provided = input.amount().unwrap_or_default();
+            if provided < max_fee {
+                return Err(ValidityError::InsufficientFeeAmount {
+                    expected: max_fee,
+                    provided,
+                });
+            }
+            non_retryable_balances.entry(*base_asset_id).or_default();
+        }
+        None => deduct_max_fee_from_base_asset(
+            &mut non_retryable_balances,
+            base_asset_id,
+            max_fee,
+        )?,
+    }
 
     reduce_free_balances_by_coin_outputs(&mut non_retryable_balances, tx)?;
 
@@ -50,16 +73,23 @@ where
 }
 
 /// Returns None if any of the balances would overflow.
+///
+/// The `fee_payer` input, if any, is excluded from the balances.
 fn add_up_input_balances<T: field::Inputs>(
     transaction: &T,
     base_asset_id: &AssetId,
+    fee_payer: Option<usize>,
 ) -> Option<(BTreeMap<AssetId, Word>, Word)> {
     let mut non_retryable_balances = BTreeMap::<AssetId, Word>::new();
     // The sum of [`AssetId::Base`] from metadata messages.
     let mut retryable_balance: Word = 0;
 
     // Add up all the inputs for each asset ID
-    for input in transaction.inputs().iter() {
+    for (index, input) in transaction.inputs().iter().enumerate() {
+        if Some(index) == fee_payer {
+            continue;
+        }
+
         match input {
             // Sum coin inputs
             Input::CoinPredicate(CoinPredicate {
@@ -110,19 +140,19 @@ fn reduce_free_balances_by_coin_outputs(
     transaction: &impl field::Outputs,
 ) -> Result<(), ValidityError> {
     // reduce free balances by coin outputs
-    for (asset_id, amount) in
-        transaction
-            .outputs()
-            .iter()
-            .filter_map(|output| match output {
-                Output::Coin {
-                    asset_id, amount, ..
-                } => Some((asset_id, amount)),
-                _ => None,
-            })
-    {
+    for (index, asset_id, amount) in transaction.outputs().iter().enumerate().filter_map(
+        |(index, output)| match output {
+            Output::Coin {
+                asset_id, amount, ..
+            } => Some((index, asset_id, amount)),
+            _ => None,
+        },
+    ) {
         let balance = non_retryable_balances.get_mut(asset_id).ok_or(
-            ValidityError::TransactionOutputCoinAssetIdNotFound(*asset_id),
+            ValidityError::TransactionOutputCoinAssetIdNotFound {
+                index,
+                asset_id: *asset_id,
+            },
         )?;
         *balance = balance.checked_sub(*amount).ok_or(
             ValidityError::InsufficientInputAmount {
@@ -1,18 +1,26 @@
 //! Extension trait for [`fuel_tx::TransactionBuilder`]
 
 use super::{
+    CheckError,
+    CheckPredicateParams,
     Checked,
     IntoChecked,
 };
 use crate::{
     checked_transaction::CheckPredicates,
+    interpreter::Memory,
     prelude::*,
 };
 use fuel_tx::{
+    FeeEstimate,
     Finalizable,
+    SignerType,
     TransactionBuilder,
 };
-use fuel_types::BlockHeight;
+use fuel_types::{
+    BlockHeight,
+    Word,
+};
 
 /// Extension trait for [`fuel_tx::TransactionBuilder`] adding finalization methods
 pub trait TransactionBuilderExt<Tx>
@@ -25,6 +33,18 @@ where
     /// Finalize the builder into a [`Checked<Tx>`] of the correct type, with basic checks
     /// only
     fn finalize_checked_basic(&self, height: BlockHeight) -> Checked<Tx>;
+
+    /// Estimates the predicates of the transaction, then its size and fee bounds at
+    /// `gas_price` once it is signed by the builder's keys and the external `signers`.
+    ///
+    /// The predicates are estimated before the missing witnesses are provided, so
+    /// they must not depend on them.
+    fn estimate_fee_with_predicates(
+        &self,
+        signers: &[(u16, SignerType)],
+        gas_price: Word,
+        memory: impl Memory,
+    ) -> Result<FeeEstimate, CheckError>;
 }
 
 impl<Tx: ExecutableTransaction> TransactionBuilderExt<Tx> for TransactionBuilder<Tx>
@@ -43,4 +63,18 @@ where
             .into_checked_basic(height, self.get_params())
             .expect("failed to check tx")
     }
+
+    fn estimate_fee_with_predicates(
+        &self,
+        signers: &[(u16, SignerType)],
+        gas_price: Word,
+        memory: impl Memory,
+    ) -> Result<FeeEstimate, CheckError> {
+        let params = self.get_params();
+        let mut tx = self.finalize_without_signature();
+        tx.estimate_predicates(&CheckPredicateParams::from(params), memory)?;
+
+        tx.estimate_fee(params.gas_costs(), params.fee_params(), signers, gas_price)
+            .map_err(CheckError::FeeEstimate)
+    }
 }
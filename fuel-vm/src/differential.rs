@@ -0,0 +1,924 @@
+//! Differential testing of the interpreter against a reference model.
+//!
+//! [`ReferenceVm`] is a deliberately naive model of the ALU, memory and logging
+//! opcodes. It shares no code with the interpreter beyond the instruction and receipt
+//! types, so that refactoring an opcode can't silently change both sides at once.
+//!
+//! With the `arbitrary` feature, `check_program` runs a script through the full
+//! `Transactor` and through the model, and reports the first difference in the
+//! outcome, registers, memory or receipts, and [`Program`] implements
+//! `arbitrary::Arbitrary` to generate the scripts.
+
+use alloc::{
+    vec,
+    vec::Vec,
+};
+use core::ops::Range;
+
+use fuel_asm::{
+    Instruction,
+    Opcode,
+    PanicInstruction,
+    PanicReason,
+    RawInstruction,
+    RegId,
+};
+use fuel_tx::{
+    DependentCost,
+    GasCosts,
+    Receipt,
+};
+use fuel_types::{
+    ContractId,
+    Word,
+};
+
+use crate::{
+    consts::{
+        MEM_SIZE,
+        VM_MAX_RAM,
+        VM_REGISTER_COUNT,
+        WORD_SIZE,
+    },
+    interpreter::MemoryInstance,
+};
+
+#[cfg(all(feature = "arbitrary", feature = "test-helpers"))]
+mod check;
+
+#[cfg(all(feature = "arbitrary", feature = "test-helpers"))]
+pub use check::check_program;
+
+/// Gas limit of the scripts run by `check_program`.
+pub const SCRIPT_GAS_LIMIT: Word = 1_000_000;
+
+/// How a program terminated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The program executed `RET` with the given value.
+    Return(Word),
+    /// The program panicked.
+    Panic(PanicReason),
+}
+
+/// The first difference found between the interpreter and the reference model.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mismatch {
+    /// The program terminated differently.
+    Outcome {
+        /// Outcome of the interpreter.
+        interpreter: Outcome,
+        /// Outcome of the reference model.
+        reference: Outcome,
+    },
+    /// A register holds a different value.
+    Register {
+        /// Index of the register.
+        register: usize,
+        /// Value in the interpreter.
+        interpreter: Word,
+        /// Value in the reference model.
+        reference: Word,
+    },
+    /// The writable memory differs, starting at `address`.
+    Memory {
+        /// First differing address.
+        address: usize,
+    },
+    /// The receipts differ.
+    Receipts {
+        /// Receipts of the interpreter, without the final `ScriptResult`.
+        interpreter: Vec<Receipt>,
+        /// Receipts of the reference model.
+        reference: Vec<Receipt>,
+    },
+}
+
+/// Pure reference model of the ALU, memory and logging opcodes of a script.
+#[derive(Debug, Clone)]
+pub struct ReferenceVm {
+    gas_costs: GasCosts,
+    registers: [Word; VM_REGISTER_COUNT],
+    /// Memory from address zero up to the highest stack pointer seen so far.
+    stack: Vec<u8>,
+    /// Memory from `$hp` to the end of the address space.
+    heap: Vec<u8>,
+    receipts: Vec<Receipt>,
+}
+
+impl ReferenceVm {
+    /// Returns `true` if the model implements the given opcode.
+    pub const fn supports(opcode: Opcode) -> bool {
+        use Opcode::*;
+        matches!(
+            opcode,
+            ADD | ADDI
+                | AND
+                | ANDI
+                | DIV
+                | DIVI
+                | EQ
+                | EXP
+                | EXPI
+                | GT
+                | LT
+                | MLOG
+                | MOD
+                | MODI
+                | MOVE
+                | MOVI
+                | MROO
+                | MUL
+                | MULI
+                | NOOP
+                | NOT
+                | OR
+                | ORI
+                | SLL
+                | SLLI
+                | SRL
+                | SRLI
+                | SUB
+                | SUBI
+                | XOR
+                | XORI
+                | ALOC
+                | CFE
+                | CFEI
+                | CFS
+                | CFSI
+                | LB
+                | LW
+                | MCL
+                | MCLI
+                | MCP
+                | MCPI
+                | MEQ
+                | SB
+                | SW
+                | FLAG
+                | LOG
+                | RET
+        )
+    }
+
+    /// Start the model from a snapshot of the interpreter, taken before the first
+    /// instruction of the script is executed.
+    pub fn new(registers: &[Word], memory: &MemoryInstance, gas_costs: GasCosts) -> Self {
+        let mut snapshot = [0; VM_REGISTER_COUNT];
+        snapshot.copy_from_slice(registers);
+
+        let heap = live_heap(memory, snapshot[RegId::HP]).to_vec();
+
+        Self {
+            gas_costs,
+            registers: snapshot,
+            stack: memory.stack_raw().to_vec(),
+            heap,
+            receipts: Vec::new(),
+        }
+    }
+
+    /// Registers of the model.
+    pub fn registers(&self) -> &[Word] {
+        &self.registers
+    }
+
+    /// Memory from address zero up to the highest stack pointer seen so far.
+    pub fn stack(&self) -> &[u8] {
+        &self.stack
+    }
+
+    /// Memory from `$hp` to the end of the address space.
+    pub fn heap(&self) -> &[u8] {
+        &self.heap
+    }
+
+    /// Receipts produced so far.
+    pub fn receipts(&self) -> &[Receipt] {
+        &self.receipts
+    }
+
+    /// Execute `program`, which is expected to be loaded at `$is`, until it returns
+    /// or panics.
+    ///
+    /// # Panics
+    ///
+    /// If the program runs past its end, or contains an unsupported instruction.
+    pub fn run(&mut self, program: &[Instruction]) -> Outcome {
+        loop {
+            let offset = self.registers[RegId::PC]
+                .checked_sub(self.registers[RegId::IS])
+                .expect("$pc is never below $is without jumps");
+            #[allow(clippy::arithmetic_side_effects)] // Safety: the size is non-zero
+            let index = to_usize(offset / Instruction::SIZE as Word);
+            let instruction = *program
+                .get(index)
+                .expect("The program must terminate with RET");
+
+            match self.step(instruction) {
+                Ok(None) => {}
+                Ok(Some(value)) => return Outcome::Return(value),
+                Err(reason) => {
+                    self.receipts.push(Receipt::panic(
+                        ContractId::zeroed(),
+                        PanicInstruction::error(
+                            reason,
+                            RawInstruction::from(instruction),
+                        ),
+                        self.registers[RegId::PC],
+                        self.registers[RegId::IS],
+                    ));
+                    return Outcome::Panic(reason)
+                }
+            }
+        }
+    }
+
+    /// Execute a single instruction. Returns the value passed to `RET`, if the
+    /// instruction was one.
+    ///
+    /// # Panics
+    ///
+    /// If the instruction isn't [supported](Self::supports).
+    pub fn step(
+        &mut self,
+        instruction: Instruction,
+    ) -> Result<Option<Word>, PanicReason> {
+        let costs = self.gas_costs.clone();
+
+        match instruction {
+            Instruction::ADD(op) => {
+                self.charge(costs.add())?;
+                let (a, b, c) = op.unpack();
+                self.capture_overflow(a, self.r(b), self.r(c), u128::checked_add)?;
+            }
+            Instruction::ADDI(op) => {
+                self.charge(costs.addi())?;
+                let (a, b, imm) = op.unpack();
+                self.capture_overflow(a, self.r(b), imm.into(), u128::checked_add)?;
+            }
+            Instruction::SUB(op) => {
+                self.charge(costs.sub())?;
+                let (a, b, c) = op.unpack();
+                self.capture_overflow(a, self.r(b), self.r(c), wide_sub)?;
+            }
+            Instruction::SUBI(op) => {
+                self.charge(costs.subi())?;
+                let (a, b, imm) = op.unpack();
+                self.capture_overflow(a, self.r(b), imm.into(), wide_sub)?;
+            }
+            Instruction::MUL(op) => {
+                self.charge(costs.mul())?;
+                let (a, b, c) = op.unpack();
+                self.capture_overflow(a, self.r(b), self.r(c), u128::checked_mul)?;
+            }
+            Instruction::MULI(op) => {
+                self.charge(costs.muli())?;
+                let (a, b, imm) = op.unpack();
+                self.capture_overflow(a, self.r(b), imm.into(), u128::checked_mul)?;
+            }
+            Instruction::EXP(op) => {
+                self.charge(costs.exp())?;
+                let (a, b, c) = op.unpack();
+                self.boolean_overflow(a, pow(self.r(b), self.r(c)))?;
+            }
+            Instruction::EXPI(op) => {
+                self.charge(costs.expi())?;
+                let (a, b, imm) = op.unpack();
+                self.boolean_overflow(a, pow(self.r(b), imm.into()))?;
+            }
+            Instruction::DIV(op) => {
+                self.charge(costs.div())?;
+                let (a, b, c) = op.unpack();
+                self.fallible(a, self.r(b).checked_div(self.r(c)))?;
+            }
+            Instruction::DIVI(op) => {
+                self.charge(costs.divi())?;
+                let (a, b, imm) = op.unpack();
+                self.fallible(a, self.r(b).checked_div(imm.into()))?;
+            }
+            Instruction::MOD(op) => {
+                self.charge(costs.mod_op())?;
+                let (a, b, c) = op.unpack();
+                self.fallible(a, self.r(b).checked_rem(self.r(c)))?;
+            }
+            Instruction::MODI(op) => {
+                self.charge(costs.modi())?;
+                let (a, b, imm) = op.unpack();
+                self.fallible(a, self.r(b).checked_rem(imm.into()))?;
+            }
+            Instruction::MLOG(op) => {
+                self.charge(costs.mlog())?;
+                let (a, b, c) = op.unpack();
+                self.fallible(a, log(self.r(b), self.r(c)))?;
+            }
+            Instruction::MROO(op) => {
+                self.charge(costs.mroo())?;
+                let (a, b, c) = op.unpack();
+                self.fallible(a, root(self.r(b), self.r(c)))?;
+            }
+            Instruction::AND(op) => {
+                self.charge(costs.and())?;
+                let (a, b, c) = op.unpack();
+                self.set(a, self.r(b) & self.r(c))?;
+            }
+            Instruction::ANDI(op) => {
+                self.charge(costs.andi())?;
+                let (a, b, imm) = op.unpack();
+                self.set(a, self.r(b) & Word::from(imm))?;
+            }
+            Instruction::OR(op) => {
+                self.charge(costs.or())?;
+                let (a, b, c) = op.unpack();
+                self.set(a, self.r(b) | self.r(c))?;
+            }
+            Instruction::ORI(op) => {
+                self.charge(costs.ori())?;
+                let (a, b, imm) = op.unpack();
+                self.set(a, self.r(b) | Word::from(imm))?;
+            }
+            Instruction::XOR(op) => {
+                self.charge(costs.xor())?;
+                let (a, b, c) = op.unpack();
+                self.set(a, self.r(b) ^ self.r(c))?;
+            }
+            Instruction::XORI(op) => {
+                self.charge(costs.xori())?;
+                let (a, b, imm) = op.unpack();
+                self.set(a, self.r(b) ^ Word::from(imm))?;
+            }
+            Instruction::NOT(op) => {
+                self.charge(costs.not())?;
+                let (a, b) = op.unpack();
+                self.set(a, !self.r(b))?;
+            }
+            Instruction::EQ(op) => {
+                self.charge(costs.eq_())?;
+                let (a, b, c) = op.unpack();
+                self.set(a, Word::from(self.r(b) == self.r(c)))?;
+            }
+            Instruction::GT(op) => {
+                self.charge(costs.gt())?;
+                let (a, b, c) = op.unpack();
+                self.set(a, Word::from(self.r(b) > self.r(c)))?;
+            }
+            Instruction::LT(op) => {
+                self.charge(costs.lt())?;
+                let (a, b, c) = op.unpack();
+                self.set(a, Word::from(self.r(b) < self.r(c)))?;
+            }
+            Instruction::SLL(op) => {
+                self.charge(costs.sll())?;
+                let (a, b, c) = op.unpack();
+                self.set(a, shift(self.r(b), self.r(c), Word::checked_shl))?;
+            }
+            Instruction::SLLI(op) => {
+                self.charge(costs.slli())?;
+                let (a, b, imm) = op.unpack();
+                self.set(a, shift(self.r(b), imm.into(), Word::checked_shl))?;
+            }
+            Instruction::SRL(op) => {
+                self.charge(costs.srl())?;
+                let (a, b, c) = op.unpack();
+                self.set(a, shift(self.r(b), self.r(c), Word::checked_shr))?;
+            }
+            Instruction::SRLI(op) => {
+                self.charge(costs.srli())?;
+                let (a, b, imm) = op.unpack();
+                self.set(a, shift(self.r(b), imm.into(), Word::checked_shr))?;
+            }
+            Instruction::MOVE(op) => {
+                self.charge(costs.move_op())?;
+                let (a, b) = op.unpack();
+                self.set(a, self.r(b))?;
+            }
+            Instruction::MOVI(op) => {
+                self.charge(costs.movi())?;
+                let (a, imm) = op.unpack();
+                self.set(a, imm.into())?;
+            }
+            Instruction::NOOP(_) => {
+                self.charge(costs.noop())?;
+                self.registers[RegId::OF] = 0;
+                self.registers[RegId::ERR] = 0;
+                self.next()?;
+            }
+            Instruction::FLAG(op) => {
+                self.charge(costs.flag())?;
+                let value = self.r(op.unpack());
+                // Only the UNSAFEMATH and WRAPPING bits are defined.
+                if value & !0b11 != 0 {
+                    return Err(PanicReason::InvalidFlags)
+                }
+                self.registers[RegId::FLAG] = value;
+                self.next()?;
+            }
+            Instruction::LB(op) => {
+                self.charge(costs.lb())?;
+                let (a, b, imm) = op.unpack();
+                let dest = writable(a)?;
+                let range = self.range(self.r(b).saturating_add(imm.into()), 1)?;
+                self.registers[dest] = Word::from(self.read(range)[0]);
+                self.next()?;
+            }
+            Instruction::LW(op) => {
+                self.charge(costs.lw())?;
+                let (a, b, imm) = op.unpack();
+                let dest = writable(a)?;
+                let addr = self
+                    .r(b)
+                    .checked_add(words(imm.into()))
+                    .ok_or(PanicReason::MemoryOverflow)?;
+                let range = self.range(addr, WORD_SIZE as Word)?;
+                let mut bytes = [0; WORD_SIZE];
+                bytes.copy_from_slice(self.read(range));
+                self.registers[dest] = Word::from_be_bytes(bytes);
+                self.next()?;
+            }
+            Instruction::SB(op) => {
+                self.charge(costs.sb())?;
+                let (a, b, imm) = op.unpack();
+                let addr = self.r(a).saturating_add(imm.into());
+                let byte = self.r(b).to_le_bytes()[0];
+                self.write(addr, &[byte])?;
+                self.next()?;
+            }
+            Instruction::SW(op) => {
+                self.charge(costs.sw())?;
+                let (a, b, imm) = op.unpack();
+                let addr = self.r(a).saturating_add(words(imm.into()));
+                self.write(addr, &self.r(b).to_be_bytes())?;
+                self.next()?;
+            }
+            Instruction::MCP(op) => {
+                let (a, b, c) = op.unpack();
+                let len = self.r(c);
+                self.charge_dependent(costs.mcp(), len)?;
+                self.copy(self.r(a), self.r(b), len)?;
+            }
+            Instruction::MCPI(op) => {
+                let (a, b, imm) = op.unpack();
+                let len = Word::from(imm);
+                self.charge_dependent(costs.mcpi(), len)?;
+                self.copy(self.r(a), self.r(b), len)?;
+            }
+            Instruction::MCL(op) => {
+                let (a, b) = op.unpack();
+                let len = self.r(b);
+                self.charge_dependent(costs.mcl(), len)?;
+                self.clear(self.r(a), len)?;
+            }
+            Instruction::MCLI(op) => {
+                let (a, imm) = op.unpack();
+                let len = Word::from(imm);
+                self.charge_dependent(costs.mcli(), len)?;
+                self.clear(self.r(a), len)?;
+            }
+            Instruction::MEQ(op) => {
+                let (a, b, c, d) = op.unpack();
+                let len = self.r(d);
+                self.charge_dependent(costs.meq(), len)?;
+                // Unlike the ALU, `MEQ` leaves `$of` and `$err` untouched.
+                let dest = writable(a)?;
+                let lhs = self.range(self.r(b), len)?;
+                let rhs = self.range(self.r(c), len)?;
+                self.registers[dest] = Word::from(self.read(lhs) == self.read(rhs));
+                self.next()?;
+            }
+            Instruction::ALOC(op) => {
+                let amount = self.r(op.unpack());
+                self.charge_dependent(costs.aloc(), amount)?;
+                let hp = self.registers[RegId::HP]
+                    .checked_sub(amount)
+                    .ok_or(PanicReason::MemoryOverflow)?;
+                if hp < self.registers[RegId::SP] {
+                    return Err(PanicReason::MemoryGrowthOverlap)
+                }
+                let mut heap = vec![0; to_usize(amount)];
+                heap.extend_from_slice(&self.heap);
+                self.heap = heap;
+                self.stack.truncate(to_usize(hp));
+                self.registers[RegId::HP] = hp;
+                self.next()?;
+            }
+            Instruction::CFEI(op) => {
+                let amount = op.unpack().into();
+                self.charge_dependent(costs.cfei(), amount)?;
+                let sp = self.registers[RegId::SP]
+                    .checked_add(amount)
+                    .ok_or(PanicReason::MemoryOverflow)?;
+                self.move_stack_pointer(sp)?;
+            }
+            Instruction::CFE(op) => {
+                let amount = self.r(op.unpack());
+                self.charge_dependent(costs.cfe(), amount)?;
+                let sp = self.registers[RegId::SP]
+                    .checked_add(amount)
+                    .ok_or(PanicReason::MemoryOverflow)?;
+                self.move_stack_pointer(sp)?;
+            }
+            Instruction::CFSI(op) => {
+                self.charge(costs.cfsi())?;
+                let sp = self.registers[RegId::SP]
+                    .checked_sub(op.unpack().into())
+                    .ok_or(PanicReason::MemoryOverflow)?;
+                self.move_stack_pointer(sp)?;
+            }
+            Instruction::CFS(op) => {
+                // `CFS` is priced like `CFSI`.
+                self.charge(costs.cfsi())?;
+                let sp = self.registers[RegId::SP]
+                    .checked_sub(self.r(op.unpack()))
+                    .ok_or(PanicReason::MemoryOverflow)?;
+                self.move_stack_pointer(sp)?;
+            }
+            Instruction::LOG(op) => {
+                self.charge(costs.log())?;
+                let (a, b, c, d) = op.unpack();
+                self.receipts.push(Receipt::log(
+                    ContractId::zeroed(),
+                    self.r(a),
+                    self.r(b),
+                    self.r(c),
+                    self.r(d),
+                    self.registers[RegId::PC],
+                    self.registers[RegId::IS],
+                ));
+                self.next()?;
+            }
+            Instruction::RET(op) => {
+                self.charge(costs.ret())?;
+                let value = self.r(op.unpack());
+                self.receipts.push(Receipt::ret(
+                    ContractId::zeroed(),
+                    value,
+                    self.registers[RegId::PC],
+                    self.registers[RegId::IS],
+                ));
+                self.registers[RegId::RET] = value;
+                self.registers[RegId::RETL] = 0;
+                self.next()?;
+                return Ok(Some(value))
+            }
+            _ => panic!("{:?} isn't supported by the reference model", instruction),
+        }
+
+        Ok(None)
+    }
+
+    fn r(&self, reg: RegId) -> Word {
+        self.registers[reg]
+    }
+
+    fn next(&mut self) -> Result<(), PanicReason> {
+        self.registers[RegId::PC] = self.registers[RegId::PC]
+            .checked_add(Instruction::SIZE as Word)
+            .ok_or(PanicReason::MemoryOverflow)?;
+        Ok(())
+    }
+
+    fn charge(&mut self, gas: Word) -> Result<(), PanicReason> {
+        let cgas = self.registers[RegId::CGAS];
+        if gas > cgas {
+            self.registers[RegId::GGAS] = self.registers[RegId::GGAS]
+                .checked_sub(cgas)
+                .expect("$cgas never exceeds $ggas");
+            self.registers[RegId::CGAS] = 0;
+            return Err(PanicReason::OutOfGas)
+        }
+        self.registers[RegId::CGAS] = cgas.checked_sub(gas).expect("Checked above");
+        self.registers[RegId::GGAS] = self.registers[RegId::GGAS]
+            .checked_sub(gas)
+            .expect("$cgas never exceeds $ggas");
+        Ok(())
+    }
+
+    fn charge_dependent(
+        &mut self,
+        cost: DependentCost,
+        units: Word,
+    ) -> Result<(), PanicReason> {
+        let dependent = match cost {
+            DependentCost::LightOperation { units_per_gas, .. } => units
+                .checked_div(units_per_gas)
+                .expect("units_per_gas is never zero"),
+            DependentCost::HeavyOperation { gas_per_unit, .. } => {
+                units.saturating_mul(gas_per_unit)
+            }
+        };
+        self.charge(cost.base().saturating_add(dependent))
+    }
+
+    /// Arithmetic computed in 128 bits, with the high half going to `$of`.
+    fn capture_overflow(
+        &mut self,
+        a: RegId,
+        b: Word,
+        c: Word,
+        f: fn(u128, u128) -> Option<u128>,
+    ) -> Result<(), PanicReason> {
+        let dest = writable(a)?;
+        let result = f(b.into(), c.into()).expect("Can't overflow 128 bits");
+        let [low, high] = split(result);
+        if high != 0 && !self.flag(WRAPPING) {
+            return Err(PanicReason::ArithmeticOverflow)
+        }
+        self.registers[RegId::OF] = high;
+        self.registers[RegId::ERR] = 0;
+        self.registers[dest] = low;
+        self.next()
+    }
+
+    /// Arithmetic where an overflow sets `$of` to one and the result to zero.
+    fn boolean_overflow(
+        &mut self,
+        a: RegId,
+        result: Option<Word>,
+    ) -> Result<(), PanicReason> {
+        let dest = writable(a)?;
+        if result.is_none() && !self.flag(WRAPPING) {
+            return Err(PanicReason::ArithmeticOverflow)
+        }
+        self.registers[RegId::OF] = Word::from(result.is_none());
+        self.registers[RegId::ERR] = 0;
+        self.registers[dest] = result.unwrap_or(0);
+        self.next()
+    }
+
+    /// Arithmetic where an undefined result sets `$err` to one and the result to
+    /// zero.
+    fn fallible(&mut self, a: RegId, result: Option<Word>) -> Result<(), PanicReason> {
+        let dest = writable(a)?;
+        if result.is_none() && !self.flag(UNSAFEMATH) {
+            return Err(PanicReason::ArithmeticError)
+        }
+        self.registers[RegId::OF] = 0;
+        self.registers[RegId::ERR] = Word::from(result.is_none());
+        self.registers[dest] = result.unwrap_or(0);
+        self.next()
+    }
+
+    fn set(&mut self, a: RegId, value: Word) -> Result<(), PanicReason> {
+        let dest = writable(a)?;
+        self.registers[RegId::OF] = 0;
+        self.registers[RegId::ERR] = 0;
+        self.registers[dest] = value;
+        self.next()
+    }
+
+    fn flag(&self, bit: Word) -> bool {
+        self.registers[RegId::FLAG] & bit != 0
+    }
+
+    fn move_stack_pointer(&mut self, sp: Word) -> Result<(), PanicReason> {
+        if sp < self.registers[RegId::SSP] {
+            return Err(PanicReason::MemoryOverflow)
+        }
+        if sp > self.registers[RegId::HP] {
+            return Err(PanicReason::MemoryGrowthOverlap)
+        }
+        self.registers[RegId::SP] = sp;
+        let sp = to_usize(sp);
+        if sp > self.stack.len() {
+            self.stack.resize(sp, 0);
+        }
+        self.next()
+    }
+
+    /// Readable memory range, i.e. below the stack high-water mark or above `$hp`.
+    fn range(&self, addr: Word, len: Word) -> Result<Range<usize>, PanicReason> {
+        if addr > VM_MAX_RAM || len > VM_MAX_RAM {
+            return Err(PanicReason::MemoryOverflow)
+        }
+        let start = to_usize(addr);
+        let end = start.saturating_add(to_usize(len));
+        if end > MEM_SIZE {
+            return Err(PanicReason::MemoryOverflow)
+        }
+        if end <= self.stack.len() || start >= to_usize(self.registers[RegId::HP]) {
+            Ok(start..end)
+        } else {
+            Err(PanicReason::UninitalizedMemoryAccess)
+        }
+    }
+
+    /// Whether the script may write to the range: the live stack `$ssp..$sp`, or
+    /// the heap it allocated.
+    fn owns(&self, range: &Range<usize>) -> bool {
+        let (start, end) = (range.start as Word, range.end as Word);
+        let ssp = self.registers[RegId::SSP];
+        let sp = self.registers[RegId::SP];
+        let hp = self.registers[RegId::HP];
+
+        let stack = if start == end {
+            ssp <= start && start <= sp && (start == ssp || start < sp)
+        } else {
+            ssp <= start && end <= sp
+        };
+        let heap = if start == end && start == hp {
+            true
+        } else {
+            start >= hp && hp != VM_MAX_RAM
+        };
+        stack || heap
+    }
+
+    #[allow(clippy::arithmetic_side_effects)] // Safety: the range was verified
+    fn read(&self, range: Range<usize>) -> &[u8] {
+        if range.end <= self.stack.len() {
+            &self.stack[range]
+        } else {
+            let hp = to_usize(self.registers[RegId::HP]);
+            &self.heap[range.start - hp..range.end - hp]
+        }
+    }
+
+    #[allow(clippy::arithmetic_side_effects)] // Safety: the range was verified
+    fn bytes_mut(&mut self, range: Range<usize>) -> &mut [u8] {
+        if range.end <= self.stack.len() {
+            &mut self.stack[range]
+        } else {
+            let hp = to_usize(self.registers[RegId::HP]);
+            &mut self.heap[range.start - hp..range.end - hp]
+        }
+    }
+
+    /// Copy `len` bytes from `src` to `dst`, which must not overlap.
+    fn copy(&mut self, dst: Word, src: Word, len: Word) -> Result<(), PanicReason> {
+        let dst = self.range(dst, len)?;
+        let src = self.range(src, len)?;
+        if len > 0 && dst.start < src.end && src.start < dst.end {
+            return Err(PanicReason::MemoryWriteOverlap)
+        }
+        if !self.owns(&dst) {
+            return Err(PanicReason::MemoryOwnership)
+        }
+        let bytes = self.read(src).to_vec();
+        self.bytes_mut(dst).copy_from_slice(&bytes);
+        self.next()
+    }
+
+    fn clear(&mut self, addr: Word, len: Word) -> Result<(), PanicReason> {
+        let range = self.range(addr, len)?;
+        if !self.owns(&range) {
+            return Err(PanicReason::MemoryOwnership)
+        }
+        self.bytes_mut(range).fill(0);
+        self.next()
+    }
+
+    fn write(&mut self, addr: Word, bytes: &[u8]) -> Result<(), PanicReason> {
+        let range = self.range(addr, bytes.len() as Word)?;
+        if !self.owns(&range) {
+            return Err(PanicReason::MemoryOwnership)
+        }
+        self.bytes_mut(range).copy_from_slice(bytes);
+        Ok(())
+    }
+}
+
+const UNSAFEMATH: Word = 0b01;
+const WRAPPING: Word = 0b10;
+
+fn writable(reg: RegId) -> Result<RegId, PanicReason> {
+    if reg < RegId::WRITABLE {
+        Err(PanicReason::ReservedRegisterNotWritable)
+    } else {
+        Ok(reg)
+    }
+}
+
+fn to_usize(value: Word) -> usize {
+    usize::try_from(value).expect("Memory addresses fit into usize")
+}
+
+fn words(count: Word) -> Word {
+    count
+        .checked_mul(WORD_SIZE as Word)
+        .expect("Immediate word offsets can't overflow")
+}
+
+fn split(value: u128) -> [Word; 2] {
+    let bytes = value.to_le_bytes();
+    let mut low = [0; WORD_SIZE];
+    let mut high = [0; WORD_SIZE];
+    low.copy_from_slice(&bytes[..WORD_SIZE]);
+    high.copy_from_slice(&bytes[WORD_SIZE..]);
+    [Word::from_le_bytes(low), Word::from_le_bytes(high)]
+}
+
+/// Subtraction in 128 bits, wrapping like two's complement when `c > b`.
+fn wide_sub(b: u128, c: u128) -> Option<u128> {
+    Some(b.wrapping_sub(c))
+}
+
+fn shift(value: Word, by: Word, f: fn(Word, u32) -> Option<Word>) -> Word {
+    u32::try_from(by)
+        .ok()
+        .and_then(|by| f(value, by))
+        .unwrap_or(0)
+}
+
+/// `b` to the power of `c`, or `None` if the result doesn't fit into a word.
+fn pow(b: Word, c: Word) -> Option<Word> {
+    if c == 0 {
+        return Some(1)
+    }
+    if b <= 1 {
+        return Some(b)
+    }
+    let mut result: Word = 1;
+    for _ in 0..c {
+        result = result.checked_mul(b)?;
+    }
+    Some(result)
+}
+
+/// Integer logarithm of `b` in base `c`, rounded down.
+#[allow(clippy::arithmetic_side_effects)] // Safety: `c > 1`, and the result is below 64
+fn log(b: Word, c: Word) -> Option<Word> {
+    if b == 0 || c <= 1 {
+        return None
+    }
+    let mut result = 0;
+    let mut rest = b;
+    while rest >= c {
+        rest /= c;
+        result += 1;
+    }
+    Some(result)
+}
+
+/// Integer `c`th root of `b`, rounded down.
+#[allow(clippy::arithmetic_side_effects)] // Safety: `low < mid <= high <= b`
+fn root(b: Word, c: Word) -> Option<Word> {
+    if c == 0 {
+        return None
+    }
+    let at_most = |x: Word| match u32::try_from(c) {
+        Ok(c) => u128::from(x)
+            .checked_pow(c)
+            .is_some_and(|pow| pow <= u128::from(b)),
+        Err(_) => x <= 1,
+    };
+    // Binary search for the largest `x` with `x^c <= b`.
+    let (mut low, mut high) = (0, b);
+    while low < high {
+        let mid = low + (high - low).div_ceil(2);
+        if at_most(mid) {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+    Some(low)
+}
+
+/// A script body for differential testing, built from instructions supported by
+/// the [`ReferenceVm`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Program(pub Vec<Instruction>);
+
+#[cfg(all(feature = "arbitrary", feature = "test-helpers"))]
+impl<'a> arbitrary::Arbitrary<'a> for Program {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        const MAX_LEN: usize = 64;
+
+        let mut instructions = Vec::new();
+        while instructions.len() < MAX_LEN && !u.is_empty() {
+            let instruction: Instruction = u.arbitrary()?;
+            if ReferenceVm::supports(instruction.opcode()) {
+                instructions.push(focus_registers(instruction));
+            }
+        }
+        Ok(Self(instructions))
+    }
+}
+
+/// Fold the user registers onto `0x10..0x18`, so that values flow between
+/// instructions instead of being spread over 48 registers. Reserved registers are
+/// kept, as they hold the interesting addresses.
+#[cfg(all(feature = "arbitrary", feature = "test-helpers"))]
+#[allow(clippy::arithmetic_side_effects)] // Safety: at most four 6-bit fields
+fn focus_registers(instruction: Instruction) -> Instruction {
+    let mut raw = RawInstruction::from(instruction);
+    for (i, reg) in instruction.reg_ids().into_iter().enumerate() {
+        let Some(reg) = reg else { continue };
+        if reg >= RegId::WRITABLE {
+            let shift = 18 - 6 * i;
+            let focused = u32::from(RegId::WRITABLE.to_u8() + reg.to_u8() % 8);
+            raw = raw & !(0x3f << shift) | focused << shift;
+        }
+    }
+    Instruction::try_from(raw).expect("Only register fields were changed")
+}
+
+/// The heap of the interpreter from `hp` on; the raw buffer may extend below it.
+fn live_heap(memory: &MemoryInstance, hp: Word) -> &[u8] {
+    let heap = memory.heap_raw();
+    let start = MEM_SIZE
+        .checked_sub(heap.len())
+        .expect("Heap is never larger than the memory");
+    &heap[to_usize(hp).checked_sub(start).expect("$hp is in the heap")..]
+}
@@ -0,0 +1,178 @@
+use alloc::{
+    vec,
+    vec::Vec,
+};
+
+use fuel_asm::{
+    op,
+    Instruction,
+    RegId,
+};
+use fuel_crypto::SecretKey;
+use fuel_tx::{
+    ConsensusParameters,
+    Finalizable,
+    Receipt,
+    Script,
+    TransactionBuilder,
+};
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+
+use super::{
+    live_heap,
+    to_usize,
+    Mismatch,
+    Outcome,
+    ReferenceVm,
+    SCRIPT_GAS_LIMIT,
+};
+use crate::{
+    checked_transaction::{
+        Checked,
+        IntoChecked,
+    },
+    interpreter::{
+        Interpreter,
+        InterpreterParams,
+        MemoryInstance,
+    },
+    state::{
+        Breakpoint,
+        ProgramState,
+    },
+    storage::MemoryStorage,
+    transactor::Transactor,
+};
+
+/// Run `program`, followed by `ret $one`, as a script through the [`Transactor`]
+/// and the [`ReferenceVm`], and compare the results.
+///
+/// # Panics
+///
+/// If the program contains an instruction the reference model doesn't
+/// [support](ReferenceVm::supports).
+pub fn check_program(program: &[Instruction]) -> Result<(), Mismatch> {
+    let mut program = program.to_vec();
+    program.push(op::ret(RegId::ONE));
+
+    let params = ConsensusParameters::standard();
+    let interpreter_params = InterpreterParams::new(0, &params);
+    let tx = script(&program, &params);
+
+    // Capture the state right before the first instruction of the script.
+    let mut vm = Interpreter::<_, _, Script>::with_storage(
+        MemoryInstance::new(),
+        MemoryStorage::default(),
+        interpreter_params.clone(),
+    );
+    vm.set_breakpoint(Breakpoint::script(0));
+    let ready = tx
+        .clone()
+        .into_ready(0, params.gas_costs(), params.fee_params())
+        .expect("The script is valid");
+    let state = vm
+        .transact(ready)
+        .map(ProgramState::from)
+        .expect("The script is valid");
+    assert!(state.is_debug(), "The breakpoint must be hit");
+    let mut reference =
+        ReferenceVm::new(vm.registers(), vm.memory(), params.gas_costs().clone());
+    let expected = reference.run(&program);
+
+    let mut transactor = Transactor::<_, _, Script>::new(
+        MemoryInstance::new(),
+        MemoryStorage::default(),
+        interpreter_params,
+    );
+    transactor.transact(tx);
+    let interpreter = transactor.interpreter();
+    let receipts = interpreter
+        .receipts()
+        .iter()
+        .filter(|receipt| !matches!(receipt, Receipt::ScriptResult { .. }))
+        .cloned()
+        .collect::<Vec<_>>();
+    let actual = match receipts.last() {
+        Some(Receipt::Return { val, .. }) => Outcome::Return(*val),
+        Some(Receipt::Panic { reason, .. }) => Outcome::Panic(*reason.reason()),
+        _ => panic!("The script must end with a return or a panic"),
+    };
+
+    if actual != expected {
+        return Err(Mismatch::Outcome {
+            interpreter: actual,
+            reference: expected,
+        })
+    }
+
+    for (register, (&interpreter, &reference)) in interpreter
+        .registers()
+        .iter()
+        .zip(reference.registers())
+        .enumerate()
+    {
+        if interpreter != reference {
+            return Err(Mismatch::Register {
+                register,
+                interpreter,
+                reference,
+            })
+        }
+    }
+
+    // Only `$ssp..` can be written by a script; the transaction itself is updated
+    // by the interpreter after execution.
+    let ssp = to_usize(reference.registers()[RegId::SSP]);
+    let hp = reference.registers()[RegId::HP];
+    let memory = interpreter.memory();
+    if let Some(offset) =
+        first_difference(&memory.stack_raw()[ssp..], &reference.stack()[ssp..])
+    {
+        return Err(Mismatch::Memory {
+            address: ssp.saturating_add(offset),
+        })
+    }
+    if let Some(offset) = first_difference(live_heap(memory, hp), reference.heap()) {
+        return Err(Mismatch::Memory {
+            address: to_usize(hp).saturating_add(offset),
+        })
+    }
+
+    if receipts != reference.receipts() {
+        return Err(Mismatch::Receipts {
+            interpreter: receipts,
+            reference: reference.receipts().to_vec(),
+        })
+    }
+
+    Ok(())
+}
+
+fn script(program: &[Instruction], params: &ConsensusParameters) -> Checked<Script> {
+    let mut rng = StdRng::seed_from_u64(2322);
+    let max_fee = 1_000;
+    TransactionBuilder::script(program.iter().copied().collect(), vec![])
+        .max_fee_limit(max_fee)
+        .script_gas_limit(SCRIPT_GAS_LIMIT)
+        .add_unsigned_coin_input(
+            SecretKey::random(&mut rng),
+            rng.gen(),
+            max_fee,
+            *params.base_asset_id(),
+            Default::default(),
+        )
+        .finalize()
+        .into_checked(Default::default(), params)
+        .expect("The script is valid")
+}
+
+fn first_difference(a: &[u8], b: &[u8]) -> Option<usize> {
+    a.iter()
+        .zip(b)
+        .position(|(a, b)| a != b)
+        .or_else(|| (a.len() != b.len()).then(|| a.len().min(b.len())))
+}
@@ -23,13 +23,18 @@ use fuel_asm::{
     PanicReason,
 };
 use fuel_tx::{
-    field,
+    field::{
+        self,
+        FeePayer,
+        MaxFeeLimit,
+    },
     Blob,
     Chargeable,
     Create,
     Executable,
     FeeParameters,
     GasCosts,
+    Input,
     Output,
     PrepareSign,
     Receipt,
@@ -489,51 +494,79 @@ pub trait ExecutableTransaction:
             .refund_fee(gas_costs, fee_params, used_gas, gas_price)
             .ok_or(ValidityError::GasCostsCoinsOverflow)?;
 
-        self.outputs_mut().iter_mut().try_for_each(|o| match o {
-            // If revert, set base asset to initial balance and refund unused gas
-            //
-            // Note: the initial balance deducts the gas limit from base asset
-            Output::Change {
-                asset_id, amount, ..
-            } if revert && asset_id == base_asset_id => initial_balances.non_retryable
-                [base_asset_id]
-                .checked_add(gas_refund)
-                .map(|v| *amount = v)
-                .ok_or(ValidityError::BalanceOverflow),
-
-            // If revert, reset any non-base asset to its initial balance
-            Output::Change {
-                asset_id, amount, ..
-            } if revert => {
-                *amount = initial_balances.non_retryable[asset_id];
-                Ok(())
-            }
-
-            // The change for the base asset will be the available balance + unused gas
-            Output::Change {
-                asset_id, amount, ..
-            } if asset_id == base_asset_id => balances[asset_id]
-                .checked_add(gas_refund)
-                .map(|v| *amount = v)
-                .ok_or(ValidityError::BalanceOverflow),
-
-            // Set changes to the remainder provided balances
-            Output::Change {
-                asset_id, amount, ..
-            } => {
-                *amount = balances[asset_id];
-                Ok(())
+        // If the fee is sponsored, the remainder of the fee payer input and the unused
+        // gas go to the change output designated by the policy instead of the base
+        // asset change.
+        let fee_payer_change = self.fee_payer_change().map(usize::from);
+        let (gas_refund, fee_payer_refund) = match fee_payer_change {
+            Some(_) => {
+                let fee_payer_refund = self
+                    .fee_payer_input()
+                    .and_then(Input::amount)
+                    .unwrap_or_default()
+                    .checked_sub(self.max_fee_limit())
+                    .and_then(|remainder| remainder.checked_add(gas_refund))
+                    .ok_or(ValidityError::BalanceOverflow)?;
+                (0, fee_payer_refund)
             }
+            None => (gas_refund, 0),
+        };
 
-            // If revert, zeroes all variable output values
-            Output::Variable { amount, .. } if revert => {
-                *amount = 0;
-                Ok(())
-            }
-
-            // Other outputs are unaffected
-            _ => Ok(()),
-        })
+        self.outputs_mut()
+            .iter_mut()
+            .enumerate()
+            .try_for_each(|(index, o)| match o {
+                // The change of the fee payer doesn't depend on the execution
+                Output::Change { amount, .. } if fee_payer_change == Some(index) => {
+                    *amount = fee_payer_refund;
+                    Ok(())
+                }
+
+                // If revert, set base asset to initial balance and refund unused gas
+                //
+                // Note: the initial balance deducts the gas limit from base asset
+                Output::Change {
+                    asset_id, amount, ..
+                } if revert && asset_id == base_asset_id => initial_balances
+                    .non_retryable[base_asset_id]
+                    .checked_add(gas_refund)
+                    .map(|v| *amount = v)
+                    .ok_or(ValidityError::BalanceOverflow),
+
+                // If revert, reset any non-base asset to its initial balance
+                Output::Change {
+                    asset_id, amount, ..
+                } if revert => {
+                    *amount = initial_balances.non_retryable[asset_id];
+                    Ok(())
+                }
+
+                // The change for the base asset will be the available balance + unused
+                // gas
+                Output::Change {
+                    asset_id, amount, ..
+                } if asset_id == base_asset_id => balances[asset_id]
+                    .checked_add(gas_refund)
+                    .map(|v| *amount = v)
+                    .ok_or(ValidityError::BalanceOverflow),
+
+                // Set changes to the remainder provided balances
+                Output::Change {
+                    asset_id, amount, ..
+                } => {
+                    *amount = balances[asset_id];
+                    Ok(())
+                }
+
+                // If revert, zeroes all variable output values
+                Output::Variable { amount, .. } if revert => {
+                    *amount = 0;
+                    Ok(())
+                }
+
+                // Other outputs are unaffected
+                _ => Ok(()),
+            })
     }
 }
 
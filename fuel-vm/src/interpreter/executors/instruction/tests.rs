@@ -3,3 +3,4 @@ use super::*;
 
 mod math_operations;
 mod reserved_registers;
+mod storage_access;
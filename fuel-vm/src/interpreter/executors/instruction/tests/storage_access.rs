@@ -0,0 +1,54 @@
+use crate::{
+    consts::VM_MAX_RAM,
+    context::Context,
+    fuel_asm::{
+        op,
+        semantics::StorageAccess,
+        Instruction,
+        RegId,
+    },
+    interpreter::InterpreterParams,
+    prelude::*,
+    storage::PredicateStorage,
+};
+
+/// Executes the `instruction` on a storage failing on every access, and returns whether
+/// the execution failed on the storage.
+fn accesses_storage(instruction: Instruction) -> bool {
+    let mut vm = Interpreter::<_, _, Script>::with_storage(
+        MemoryInstance::new(),
+        PredicateStorage,
+        InterpreterParams::default(),
+    );
+    vm.context = Context::Script {
+        block_height: Default::default(),
+    };
+    let buffer = VM_MAX_RAM - 32;
+    let registers = vm.registers_mut();
+    registers[RegId::CGAS] = 1_000_000;
+    registers[RegId::GGAS] = 1_000_000;
+    registers[RegId::HP] = buffer;
+    registers[0x10] = buffer;
+    registers[0x11] = 0;
+
+    matches!(
+        vm.instruction(instruction),
+        Err(InterpreterError::Storage(_))
+    )
+}
+
+#[test]
+fn block_opcodes_declare_the_storage_access_of_the_interpreter() {
+    let instructions = [
+        op::bhei(0x12),
+        op::bhsh(0x10, 0x11),
+        op::cb(0x10),
+        op::time(0x12, 0x11),
+    ];
+
+    for instruction in instructions {
+        let opcode = instruction.opcode();
+        let declared = opcode.semantics().storage != StorageAccess::None;
+        assert_eq!(declared, accesses_storage(instruction), "{opcode:?}");
+    }
+}
@@ -167,7 +167,10 @@ mod tests {
         interpreter::receipts::ReceiptsCtx,
     };
     use core::iter;
-    use fuel_tx::Receipt;
+    use fuel_tx::{
+        Receipt,
+        ReceiptProof,
+    };
     use fuel_types::canonical::Serialize;
 
     use alloc::vec::Vec;
@@ -223,4 +226,21 @@ mod tests {
         let expected_root = ephemeral_merkle_root(leaves);
         assert_eq!(root, expected_root)
     }
+
+    #[test]
+    fn root_is_verifiable_with_receipt_proofs() {
+        let mut ctx = ReceiptsCtx::default();
+        for i in 0..7u8 {
+            ctx.push(Receipt::log(Default::default(), i.into(), 0, 0, 0, 0, 0))
+                .expect("context not full");
+        }
+
+        let root = ctx.root();
+        assert_eq!(root, Receipt::receipts_root(ctx.as_ref()));
+
+        for (index, receipt) in ctx.as_ref().iter().enumerate() {
+            let proof = ReceiptProof::new(ctx.as_ref(), index).expect("index in bounds");
+            assert!(proof.verify(&root, receipt));
+        }
+    }
 }
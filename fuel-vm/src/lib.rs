@@ -19,6 +19,8 @@ pub extern crate alloc;
 extern crate core;
 #[cfg(feature = "std")]
 extern crate libm as _; // Not needed with stdlib
+#[cfg(all(feature = "arbitrary", not(feature = "test-helpers")))]
+use arbitrary as _; // Only `differential` uses it
 #[cfg(test)]
 use criterion as _;
 
@@ -30,12 +32,16 @@ pub mod consts;
 pub mod context;
 mod convert;
 pub mod crypto;
+#[cfg(feature = "test-helpers")]
+pub mod differential;
 pub mod error;
 pub mod interpreter;
 #[cfg(feature = "test-helpers")]
 pub mod memory_client;
 pub mod pool;
 pub mod predicate;
+#[cfg(feature = "replay")]
+pub mod replay;
 pub mod state;
 pub mod storage;
 pub mod transactor;
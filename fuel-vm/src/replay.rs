@@ -0,0 +1,444 @@
+//! Offline transaction replay.
+//!
+//! Re-executes a transaction against a [`StorageSnapshot`] of the on-chain state it
+//! touches and reports the receipts, the gas usage per instruction and the resulting
+//! state changes. This is the library side of the `fuel-vm-replay` binary.
+//!
+//! The state changes cover the contracts only: coins and messages are not part of the
+//! VM storage, the coins spent and created by the transaction are its inputs and
+//! outputs.
+//!
+//! Signatures and predicates are not re-verified: the transaction is expected to
+//! come from a block where it was already accepted, and replaying a modified
+//! transaction is a common debugging step.
+
+use alloc::{
+    collections::{
+        BTreeMap,
+        BTreeSet,
+    },
+    string::{
+        String,
+        ToString,
+    },
+    vec::Vec,
+};
+use core::{
+    convert::Infallible,
+    fmt,
+};
+
+use fuel_tx::{
+    ConsensusParameters,
+    Receipt,
+    Transaction,
+    UniqueIdentifier,
+};
+use fuel_types::{
+    canonical::Deserialize,
+    AssetId,
+    Bytes32,
+    ContractId,
+    Word,
+};
+
+use crate::{
+    backtrace::Backtrace,
+    checked_transaction::{
+        CheckError,
+        IntoChecked,
+    },
+    error::InterpreterError,
+    interpreter::{
+        InterpreterParams,
+        MemoryInstance,
+    },
+    profiler::GasProfilingData,
+    storage::{
+        snapshot::StorageSnapshot,
+        ContractsAssetKey,
+        ContractsStateKey,
+        InterpreterStorage,
+        MemoryStorage,
+    },
+    transactor::Transactor,
+};
+
+/// The failure of a replay.
+#[derive(Debug, derive_more::Display)]
+pub enum ReplayError {
+    /// One of the inputs couldn't be decoded.
+    #[display(fmt = "Failed to decode the {what}: {reason}")]
+    Decode {
+        /// The input that failed to decode.
+        what: &'static str,
+        /// The decoding error.
+        reason: String,
+    },
+    /// The transaction is not valid in the snapshot state.
+    #[display(fmt = "Failed to check the transaction: {_0:?}")]
+    Check(CheckError),
+    /// The interpreter failed to execute the transaction.
+    #[display(fmt = "{reason}")]
+    Execution {
+        /// The interpreter error.
+        reason: String,
+        /// Receipts produced before the failure.
+        receipts: Vec<Receipt>,
+    },
+    /// `Mint` transactions are produced by the block producer and can't be executed.
+    #[display(fmt = "Mint transactions can't be replayed")]
+    UnsupportedTransaction,
+}
+
+impl From<CheckError> for ReplayError {
+    fn from(e: CheckError) -> Self {
+        Self::Check(e)
+    }
+}
+
+impl From<&InterpreterError<Infallible>> for ReplayError {
+    fn from(e: &InterpreterError<Infallible>) -> Self {
+        Self::Execution {
+            reason: e.to_string(),
+            receipts: Vec::new(),
+        }
+    }
+}
+
+impl From<InterpreterError<Infallible>> for ReplayError {
+    fn from(e: InterpreterError<Infallible>) -> Self {
+        (&e).into()
+    }
+}
+
+/// Decodes a canonical-encoded transaction, given as raw bytes or as hex.
+pub fn decode_transaction(input: &[u8]) -> Result<Transaction, ReplayError> {
+    const WHAT: &str = "transaction";
+
+    let bytes = decode_hex(WHAT, input)?;
+    Transaction::from_bytes(&bytes).map_err(|e| decode_error(WHAT, e))
+}
+
+/// Decodes consensus parameters given as JSON, or as hex or raw bytes using the same
+/// encoding as `Upgrade` transactions.
+pub fn decode_consensus_parameters(
+    input: &[u8],
+) -> Result<ConsensusParameters, ReplayError> {
+    const WHAT: &str = "consensus parameters";
+
+    if is_json(input) {
+        return serde_json::from_slice(input).map_err(|e| decode_error(WHAT, e))
+    }
+
+    let bytes = decode_hex(WHAT, input)?;
+    postcard::from_bytes(&bytes).map_err(|e| decode_error(WHAT, e))
+}
+
+/// Decodes a [`StorageSnapshot`] given as JSON, or as hex or raw canonical bytes.
+pub fn decode_snapshot(input: &[u8]) -> Result<StorageSnapshot, ReplayError> {
+    const WHAT: &str = "storage snapshot";
+
+    if is_json(input) {
+        return serde_json::from_slice(input).map_err(|e| decode_error(WHAT, e))
+    }
+
+    let bytes = decode_hex(WHAT, input)?;
+    StorageSnapshot::from_bytes(&bytes).map_err(|e| decode_error(WHAT, e))
+}
+
+fn decode_error(what: &'static str, reason: impl fmt::Debug) -> ReplayError {
+    ReplayError::Decode {
+        what,
+        reason: alloc::format!("{reason:?}"),
+    }
+}
+
+fn is_json(input: &[u8]) -> bool {
+    input.iter().find(|b| !b.is_ascii_whitespace()) == Some(&b'{')
+}
+
+/// Inputs made only of hex digits are decoded, anything else is taken as raw bytes.
+fn decode_hex(what: &'static str, input: &[u8]) -> Result<Vec<u8>, ReplayError> {
+    let text = core::str::from_utf8(input)
+        .map(str::trim)
+        .unwrap_or_default();
+    let digits = text.strip_prefix("0x").unwrap_or(text);
+
+    if !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+        hex::decode(digits).map_err(|e| decode_error(what, e))
+    } else {
+        Ok(input.to_vec())
+    }
+}
+
+/// A single difference between the state before and after the replay.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StateChange {
+    /// A contract was deployed.
+    Deployed {
+        /// Id of the new contract.
+        contract: ContractId,
+        /// Size of the bytecode.
+        size: usize,
+    },
+    /// A storage slot was written or cleared.
+    Slot {
+        /// Contract owning the slot.
+        contract: ContractId,
+        /// Storage key of the slot.
+        key: Bytes32,
+        /// Value before the replay, if the slot was set.
+        before: Option<Vec<u8>>,
+        /// Value after the replay, if the slot is set.
+        after: Option<Vec<u8>>,
+    },
+    /// A contract balance changed.
+    Balance {
+        /// Contract owning the asset.
+        contract: ContractId,
+        /// The asset.
+        asset_id: AssetId,
+        /// Balance before the replay.
+        before: Word,
+        /// Balance after the replay.
+        after: Word,
+    },
+}
+
+impl fmt::Display for StateChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Deployed { contract, size } => {
+                write!(f, "contract {contract:#x} deployed ({size} bytes)")
+            }
+            Self::Slot {
+                contract,
+                key,
+                before,
+                after,
+            } => {
+                let hex = |v: &Option<Vec<u8>>| {
+                    v.as_ref().map_or_else(|| "unset".to_string(), hex::encode)
+                };
+                write!(
+                    f,
+                    "contract {contract:#x} slot {key:#x}: {} -> {}",
+                    hex(before),
+                    hex(after)
+                )
+            }
+            Self::Balance {
+                contract,
+                asset_id,
+                before,
+                after,
+            } => write!(
+                f,
+                "contract {contract:#x} asset {asset_id:#x}: {before} -> {after}",
+            ),
+        }
+    }
+}
+
+/// Contract state of a storage, ordered so that two states can be compared.
+#[derive(Debug, Default)]
+struct ContractsView {
+    code: BTreeMap<ContractId, usize>,
+    state: BTreeMap<ContractsStateKey, Vec<u8>>,
+    balances: BTreeMap<ContractsAssetKey, Word>,
+}
+
+impl ContractsView {
+    fn new(storage: &MemoryStorage) -> Self {
+        Self {
+            code: storage
+                .all_contracts()
+                .map(|(id, code)| (*id, code.as_ref().len()))
+                .collect(),
+            state: storage
+                .all_contract_state()
+                .map(|(key, value)| (*key, value.as_ref().to_vec()))
+                .collect(),
+            // A missing balance is the same as a zero balance.
+            balances: storage
+                .all_contract_balances()
+                .filter(|(_, amount)| **amount != 0)
+                .map(|(key, amount)| (*key, *amount))
+                .collect(),
+        }
+    }
+
+    fn diff(&self, after: &Self) -> Vec<StateChange> {
+        let deployed =
+            changes(&self.code, &after.code).filter_map(|(contract, _, size)| {
+                Some(StateChange::Deployed {
+                    contract,
+                    size: size?,
+                })
+            });
+        let slots = changes(&self.state, &after.state).map(|(key, before, after)| {
+            StateChange::Slot {
+                contract: *key.contract_id(),
+                key: *key.state_key(),
+                before,
+                after,
+            }
+        });
+        let balances =
+            changes(&self.balances, &after.balances).map(|(key, before, after)| {
+                StateChange::Balance {
+                    contract: *key.contract_id(),
+                    asset_id: *key.asset_id(),
+                    before: before.unwrap_or_default(),
+                    after: after.unwrap_or_default(),
+                }
+            });
+
+        deployed.chain(slots).chain(balances).collect()
+    }
+}
+
+/// Entries that differ between the two maps, in key order.
+fn changes<'a, K, V>(
+    before: &'a BTreeMap<K, V>,
+    after: &'a BTreeMap<K, V>,
+) -> impl Iterator<Item = (K, Option<V>, Option<V>)> + 'a
+where
+    K: Ord + Copy,
+    V: PartialEq + Clone,
+{
+    let keys: BTreeSet<K> = before.keys().chain(after.keys()).copied().collect();
+
+    keys.into_iter().filter_map(|key| {
+        let (b, a) = (before.get(&key), after.get(&key));
+        (b != a).then(|| (key, b.cloned(), a.cloned()))
+    })
+}
+
+/// The outcome of a replayed transaction.
+#[derive(Debug)]
+pub struct Replay {
+    /// Id of the replayed transaction.
+    pub tx_id: Bytes32,
+    /// Receipts produced by the execution. Empty for transactions without a script.
+    pub receipts: Vec<Receipt>,
+    /// Gas used by the script, as reported in its `ScriptResult` receipt.
+    pub gas_used: Option<Word>,
+    /// State of the VM when the script reverted.
+    pub backtrace: Option<Backtrace>,
+    /// Gas used per instruction location.
+    pub gas_profile: GasProfilingData,
+    /// Differences between the contracts of the snapshot and the contracts after the
+    /// execution.
+    pub state_diff: Vec<StateChange>,
+}
+
+impl fmt::Display for Replay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Transaction {:#x}", self.tx_id)?;
+
+        writeln!(f, "\nReceipts:")?;
+        for receipt in &self.receipts {
+            writeln!(f, "  {receipt:?}")?;
+        }
+
+        if let Some(gas_used) = self.gas_used {
+            writeln!(f, "\nGas used: {gas_used}")?;
+        }
+
+        writeln!(f, "\nGas profile:")?;
+        write!(f, "{}", self.gas_profile)?;
+
+        if let Some(backtrace) = &self.backtrace {
+            let registers = backtrace.registers();
+            writeln!(f, "\nBacktrace:")?;
+            writeln!(f, "  result: {:?}", backtrace.result())?;
+            writeln!(f, "  contract: {:#x}", backtrace.contract())?;
+            writeln!(
+                f,
+                "  $pc: {:#x}, $is: {:#x}",
+                registers[fuel_asm::RegId::PC],
+                registers[fuel_asm::RegId::IS]
+            )?;
+            for frame in backtrace.call_stack().iter().rev() {
+                writeln!(f, "  called {:#x}", frame.to())?;
+            }
+        }
+
+        writeln!(f, "\nState diff:")?;
+        for change in &self.state_diff {
+            writeln!(f, "  {change}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Executes `tx` against `snapshot` with the given consensus parameters and gas price.
+///
+/// If the interpreter fails to execute a script, the receipts produced before the
+/// failure are returned with the error.
+pub fn replay(
+    tx: Transaction,
+    params: &ConsensusParameters,
+    snapshot: &StorageSnapshot,
+    gas_price: Word,
+) -> Result<Replay, ReplayError> {
+    let storage = MemoryStorage::from_snapshot(snapshot.clone());
+    let before = ContractsView::new(&storage);
+    let tx_id = tx.id(&params.chain_id());
+    let height = storage.block_height().expect("Infallible");
+
+    let mut vm = Transactor::<_, _, fuel_tx::Script>::new(
+        MemoryInstance::new(),
+        storage,
+        InterpreterParams::new(gas_price, params),
+    );
+
+    let (receipts, backtrace) = match tx {
+        Transaction::Script(tx) => {
+            vm.transact(tx.into_checked_basic(height, params)?);
+            if let Some(e) = vm.error() {
+                return Err(ReplayError::Execution {
+                    reason: e.to_string(),
+                    receipts: vm.interpreter().receipts().to_vec(),
+                })
+            }
+            let receipts = vm.receipts().unwrap_or_default().to_vec();
+            let backtrace = vm.is_reverted().then(|| vm.backtrace()).flatten();
+
+            (receipts, backtrace)
+        }
+        Transaction::Create(tx) => {
+            vm.deploy(tx.into_checked_basic(height, params)?)?;
+            Default::default()
+        }
+        Transaction::Upgrade(tx) => {
+            vm.upgrade(tx.into_checked_basic(height, params)?)?;
+            Default::default()
+        }
+        Transaction::Upload(tx) => {
+            vm.upload(tx.into_checked_basic(height, params)?)?;
+            Default::default()
+        }
+        Transaction::Blob(tx) => {
+            vm.blob(tx.into_checked_basic(height, params)?)?;
+            Default::default()
+        }
+        Transaction::Mint(_) => return Err(ReplayError::UnsupportedTransaction),
+    };
+
+    let gas_used = receipts.iter().find_map(Receipt::gas_used);
+    let gas_profile = vm.interpreter().profiler().data().gas().clone();
+    let state_diff = before.diff(&ContractsView::new(vm.as_ref()));
+
+    Ok(Replay {
+        tx_id,
+        receipts,
+        gas_used,
+        backtrace,
+        gas_profile,
+        state_diff,
+    })
+}
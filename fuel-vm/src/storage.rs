@@ -32,6 +32,8 @@ pub use interpreter::{
     ContractsAssetsStorage,
     InterpreterStorage,
 };
+#[cfg(feature = "snapshot")]
+pub use memory::snapshot;
 #[cfg(feature = "test-helpers")]
 pub use memory::MemoryStorage;
 pub use predicate::PredicateStorage;
@@ -41,6 +43,7 @@ use alloc::vec::Vec;
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(fuel_types::canonical::Deserialize, fuel_types::canonical::Serialize)]
 /// The uploaded bytecode can be in two states: fully uploaded or partially uploaded.
 pub enum UploadedBytecode {
     /// The bytecode is partially uploaded.
@@ -30,6 +30,7 @@ impl Mappable for BlobData {
 #[derive(Derivative, Clone, PartialEq, Eq, Hash)]
 #[derivative(Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(fuel_types::canonical::Deserialize, fuel_types::canonical::Serialize)]
 pub struct BlobBytes(
     #[derivative(Debug(format_with = "fmt_truncated_hex::<16>"))] pub Vec<u8>,
 );
@@ -47,6 +47,7 @@ double_key!(
 #[derive(Derivative, Clone, PartialEq, Eq, Hash)]
 #[derivative(Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(fuel_types::canonical::Deserialize, fuel_types::canonical::Serialize)]
 pub struct ContractsStateData(
     #[derivative(Debug(format_with = "fmt_truncated_hex::<16>"))] pub Vec<u8>,
 );
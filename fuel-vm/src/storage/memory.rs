@@ -48,6 +48,9 @@ use super::{
     BlobData,
 };
 
+#[cfg(feature = "snapshot")]
+pub mod snapshot;
+
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
 struct MemoryStorageInner {
     contracts: BTreeMap<ContractId, Contract>,
@@ -111,6 +114,18 @@ impl MemoryStorage {
         self.memory.contract_state.iter()
     }
 
+    /// Iterate over all contracts code in storage
+    pub fn all_contracts(&self) -> impl Iterator<Item = (&ContractId, &Contract)> {
+        self.memory.contracts.iter()
+    }
+
+    /// Iterate over all contract balances in storage
+    pub fn all_contract_balances(
+        &self,
+    ) -> impl Iterator<Item = (&ContractsAssetKey, &Word)> {
+        self.memory.balances.iter()
+    }
+
     /// Fetch a mapping from the contract state.
     pub fn contract_state(
         &self,
@@ -0,0 +1,580 @@
+//! Portable snapshots of a [`MemoryStorage`].
+//!
+//! A snapshot captures the whole chain state of the storage, so test fixtures and bug
+//! reports can ship a reproducible state. Snapshots are versioned and can be encoded
+//! either with serde (e.g. JSON) or with the canonical encoding.
+
+use alloc::vec::Vec;
+
+use fuel_tx::{
+    ConsensusParameters,
+    Contract,
+};
+use fuel_types::{
+    canonical::{
+        Deserialize,
+        Error,
+        Input,
+        Output,
+        Serialize,
+    },
+    AssetId,
+    BlobId,
+    BlockHeight,
+    Bytes32,
+    ContractId,
+    Word,
+};
+
+use super::{
+    MemoryStorage,
+    MemoryStorageInner,
+};
+use crate::storage::{
+    BlobBytes,
+    ContractsStateData,
+    UploadedBytecode,
+};
+
+/// A versioned snapshot of a [`MemoryStorage`].
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
+    Deserialize,
+    Serialize,
+)]
+pub enum StorageSnapshot {
+    /// Version 1 of the snapshot format.
+    V1(StorageSnapshotV1),
+}
+
+/// Version 1 of the [`StorageSnapshot`] format.
+///
+/// Every table is stored as a list of entries sorted by key.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
+    Deserialize,
+    Serialize,
+)]
+pub struct StorageSnapshotV1 {
+    /// Current block height.
+    pub block_height: BlockHeight,
+    /// Coinbase contract of the current block.
+    pub coinbase: ContractId,
+    /// Version of the consensus parameters in use.
+    pub consensus_parameters_version: u32,
+    /// Version of the state transition function in use.
+    pub state_transition_version: u32,
+    /// Deployed contracts.
+    pub contracts: Vec<ContractEntry>,
+    /// Storage slots of the contracts.
+    pub contract_state: Vec<ContractStateEntry>,
+    /// Asset balances of the contracts.
+    pub contract_balances: Vec<ContractBalanceEntry>,
+    /// Uploaded blobs.
+    pub blobs: Vec<BlobEntry>,
+    /// Consensus parameters by version.
+    pub consensus_parameters: Vec<ConsensusParametersEntry>,
+    /// Uploaded state transition bytecodes by Merkle root.
+    pub uploaded_bytecodes: Vec<UploadedBytecodeEntry>,
+    /// Roots of the state transition bytecodes by version.
+    pub state_transition_bytecodes: Vec<StateTransitionBytecodeEntry>,
+}
+
+/// The bytecode of a deployed contract.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
+    Deserialize,
+    Serialize,
+)]
+pub struct ContractEntry {
+    /// Id of the contract.
+    pub contract_id: ContractId,
+    /// Bytecode of the contract.
+    pub code: Contract,
+}
+
+/// A storage slot of a contract.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
+    Deserialize,
+    Serialize,
+)]
+pub struct ContractStateEntry {
+    /// Id of the contract.
+    pub contract_id: ContractId,
+    /// Key of the slot.
+    pub key: Bytes32,
+    /// Value of the slot.
+    pub value: ContractsStateData,
+}
+
+/// The balance of an asset owned by a contract.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
+    Deserialize,
+    Serialize,
+)]
+pub struct ContractBalanceEntry {
+    /// Id of the contract.
+    pub contract_id: ContractId,
+    /// The asset.
+    pub asset_id: AssetId,
+    /// The balance.
+    pub amount: Word,
+}
+
+/// An uploaded blob.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
+    Deserialize,
+    Serialize,
+)]
+pub struct BlobEntry {
+    /// Id of the blob.
+    pub blob_id: BlobId,
+    /// Content of the blob.
+    pub data: BlobBytes,
+}
+
+/// A version of the consensus parameters.
+///
+/// `ConsensusParameters` have no canonical encoding, so the canonical encoding of the
+/// entry embeds them as bytes, in the same format as `Upgrade` transactions. The bytes
+/// are encoded once, when the entry is created.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(
+    into = "ConsensusParametersVersion",
+    try_from = "ConsensusParametersVersion"
+)]
+pub struct ConsensusParametersEntry {
+    parameters: ConsensusParameters,
+    encoded: EncodedConsensusParameters,
+}
+
+/// Serde representation of [`ConsensusParametersEntry`].
+#[derive(serde::Serialize, serde::Deserialize)]
+struct ConsensusParametersVersion {
+    version: u32,
+    parameters: ConsensusParameters,
+}
+
+/// Canonical representation of [`ConsensusParametersEntry`].
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+struct EncodedConsensusParameters {
+    version: u32,
+    parameters: Vec<u8>,
+}
+
+impl ConsensusParametersEntry {
+    /// Creates the entry of the `version` of the consensus parameters.
+    pub fn new(version: u32, parameters: ConsensusParameters) -> Result<Self, Error> {
+        let encoded = postcard::to_allocvec(&parameters)
+            .map_err(|_| Error::Unknown("Failed to encode consensus parameters"))?;
+
+        Ok(Self {
+            parameters,
+            encoded: EncodedConsensusParameters {
+                version,
+                parameters: encoded,
+            },
+        })
+    }
+
+    /// Version of the consensus parameters.
+    pub fn version(&self) -> u32 {
+        self.encoded.version
+    }
+
+    /// The consensus parameters.
+    pub fn parameters(&self) -> &ConsensusParameters {
+        &self.parameters
+    }
+}
+
+impl From<ConsensusParametersEntry> for ConsensusParametersVersion {
+    fn from(entry: ConsensusParametersEntry) -> Self {
+        Self {
+            version: entry.version(),
+            parameters: entry.parameters,
+        }
+    }
+}
+
+impl TryFrom<ConsensusParametersVersion> for ConsensusParametersEntry {
+    type Error = Error;
+
+    fn try_from(value: ConsensusParametersVersion) -> Result<Self, Self::Error> {
+        Self::new(value.version, value.parameters)
+    }
+}
+
+impl Serialize for ConsensusParametersEntry {
+    fn size_static(&self) -> usize {
+        self.encoded.size_static()
+    }
+
+    fn size_dynamic(&self) -> usize {
+        self.encoded.size_dynamic()
+    }
+
+    fn encode_static<O: Output + ?Sized>(&self, buffer: &mut O) -> Result<(), Error> {
+        self.encoded.encode_static(buffer)
+    }
+
+    fn encode_dynamic<O: Output + ?Sized>(&self, buffer: &mut O) -> Result<(), Error> {
+        self.encoded.encode_dynamic(buffer)
+    }
+}
+
+impl Deserialize for ConsensusParametersEntry {
+    fn decode_static<I: Input + ?Sized>(buffer: &mut I) -> Result<Self, Error> {
+        let encoded = EncodedConsensusParameters::decode(buffer)?;
+        let parameters = postcard::from_bytes(&encoded.parameters)
+            .map_err(|_| Error::Unknown("Failed to decode consensus parameters"))?;
+
+        Ok(Self {
+            parameters,
+            encoded,
+        })
+    }
+}
+
+/// A partially or fully uploaded state transition bytecode.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
+    Deserialize,
+    Serialize,
+)]
+pub struct UploadedBytecodeEntry {
+    /// Merkle root of the bytecode.
+    pub root: Bytes32,
+    /// The bytecode.
+    pub bytecode: UploadedBytecode,
+}
+
+/// A version of the state transition function.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    serde::Deserialize,
+    Deserialize,
+    Serialize,
+)]
+pub struct StateTransitionBytecodeEntry {
+    /// Version of the state transition function.
+    pub version: u32,
+    /// Merkle root of the bytecode.
+    pub root: Bytes32,
+}
+
+impl MemoryStorage {
+    /// Captures the current state of the storage.
+    pub fn snapshot(&self) -> Result<StorageSnapshot, Error> {
+        let memory = &self.memory;
+
+        Ok(StorageSnapshot::V1(StorageSnapshotV1 {
+            block_height: self.block_height,
+            coinbase: self.coinbase,
+            consensus_parameters_version: self.consensus_parameters_version,
+            state_transition_version: self.state_transition_version,
+            contracts: memory
+                .contracts
+                .iter()
+                .map(|(contract_id, code)| ContractEntry {
+                    contract_id: *contract_id,
+                    code: code.clone(),
+                })
+                .collect(),
+            contract_state: memory
+                .contract_state
+                .iter()
+                .map(|(key, value)| ContractStateEntry {
+                    contract_id: *key.contract_id(),
+                    key: *key.state_key(),
+                    value: value.clone(),
+                })
+                .collect(),
+            contract_balances: memory
+                .balances
+                .iter()
+                .map(|(key, amount)| ContractBalanceEntry {
+                    contract_id: *key.contract_id(),
+                    asset_id: *key.asset_id(),
+                    amount: *amount,
+                })
+                .collect(),
+            blobs: memory
+                .blobs
+                .iter()
+                .map(|(blob_id, data)| BlobEntry {
+                    blob_id: *blob_id,
+                    data: data.clone(),
+                })
+                .collect(),
+            consensus_parameters: memory
+                .consensus_parameters_versions
+                .iter()
+                .map(|(version, parameters)| {
+                    ConsensusParametersEntry::new(*version, parameters.clone())
+                })
+                .collect::<Result<_, _>>()?,
+            uploaded_bytecodes: memory
+                .state_transition_bytecodes
+                .iter()
+                .map(|(root, bytecode)| UploadedBytecodeEntry {
+                    root: *root,
+                    bytecode: bytecode.clone(),
+                })
+                .collect(),
+            state_transition_bytecodes: memory
+                .state_transition_bytecodes_versions
+                .iter()
+                .map(|(version, root)| StateTransitionBytecodeEntry {
+                    version: *version,
+                    root: *root,
+                })
+                .collect(),
+        }))
+    }
+
+    /// Creates a storage from a snapshot. The snapshot state is persisted.
+    pub fn from_snapshot(snapshot: StorageSnapshot) -> Self {
+        let StorageSnapshot::V1(snapshot) = snapshot;
+
+        let inner = MemoryStorageInner {
+            contracts: snapshot
+                .contracts
+                .into_iter()
+                .map(|entry| (entry.contract_id, entry.code))
+                .collect(),
+            balances: snapshot
+                .contract_balances
+                .into_iter()
+                .map(|entry| ((&entry.contract_id, &entry.asset_id).into(), entry.amount))
+                .collect(),
+            contract_state: snapshot
+                .contract_state
+                .into_iter()
+                .map(|entry| ((&entry.contract_id, &entry.key).into(), entry.value))
+                .collect(),
+            blobs: snapshot
+                .blobs
+                .into_iter()
+                .map(|entry| (entry.blob_id, entry.data))
+                .collect(),
+            consensus_parameters_versions: snapshot
+                .consensus_parameters
+                .into_iter()
+                .map(|entry| (entry.version(), entry.parameters))
+                .collect(),
+            state_transition_bytecodes: snapshot
+                .uploaded_bytecodes
+                .into_iter()
+                .map(|entry| (entry.root, entry.bytecode))
+                .collect(),
+            state_transition_bytecodes_versions: snapshot
+                .state_transition_bytecodes
+                .into_iter()
+                .map(|entry| (entry.version, entry.root))
+                .collect(),
+        };
+
+        Self {
+            block_height: snapshot.block_height,
+            coinbase: snapshot.coinbase,
+            consensus_parameters_version: snapshot.consensus_parameters_version,
+            state_transition_version: snapshot.state_transition_version,
+            memory: inner.clone(),
+            transacted: inner.clone(),
+            persisted: inner,
+        }
+    }
+
+    /// Creates a storage from a canonical-encoded [`StorageSnapshot`].
+    pub fn from_snapshot_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        StorageSnapshot::from_bytes(bytes).map(Self::from_snapshot)
+    }
+
+    /// Creates a storage from a JSON-encoded [`StorageSnapshot`].
+    pub fn from_snapshot_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json).map(Self::from_snapshot)
+    }
+}
+
+impl From<StorageSnapshot> for MemoryStorage {
+    fn from(snapshot: StorageSnapshot) -> Self {
+        Self::from_snapshot(snapshot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{
+        BlobData,
+        ContractsAssets,
+        ContractsRawCode,
+        ContractsState,
+        InterpreterStorage,
+    };
+    use alloc::vec;
+    use fuel_storage::{
+        StorageAsMut,
+        StorageAsRef,
+    };
+
+    fn storage() -> MemoryStorage {
+        let contract_id = ContractId::new([1; 32]);
+        let mut storage = MemoryStorage::new(10.into(), ContractId::new([2; 32]));
+        storage.set_consensus_parameters_version(1);
+        storage.set_state_transition_version(2);
+
+        storage
+            .storage_as_mut::<ContractsRawCode>()
+            .insert(&contract_id, &[1, 2, 3])
+            .unwrap();
+        storage
+            .storage_as_mut::<ContractsState>()
+            .insert(&(&contract_id, &Bytes32::new([3; 32])).into(), &[4; 32])
+            .unwrap();
+        storage
+            .storage_as_mut::<ContractsAssets>()
+            .insert(&(&contract_id, &AssetId::new([5; 32])).into(), &100)
+            .unwrap();
+        storage
+            .storage_as_mut::<BlobData>()
+            .insert(&BlobId::new([6; 32]), &[7, 8])
+            .unwrap();
+        storage
+            .consensus_parameters_versions_mut()
+            .insert(1, ConsensusParameters::standard());
+        storage.state_transition_bytecodes_mut().insert(
+            Bytes32::new([9; 32]),
+            UploadedBytecode::Uncompleted {
+                bytecode: vec![10; 16],
+                uploaded_subsections_number: 1,
+            },
+        );
+        storage
+            .state_transition_bytecodes_versions_mut()
+            .insert(2, Bytes32::new([9; 32]));
+
+        storage
+    }
+
+    #[test]
+    fn snapshot_restores_the_storage() {
+        let snapshot = storage().snapshot().unwrap();
+
+        let restored = MemoryStorage::from_snapshot(snapshot.clone());
+
+        assert_eq!(restored.snapshot().unwrap(), snapshot);
+        assert_eq!(restored.block_height().unwrap(), 10.into());
+        assert_eq!(restored.consensus_parameters_version().unwrap(), 1);
+        assert_eq!(
+            restored
+                .storage::<ContractsAssets>()
+                .get(&(&ContractId::new([1; 32]), &AssetId::new([5; 32])).into())
+                .unwrap()
+                .unwrap()
+                .into_owned(),
+            100
+        );
+        assert_eq!(
+            restored
+                .storage::<BlobData>()
+                .get(&BlobId::new([6; 32]))
+                .unwrap()
+                .unwrap()
+                .into_owned(),
+            vec![7, 8].into()
+        );
+    }
+
+    #[test]
+    fn snapshot_canonical_round_trip() {
+        let snapshot = storage().snapshot().unwrap();
+
+        let bytes = snapshot.to_bytes();
+        let restored = MemoryStorage::from_snapshot_bytes(&bytes).unwrap();
+
+        // The version is encoded first
+        assert_eq!(bytes[..8], 0u64.to_be_bytes());
+        assert_eq!(restored.snapshot().unwrap(), snapshot);
+    }
+
+    #[test]
+    fn snapshot_json_round_trip() {
+        let snapshot = storage().snapshot().unwrap();
+
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let restored = MemoryStorage::from_snapshot_json(&json).unwrap();
+
+        assert!(json.starts_with(r#"{"V1":"#));
+        assert_eq!(restored.snapshot().unwrap(), snapshot);
+    }
+
+    #[test]
+    fn consensus_parameters_entry_size_matches_its_encoding() {
+        let entry =
+            ConsensusParametersEntry::new(1, ConsensusParameters::standard()).unwrap();
+
+        let bytes = entry.to_bytes();
+
+        assert_eq!(entry.size(), bytes.len());
+        assert_eq!(ConsensusParametersEntry::from_bytes(&bytes), Ok(entry));
+    }
+
+    #[test]
+    fn consensus_parameters_entry_rejects_invalid_parameters() {
+        let encoded = EncodedConsensusParameters {
+            version: 1,
+            parameters: vec![0xff; 4],
+        };
+
+        let result = ConsensusParametersEntry::from_bytes(&encoded.to_bytes());
+
+        assert_eq!(
+            result,
+            Err(Error::Unknown("Failed to decode consensus parameters"))
+        );
+    }
+}
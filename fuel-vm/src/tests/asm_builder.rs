@@ -0,0 +1,73 @@
+use alloc::{
+    vec,
+    vec::Vec,
+};
+
+use fuel_asm::{
+    op,
+    AsmBuilder,
+    RegId,
+    Word,
+};
+use fuel_tx::Receipt;
+use rstest::rstest;
+
+use super::test_helpers::{
+    assert_success,
+    run_script_bytes,
+};
+
+fn logged_values(receipts: &[Receipt]) -> Vec<Word> {
+    receipts
+        .iter()
+        .filter_map(|receipt| match receipt {
+            Receipt::Log { ra, .. } => Some(*ra),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn loop_with_labels_sums_range() {
+    let mut asm = AsmBuilder::new();
+    let top = asm.new_label();
+    let done = asm.new_label();
+
+    asm.push(op::movi(0x10, 100))
+        .push(op::movi(0x11, 0))
+        .bind(top)
+        .push(op::add(0x11, 0x11, 0x10))
+        .push(op::subi(0x10, 0x10, 1))
+        .jump_if_not_zero(0x10, top)
+        .jump(done)
+        .push(op::rvrt(RegId::ONE))
+        .bind(done)
+        .push(op::log(0x11, RegId::ZERO, RegId::ZERO, RegId::ZERO))
+        .push(op::ret(RegId::ONE));
+
+    let receipts = run_script_bytes(asm.build().unwrap());
+    assert_success(&receipts);
+    assert_eq!(logged_values(&receipts), vec![5050]);
+}
+
+#[rstest]
+fn load_word_matches_embedded_data(
+    #[values(0, 1, 0x3_ffff, 0x4_0000, 0xdead_beef, Word::MAX - 1, Word::MAX)]
+    value: Word,
+) {
+    let mut asm = AsmBuilder::new();
+    let data = asm.data_word(value);
+    asm.load_word(0x10, value)
+        .load_data_word(0x11, data)
+        .push(op::log(0x10, 0x11, RegId::ZERO, RegId::ZERO))
+        .push(op::ret(RegId::ONE));
+
+    let receipts = run_script_bytes(asm.build().unwrap());
+    assert_success(&receipts);
+
+    let Some(Receipt::Log { ra, rb, .. }) = receipts.first() else {
+        panic!("Expected a log receipt");
+    };
+    assert_eq!(*ra, value);
+    assert_eq!(*rb, value);
+}
@@ -0,0 +1,108 @@
+use alloc::{
+    vec,
+    vec::Vec,
+};
+use arbitrary::{
+    Arbitrary,
+    Unstructured,
+};
+use fuel_asm::{
+    op,
+    Instruction,
+    RegId,
+};
+use proptest::prelude::*;
+use rstest::rstest;
+
+use crate::differential::{
+    check_program,
+    Program,
+};
+
+#[rstest]
+#[case::wrapping_add(vec![
+    op::movi(0x10, 0b10),
+    op::flag(0x10),
+    op::not(0x11, RegId::ZERO),
+    op::add(0x12, 0x11, 0x11),
+    op::log(0x12, RegId::OF, RegId::ERR, RegId::FLAG),
+])]
+#[case::overflowing_exp(vec![op::movi(0x10, 2), op::expi(0x11, 0x10, 64)])]
+#[case::unsafe_math(vec![
+    op::movi(0x10, 0b01),
+    op::flag(0x10),
+    op::div(0x11, 0x10, RegId::ZERO),
+    op::mlog(0x12, 0x10, RegId::ONE),
+    op::mroo(0x13, 0x10, RegId::ZERO),
+    op::log(0x11, 0x12, 0x13, RegId::ERR),
+])]
+#[case::roots_and_logs(vec![
+    op::movi(0x10, 1000),
+    op::movi(0x11, 3),
+    op::mroo(0x12, 0x10, 0x11),
+    op::mlog(0x13, 0x10, 0x11),
+    op::log(0x12, 0x13, RegId::ZERO, RegId::ZERO),
+])]
+#[case::stack_memory(vec![
+    op::cfei(32),
+    op::movi(0x10, 0xabcd),
+    op::sw(RegId::SSP, 0x10, 1),
+    op::sb(RegId::SSP, 0x10, 3),
+    op::mcpi(RegId::SSP, 0x11, 0),
+    op::addi(0x11, RegId::SSP, 16),
+    op::mcpi(0x11, RegId::SSP, 16),
+    op::lw(0x12, 0x11, 1),
+    op::lb(0x13, 0x11, 3),
+    op::cfsi(8),
+    op::log(0x12, 0x13, RegId::SP, RegId::ZERO),
+])]
+#[case::heap_memory(vec![
+    op::movi(0x10, 64),
+    op::aloc(0x10),
+    op::sw(RegId::HP, 0x10, 7),
+    op::lw(0x11, RegId::HP, 7),
+    op::log(0x11, RegId::HP, RegId::ZERO, RegId::ZERO),
+])]
+#[case::register_memory(vec![
+    op::movi(0x10, 48),
+    op::cfe(0x10),
+    op::not(0x11, RegId::ZERO),
+    op::sw(RegId::SSP, 0x11, 0),
+    op::movi(0x12, 8),
+    op::addi(0x13, RegId::SSP, 16),
+    op::mcp(0x13, RegId::SSP, 0x12),
+    op::meq(0x14, RegId::SSP, 0x13, 0x12),
+    op::mcli(RegId::SSP, 4),
+    op::meq(0x15, RegId::SSP, 0x13, 0x12),
+    op::mcl(0x13, 0x12),
+    op::lw(0x16, 0x13, 0),
+    op::cfs(0x12),
+    op::log(0x14, 0x15, 0x16, RegId::SP),
+])]
+#[case::clear_outside_stack(vec![op::movi(0x10, 8), op::mcl(RegId::SSP, 0x10)])]
+#[case::compare_uninitialized_memory(vec![
+    op::movi(0x10, 8),
+    op::meq(0x11, RegId::SP, RegId::SSP, 0x10),
+])]
+#[case::shrink_below_stack_start(vec![op::cfs(RegId::ONE)])]
+#[case::write_outside_stack(vec![op::sw(RegId::SSP, RegId::ONE, 0)])]
+#[case::overlapping_copy(vec![op::cfei(16), op::mcpi(RegId::SSP, RegId::SSP, 8)])]
+#[case::reserved_register(vec![op::movi(RegId::SP, 1)])]
+#[case::invalid_flags(vec![op::movi(0x10, 4), op::flag(0x10)])]
+#[case::out_of_gas(vec![op::not(0x10, RegId::ZERO), op::aloc(0x10)])]
+fn interpreter_matches_reference(#[case] program: Vec<Instruction>) {
+    assert_eq!(check_program(&program), Ok(()));
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(256))]
+
+    #[test]
+    fn arbitrary_programs_match_reference(
+        bytes in prop::collection::vec(any::<u8>(), 0..1024)
+    ) {
+        let program = Program::arbitrary_take_rest(Unstructured::new(&bytes))
+            .expect("Programs can be built from any input");
+        prop_assert_eq!(check_program(&program.0), Ok(()), "{:?}", program);
+    }
+}
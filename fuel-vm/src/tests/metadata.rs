@@ -26,7 +26,10 @@ use fuel_tx::{
         Script as ScriptField,
         Witnesses,
     },
-    policies::PoliciesBits,
+    policies::{
+        fee_payer_value,
+        PoliciesBits,
+    },
     ConsensusParameters,
     Finalizable,
     Receipt,
@@ -368,6 +371,7 @@ fn get_transaction_fields() {
     let tip = 4321;
     let gas_limit = 10_000_000;
     let maturity = 50.into();
+    let expiration: BlockHeight = 200.into();
     let height = 122.into();
     let input = 10_000_000;
 
@@ -439,12 +443,17 @@ fn get_transaction_fields() {
     let asset = rng.gen();
     let asset_amt = 27;
 
+    // The first input pays the fee, with the refund going to the last output
+    let fee_payer_secret = SecretKey::random(rng);
+    let fee_payer_owner = Input::owner(&fee_payer_secret.public_key());
+    let fee_payer = fee_payer_value(0, 4);
+
     let tx = TransactionBuilder::script(vec![], vec![])
         .maturity(maturity)
         .with_gas_costs(gas_costs)
         .script_gas_limit(gas_limit)
         .add_unsigned_coin_input(
-            SecretKey::random(rng),
+            fee_payer_secret,
             rng.gen(),
             input,
             AssetId::zeroed(),
@@ -482,6 +491,7 @@ fn get_transaction_fields() {
         )
         .add_output(Output::coin(rng.gen(), asset_amt, asset))
         .add_output(Output::change(rng.gen(), rng.gen_range(10..1000), asset))
+        .add_output(Output::change(fee_payer_owner, 0, AssetId::zeroed()))
         .finalize_checked(height);
 
     let inputs = tx.as_ref().inputs();
@@ -602,6 +612,12 @@ fn get_transaction_fields() {
         op::eq(0x10, 0x10, 0x11),
         op::and(0x20, 0x20, 0x10),
 
+        op::movi(0x19, 0x00),
+        op::movi(0x11, *expiration as Immediate18),
+        op::gtf_args(0x10, 0x19, GTFArgs::PolicyExpiration),
+        op::eq(0x10, 0x10, 0x11),
+        op::and(0x20, 0x20, 0x10),
+
         op::movi(0x19, 0x00),
         op::movi(0x11, max_fee_limit as Immediate18),
         op::gtf_args(0x10, 0x19, GTFArgs::PolicyMaxFee),
@@ -614,6 +630,15 @@ fn get_transaction_fields() {
         op::eq(0x10, 0x10, 0x11),
         op::and(0x20, 0x20, 0x10),
 
+        op::movi(0x19, 0x00),
+        op::movi(0x11, (fee_payer & 0x3ffff) as Immediate18),
+        op::movi(0x12, (fee_payer >> 18) as Immediate18),
+        op::slli(0x12, 0x12, 18),
+        op::or(0x11, 0x11, 0x12),
+        op::gtf_args(0x10, 0x19, GTFArgs::PolicyFeePayer),
+        op::eq(0x10, 0x10, 0x11),
+        op::and(0x20, 0x20, 0x10),
+
         op::movi(0x19, 0x00),
         op::movi(0x11, PoliciesBits::all().bits() as Immediate18),
         op::gtf_args(0x10, 0x19, GTFArgs::PolicyTypes),
@@ -943,9 +968,11 @@ fn get_transaction_fields() {
     let tx = builder
         .tip(tip)
         .maturity(maturity)
+        .expiration(expiration)
         .script_gas_limit(gas_limit)
         .witness_limit(witness_limit)
         .max_fee_limit(max_fee_limit)
+        .fee_payer(0, 4)
         .finalize_checked_basic(height);
 
     let receipts = client.transact(tx);
@@ -2,12 +2,15 @@
 
 use futures as _;
 use ntest as _;
+#[cfg(not(feature = "arbitrary"))]
+use proptest as _;
 use tokio as _;
 use tokio_rayon as _;
 
 mod test_helpers;
 
 mod alu;
+mod asm_builder;
 mod backtrace;
 mod blob;
 mod blockchain;
@@ -16,6 +19,8 @@ mod code_coverage;
 mod coins;
 mod contract;
 mod crypto;
+#[cfg(feature = "arbitrary")]
+mod differential;
 mod encoding;
 mod external;
 mod flow;
@@ -30,6 +35,8 @@ mod outputs;
 mod predicate;
 mod profile_gas;
 mod receipts;
+#[cfg(feature = "replay")]
+mod replay;
 mod serde_profile;
 mod spec;
 mod upgrade;
@@ -4,8 +4,12 @@ use alloc::{
 };
 
 use crate::{
+    checked_transaction::CheckError,
     prelude::{
-        field::Outputs,
+        field::{
+            Inputs,
+            Outputs,
+        },
         *,
     },
     util::test_helpers::{
@@ -177,3 +181,230 @@ fn correct_change_is_provided_for_coin_outputs_create() {
 
     assert_eq!(change, input_amount - spend_amount);
 }
+
+#[test]
+fn fee_payer_pays_the_fee_and_gets_the_refund() {
+    let mut rng = StdRng::seed_from_u64(2322u64);
+    let base_asset_id = rng.gen();
+    let input_amount = 1000;
+    let spend_amount = 600;
+    let fee_payer_amount = 2000;
+    let max_fee_limit = 1000;
+
+    let outputs = TestBuilder::new(2322u64)
+        .max_fee_limit(max_fee_limit)
+        .gas_price(1)
+        .base_asset_id(base_asset_id)
+        .coin_input(base_asset_id, input_amount)
+        .change_output(base_asset_id)
+        .coin_output(base_asset_id, spend_amount)
+        .fee_payer_input(fee_payer_amount)
+        .execute_get_outputs();
+
+    let change = find_change(outputs.clone(), base_asset_id);
+    assert_eq!(change, input_amount - spend_amount);
+
+    let fee_payer_change = outputs[2].amount().expect("Expected change output");
+    assert!(fee_payer_change > fee_payer_amount - max_fee_limit);
+    assert!(fee_payer_change < fee_payer_amount);
+}
+
+#[test]
+fn fee_payer_gets_the_refund_on_revert() {
+    let mut rng = StdRng::seed_from_u64(2322u64);
+    let base_asset_id = rng.gen();
+    let input_amount = 1000;
+    let spend_amount = 600;
+    let fee_payer_amount = 2000;
+    let max_fee_limit = 1000;
+
+    let outputs = TestBuilder::new(2322u64)
+        .start_script(vec![op::rvrt(RegId::ONE)].into_iter().collect(), vec![])
+        .max_fee_limit(max_fee_limit)
+        .gas_price(1)
+        .base_asset_id(base_asset_id)
+        .coin_input(base_asset_id, input_amount)
+        .change_output(base_asset_id)
+        .coin_output(base_asset_id, spend_amount)
+        .fee_payer_input(fee_payer_amount)
+        .execute_get_outputs();
+
+    let change = find_change(outputs.clone(), base_asset_id);
+    assert_eq!(change, input_amount - spend_amount);
+
+    let fee_payer_change = outputs[2].amount().expect("Expected change output");
+    assert!(fee_payer_change > fee_payer_amount - max_fee_limit);
+    assert!(fee_payer_change < fee_payer_amount);
+}
+
+#[test]
+fn fee_payer_and_spender_with_the_same_owner_get_separate_change() {
+    let mut rng = StdRng::seed_from_u64(2322u64);
+    let base_asset_id = rng.gen();
+    let input_amount = 1000;
+    let spend_amount = 600;
+    let fee_payer_amount = 2000;
+    let max_fee_limit = 1000;
+
+    let mut context = TestBuilder::new(2322u64);
+    let context = context.gas_price(1).base_asset_id(base_asset_id);
+    let consensus_params = ConsensusParameters::new(
+        *context.get_tx_params(),
+        *context.get_predicate_params(),
+        *context.get_script_params(),
+        *context.get_contract_params(),
+        *context.get_fee_params(),
+        context.get_chain_id(),
+        context.get_gas_costs().to_owned(),
+        *context.get_base_asset_id(),
+        context.get_block_gas_limit(),
+        *context.get_privileged_address(),
+    );
+
+    // The same key owns the spent input and the fee payer input, so both change
+    // outputs go to the same address
+    let secret = SecretKey::random(&mut rng);
+    let owner = Input::owner(&secret.public_key());
+    let tx = TransactionBuilder::script(
+        vec![op::ret(RegId::ONE)].into_iter().collect(),
+        vec![],
+    )
+    .with_params(consensus_params)
+    .script_gas_limit(100)
+    .max_fee_limit(max_fee_limit)
+    .add_unsigned_coin_input(
+        secret,
+        rng.gen(),
+        input_amount,
+        base_asset_id,
+        Default::default(),
+    )
+    .add_unsigned_coin_input(
+        secret,
+        rng.gen(),
+        fee_payer_amount,
+        base_asset_id,
+        Default::default(),
+    )
+    .add_output(Output::coin(rng.gen(), spend_amount, base_asset_id))
+    .add_output(Output::change(owner, 0, base_asset_id))
+    .add_output(Output::change(owner, 0, base_asset_id))
+    .fee_payer(1, 2)
+    .finalize_checked(context.get_block_height());
+
+    let state = context
+        .execute_tx(tx)
+        .expect("expected successful vm execution");
+    let outputs = state.tx().outputs();
+
+    let change = outputs[1].amount().expect("Expected change output");
+    assert_eq!(change, input_amount - spend_amount);
+
+    let fee_payer_change = outputs[2].amount().expect("Expected change output");
+    assert!(fee_payer_change > fee_payer_amount - max_fee_limit);
+    assert!(fee_payer_change < fee_payer_amount);
+}
+
+#[test]
+fn fee_payer_input_is_not_spendable_by_the_transaction() {
+    let mut rng = StdRng::seed_from_u64(2322u64);
+    let base_asset_id = rng.gen();
+    let fee_payer_amount = 2000;
+
+    let err = TestBuilder::new(2322u64)
+        .max_fee_limit(1000)
+        .gas_price(1)
+        .base_asset_id(base_asset_id)
+        .coin_input(base_asset_id, 100)
+        .coin_output(base_asset_id, 500)
+        .fee_payer_input(fee_payer_amount)
+        .try_build()
+        .expect_err("Expected insufficient input amount");
+
+    assert_eq!(
+        err,
+        CheckError::Validity(ValidityError::InsufficientInputAmount {
+            asset: base_asset_id,
+            expected: 500,
+            provided: 100,
+        })
+    );
+}
+
+#[test]
+fn fee_payer_must_cover_the_max_fee() {
+    let mut rng = StdRng::seed_from_u64(2322u64);
+    let base_asset_id = rng.gen();
+
+    let err = TestBuilder::new(2322u64)
+        .max_fee_limit(1000)
+        .base_asset_id(base_asset_id)
+        .coin_input(base_asset_id, 5000)
+        .fee_payer_input(999)
+        .try_build()
+        .expect_err("Expected insufficient fee amount");
+
+    assert_eq!(
+        err,
+        CheckError::Validity(ValidityError::InsufficientFeeAmount {
+            expected: 1000,
+            provided: 999,
+        })
+    );
+}
+
+#[test]
+fn fund_leaves_the_fee_to_the_fee_payer() {
+    let mut rng = StdRng::seed_from_u64(2322u64);
+    let params = ConsensusParameters::standard();
+    let base_asset_id = *params.base_asset_id();
+    let spend_amount = 600;
+
+    let sponsor = SecretKey::random(&mut rng);
+    let spender = SecretKey::random(&mut rng);
+    let mut builder = TransactionBuilder::script(
+        vec![op::ret(RegId::ONE)].into_iter().collect(),
+        vec![],
+    );
+    builder
+        .with_params(params.clone())
+        .script_gas_limit(100)
+        .add_unsigned_coin_input(
+            sponsor,
+            rng.gen(),
+            1_000_000,
+            base_asset_id,
+            Default::default(),
+        )
+        .add_output(Output::change(
+            Input::owner(&sponsor.public_key()),
+            0,
+            base_asset_id,
+        ))
+        .add_output(Output::coin(rng.gen(), spend_amount, base_asset_id))
+        .fee_payer(0, 0);
+
+    // The spender only has the amount of the coin output, so the funding fails if the
+    // fee isn't left to the fee payer or if the fee payer input covers the output
+    let witness_index = builder.add_signer(spender);
+    let available = Input::coin_signed(
+        rng.gen(),
+        Input::owner(&spender.public_key()),
+        spend_amount,
+        base_asset_id,
+        Default::default(),
+        witness_index,
+    );
+    builder
+        .fund(
+            vec![available.clone()].into_iter(),
+            CoinSelection::LargestFirst,
+            1,
+        )
+        .expect("The transaction should be funded");
+
+    let tx = builder.finalize();
+    assert_eq!(tx.inputs()[1], available);
+    tx.into_checked(Default::default(), &params)
+        .expect("The funded transaction should be valid");
+}
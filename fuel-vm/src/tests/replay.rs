@@ -0,0 +1,196 @@
+#![allow(non_snake_case)]
+
+use alloc::{
+    vec,
+    vec::Vec,
+};
+
+use fuel_asm::{
+    op,
+    GTFArgs,
+    Instruction,
+    RegId,
+};
+use fuel_storage::StorageAsMut;
+use fuel_tx::{
+    ConsensusParameters,
+    Input,
+    Output,
+    Receipt,
+    Transaction,
+    TransactionBuilder,
+};
+use fuel_types::{
+    canonical::Serialize,
+    AssetId,
+    ContractId,
+};
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+
+use crate::{
+    replay::{
+        decode_consensus_parameters,
+        decode_snapshot,
+        decode_transaction,
+        replay,
+        ReplayError,
+        StateChange,
+    },
+    storage::{
+        snapshot::StorageSnapshot,
+        ContractsAssets,
+        ContractsRawCode,
+        MemoryStorage,
+    },
+};
+
+fn call_contract(contract_id: ContractId) -> Transaction {
+    let rng = &mut StdRng::seed_from_u64(2322u64);
+    let script = vec![
+        op::gtf_args(0x10, RegId::ZERO, GTFArgs::ScriptData),
+        op::call(0x10, RegId::ZERO, RegId::ZERO, RegId::CGAS),
+        op::ret(RegId::ONE),
+    ];
+    let script_data = contract_id.iter().copied().chain([0; 16]).collect();
+
+    TransactionBuilder::script(script.into_iter().collect(), script_data)
+        .script_gas_limit(1_000_000)
+        .add_input(Input::contract(
+            rng.gen(),
+            Default::default(),
+            Default::default(),
+            Default::default(),
+            contract_id,
+        ))
+        .add_output(Output::contract(0, Default::default(), Default::default()))
+        .add_random_fee_input()
+        .finalize_as_transaction()
+}
+
+fn snapshot(contract_id: ContractId, code: Vec<Instruction>) -> StorageSnapshot {
+    let mut storage = MemoryStorage::default();
+    storage
+        .storage_as_mut::<ContractsRawCode>()
+        .insert(&contract_id, &code.into_iter().collect::<Vec<u8>>())
+        .unwrap();
+    storage
+        .storage_as_mut::<ContractsAssets>()
+        .insert(&(&contract_id, &AssetId::new([2; 32])).into(), &100)
+        .unwrap();
+
+    storage.snapshot().unwrap()
+}
+
+#[test]
+fn replay__reports_receipts_and_state_diff() {
+    let contract_id = ContractId::new([1; 32]);
+    let snapshot = snapshot(
+        contract_id,
+        vec![
+            op::movi(0x10, 42),
+            op::sww(RegId::ZERO, 0x11, 0x10),
+            op::ret(RegId::ONE),
+        ],
+    );
+    let tx = call_contract(contract_id);
+
+    let replay = replay(tx, &ConsensusParameters::standard(), &snapshot, 0)
+        .expect("The transaction should be replayed");
+
+    assert!(replay.gas_used.is_some());
+    assert!(replay.backtrace.is_none());
+    assert!(replay.gas_profile.iter().next().is_some());
+    assert!(replay.receipts.iter().any(
+        |receipt| matches!(receipt, Receipt::Call { to, .. } if *to == contract_id)
+    ));
+    match replay.state_diff.as_slice() {
+        [StateChange::Slot {
+            contract,
+            before: None,
+            after: Some(value),
+            ..
+        }] => {
+            assert_eq!(*contract, contract_id);
+            assert_eq!(value[..8], 42u64.to_be_bytes());
+        }
+        diff => panic!("Unexpected state diff: {diff:?}"),
+    }
+}
+
+#[test]
+fn replay__reverted_script_has_backtrace() {
+    let contract_id = ContractId::new([1; 32]);
+    let snapshot = snapshot(contract_id, vec![op::rvrt(RegId::ONE)]);
+    let tx = call_contract(contract_id);
+
+    let replay = replay(tx, &ConsensusParameters::standard(), &snapshot, 0)
+        .expect("The transaction should be replayed");
+
+    let backtrace = replay.backtrace.expect("The script should revert");
+    assert_eq!(*backtrace.contract(), contract_id);
+    assert!(replay.state_diff.is_empty());
+}
+
+#[test]
+fn replay__execution_error_returns_receipts() {
+    let contract_id = ContractId::new([1; 32]);
+    let snapshot = MemoryStorage::default().snapshot().unwrap();
+    let tx = call_contract(contract_id);
+
+    let result = replay(tx, &ConsensusParameters::standard(), &snapshot, 0);
+
+    match result {
+        Err(ReplayError::Execution { reason, receipts }) => {
+            assert!(reason.contains("ContractNotInInputs"));
+            assert!(receipts.is_empty());
+        }
+        result => panic!("Unexpected result: {result:?}"),
+    }
+}
+
+#[test]
+fn replay__mint_is_unsupported() {
+    let tx = Transaction::mint(
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        Default::default(),
+    );
+
+    let result = replay(
+        tx.into(),
+        &ConsensusParameters::standard(),
+        &MemoryStorage::default().snapshot().unwrap(),
+        0,
+    );
+
+    assert!(matches!(result, Err(ReplayError::UnsupportedTransaction)));
+}
+
+#[test]
+fn decode__inputs_accept_binary_hex_and_json() {
+    let contract_id = ContractId::new([1; 32]);
+    let tx = call_contract(contract_id);
+    let params = ConsensusParameters::standard();
+    let snapshot = snapshot(contract_id, vec![op::ret(RegId::ONE)]);
+
+    let tx_hex = format!("0x{}\n", hex::encode(tx.to_bytes()));
+    assert_eq!(decode_transaction(tx_hex.as_bytes()).unwrap(), tx);
+    assert_eq!(decode_transaction(&tx.to_bytes()).unwrap(), tx);
+
+    let params_bytes = postcard::to_allocvec(&params).unwrap();
+    let params_json = serde_json::to_vec(&params).unwrap();
+    assert_eq!(decode_consensus_parameters(&params_bytes).unwrap(), params);
+    assert_eq!(decode_consensus_parameters(&params_json).unwrap(), params);
+
+    let snapshot_hex = hex::encode(snapshot.to_bytes());
+    let snapshot_json = serde_json::to_vec_pretty(&snapshot).unwrap();
+    assert_eq!(decode_snapshot(snapshot_hex.as_bytes()).unwrap(), snapshot);
+    assert_eq!(decode_snapshot(&snapshot_json).unwrap(), snapshot);
+}
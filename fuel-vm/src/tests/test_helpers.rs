@@ -25,11 +25,15 @@ pub fn set_full_word(r: RegisterId, v: Word) -> Vec<Instruction> {
 
 /// Run a instructions-only script with reasonable defaults, and return receipts
 pub fn run_script(script: Vec<Instruction>) -> Vec<Receipt> {
+    run_script_bytes(script.into_iter().collect())
+}
+
+/// Run a script given as raw bytecode with reasonable defaults, and return receipts
+pub fn run_script_bytes(script: Vec<u8>) -> Vec<Receipt> {
     use rand::{
         Rng,
         SeedableRng,
     };
-    let script = script.into_iter().collect();
     let mut client = MemoryClient::default();
     let arb_max_fee = 1000;
 
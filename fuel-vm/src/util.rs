@@ -91,6 +91,7 @@ pub mod test_helpers {
     use crate::{
         checked_transaction::{
             builder::TransactionBuilderExt,
+            CheckError,
             Checked,
             IntoChecked,
         },
@@ -289,6 +290,30 @@ pub mod test_helpers {
             self
         }
 
+        /// Adds a base asset coin input paying the fee of the transaction and the
+        /// change output of its owner.
+        pub fn fee_payer_input(&mut self, amount: Word) -> &mut TestBuilder {
+            let secret = fuel_crypto::SecretKey::random(&mut self.rng);
+            let owner = Input::owner(&secret.public_key());
+            let input_index = u16::try_from(self.builder.inputs().len())
+                .expect("The input index is more than allowed");
+            let change_index = u16::try_from(self.builder.outputs().len())
+                .expect("The output index is more than allowed");
+            let base_asset_id = *self.get_base_asset_id();
+
+            self.builder.add_unsigned_coin_input(
+                secret,
+                self.rng.gen(),
+                amount,
+                base_asset_id,
+                Default::default(),
+            );
+            self.builder.fee_payer(input_index, change_index);
+            self.builder
+                .add_output(Output::change(owner, 0, base_asset_id));
+            self
+        }
+
         pub fn fee_input(&mut self) -> &mut TestBuilder {
             self.builder.add_random_fee_input();
             self
@@ -337,6 +362,19 @@ pub mod test_helpers {
         }
 
         pub fn build(&mut self) -> Checked<Script> {
+            self.prepare_builder();
+            self.builder.finalize_checked(self.block_height)
+        }
+
+        /// Builds the test tx, returning the error if it doesn't pass the checks.
+        pub fn try_build(&mut self) -> Result<Checked<Script>, CheckError> {
+            self.prepare_builder();
+            self.builder
+                .finalize()
+                .into_checked(self.block_height, &self.consensus_params)
+        }
+
+        fn prepare_builder(&mut self) {
             self.builder.max_fee_limit(self.max_fee_limit);
             self.builder.with_tx_params(*self.get_tx_params());
             self.builder
@@ -346,7 +384,6 @@ pub mod test_helpers {
             self.builder.with_script_params(*self.get_script_params());
             self.builder.with_fee_params(*self.get_fee_params());
             self.builder.with_base_asset_id(*self.get_base_asset_id());
-            self.builder.finalize_checked(self.block_height)
         }
 
         pub fn get_tx_params(&self) -> &TxParameters {